@@ -0,0 +1,179 @@
+use crate::central_context::central_context::CentralContext;
+use crate::constants::SOLANA_PROGRAMS;
+use crate::types::market_update::MarketUpdate;
+use crate::types::pool::PoolTrait;
+use crate::types::token_metadata::TokenMetadata;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::{Arc, RwLock};
+
+impl CentralContext {
+  /// Apply a raw account update (e.g. from a gRPC or websocket account stream) to the pools that
+  /// depend on the account, without any RPC round trips
+  ///
+  /// The pools are looked up in `account_dependents`, so an account shared between pools (e.g. a
+  /// Meteora vault) updates all of them. Each decodes only the fields derived from this account:
+  /// pool state, SPL token vault balances, Meteora vault and LP mint state, or bonding curve
+  /// reserves. `slot` advances `current_slot` if it is newer.
+  ///
+  /// A closed account (owned by the system program or emptied) that is a pool's own address marks
+  /// the pool as closed, other closed accounts are ignored. Closes go through the same ordering as
+  /// other updates, so a late close can't close a pool that has newer state. Pools whose lifecycle
+  /// state the `sweep_policy` says to remove are removed with `remove_pool` once the update is
+  /// applied, after their graduation is tracked (see `graduations`).
+  ///
+  /// Updates are ordered per account by `slot`, then by `write_version` when the source provides
+  /// one (gRPC does, websocket does not). An update older than the last one applied to the same
+  /// account is rejected so a late delivery can't overwrite newer state. Without a write version,
+  /// updates from the same slot as the last applied one are accepted.
+  ///
+  /// Updates to a mint in `token_registry` refresh its metadata, updates from a slot older than
+  /// the metadata's `last_updated_slot` are ignored. A closed mint is removed from the registry,
+  /// unless the close is older than the metadata.
+  ///
+  /// A `MarketUpdate` is published for every pool whose prices or reserves changed.
+  ///
+  /// Returns the updated pools, empty if the update was stale, no pool depends on the account, or
+  /// the data could not be decoded.
+  pub fn apply_account_update(
+    &self,
+    pubkey: &Pubkey,
+    owner: &Pubkey,
+    data: &[u8],
+    slot: u64,
+    write_version: Option<u64>,
+  ) -> Vec<Arc<RwLock<dyn PoolTrait>>> {
+    let closed = is_closed_account(owner, data);
+
+    self.apply_slot_update(slot);
+
+    self.apply_token_metadata_update(pubkey, owner, data, slot);
+
+    // Look up the dependents and claim the account's version together, so an update racing
+    // `remove_pool` can't leave a version behind for an account that was just removed. Pools are
+    // only written after checking the claim still holds (see `is_newest_update`)
+    let version = (slot, write_version.unwrap_or(0));
+    let dependents = {
+      let account_dependents = self.account_dependents.read().unwrap();
+      let Some(dependents) = account_dependents.get(pubkey).cloned() else {
+        return Vec::new();
+      };
+      let mut account_update_versions = self.account_update_versions.write().unwrap();
+      if let Some(&(last_slot, last_write_version)) = account_update_versions.get(pubkey) {
+        let stale = match write_version {
+          Some(write_version) => (slot, write_version) <= (last_slot, last_write_version),
+          None => slot < last_slot,
+        };
+        if stale {
+          return Vec::new();
+        }
+      }
+      account_update_versions.insert(*pubkey, version);
+      dependents
+    };
+
+    if closed {
+      // Same as pools, a newer update may have claimed the account since
+      if self.is_newest_update(pubkey, version) {
+        self.close_pool(pubkey);
+      }
+      return Vec::new();
+    }
+
+    // Shared state (e.g. a Meteora vault) changes for every dependent once the first applies the
+    // update, so take all the previous market states first
+    let previous_updates: Vec<_> = dependents
+      .iter()
+      .map(|pool| MarketUpdate::from_pool(&*pool.read().unwrap()))
+      .collect();
+    let mut updated_pools = Vec::new();
+    let mut market_updates = Vec::new();
+    for (pool, previous_update) in dependents.into_iter().zip(previous_updates) {
+      let mut pool_unlocked = pool.write().unwrap();
+      // A newer update claimed the account while this one waited for the pool
+      if !self.is_newest_update(pubkey, version) {
+        break;
+      }
+      if !pool_unlocked.apply_account_update(pubkey, data) {
+        continue;
+      }
+      if slot > pool_unlocked.last_updated_slot() {
+        pool_unlocked.set_last_updated_slot(slot);
+      }
+      market_updates.extend(
+        MarketUpdate::from_pool(&*pool_unlocked)
+          .filter(|update| previous_update.as_ref() != Some(update)),
+      );
+      drop(pool_unlocked);
+      updated_pools.push(pool);
+    }
+
+    for market_update in &market_updates {
+      self.publish_market_update(market_update);
+    }
+
+    for pool in &updated_pools {
+      // Track graduations before the sweep can remove the curve
+      self.track_graduation(pool);
+      let lifecycle = self.get_pool_lifecycle(&*pool.read().unwrap());
+      if self.sweep_policy.read().unwrap().should_remove(lifecycle) {
+        let pool_address = *pool.read().unwrap().pool_address();
+        self.remove_pool(&pool_address);
+      }
+    }
+    updated_pools
+  }
+
+  /// Whether `version` is still the newest version claimed for `pubkey`
+  ///
+  /// Checked with the pool's write lock held: an update that claims a newer version applies it
+  /// under the same lock, so an older update can't overwrite it.
+  fn is_newest_update(&self, pubkey: &Pubkey, version: (u64, u64)) -> bool {
+    self.account_update_versions.read().unwrap().get(pubkey) == Some(&version)
+  }
+
+  /// Refresh the metadata of a mint tracked in `token_registry` from its account data, removing it
+  /// if the mint was closed
+  fn apply_token_metadata_update(&self, pubkey: &Pubkey, owner: &Pubkey, data: &[u8], slot: u64) {
+    // Most updates aren't for tracked mints, only take the write lock when needed
+    if !self.token_registry.read().unwrap().contains_key(pubkey) {
+      return;
+    }
+    let mut token_registry = self.token_registry.write().unwrap();
+    let Some(token_metadata) = token_registry.get_mut(pubkey) else {
+      return;
+    };
+    if slot < token_metadata.last_updated_slot {
+      return;
+    }
+    if is_closed_account(owner, data) {
+      token_registry.remove(pubkey);
+      return;
+    }
+    match TokenMetadata::try_from_mint_account(*pubkey, owner, data, slot) {
+      Ok(updated_token_metadata) => *token_metadata = updated_token_metadata,
+      Err(error) => println!("apply_account_update: {}", error),
+    }
+  }
+
+  /// Mark the pool at `pool_address` as closed and remove it if the sweep policy says so
+  fn close_pool(&self, pool_address: &Pubkey) {
+    let is_pool_address = self
+      .pools_map
+      .read()
+      .unwrap()
+      .get(pool_address)
+      .is_some_and(|pool| pool.read().unwrap().pool_address() == pool_address);
+    if !is_pool_address {
+      return;
+    }
+    self.closed_pools.write().unwrap().insert(*pool_address);
+    if self.sweep_policy.read().unwrap().remove_closed {
+      self.remove_pool(pool_address);
+    }
+  }
+}
+
+/// Whether the account was closed: reassigned to the system program or emptied
+fn is_closed_account(owner: &Pubkey, data: &[u8]) -> bool {
+  *owner == SOLANA_PROGRAMS.system_program || data.is_empty()
+}
@@ -0,0 +1,14 @@
+use crate::central_context::central_context::CentralContext;
+
+impl CentralContext {
+  /// Advance `current_slot` from a stream slot update (e.g. a geyser gRPC slot message)
+  ///
+  /// Slot updates can arrive out of order across connections, so `current_slot` only moves
+  /// forward.
+  pub fn apply_slot_update(&self, slot: u64) {
+    let mut current_slot = self.current_slot.write().unwrap();
+    if slot > *current_slot {
+      *current_slot = slot;
+    }
+  }
+}
@@ -0,0 +1,218 @@
+use crate::central_context::central_context::CentralContext;
+use crate::central_context::restore_snapshot::{RestoredPool, deserialize_pool};
+use crate::central_context::save_snapshot::serialize_pool;
+use crate::constants::LAMPORTS_PER_SOL;
+use crate::types::field_mismatch::FieldMismatch;
+use crate::types::pool::PoolTrait;
+use crate::types::pool_audit::PoolAudit;
+use crate::types::swap_direction::SwapDirection;
+use rand::seq::IndexedRandom;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::rpc_config::RpcAccountInfoConfig;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use std::io;
+use std::sync::mpsc::{SyncSender, TrySendError};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+impl CentralContext {
+  /// Compare in-memory pools with their state on chain
+  ///
+  /// For each known pool in `pool_addresses`, the pool's dependent accounts are fetched with
+  /// getMultipleAccounts at a slot no older than the pool's `last_updated_slot`, and applied to a
+  /// copy of the pool. Every field of `PoolTrait::state_fields` and the derived reserves, prices
+  /// and fees of the copy are then diffed against the in-memory pool. Nothing in the context is
+  /// modified.
+  ///
+  /// Pools that failed to be audited (RPC errors, RPC node behind the pool) are logged and left
+  /// out. Works against any `json_rpc_client_async`, including mock clients in tests.
+  pub async fn audit_pools(self: &Arc<Self>, pool_addresses: &[Pubkey]) -> Vec<PoolAudit> {
+    let mut audits = Vec::new();
+    for pool in self.get_known_pools(pool_addresses) {
+      match self.audit_pool(&pool).await {
+        Ok(audit) => audits.push(audit),
+        Err(error) => println!(
+          "audit_pools: failed to audit pool {}: {}",
+          pool.read().unwrap().pool_address(),
+          error
+        ),
+      }
+    }
+    audits
+  }
+
+  /// Audit `sample_size` random pools every `interval` with `audit_pools`, as a background task
+  ///
+  /// Audits with mismatches or missing accounts are logged and sent to `audits`, they are dropped
+  /// if the channel is full. Returns once the receiver is dropped. Requires a tokio runtime.
+  pub async fn run_state_audit(
+    self: &Arc<Self>,
+    interval: Duration,
+    sample_size: usize,
+    audits: SyncSender<PoolAudit>,
+  ) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+      ticker.tick().await;
+      let pool_addresses: Vec<Pubkey> = {
+        let pools_map = self.pools_map.read().unwrap();
+        let pool_addresses: HashSet<Pubkey> = pools_map
+          .values()
+          .map(|pool| *pool.read().unwrap().pool_address())
+          .collect();
+        let pool_addresses: Vec<Pubkey> = pool_addresses.into_iter().collect();
+        pool_addresses
+          .choose_multiple(&mut rand::rng(), sample_size)
+          .copied()
+          .collect()
+      };
+      for audit in self.audit_pools(&pool_addresses).await {
+        if audit.is_consistent() {
+          continue;
+        }
+        println!(
+          "run_state_audit: pool {} drifted at slot {} (in memory slot {}): {:?}, missing accounts: {:?}",
+          audit.pool_address,
+          audit.slot,
+          audit.in_memory_slot,
+          audit.mismatches,
+          audit.missing_accounts
+        );
+        if let Err(TrySendError::Disconnected(_)) = audits.try_send(audit) {
+          return;
+        }
+      }
+    }
+  }
+
+  /// Fetch the dependent accounts of one pool and diff them against the in-memory pool
+  async fn audit_pool(
+    self: &Arc<Self>,
+    pool: &RwLock<dyn PoolTrait>,
+  ) -> Result<PoolAudit, ClientError> {
+    let (dependent_accounts, last_updated_slot) = {
+      let pool = pool.read().unwrap();
+      (pool.dependent_accounts(), pool.last_updated_slot())
+    };
+    let config = RpcAccountInfoConfig {
+      encoding: Some(UiAccountEncoding::Base64),
+      data_slice: None,
+      commitment: Some(self.json_rpc_client_async.commitment()),
+      min_context_slot: (last_updated_slot > 0).then_some(last_updated_slot),
+    };
+    let response = self
+      .json_rpc_client_async
+      .get_multiple_ui_accounts_with_config(&dependent_accounts, config)
+      .await?;
+
+    // Copy and read the pool after the fetch so it is as close as possible to the fetched slot
+    let pool = pool.read().unwrap();
+    let on_chain_pool = copy_pool(&*pool).map_err(|error| {
+      ClientErrorKind::Custom(format!(
+        "Pool {} can't be copied: {}",
+        pool.pool_address(),
+        error
+      ))
+    })?;
+    let in_memory_values = get_audit_values(&*pool, self);
+
+    let mut on_chain_pool = on_chain_pool.write().unwrap();
+    let mut missing_accounts = Vec::new();
+    for (address, account) in dependent_accounts.iter().zip(response.value) {
+      match account.and_then(|account| account.decode::<Account>()) {
+        Some(account) => {
+          on_chain_pool.apply_account_update(address, &account.data);
+        }
+        None => missing_accounts.push(*address),
+      }
+    }
+
+    let mismatches = in_memory_values
+      .into_iter()
+      .zip(get_audit_values(&*on_chain_pool, self))
+      .filter(|((_, in_memory), (_, on_chain))| in_memory != on_chain)
+      .map(|((field, in_memory), (_, on_chain))| FieldMismatch {
+        field,
+        in_memory,
+        on_chain,
+      })
+      .collect();
+    Ok(PoolAudit {
+      pool_address: *pool.pool_address(),
+      pool_type: *pool.pool_type(),
+      slot: response.context.slot,
+      in_memory_slot: pool.last_updated_slot(),
+      missing_accounts,
+      mismatches,
+    })
+  }
+}
+
+/// Copy a pool through its snapshot encoding. Meteora vaults are copied too instead of shared, so
+/// updating the copy never touches the original.
+fn copy_pool(pool: &dyn PoolTrait) -> io::Result<Arc<RwLock<dyn PoolTrait>>> {
+  let mut data = Vec::new();
+  serialize_pool(pool, &mut data)?;
+  Ok(match deserialize_pool(&mut data.as_slice())? {
+    RestoredPool::MeteoraAmm(pool) => Arc::new(RwLock::new(pool)),
+    RestoredPool::MeteoraDbc(pool) => Arc::new(RwLock::new(pool)),
+    RestoredPool::PfBondingCurve(pool) => Arc::new(RwLock::new(pool)),
+    RestoredPool::RaydiumLaunchpad(pool) => Arc::new(RwLock::new(pool)),
+    RestoredPool::Other(pool) => pool,
+  })
+}
+
+/// The state fields of a pool followed by its derived reserves, prices and fees. Prices are 0 while
+/// either side has no reserves. Directional fee fractions are in lamports (10^9 = 100%), rounded.
+fn get_audit_values(
+  pool: &dyn PoolTrait,
+  central_context: &Arc<CentralContext>,
+) -> Vec<(&'static str, u128)> {
+  let token_a_amount_units = pool.token_a_amount_units();
+  let token_b_amount_units = pool.token_b_amount_units();
+  let has_reserves = token_a_amount_units > 0 && token_b_amount_units > 0;
+  let mut values = pool.state_fields();
+  values.extend([
+    ("token_a_amount_units", token_a_amount_units as u128),
+    ("token_b_amount_units", token_b_amount_units as u128),
+    (
+      "price_a_over_b_lp",
+      if has_reserves {
+        pool.price_a_over_b_lp()
+      } else {
+        0
+      },
+    ),
+    (
+      "price_b_over_a_lp",
+      if has_reserves {
+        pool.price_b_over_a_lp()
+      } else {
+        0
+      },
+    ),
+    (
+      "total_swap_fee_lp",
+      pool.total_swap_fee_lp(central_context) as u128,
+    ),
+  ]);
+  for (direction, fee_a_field, fee_b_field) in [
+    (SwapDirection::AToB, "a_to_b_fee_a_lp", "a_to_b_fee_b_lp"),
+    (SwapDirection::BToA, "b_to_a_fee_a_lp", "b_to_a_fee_b_lp"),
+  ] {
+    let (fee_a, fee_b) = pool.directional_fees(direction, central_context);
+    values.extend([
+      (fee_a_field, fraction_to_lamports(fee_a)),
+      (fee_b_field, fraction_to_lamports(fee_b)),
+    ]);
+  }
+  values
+}
+
+/// Convert a fee fraction to lamports (10^9 = 1.0), saturating at 0 for negative or NaN fractions
+fn fraction_to_lamports(fraction: f64) -> u128 {
+  (fraction * LAMPORTS_PER_SOL as f64).round() as u128
+}
@@ -0,0 +1,44 @@
+use crate::central_context::central_context::CentralContext;
+use crate::types::meteora_dbc::MeteoraDbc;
+
+impl CentralContext {
+  /// Build the pending DBC pools whose config is now cached
+  ///
+  /// Called by `load_dbc_pool_configs` after inserting configs. Each pool is built from its
+  /// stored initialize instruction and inserted like `process_token_creation_instruction` does.
+  pub(crate) fn build_pending_dbc_pools(&self) {
+    let ready_pools = {
+      let dbc_pool_configs = self.dbc_pool_configs.read().unwrap();
+      let mut pending_dbc_pools = self.pending_dbc_pools.lock().unwrap();
+      let ready_config_addresses: Vec<_> = pending_dbc_pools
+        .keys()
+        .filter(|config_address| dbc_pool_configs.contains_key(config_address))
+        .copied()
+        .collect();
+      ready_config_addresses
+        .into_iter()
+        .filter_map(|config_address| {
+          let pending = pending_dbc_pools.remove(&config_address)?;
+          Some((dbc_pool_configs.get(&config_address)?.clone(), pending))
+        })
+        .collect::<Vec<_>>()
+    };
+
+    for (dbc_pool_config, pending) in &ready_pools {
+      for pending_dbc_pool in pending {
+        let Some((dbc, _)) = MeteoraDbc::from_initialize_instruction(
+          &pending_dbc_pool.instruction(),
+          dbc_pool_config,
+          pending_dbc_pool.block_time,
+          pending_dbc_pool.slot,
+          pending_dbc_pool.index,
+          pending_dbc_pool.atomic_instruction_index,
+          pending_dbc_pool.signature,
+        ) else {
+          continue;
+        };
+        self.cache_token_creation_pool(&self.meteora_dbcs, dbc);
+      }
+    }
+  }
+}
@@ -1,29 +1,39 @@
-use crate::constants::PUMP_CONSTANTS;
-use crate::constants::RAYDIUM_CONSTANTS;
+use crate::central_context::central_context_builder::CentralContextBuilder;
+use crate::protocol_idls::meteora::DbcPoolConfig;
+use crate::types::graduation::Graduation;
+use crate::types::market_update_subscriber::MarketUpdateSubscriber;
+use crate::types::meteora_dbc::MeteoraDbc;
 use crate::types::meteora_vault::MeteoraVault;
+use crate::types::pending_dbc_pool::PendingDbcPool;
 use crate::types::pf_bonding_curve::PfBondingCurve;
 use crate::types::pool::PoolTrait;
 use crate::types::raydium_launchpad::RaydiumLaunchpad;
+use crate::types::sweep_policy::SweepPolicy;
+use crate::types::token_metadata::TokenMetadata;
 use solana_client;
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::hash::Hash;
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::env;
+use std::sync::mpsc::SyncSender;
 use std::sync::{Arc, Mutex, RwLock};
-use std::time::Duration;
 
 /// Central context for managing DEX liquidity pools, markets, and RPC clients
 ///
 /// This structure serves as the shared state across all pool operations, providing
 /// thread-safe access to:
 /// - Market graphs with bidirectional token pair mappings (markets["wsol"]["usdc"] will give you
-/// the same thread safe access as if you did markets["usdc"]["wsol"])
+///   the same thread safe access as if you did markets["usdc"]["wsol"])
 /// - Protocol-specific pools and vaults
 /// - JSON RPC clients for on-chain data fetching
 /// - Token validation and legitimacy tracking
 /// - Current network state (slot, blockhash)
+///
+/// Locks are taken in this order: `markets`, a pair's pool vec, `pools_map`, `account_dependents`,
+/// then the pools themselves. A pool lock is never held while taking one of those map locks, only
+/// short leaf locks (`account_update_versions`, `closed_pools`, `graduations`, `current_slot`, ...)
+/// that are never held while taking a pool lock.
 pub struct CentralContext {
   /// Bidirectional market graph: token A -> token B -> list of pools
   ///
@@ -34,6 +44,7 @@ pub struct CentralContext {
   /// - Arc on the vector for bidirectional reference
   /// - RwLock on the vector for mutability and adding new markets in
   /// - RwLock on the pool traits to modify the pools states
+  #[allow(clippy::type_complexity)]
   pub markets:
     RwLock<HashMap<Pubkey, HashMap<Pubkey, Arc<RwLock<Vec<Arc<RwLock<dyn PoolTrait>>>>>>>>,
   /// Synchronous JSON RPC client for Solana network requests
@@ -61,6 +72,22 @@ pub struct CentralContext {
   /// Protected by Mutex for thread-safe read-check-write operations when
   /// creating new bonding curve instances.
   pub raydium_launchpads: Mutex<HashMap<Pubkey, Arc<RwLock<RaydiumLaunchpad>>>>,
+  /// Cache of Meteora DBC virtual pools keyed by pool address
+  ///
+  /// Protected by Mutex for thread-safe read-check-write operations when
+  /// creating new bonding curve instances.
+  pub meteora_dbcs: Mutex<HashMap<Pubkey, Arc<RwLock<MeteoraDbc>>>>,
+  /// Cache of Meteora DBC pool configs keyed by config account address
+  ///
+  /// Configs are shared by many virtual pools and never change once created, so they are fetched
+  /// once and reused when building `MeteoraDbc` pools.
+  pub dbc_pool_configs: RwLock<HashMap<Pubkey, Arc<DbcPoolConfig>>>,
+  /// DBC pool initializations seen before their config was cached, keyed by config address
+  ///
+  /// Filled by `process_token_creation_instruction` instead of fetching the config on the
+  /// ingestion thread. `load_dbc_pool_configs` builds and inserts the pools once their config is
+  /// loaded, see `pending_dbc_pool_configs`.
+  pub(crate) pending_dbc_pools: Mutex<HashMap<Pubkey, Vec<PendingDbcPool>>>,
   /// Cache of token legitimacy flags keyed by token address
   ///
   /// A token is considered legit if it has Metaplex metadata and the update authority
@@ -74,9 +101,52 @@ pub struct CentralContext {
   pub legit_update_authorities: HashSet<Pubkey>,
   /// Map of account addresses to pools for efficient pool lookup
   ///
-  /// A pool can be looked up by its pool address, token A vault address, or token B vault address.
-  /// Updated automatically when pools are inserted via `insert_pool`.
+  /// A pool can be looked up by its pool address, token A vault address, token B vault address, or
+  /// any other account in its `dependent_accounts`. Updated automatically when pools are inserted
+  /// via `insert_pool`. Accounts shared between pools (e.g. Meteora vaults) map to the last pool
+  /// inserted, see `account_dependents` for all of them.
   pub pools_map: RwLock<HashMap<Pubkey, Arc<RwLock<dyn PoolTrait>>>>,
+  /// Every pool depending on each account in `pools_map`
+  ///
+  /// Accounts shared between pools (e.g. Meteora vaults) map to a single pool in `pools_map`, this
+  /// index lets `apply_account_update` apply their updates to all of them. Maintained by
+  /// `insert_pool` and `remove_pool`.
+  #[allow(clippy::type_complexity)]
+  pub account_dependents: RwLock<HashMap<Pubkey, Vec<Arc<RwLock<dyn PoolTrait>>>>>,
+  /// Slot and write version of the newest update accepted for each pool dependent account
+  ///
+  /// Used by `apply_account_update` to reject stream updates that arrive out of order. The write
+  /// version is 0 for sources that don't provide one.
+  pub account_update_versions: RwLock<HashMap<Pubkey, (u64, u64)>>,
+  /// Cache of address lookup table contents keyed by lookup table address
+  ///
+  /// Used to resolve the accounts v0 transactions load from lookup tables. See
+  /// `resolve_loaded_addresses`.
+  pub address_lookup_tables: RwLock<HashMap<Pubkey, Arc<Vec<Pubkey>>>>,
+  /// Metadata of token mints (decimals, token program, supply, authorities) keyed by mint address
+  ///
+  /// Populated lazily by `get_token_metadata` or in bulk by `load_token_metadata`, then kept up to
+  /// date by mint account updates passed to `apply_account_update`. See `get_ui_prices`.
+  pub token_registry: RwLock<HashMap<Pubkey, TokenMetadata>>,
+  /// Registered market update subscribers
+  ///
+  /// Managed through `subscribe_market_updates`. Subscribers whose receiver was dropped are
+  /// removed on the next publish.
+  pub(crate) market_update_subscribers: Mutex<Vec<Arc<MarketUpdateSubscriber>>>,
+  /// Which lifecycle states get pools removed automatically
+  ///
+  /// Applied by `apply_account_update` as pools change state and by `sweep_pools`. Defaults to
+  /// never removing pools.
+  pub sweep_policy: RwLock<SweepPolicy>,
+  /// Pools whose account was closed on chain, seen through `apply_account_update`
+  pub closed_pools: RwLock<HashSet<Pubkey>>,
+  /// Graduated bonding curves keyed by curve address, linked to the AMM pool they migrate to
+  ///
+  /// Entries are kept after the curve is removed from the markets. See `track_graduation`,
+  /// `process_migration_instruction` and `subscribe_graduations`.
+  pub graduations: RwLock<HashMap<Pubkey, Graduation>>,
+  /// Registered graduation subscribers, see `subscribe_graduations`
+  pub(crate) graduation_subscribers: Mutex<Vec<SyncSender<Graduation>>>,
   /// Current slot being produced by the Solana network
   pub current_slot: RwLock<u64>,
   /// Most recent blockhash produced by the network
@@ -89,21 +159,22 @@ impl CentralContext {
   /// Create a new `CentralContext` instance
   ///
   /// Initializes RPC clients, empty caches, and sets up legitimate update authorities
-  /// from known reputable launchpads. Requires `RPC_NODE_URL` environment variable to be set.
+  /// from known reputable launchpads. Requires `RPC_NODE_URL` environment variable to be set,
+  /// `RPC_FALLBACK_URLS` optionally adds comma separated fallback endpoints. Use
+  /// `CentralContextBuilder` to configure endpoints, commitment, timeouts and retries explicitly.
   pub fn new() -> Self {
-    let rpc_url = env::var("RPC_NODE_URL").expect("RPC_NODE_URL must be set");
-    let json_rpc_client = RpcClient::new_with_timeout(&rpc_url, Duration::from_secs(300));
-    let json_rpc_client_async = solana_client::nonblocking::rpc_client::RpcClient::new_with_timeout(
-      rpc_url,
-      Duration::from_secs(300),
-    );
-
-    let mut legit_update_authorities = HashSet::new();
-    // Raydium launchpad update authority
-    legit_update_authorities.insert(RAYDIUM_CONSTANTS.launchpad_authority);
-    // Pumpfun bonding curve update authority
-    legit_update_authorities.insert(PUMP_CONSTANTS.bonding_curve_update_authority);
+    CentralContextBuilder::new()
+      .endpoints_from_env()
+      .build()
+      .expect("RPC_NODE_URL must be set")
+  }
 
+  /// Create a context with empty caches around the given RPC clients
+  pub(crate) fn with_clients(
+    json_rpc_client: RpcClient,
+    json_rpc_client_async: solana_client::nonblocking::rpc_client::RpcClient,
+    legit_update_authorities: HashSet<Pubkey>,
+  ) -> Self {
     Self {
       markets: RwLock::new(HashMap::new()),
       pf_bonding_curves: Mutex::new(HashMap::new()),
@@ -111,12 +182,30 @@ impl CentralContext {
       json_rpc_client_async,
       raydium_cpmm_fee_rates_lp: HashMap::new(),
       raydium_launchpads: Mutex::new(HashMap::new()),
+      meteora_dbcs: Mutex::new(HashMap::new()),
+      dbc_pool_configs: RwLock::new(HashMap::new()),
+      pending_dbc_pools: Mutex::new(HashMap::new()),
       meteora_vault_cache: Mutex::new(HashMap::new()),
       current_slot: RwLock::new(0),
       pools_map: RwLock::new(HashMap::new()),
+      account_dependents: RwLock::new(HashMap::new()),
+      account_update_versions: RwLock::new(HashMap::new()),
+      address_lookup_tables: RwLock::new(HashMap::new()),
+      token_registry: RwLock::new(HashMap::new()),
+      market_update_subscribers: Mutex::new(Vec::new()),
+      sweep_policy: RwLock::new(SweepPolicy::default()),
+      closed_pools: RwLock::new(HashSet::new()),
+      graduations: RwLock::new(HashMap::new()),
+      graduation_subscribers: Mutex::new(Vec::new()),
       latest_blockhash: RwLock::new(Hash::default()),
       legit_tokens: Mutex::new(HashMap::new()),
       legit_update_authorities,
     }
   }
 }
+
+impl Default for CentralContext {
+  fn default() -> Self {
+    Self::new()
+  }
+}
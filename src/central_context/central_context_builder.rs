@@ -0,0 +1,163 @@
+use crate::central_context::central_context::CentralContext;
+use crate::constants::{PUMP_CONSTANTS, RAYDIUM_CONSTANTS};
+use crate::types::backend_rpc_sender::BackendRpcSender;
+use crate::types::failover_rpc_sender::FailoverRpcSender;
+use crate::types::retry_policy::RetryPolicy;
+use crate::types::rpc_backend::RpcBackend;
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::rpc_client::{RpcClient, RpcClientConfig};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Builder for a `CentralContext` with explicit RPC configuration
+///
+/// Both RPC clients of the context send through a `FailoverRpcSender` over the configured
+/// endpoints: the first one is the primary, the others are fallbacks in order. Defaults to the
+/// default commitment (finalized), a 300s request timeout, the default `RetryPolicy` and the
+/// Raydium Launchpad and Pumpfun update authorities as legit update authorities.
+///
+/// With an `RpcBackend` set, both clients are served by it through a `BackendRpcSender` instead,
+/// no endpoint is needed and the endpoints, timeout and retry policy are ignored.
+///
+/// ```no_run
+/// use solana_central::{CentralContextBuilder, RetryPolicy};
+/// use solana_commitment_config::CommitmentConfig;
+/// use std::time::Duration;
+///
+/// let central_context = CentralContextBuilder::new()
+///   .endpoint("https://primary.example.com")
+///   .endpoint("https://fallback.example.com")
+///   .commitment(CommitmentConfig::confirmed())
+///   .timeout(Duration::from_secs(30))
+///   .retry_policy(RetryPolicy::default())
+///   .build()
+///   .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct CentralContextBuilder {
+  endpoints: Vec<String>,
+  rpc_backend: Option<Arc<dyn RpcBackend>>,
+  commitment: CommitmentConfig,
+  timeout: Duration,
+  retry_policy: RetryPolicy,
+  legit_update_authorities: HashSet<Pubkey>,
+}
+
+impl CentralContextBuilder {
+  /// Create a builder with no endpoints and the default settings
+  pub fn new() -> Self {
+    let mut legit_update_authorities = HashSet::new();
+    // Raydium launchpad update authority
+    legit_update_authorities.insert(RAYDIUM_CONSTANTS.launchpad_authority);
+    // Pumpfun bonding curve update authority
+    legit_update_authorities.insert(PUMP_CONSTANTS.bonding_curve_update_authority);
+
+    Self {
+      endpoints: Vec::new(),
+      rpc_backend: None,
+      commitment: CommitmentConfig::default(),
+      timeout: Duration::from_secs(300),
+      retry_policy: RetryPolicy::default(),
+      legit_update_authorities,
+    }
+  }
+
+  /// Add an RPC endpoint. The first endpoint added is the primary, later ones are fallbacks.
+  pub fn endpoint(mut self, url: impl Into<String>) -> Self {
+    self.endpoints.push(url.into());
+    self
+  }
+
+  /// Add the endpoints from the environment: `RPC_NODE_URL`, then the comma separated
+  /// `RPC_FALLBACK_URLS`. Unset variables add nothing.
+  pub fn endpoints_from_env(mut self) -> Self {
+    if let Ok(url) = env::var("RPC_NODE_URL") {
+      self.endpoints.push(url);
+    }
+    if let Ok(urls) = env::var("RPC_FALLBACK_URLS") {
+      self.endpoints.extend(
+        urls
+          .split(',')
+          .map(str::trim)
+          .filter(|url| !url.is_empty())
+          .map(String::from),
+      );
+    }
+    self
+  }
+
+  /// Serve both RPC clients from `rpc_backend` instead of RPC endpoints, e.g. a
+  /// `FixtureRpcBackend` to run offline
+  pub fn rpc_backend(mut self, rpc_backend: Arc<dyn RpcBackend>) -> Self {
+    self.rpc_backend = Some(rpc_backend);
+    self
+  }
+
+  /// Set the commitment used by both RPC clients
+  pub fn commitment(mut self, commitment: CommitmentConfig) -> Self {
+    self.commitment = commitment;
+    self
+  }
+
+  /// Set the timeout of each request to one endpoint
+  pub fn timeout(mut self, timeout: Duration) -> Self {
+    self.timeout = timeout;
+    self
+  }
+
+  /// Set how requests are retried once every endpoint failed
+  pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+    self.retry_policy = retry_policy;
+    self
+  }
+
+  /// Replace the update authorities used to validate tokens, see
+  /// `CentralContext::legit_update_authorities`
+  pub fn legit_update_authorities(mut self, legit_update_authorities: HashSet<Pubkey>) -> Self {
+    self.legit_update_authorities = legit_update_authorities;
+    self
+  }
+
+  /// Build the context, failing if neither an endpoint nor an RPC backend was configured
+  pub fn build(self) -> Result<CentralContext, ClientError> {
+    if let Some(rpc_backend) = self.rpc_backend {
+      return Ok(CentralContext::with_clients(
+        RpcClient::new_sender(
+          BackendRpcSender::new(rpc_backend.clone()),
+          RpcClientConfig::with_commitment(self.commitment),
+        ),
+        solana_client::nonblocking::rpc_client::RpcClient::new_sender(
+          BackendRpcSender::new(rpc_backend),
+          RpcClientConfig::with_commitment(self.commitment),
+        ),
+        self.legit_update_authorities,
+      ));
+    }
+    if self.endpoints.is_empty() {
+      return Err(ClientErrorKind::Custom("No RPC endpoint configured".to_string()).into());
+    }
+    let json_rpc_client = RpcClient::new_sender(
+      FailoverRpcSender::new(&self.endpoints, self.timeout, self.retry_policy.clone()),
+      RpcClientConfig::with_commitment(self.commitment),
+    );
+    let json_rpc_client_async = solana_client::nonblocking::rpc_client::RpcClient::new_sender(
+      FailoverRpcSender::new(&self.endpoints, self.timeout, self.retry_policy),
+      RpcClientConfig::with_commitment(self.commitment),
+    );
+    Ok(CentralContext::with_clients(
+      json_rpc_client,
+      json_rpc_client_async,
+      self.legit_update_authorities,
+    ))
+  }
+}
+
+impl Default for CentralContextBuilder {
+  fn default() -> Self {
+    Self::new()
+  }
+}
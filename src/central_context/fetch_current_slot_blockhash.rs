@@ -1,4 +1,5 @@
 use crate::central_context::central_context::CentralContext;
+use crate::types::error::Error;
 
 impl CentralContext {
   /// Refresh current slot and latest blockhash from JSON RPC
@@ -11,4 +12,17 @@ impl CentralContext {
     let mut latest_blockhash = self.latest_blockhash.write().unwrap();
     *latest_blockhash = self.json_rpc_client.get_latest_blockhash().unwrap();
   }
+
+  /// Async version of `fetch_current_slot_blockhash` using `json_rpc_client_async`
+  ///
+  /// Both values are fetched before either is written, so on error neither field changes. The slot
+  /// goes through `apply_slot_update`, so a lagging RPC node can't move `current_slot` back behind
+  /// the streams.
+  pub async fn fetch_current_slot_blockhash_async(&self) -> Result<(), Error> {
+    let slot = self.json_rpc_client_async.get_slot().await?;
+    let blockhash = self.json_rpc_client_async.get_latest_blockhash().await?;
+    self.apply_slot_update(slot);
+    *self.latest_blockhash.write().unwrap() = blockhash;
+    Ok(())
+  }
 }
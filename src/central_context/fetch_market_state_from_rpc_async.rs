@@ -0,0 +1,68 @@
+use crate::central_context::central_context::CentralContext;
+use crate::types::error::Error;
+use solana_client::client_error::ClientErrorKind;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+
+impl CentralContext {
+  /// Async counterpart of `PoolTrait::fetch_market_state_from_rpc` using `json_rpc_client_async`
+  ///
+  /// Fetches all the dependent accounts of the pool at `pool_address` in one getMultipleAccounts
+  /// call and applies them like `refresh_pools_async`: through `apply_account_update` at the
+  /// response's slot, so updates older than the pool's state are rejected, shared accounts update
+  /// every dependent pool and market changes are published. Accounts only known once the first
+  /// pass is applied (e.g. the LP mint of a Meteora vault) are fetched in a second call, and an
+  /// unhydrated pool is hydrated once every dependent account was fetched.
+  ///
+  /// No lock is held across the RPC calls. Network errors are returned instead of panicking and
+  /// leave the pool untouched. Missing accounts are reported as an error once the fetched ones are
+  /// applied. Fails with `Error::InvalidPool` if the pool isn't in `pools_map`. Lives on the
+  /// context rather than `PoolTrait` so the trait stays object safe.
+  pub async fn fetch_market_state_from_rpc_async(
+    &self,
+    pool_address: &Pubkey,
+  ) -> Result<(), Error> {
+    let pools = self.get_known_pools(&[*pool_address]);
+    let Some(pool) = pools.first() else {
+      return Err(Error::InvalidPool {
+        pool: *pool_address,
+        reason: "not in pools_map".to_string(),
+      });
+    };
+    let requested_accounts = pool.read().unwrap().dependent_accounts();
+    let mut fetched_accounts = self.fetch_accounts_async(&requested_accounts).await?;
+    let new_accounts = self.register_new_dependent_accounts(&pools, &requested_accounts);
+    if !new_accounts.is_empty() {
+      fetched_accounts.extend(self.fetch_accounts_async(&new_accounts).await?);
+    }
+    self.mark_pools_hydrated(&pools, &fetched_accounts);
+
+    let missing_account = pool
+      .read()
+      .unwrap()
+      .dependent_accounts()
+      .into_iter()
+      .find(|account| !fetched_accounts.contains(account));
+    match missing_account {
+      Some(account) => Err(Error::Rpc(
+        ClientErrorKind::Custom(format!("Account {} not found", account)).into(),
+      )),
+      None => Ok(()),
+    }
+  }
+
+  /// Fetch `accounts` with one getMultipleAccounts call and apply them at the response's slot,
+  /// returning the accounts that exist
+  async fn fetch_accounts_async(&self, accounts: &[Pubkey]) -> Result<HashSet<Pubkey>, Error> {
+    let response = self
+      .json_rpc_client_async
+      .get_multiple_accounts_with_commitment(accounts, self.json_rpc_client_async.commitment())
+      .await?;
+    Ok(
+      self
+        .apply_fetched_accounts(accounts, response.value, response.context.slot)
+        .into_iter()
+        .collect(),
+    )
+  }
+}
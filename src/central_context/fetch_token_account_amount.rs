@@ -0,0 +1,15 @@
+use crate::central_context::central_context::CentralContext;
+use crate::types::error::Error;
+use solana_sdk::pubkey::Pubkey;
+
+impl CentralContext {
+  /// Fetch the raw amount of an SPL token account with getTokenAccountBalance
+  pub(crate) fn fetch_token_account_amount(&self, token_account: &Pubkey) -> Result<u64, Error> {
+    self
+      .json_rpc_client
+      .get_token_account_balance(token_account)?
+      .amount
+      .parse()
+      .map_err(|error| Error::decode(*token_account, error))
+  }
+}
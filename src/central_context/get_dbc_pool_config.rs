@@ -0,0 +1,26 @@
+use crate::central_context::central_context::CentralContext;
+use crate::protocol_idls::meteora::DbcPoolConfig;
+use borsh::BorshDeserialize;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+
+impl CentralContext {
+  /// Get a Meteora DBC pool config by its account address
+  ///
+  /// Checks the `dbc_pool_configs` cache first, and if not found, fetches and decodes the config
+  /// account from JSON RPC and caches it. Returns `None` if the account can't be fetched or decoded.
+  pub fn get_dbc_pool_config(&self, config_address: &Pubkey) -> Option<Arc<DbcPoolConfig>> {
+    if let Some(config) = self.dbc_pool_configs.read().unwrap().get(config_address) {
+      return Some(config.clone());
+    }
+
+    let account = self.json_rpc_client.get_account(config_address).ok()?;
+    let config = Arc::new(DbcPoolConfig::deserialize(&mut account.data.as_slice()).ok()?);
+    self
+      .dbc_pool_configs
+      .write()
+      .unwrap()
+      .insert(*config_address, config.clone());
+    Some(config)
+  }
+}
@@ -0,0 +1,21 @@
+use crate::central_context::central_context::CentralContext;
+use crate::types::pool::PoolTrait;
+use crate::types::pool_lifecycle::PoolLifecycle;
+
+impl CentralContext {
+  /// Get the lifecycle state of a pool
+  ///
+  /// `Closed` if the pool account was closed on chain (see `closed_pools`), otherwise the state
+  /// derived from the pool's own data at the current slot.
+  pub fn get_pool_lifecycle(&self, pool: &dyn PoolTrait) -> PoolLifecycle {
+    if self
+      .closed_pools
+      .read()
+      .unwrap()
+      .contains(pool.pool_address())
+    {
+      return PoolLifecycle::Closed;
+    }
+    pool.lifecycle(*self.current_slot.read().unwrap())
+  }
+}
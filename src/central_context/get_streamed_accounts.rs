@@ -0,0 +1,13 @@
+use crate::central_context::central_context::CentralContext;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+
+impl CentralContext {
+  /// Accounts the account streams keep current: every key of `pools_map` and every mint tracked in
+  /// `token_registry`
+  pub(crate) fn get_streamed_accounts(&self) -> HashSet<Pubkey> {
+    let mut accounts: HashSet<Pubkey> = self.pools_map.read().unwrap().keys().copied().collect();
+    accounts.extend(self.token_registry.read().unwrap().keys().copied());
+    accounts
+  }
+}
@@ -0,0 +1,46 @@
+use crate::central_context::central_context::CentralContext;
+use crate::types::error::Error;
+use crate::types::token_metadata::TokenMetadata;
+use solana_sdk::pubkey::Pubkey;
+
+impl CentralContext {
+  /// Get the metadata of a mint from `token_registry`, fetching and caching the mint account on a
+  /// miss. Fetched entries are stamped with `current_slot`. Use `load_token_metadata` to fetch many
+  /// mints at once.
+  pub fn get_token_metadata(&self, mint: &Pubkey) -> Result<TokenMetadata, Error> {
+    if let Some(token_metadata) = self.token_registry.read().unwrap().get(mint) {
+      return Ok(token_metadata.clone());
+    }
+    let account = self.json_rpc_client.get_account(mint)?;
+    self.insert_token_metadata(mint, &account.owner, &account.data)
+  }
+
+  /// Async version of `get_token_metadata` using `json_rpc_client_async`
+  ///
+  /// The registry lock is not held while the mint is fetched, so concurrent lookups of the same
+  /// uncached mint may both hit the RPC.
+  pub async fn get_token_metadata_async(&self, mint: &Pubkey) -> Result<TokenMetadata, Error> {
+    if let Some(token_metadata) = self.token_registry.read().unwrap().get(mint) {
+      return Ok(token_metadata.clone());
+    }
+    let account = self.json_rpc_client_async.get_account(mint).await?;
+    self.insert_token_metadata(mint, &account.owner, &account.data)
+  }
+
+  /// Decode a fetched mint account at `current_slot` and cache it
+  fn insert_token_metadata(
+    &self,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    data: &[u8],
+  ) -> Result<TokenMetadata, Error> {
+    let token_metadata =
+      TokenMetadata::try_from_mint_account(*mint, owner, data, *self.current_slot.read().unwrap())?;
+    self
+      .token_registry
+      .write()
+      .unwrap()
+      .insert(*mint, token_metadata.clone());
+    Ok(token_metadata)
+  }
+}
@@ -0,0 +1,31 @@
+use crate::central_context::central_context::CentralContext;
+use crate::constants::LAMPORTS_PER_SOL;
+use crate::types::pool::PoolTrait;
+
+impl CentralContext {
+  /// Get the prices of a pool in UI units as `(price_a_over_b, price_b_over_a)`, e.g. how many
+  /// USDC for 1 SOL rather than raw units scaled by `LAMPORTS_PER_SOL`
+  ///
+  /// Only reads `token_registry`, `None` while the metadata of either token isn't loaded (see
+  /// `load_token_metadata`), the pool isn't hydrated, or a price is undefined.
+  pub fn get_ui_prices(&self, pool: &dyn PoolTrait) -> Option<(f64, f64)> {
+    if !pool.is_hydrated() {
+      return None;
+    }
+    let (token_a_decimals, token_b_decimals) = {
+      let token_registry = self.token_registry.read().unwrap();
+      (
+        token_registry.get(pool.token_a_address())?.decimals,
+        token_registry.get(pool.token_b_address())?.decimals,
+      )
+    };
+    let price_a_over_b_lp = pool.try_price_a_over_b_lp().ok()?;
+    let price_b_over_a_lp = pool.try_price_b_over_a_lp().ok()?;
+    // Raw A per raw B becomes UI A per UI B by scaling with 10^(decimals B - decimals A)
+    let decimals_scale = 10f64.powi(token_b_decimals as i32 - token_a_decimals as i32);
+    Some((
+      price_a_over_b_lp as f64 / LAMPORTS_PER_SOL as f64 * decimals_scale,
+      price_b_over_a_lp as f64 / LAMPORTS_PER_SOL as f64 / decimals_scale,
+    ))
+  }
+}
@@ -1,22 +1,32 @@
 use crate::central_context::central_context::CentralContext;
 use crate::constants::SOLANA_PROGRAMS;
 use crate::types::pool::PoolTrait;
-use std::collections::HashMap;
+use solana_sdk::pubkey::Pubkey;
 use std::sync::{Arc, RwLock};
 
 impl CentralContext {
   /// Insert a pool into the central context
   ///
   /// Adds the pool to both the bidirectional market graph and the pools map.
-  /// Pools can then be queried by token pairs or by account addresses (pool address or vault addresses).
+  /// Pools can then be queried by token pairs or by any of their `dependent_accounts` (pool address,
+  /// vault addresses, etc.).
   /// Invalid pools (with system program addresses or duplicate tokens) are silently ignored.
+  /// Graduation tracking is updated, see `graduations`.
   pub fn insert_pool(&self, pool: Arc<RwLock<dyn PoolTrait>>) {
-    let result_unlocked = pool.read().unwrap();
-    let token_a_address: &solana_sdk::pubkey::Pubkey = result_unlocked.token_a_address();
-    let token_b_address = result_unlocked.token_b_address();
+    // Copy what's needed and release the pool lock before taking the map locks, `remove_pool`
+    // reads pools while holding them (see the lock order on `CentralContext`)
+    let (token_a_address, token_b_address, pool_address, dependent_accounts) = {
+      let result_unlocked = pool.read().unwrap();
+      (
+        *result_unlocked.token_a_address(),
+        *result_unlocked.token_b_address(),
+        *result_unlocked.pool_address(),
+        result_unlocked.dependent_accounts(),
+      )
+    };
     // Token addresses are all 0/garbage, throw this pool away
-    if *token_a_address == SOLANA_PROGRAMS.system_program
-      || *token_b_address == SOLANA_PROGRAMS.system_program
+    if token_a_address == SOLANA_PROGRAMS.system_program
+      || token_b_address == SOLANA_PROGRAMS.system_program
     {
       return;
     }
@@ -24,37 +34,60 @@ impl CentralContext {
     if token_a_address == token_b_address {
       return;
     }
-    // println!("load_pools: processing pool {}", result_unlocked.pool_address());
+    // println!("load_pools: processing pool {}", pool_address);
     // Aquire a write lock on markets
     let mut markets = self.markets.write().unwrap();
 
     // Ensure both tokens exist in the markets map
-    markets
-      .entry(token_a_address.clone())
-      .or_insert_with(|| HashMap::new());
-    markets
-      .entry(token_b_address.clone())
-      .or_insert_with(|| HashMap::new());
+    markets.entry(token_a_address).or_default();
+    markets.entry(token_b_address).or_default();
 
     // Get references to both market maps
-    let markets_a = markets.get_mut(token_a_address).unwrap();
+    let markets_a = markets.get_mut(&token_a_address).unwrap();
 
     // Check if market pair already exists
-    if let Some(existing_markets) = markets_a.get(token_b_address) {
+    if let Some(existing_markets) = markets_a.get(&token_b_address) {
       existing_markets.write().unwrap().push(pool.clone());
     } else {
       // Create new market pair
       let a_b_markets = Arc::new(RwLock::new(vec![pool.clone()]));
       // Insert bidirectional mapping
-      markets_a.insert(token_b_address.clone(), a_b_markets.clone());
-      let markets_b = markets.get_mut(token_b_address).unwrap();
-      markets_b.insert(token_a_address.clone(), a_b_markets);
+      markets_a.insert(token_b_address, a_b_markets.clone());
+      let markets_b = markets.get_mut(&token_b_address).unwrap();
+      markets_b.insert(token_a_address, a_b_markets);
     }
 
-    // Get a write lock for the token accounts and insert this pool there
+    // Get a write lock for the token accounts and insert this pool under every account it
+    // depends on. The pool address goes last so it is never shadowed by another account.
     let mut token_accounts_map = self.pools_map.write().unwrap();
-    token_accounts_map.insert(*result_unlocked.token_a_vault_address(), pool.clone());
-    token_accounts_map.insert(*result_unlocked.token_b_vault_address(), pool.clone());
-    token_accounts_map.insert(*result_unlocked.pool_address(), pool.clone());
+    for account in &dependent_accounts {
+      token_accounts_map.insert(*account, pool.clone());
+    }
+    token_accounts_map.insert(pool_address, pool.clone());
+    drop(token_accounts_map);
+    self.add_account_dependent(dependent_accounts.into_iter().chain([pool_address]), &pool);
+    drop(markets);
+
+    // Link the pool to the curve it succeeds, or track the pool itself if it already graduated
+    self.link_graduation_successor(&*pool.read().unwrap());
+    self.track_graduation(&pool);
+  }
+
+  /// Record `pool` as a dependent of each of `accounts` in `account_dependents`
+  pub(crate) fn add_account_dependent(
+    &self,
+    accounts: impl IntoIterator<Item = Pubkey>,
+    pool: &Arc<RwLock<dyn PoolTrait>>,
+  ) {
+    let mut account_dependents = self.account_dependents.write().unwrap();
+    for account in accounts {
+      let dependents = account_dependents.entry(account).or_default();
+      if !dependents
+        .iter()
+        .any(|dependent| Arc::ptr_eq(dependent, pool))
+      {
+        dependents.push(pool.clone());
+      }
+    }
   }
 }
@@ -0,0 +1,27 @@
+use crate::central_context::central_context::CentralContext;
+use crate::types::pool::PoolTrait;
+
+impl CentralContext {
+  /// Link a newly inserted pool to the graduation it is the successor of, if any, and publish the
+  /// updated graduation
+  ///
+  /// A pool is the successor only if it has the graduation's successor address, either derived
+  /// from the curve or learned from its migration instruction (see
+  /// `process_migration_instruction`). Another pool of the same type and pair isn't enough, anyone
+  /// can create one.
+  pub(crate) fn link_graduation_successor(&self, pool: &dyn PoolTrait) {
+    let mut graduations = self.graduations.write().unwrap();
+    let graduation = graduations.values_mut().find(|graduation| {
+      !graduation.successor_inserted
+        && graduation.successor_pool_type == *pool.pool_type()
+        && graduation.successor_pool_address == Some(*pool.pool_address())
+    });
+    let Some(graduation) = graduation else {
+      return;
+    };
+    graduation.successor_inserted = true;
+    let graduation = graduation.clone();
+    drop(graduations);
+    self.publish_graduation(&graduation);
+  }
+}
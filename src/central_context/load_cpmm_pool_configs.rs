@@ -1,21 +1,26 @@
 use crate::central_context::central_context::CentralContext;
 use crate::constants::RAYDIUM_CONSTANTS;
 use crate::protocol_idls::raydium::CpmmPoolConfigIdl;
+use crate::types::error::Error;
 use borsh::BorshDeserialize;
-use solana_account_decoder::UiAccountEncoding;
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
 use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
 use solana_client::rpc_filter::RpcFilterType;
+use solana_sdk::pubkey::Pubkey;
 
 impl CentralContext {
   /// Load Raydium CPMM pool configurations from on-chain data
   ///
   /// Fetches all Raydium CPMM pool config accounts and populates the `raydium_cpmm_fee_rates_lp`
   /// map with fee rates. This should be called during initialization before processing pools.
-  pub fn load_cpmm_pool_configs(&mut self) {
+  ///
+  /// Returns an error if the RPC request fails. Config accounts that can't be decoded are logged
+  /// and skipped.
+  pub fn load_cpmm_pool_configs(&mut self) -> Result<(), Error> {
     // Fetch the Raydium CPMM pool configs and make a hash map of the pool addresses to their config
     let configs = self
       .json_rpc_client
-      .get_program_accounts_with_config(
+      .get_program_ui_accounts_with_config(
         &RAYDIUM_CONSTANTS.cpmm_program,
         RpcProgramAccountsConfig {
           // The size of the Raydium CPMM pool config account
@@ -32,20 +37,34 @@ impl CentralContext {
           // fill in the rest with defaults
           ..RpcProgramAccountsConfig::default()
         },
-      )
-      .unwrap();
+      )?;
 
     for (pubkey, account) in configs {
-      let decoded_layout: CpmmPoolConfigIdl =
-        CpmmPoolConfigIdl::try_from_slice(&account.data).unwrap();
-
-      self
-        .raydium_cpmm_fee_rates_lp
-        .insert(pubkey, decoded_layout.trade_fee_rate * 1000);
+      match decode_cpmm_pool_config(&pubkey, &account) {
+        Ok(decoded_layout) => {
+          self
+            .raydium_cpmm_fee_rates_lp
+            .insert(pubkey, decoded_layout.trade_fee_rate * 1000);
+        }
+        Err(error) => println!("load_cpmm_pool_configs: Skipping config {}: {}", pubkey, error),
+      }
     }
     println!(
       "INIT app_context: Found {} Raydium CPMM pool configs. Loaded into global app context.",
       self.raydium_cpmm_fee_rates_lp.len()
     );
+    Ok(())
   }
 }
+
+/// Decode a Raydium CPMM pool config account fetched as base64
+fn decode_cpmm_pool_config(
+  pubkey: &Pubkey,
+  account: &UiAccount,
+) -> Result<CpmmPoolConfigIdl, Error> {
+  let data = account
+    .data
+    .decode()
+    .ok_or_else(|| Error::decode(*pubkey, "data isn't binary"))?;
+  CpmmPoolConfigIdl::try_from_slice(&data).map_err(|error| Error::decode(*pubkey, error))
+}
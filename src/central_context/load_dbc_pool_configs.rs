@@ -0,0 +1,58 @@
+use crate::central_context::central_context::CentralContext;
+use crate::constants::MAX_ACCOUNTS_PER_REQUEST;
+use crate::protocol_idls::meteora::DbcPoolConfig;
+use borsh::BorshDeserialize;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+
+impl CentralContext {
+  /// Fetch Meteora DBC pool configs in bulk into the `dbc_pool_configs` cache
+  ///
+  /// Configs already cached are skipped, the rest are fetched with getMultipleAccounts in chunks
+  /// of 100. Called by `load_pools` so every loaded virtual pool can be built from its config.
+  /// Failed requests and accounts that can't be decoded are logged and skipped. Pools left pending
+  /// by `process_token_creation_instruction` are built once their config is cached.
+  pub async fn load_dbc_pool_configs(&self, config_addresses: &[Pubkey]) {
+    let missing_config_addresses: Vec<Pubkey> = {
+      let dbc_pool_configs = self.dbc_pool_configs.read().unwrap();
+      config_addresses
+        .iter()
+        .filter(|config_address| !dbc_pool_configs.contains_key(config_address))
+        .copied()
+        .collect()
+    };
+
+    for chunk in missing_config_addresses.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+      let accounts = match self
+        .json_rpc_client_async
+        .get_multiple_accounts(chunk)
+        .await
+      {
+        Ok(accounts) => accounts,
+        Err(error) => {
+          println!(
+            "load_dbc_pool_configs: getMultipleAccounts failed: {}",
+            error
+          );
+          continue;
+        }
+      };
+      let mut dbc_pool_configs = self.dbc_pool_configs.write().unwrap();
+      for (config_address, account) in chunk.iter().zip(accounts) {
+        let Some(account) = account else {
+          continue;
+        };
+        match DbcPoolConfig::deserialize(&mut account.data.as_slice()) {
+          Ok(config) => {
+            dbc_pool_configs.insert(*config_address, Arc::new(config));
+          }
+          Err(error) => println!(
+            "load_dbc_pool_configs: Failed to decode config {}: {}",
+            config_address, error
+          ),
+        }
+      }
+    }
+    self.build_pending_dbc_pools();
+  }
+}
@@ -0,0 +1,54 @@
+use crate::central_context::central_context::CentralContext;
+use crate::constants::MAX_ACCOUNTS_PER_REQUEST;
+use crate::types::token_metadata::TokenMetadata;
+use solana_sdk::pubkey::Pubkey;
+
+impl CentralContext {
+  /// Fetch the metadata of mints in bulk into `token_registry`
+  ///
+  /// Mints already in the registry are skipped, the rest are fetched with getMultipleAccounts in
+  /// chunks of 100 and stamped with the response's context slot. Failed requests, missing
+  /// accounts and accounts that aren't mints are logged and skipped.
+  pub async fn load_token_metadata(&self, mints: &[Pubkey]) {
+    let missing_mints: Vec<Pubkey> = {
+      let token_registry = self.token_registry.read().unwrap();
+      mints
+        .iter()
+        .filter(|mint| !token_registry.contains_key(mint))
+        .copied()
+        .collect()
+    };
+
+    for chunk in missing_mints.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+      let response = match self
+        .json_rpc_client_async
+        .get_multiple_accounts_with_commitment(chunk, self.json_rpc_client_async.commitment())
+        .await
+      {
+        Ok(response) => response,
+        Err(error) => {
+          println!("load_token_metadata: getMultipleAccounts failed: {}", error);
+          continue;
+        }
+      };
+      let mut token_registry = self.token_registry.write().unwrap();
+      for (mint, account) in chunk.iter().zip(response.value) {
+        let Some(account) = account else {
+          println!("load_token_metadata: Mint {} doesn't exist", mint);
+          continue;
+        };
+        match TokenMetadata::try_from_mint_account(
+          *mint,
+          &account.owner,
+          &account.data,
+          response.context.slot,
+        ) {
+          Ok(token_metadata) => {
+            token_registry.insert(*mint, token_metadata);
+          }
+          Err(error) => println!("load_token_metadata: {}", error),
+        }
+      }
+    }
+  }
+}
@@ -1,4 +1,40 @@
+#[allow(clippy::module_inception)]
 pub mod central_context;
+pub mod central_context_builder;
+mod apply_account_update;
+mod apply_slot_update;
+mod audit_pools;
+mod build_pending_dbc_pools;
 mod fetch_current_slot_blockhash;
+mod fetch_market_state_from_rpc_async;
+mod fetch_token_account_amount;
+mod get_dbc_pool_config;
+mod get_pool_lifecycle;
+mod get_streamed_accounts;
+mod get_token_metadata;
+mod get_ui_prices;
 mod insert_pool;
+mod link_graduation_successor;
 mod load_cpmm_pool_configs;
+mod load_dbc_pool_configs;
+mod load_token_metadata;
+mod pending_dbc_pool_configs;
+mod pools_for_pair;
+mod process_migration_instruction;
+mod process_pool_creation_instruction;
+mod process_token_creation_instruction;
+mod publish_graduation;
+mod publish_market_update;
+mod refetch_accounts;
+mod refresh_pools;
+mod remove_pool;
+mod resolve_loaded_addresses;
+mod restore_snapshot;
+mod run_account_subscriptions;
+#[cfg(feature = "geyser")]
+mod run_geyser_subscription;
+mod save_snapshot;
+mod subscribe_graduations;
+mod subscribe_market_updates;
+mod sweep_pools;
+mod track_graduation;
@@ -0,0 +1,12 @@
+use crate::central_context::central_context::CentralContext;
+use solana_sdk::pubkey::Pubkey;
+
+impl CentralContext {
+  /// Config addresses of DBC pools waiting for their config
+  ///
+  /// `process_token_creation_instruction` never fetches a missing DBC config itself. Pass these
+  /// to `load_dbc_pool_configs` off the ingestion thread to build the pending pools.
+  pub fn pending_dbc_pool_configs(&self) -> Vec<Pubkey> {
+    self.pending_dbc_pools.lock().unwrap().keys().copied().collect()
+  }
+}
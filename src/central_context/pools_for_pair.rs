@@ -0,0 +1,130 @@
+use crate::central_context::central_context::CentralContext;
+use crate::types::pair_filter::PairFilter;
+use crate::types::pair_ranking::PairRanking;
+use crate::types::pool::PoolTrait;
+use crate::types::pool_snapshot::PoolSnapshot;
+use crate::types::swap_direction::SwapDirection;
+use solana_sdk::pubkey::Pubkey;
+use std::cmp::Ordering;
+use std::sync::{Arc, RwLock};
+
+impl CentralContext {
+  /// Get snapshots of the pools trading `token_a_address` against `token_b_address` that pass
+  /// `filter`, best first according to `filter.ranking`
+  ///
+  /// Unhydrated pools are skipped as their reserves and prices are meaningless. Locks are only
+  /// held while copying each pool's state, the returned snapshots are owned. Ties are broken by
+  /// deepest liquidity.
+  pub fn pools_for_pair(
+    self: &Arc<Self>,
+    token_a_address: &Pubkey,
+    token_b_address: &Pubkey,
+    filter: &PairFilter,
+  ) -> Vec<PoolSnapshot> {
+    let Some(pair_pools) = self
+      .markets
+      .read()
+      .unwrap()
+      .get(token_a_address)
+      .and_then(|markets_a| markets_a.get(token_b_address))
+      .cloned()
+    else {
+      return Vec::new();
+    };
+    let pools: Vec<Arc<RwLock<dyn PoolTrait>>> = pair_pools.read().unwrap().clone();
+
+    let mut snapshots: Vec<PoolSnapshot> = pools
+      .iter()
+      .filter_map(|pool| snapshot_pool(self, &*pool.read().unwrap(), token_a_address, filter))
+      .collect();
+    snapshots.sort_by(|first, second| {
+      match filter.ranking {
+        PairRanking::BestPrice { .. } => second.amount_out.cmp(&first.amount_out),
+        PairRanking::DeepestLiquidity => Ordering::Equal,
+        // Unknown fees last
+        PairRanking::LowestFee => (first.total_swap_fee_lp.is_none(), first.total_swap_fee_lp)
+          .cmp(&(second.total_swap_fee_lp.is_none(), second.total_swap_fee_lp)),
+      }
+      .then_with(|| second.token_a_amount_units.cmp(&first.token_a_amount_units))
+    });
+    snapshots
+  }
+}
+
+/// Copy the state of a pool oriented along the queried pair, `None` if it doesn't pass `filter`
+fn snapshot_pool(
+  central_context: &Arc<CentralContext>,
+  pool: &dyn PoolTrait,
+  token_a_address: &Pubkey,
+  filter: &PairFilter,
+) -> Option<PoolSnapshot> {
+  if !pool.is_hydrated()
+    || filter
+      .pool_types
+      .as_ref()
+      .is_some_and(|pool_types| !pool_types.contains(pool.pool_type()))
+  {
+    return None;
+  }
+  let lifecycle = central_context.get_pool_lifecycle(pool);
+  if filter
+    .lifecycles
+    .as_ref()
+    .is_some_and(|lifecycles| !lifecycles.contains(&lifecycle))
+  {
+    return None;
+  }
+
+  let reversed = pool.token_a_address() != token_a_address;
+  let (token_a_amount_units, token_b_amount_units) = if reversed {
+    (pool.token_b_amount_units(), pool.token_a_amount_units())
+  } else {
+    (pool.token_a_amount_units(), pool.token_b_amount_units())
+  };
+  if token_a_amount_units < filter.min_liquidity {
+    return None;
+  }
+  let total_swap_fee_lp = pool.try_total_swap_fee_lp(central_context).ok();
+  // Pools whose fee is unknown can't be shown to be under the maximum
+  if let Some(max_fee_lp) = filter.max_fee_lp
+    && total_swap_fee_lp.is_none_or(|total_swap_fee_lp| total_swap_fee_lp > max_fee_lp)
+  {
+    return None;
+  }
+  let (price_a_over_b_lp, price_b_over_a_lp) = if reversed {
+    (pool.try_price_b_over_a_lp(), pool.try_price_a_over_b_lp())
+  } else {
+    (pool.try_price_a_over_b_lp(), pool.try_price_b_over_a_lp())
+  };
+  let amount_out = match filter.ranking {
+    PairRanking::BestPrice { amount_in } => {
+      let direction = if reversed {
+        SwapDirection::BToA
+      } else {
+        SwapDirection::AToB
+      };
+      pool.try_quote(amount_in, direction, central_context).ok()
+    }
+    _ => None,
+  };
+
+  Some(PoolSnapshot {
+    pool_address: *pool.pool_address(),
+    pool_type: *pool.pool_type(),
+    token_a_address: *token_a_address,
+    token_b_address: if reversed {
+      *pool.token_a_address()
+    } else {
+      *pool.token_b_address()
+    },
+    reversed,
+    token_a_amount_units,
+    token_b_amount_units,
+    price_a_over_b_lp: price_a_over_b_lp.ok(),
+    price_b_over_a_lp: price_b_over_a_lp.ok(),
+    total_swap_fee_lp,
+    lifecycle,
+    last_updated_slot: pool.last_updated_slot(),
+    amount_out,
+  })
+}
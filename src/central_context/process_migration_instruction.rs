@@ -0,0 +1,148 @@
+use crate::central_context::central_context::CentralContext;
+use crate::constants::{METEORA_CONSTANTS, RAYDIUM_CONSTANTS};
+use crate::types::graduation::Graduation;
+use crate::types::instruction::Instruction;
+use crate::types::pools::Pools;
+use solana_sdk::pubkey::Pubkey;
+
+impl CentralContext {
+  /// Detect a bonding curve migration instruction and link the curve to the pool it migrates to
+  ///
+  /// Recognizes Meteora DBC `migrate_meteora_damm` and `migration_damm_v2`, and Raydium launchpad
+  /// `migrate_to_amm` and `migrate_to_cpswap` instructions, top level or inner. Their successor
+  /// pool address can't be derived from the curve, this is how it is learned. The curve's
+  /// graduation is recorded (or updated) with the successor address and published, the successor
+  /// is marked inserted if it is already known, otherwise `insert_pool` links it once it is. DBC
+  /// successors aren't created by an instruction this crate decodes, fetch them with
+  /// `load_graduation_successors` off the ingestion thread. Pumpfun migrations don't need this,
+  /// their successor address is derived.
+  ///
+  /// Returns the updated graduation, or `None` if the instruction is not a migration, the curve
+  /// is unknown, or the graduation already had this successor.
+  pub fn process_migration_instruction(
+    &self,
+    instruction: &Instruction,
+    slot: u64,
+  ) -> Option<Graduation> {
+    let (curve_address, successor_pool_type, successor_pool_address) =
+      decode_migration_instruction(instruction)?;
+
+    // Read the pools before taking the graduations lock, see the lock order on `CentralContext`
+    let curve = self
+      .pools_map
+      .read()
+      .unwrap()
+      .get(&curve_address)
+      .and_then(|pool| {
+        let pool = pool.read().unwrap();
+        (*pool.pool_address() == curve_address).then(|| {
+          (
+            *pool.pool_type(),
+            *pool.token_a_address(),
+            *pool.token_b_address(),
+          )
+        })
+      });
+    let successor_inserted = self
+      .pools_map
+      .read()
+      .unwrap()
+      .get(&successor_pool_address)
+      .is_some_and(|pool| {
+        let pool = pool.read().unwrap();
+        *pool.pool_address() == successor_pool_address && *pool.pool_type() == successor_pool_type
+      });
+
+    let mut graduations = self.graduations.write().unwrap();
+    // The graduation outlives the curve, it may have been swept already
+    let graduation = match graduations.get_mut(&curve_address) {
+      Some(graduation) => {
+        if graduation.successor_pool_address == Some(successor_pool_address)
+          && graduation.successor_inserted == successor_inserted
+        {
+          return None;
+        }
+        graduation.successor_pool_type = successor_pool_type;
+        graduation.successor_pool_address = Some(successor_pool_address);
+        graduation.successor_inserted = successor_inserted;
+        graduation.clone()
+      }
+      None => {
+        let (pool_type, token_a_address, token_b_address) = curve?;
+        let graduation = Graduation {
+          pool_address: curve_address,
+          pool_type,
+          token_a_address,
+          token_b_address,
+          successor_pool_type,
+          successor_pool_address: Some(successor_pool_address),
+          successor_inserted,
+          slot,
+        };
+        graduations.insert(curve_address, graduation.clone());
+        graduation
+      }
+    };
+    drop(graduations);
+    self.publish_graduation(&graduation);
+    Some(graduation)
+  }
+}
+
+/**
+Decode a migration instruction into the curve address, the successor pool type and the successor
+pool address. Returns `None` if the instruction is not a migration or has too few accounts.
+
+Accounts:
+- DBC `migrate_meteora_damm` and `migration_damm_v2`: virtual_pool, migration_metadata, config,
+  pool_authority, pool, ...
+- Launchpad `migrate_to_amm`: payer, base_mint, quote_mint, openbook_program, market,
+  request_queue, event_queue, bids, asks, market_vault_signer, market_base_vault,
+  market_quote_vault, amm_program, amm_pool, amm_authority, amm_open_orders, amm_lp_mint,
+  amm_base_vault, amm_quote_vault, amm_target_orders, amm_config, amm_create_fee_destination,
+  authority, pool_state, ...
+- Launchpad `migrate_to_cpswap`: payer, base_mint, quote_mint, platform_config, cpswap_program,
+  cpswap_pool, cpswap_authority, cpswap_lp_mint, cpswap_base_vault, cpswap_quote_vault,
+  cpswap_config, cpswap_create_pool_fee, cpswap_observation, lock_program, lock_authority,
+  lock_lp_vault, authority, pool_state, ...
+*/
+fn decode_migration_instruction(instruction: &Instruction) -> Option<(Pubkey, Pools, Pubkey)> {
+  let program_id = instruction.program_id()?;
+  let (curve_position, successor_pool_type, successor_position) =
+    if *program_id == METEORA_CONSTANTS.dbc_program {
+      if instruction
+        .data
+        .starts_with(&METEORA_CONSTANTS.dbc_migrate_damm_discriminator)
+      {
+        (0, Pools::MeteoraAmm, 4)
+      } else if instruction
+        .data
+        .starts_with(&METEORA_CONSTANTS.dbc_migrate_dammv2_discriminator)
+      {
+        (0, Pools::MeteoraDammV2, 4)
+      } else {
+        return None;
+      }
+    } else if *program_id == RAYDIUM_CONSTANTS.launchpad_program {
+      if instruction
+        .data
+        .starts_with(&RAYDIUM_CONSTANTS.launchpad_migrate_to_amm_discriminator)
+      {
+        (23, Pools::RaydiumAmmV4, 13)
+      } else if instruction
+        .data
+        .starts_with(&RAYDIUM_CONSTANTS.launchpad_migrate_to_cpswap_discriminator)
+      {
+        (17, Pools::RaydiumCpmm, 5)
+      } else {
+        return None;
+      }
+    } else {
+      return None;
+    };
+  Some((
+    *instruction.account(curve_position)?,
+    successor_pool_type,
+    *instruction.account(successor_position)?,
+  ))
+}
@@ -0,0 +1,47 @@
+use crate::central_context::central_context::CentralContext;
+use crate::types::instruction::Instruction;
+use crate::types::market_update::MarketUpdate;
+use crate::types::pool::PoolTrait;
+use crate::types::pumpswap_pool::PumpswapPool;
+use crate::types::raydium_ammv4_pool::RaydiumAmmV4Pool;
+use crate::types::raydium_cpmm_pool::RaydiumCpmmPool;
+use std::sync::{Arc, RwLock};
+
+impl CentralContext {
+  /// Detect a pool creation instruction and insert the new pool into the central context
+  ///
+  /// Recognizes Pumpswap `create_pool`, Raydium CPMM `initialize` and Raydium AMMv4 `initialize2`
+  /// instructions, top level or inner. The pool is built from the instruction's accounts and data,
+  /// including its initial reserves, and inserted with `insert_pool` so it can be traded without
+  /// waiting for a `load_pools` rescan, with `slot` (the transaction's slot) as its
+  /// `last_updated_slot`. Its initial price is published as a `MarketUpdate`.
+  /// Returns the inserted pool, or `None` if the instruction does not create a pool or the pool is
+  /// already known.
+  pub fn process_pool_creation_instruction(
+    &self,
+    instruction: &Instruction,
+    slot: u64,
+  ) -> Option<Arc<RwLock<dyn PoolTrait>>> {
+    let pool: Arc<RwLock<dyn PoolTrait>> =
+      if let Some(pool) = PumpswapPool::from_create_pool_instruction(instruction, slot) {
+        Arc::new(RwLock::new(pool))
+      } else if let Some(pool) = RaydiumCpmmPool::from_initialize_instruction(instruction, slot) {
+        Arc::new(RwLock::new(pool))
+      } else if let Some(pool) = RaydiumAmmV4Pool::from_initialize2_instruction(instruction, slot) {
+        Arc::new(RwLock::new(pool))
+      } else {
+        return None;
+      };
+
+    // The same creation can be observed more than once (e.g. from multiple streams)
+    let pool_address = *pool.read().unwrap().pool_address();
+    if self.pools_map.read().unwrap().contains_key(&pool_address) {
+      return None;
+    }
+    self.insert_pool(pool.clone());
+    if let Some(market_update) = MarketUpdate::from_pool(&*pool.read().unwrap()) {
+      self.publish_market_update(&market_update);
+    }
+    Some(pool)
+  }
+}
@@ -0,0 +1,126 @@
+use crate::central_context::central_context::CentralContext;
+use crate::types::instruction::Instruction;
+use crate::types::market_update::MarketUpdate;
+use crate::types::meteora_dbc::MeteoraDbc;
+use crate::types::pending_dbc_pool::PendingDbcPool;
+use crate::types::pf_bonding_curve::PfBondingCurve;
+use crate::types::pool::PoolTrait;
+use crate::types::raydium_launchpad::RaydiumLaunchpad;
+use crate::types::token_creation::TokenCreation;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+
+impl CentralContext {
+  /// Detect a token creation instruction and cache its new market in the central context
+  ///
+  /// Recognizes Pumpfun create/create_v2, Raydium launchpad initialize and Meteora DBC virtual
+  /// pool initialize instructions. In the same pass the matching `PfBondingCurve`,
+  /// `RaydiumLaunchpad` or `MeteoraDbc` is added to its cache and inserted with `insert_pool`.
+  /// DBC pools need their config, which is only read from `dbc_pool_configs`: if it isn't cached
+  /// the token creation is still returned but the pool is kept pending until
+  /// `load_dbc_pool_configs` loads the config (see `pending_dbc_pool_configs`). Returns the
+  /// `TokenCreation` event, or `None` if the instruction does not create a token.
+  pub fn process_token_creation_instruction(
+    &self,
+    instruction: &Instruction,
+    block_time: u64,
+    slot: u64,
+    index: u64,
+    atomic_instruction_index: u8,
+    signature: Signature,
+  ) -> Option<TokenCreation> {
+    if let Some((bonding_curve, token_creation)) = PfBondingCurve::from_create_instruction(
+      instruction,
+      block_time,
+      slot,
+      index,
+      atomic_instruction_index,
+      signature,
+    ) {
+      self.cache_token_creation_pool(&self.pf_bonding_curves, bonding_curve);
+      return Some(token_creation);
+    }
+
+    if let Some((launchpad, token_creation)) = RaydiumLaunchpad::from_initialize_instruction(
+      instruction,
+      block_time,
+      slot,
+      index,
+      atomic_instruction_index,
+      signature,
+    ) {
+      self.cache_token_creation_pool(&self.raydium_launchpads, launchpad);
+      return Some(token_creation);
+    }
+
+    let config_address = MeteoraDbc::initialize_instruction_config(instruction)?;
+    let token_creation = MeteoraDbc::token_creation_from_initialize_instruction(
+      instruction,
+      block_time,
+      slot,
+      index,
+      atomic_instruction_index,
+      signature,
+    )?;
+    let dbc_pool_config = self
+      .dbc_pool_configs
+      .read()
+      .unwrap()
+      .get(&config_address)
+      .cloned();
+    match dbc_pool_config {
+      Some(dbc_pool_config) => {
+        let (dbc, _) = MeteoraDbc::from_initialize_instruction(
+          instruction,
+          &dbc_pool_config,
+          block_time,
+          slot,
+          index,
+          atomic_instruction_index,
+          signature,
+        )?;
+        self.cache_token_creation_pool(&self.meteora_dbcs, dbc);
+      }
+      // Don't block the ingestion thread on RPC, the pool is built once the config is loaded
+      None => self
+        .pending_dbc_pools
+        .lock()
+        .unwrap()
+        .entry(config_address)
+        .or_default()
+        .push(PendingDbcPool::new(
+          instruction,
+          block_time,
+          slot,
+          index,
+          atomic_instruction_index,
+          signature,
+        )),
+    }
+    Some(token_creation)
+  }
+
+  /// Add a newly created market to its protocol cache and insert it into the market graph, unless
+  /// the same market was already seen. The new market's initial price is published as a
+  /// `MarketUpdate`.
+  pub(crate) fn cache_token_creation_pool<T: PoolTrait>(
+    &self,
+    cache: &Mutex<HashMap<Pubkey, Arc<RwLock<T>>>>,
+    pool: T,
+  ) {
+    let pool_address = *pool.pool_address();
+    let mut cache = cache.lock().unwrap();
+    if cache.contains_key(&pool_address) {
+      return;
+    }
+    let market_update = MarketUpdate::from_pool(&pool);
+    let pool = Arc::new(RwLock::new(pool));
+    cache.insert(pool_address, pool.clone());
+    self.insert_pool(pool);
+    if let Some(market_update) = market_update {
+      self.publish_market_update(&market_update);
+    }
+  }
+}
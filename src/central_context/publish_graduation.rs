@@ -0,0 +1,20 @@
+use crate::central_context::central_context::CentralContext;
+use crate::types::graduation::Graduation;
+use std::sync::mpsc::TrySendError;
+
+impl CentralContext {
+  /// Push a `Graduation` to every graduation subscriber. Full channels drop the event, subscribers
+  /// whose receiver was dropped are removed.
+  pub(crate) fn publish_graduation(&self, graduation: &Graduation) {
+    self
+      .graduation_subscribers
+      .lock()
+      .unwrap()
+      .retain(|sender| {
+        !matches!(
+          sender.try_send(graduation.clone()),
+          Err(TrySendError::Disconnected(_))
+        )
+      });
+  }
+}
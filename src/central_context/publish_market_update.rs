@@ -0,0 +1,45 @@
+use crate::central_context::central_context::CentralContext;
+use crate::types::backpressure_policy::BackpressurePolicy;
+use crate::types::market_update::MarketUpdate;
+use std::sync::Arc;
+use std::sync::mpsc::TrySendError;
+
+impl CentralContext {
+  /// Push a `MarketUpdate` to every subscriber whose subscription matches it
+  ///
+  /// Called internally from the context's update paths. Can also be called directly after
+  /// updating a pool outside the context. The subscriber list is only locked to pick the matching
+  /// subscribers, so a `BackpressurePolicy::Block` subscriber with a full channel doesn't stop
+  /// others from subscribing. Subscribers whose receiver was dropped are removed.
+  pub fn publish_market_update(&self, update: &MarketUpdate) {
+    let subscribers: Vec<_> = self
+      .market_update_subscribers
+      .lock()
+      .unwrap()
+      .iter()
+      .filter(|subscriber| subscriber.subscription.matches(update))
+      .cloned()
+      .collect();
+    let disconnected: Vec<_> = subscribers
+      .into_iter()
+      .filter(|subscriber| match subscriber.backpressure_policy {
+        BackpressurePolicy::Block => subscriber.sender.send(update.clone()).is_err(),
+        BackpressurePolicy::DropNewest => matches!(
+          subscriber.sender.try_send(update.clone()),
+          Err(TrySendError::Disconnected(_))
+        ),
+      })
+      .collect();
+    if !disconnected.is_empty() {
+      self
+        .market_update_subscribers
+        .lock()
+        .unwrap()
+        .retain(|subscriber| {
+          !disconnected
+            .iter()
+            .any(|disconnected| Arc::ptr_eq(disconnected, subscriber))
+        });
+    }
+  }
+}
@@ -0,0 +1,67 @@
+use crate::central_context::central_context::CentralContext;
+use crate::constants::MAX_ACCOUNTS_PER_REQUEST;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+
+impl CentralContext {
+  /// Fetch the current data of the given pool dependent accounts with getMultipleAccounts, in
+  /// chunks of 100, and feed it through `apply_account_update` at each response's context slot
+  ///
+  /// Failed requests are logged and skipped. Returns the accounts that were fetched and exist.
+  pub(crate) fn refetch_accounts(&self, accounts: &[Pubkey]) -> HashSet<Pubkey> {
+    let mut fetched_accounts = HashSet::new();
+    for chunk in accounts.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+      match self
+        .json_rpc_client
+        .get_multiple_accounts_with_commitment(chunk, self.json_rpc_client.commitment())
+      {
+        Ok(response) => fetched_accounts.extend(self.apply_fetched_accounts(
+          chunk,
+          response.value,
+          response.context.slot,
+        )),
+        Err(error) => println!("refetch_accounts: getMultipleAccounts failed: {}", error),
+      }
+    }
+    fetched_accounts
+  }
+
+  /// Async version of `refetch_accounts` using `json_rpc_client_async`
+  pub(crate) async fn refetch_accounts_async(&self, accounts: &[Pubkey]) -> HashSet<Pubkey> {
+    let mut fetched_accounts = HashSet::new();
+    for chunk in accounts.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+      match self
+        .json_rpc_client_async
+        .get_multiple_accounts_with_commitment(chunk, self.json_rpc_client_async.commitment())
+        .await
+      {
+        Ok(response) => fetched_accounts.extend(self.apply_fetched_accounts(
+          chunk,
+          response.value,
+          response.context.slot,
+        )),
+        Err(error) => println!("refetch_accounts: getMultipleAccounts failed: {}", error),
+      }
+    }
+    fetched_accounts
+  }
+
+  /// Apply one getMultipleAccounts response, all at the same slot. Accounts that don't exist are
+  /// skipped. Returns the accounts that exist.
+  pub(crate) fn apply_fetched_accounts(
+    &self,
+    addresses: &[Pubkey],
+    accounts: Vec<Option<Account>>,
+    slot: u64,
+  ) -> Vec<Pubkey> {
+    let mut fetched_accounts = Vec::with_capacity(addresses.len());
+    for (address, account) in addresses.iter().zip(accounts) {
+      if let Some(account) = account {
+        self.apply_account_update(address, &account.owner, &account.data, slot, None);
+        fetched_accounts.push(*address);
+      }
+    }
+    fetched_accounts
+  }
+}
@@ -0,0 +1,125 @@
+use crate::central_context::central_context::CentralContext;
+use crate::types::market_update::MarketUpdate;
+use crate::types::pool::PoolTrait;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+
+impl CentralContext {
+  /// Refresh the state of many pools at once
+  ///
+  /// Collects every account the pools depend on (pool state, vaults, ...) and fetches them with
+  /// getMultipleAccounts in chunks of 100 instead of the two or three sequential RPCs per pool
+  /// `fetch_market_state_from_rpc` makes. SPL token balances are decoded locally and each chunk is
+  /// applied through `apply_account_update` in one pass at the chunk's slot, so changes are
+  /// published as `MarketUpdate`s. Pools not in `pools_map` are ignored.
+  ///
+  /// Dependent accounts only known once the first pass is applied (e.g. the LP mints of Meteora
+  /// vaults) are added to `pools_map` and fetched in a second pass. Pools whose dependent accounts
+  /// were all fetched are marked as hydrated (see `PoolTrait::is_hydrated`) and publish their first
+  /// `MarketUpdate`.
+  pub fn refresh_pools(&self, pool_addresses: &[Pubkey]) {
+    let pools = self.get_known_pools(pool_addresses);
+    let requested_accounts = get_pools_dependent_accounts(&pools);
+    let mut fetched_accounts = self.refetch_accounts(&requested_accounts);
+    let new_accounts = self.register_new_dependent_accounts(&pools, &requested_accounts);
+    if !new_accounts.is_empty() {
+      fetched_accounts.extend(self.refetch_accounts(&new_accounts));
+    }
+    self.mark_pools_hydrated(&pools, &fetched_accounts);
+  }
+
+  /// Async version of `refresh_pools` using `json_rpc_client_async`
+  pub async fn refresh_pools_async(&self, pool_addresses: &[Pubkey]) {
+    let pools = self.get_known_pools(pool_addresses);
+    let requested_accounts = get_pools_dependent_accounts(&pools);
+    let mut fetched_accounts = self.refetch_accounts_async(&requested_accounts).await;
+    let new_accounts = self.register_new_dependent_accounts(&pools, &requested_accounts);
+    if !new_accounts.is_empty() {
+      fetched_accounts.extend(self.refetch_accounts_async(&new_accounts).await);
+    }
+    self.mark_pools_hydrated(&pools, &fetched_accounts);
+  }
+
+  /// Get the deduplicated pools in `pool_addresses` that are in `pools_map`
+  pub(crate) fn get_known_pools(&self, pool_addresses: &[Pubkey]) -> Vec<Arc<RwLock<dyn PoolTrait>>> {
+    let pools_map = self.pools_map.read().unwrap();
+    let mut seen = HashSet::new();
+    pool_addresses
+      .iter()
+      .filter_map(|pool_address| pools_map.get(pool_address))
+      .filter(|pool| seen.insert(*pool.read().unwrap().pool_address()))
+      .cloned()
+      .collect()
+  }
+
+  /// Add the dependent accounts of unhydrated pools that weren't in `requested_accounts` to
+  /// `pools_map` so their updates are routed to the pool, returning them. Accounts already mapped
+  /// to another pool (shared Meteora vaults) keep their mapping, the pool is only added to their
+  /// `account_dependents`.
+  pub(crate) fn register_new_dependent_accounts(
+    &self,
+    pools: &[Arc<RwLock<dyn PoolTrait>>],
+    requested_accounts: &[Pubkey],
+  ) -> Vec<Pubkey> {
+    let requested_accounts: HashSet<&Pubkey> = requested_accounts.iter().collect();
+    let mut pools_map = self.pools_map.write().unwrap();
+    let mut seen = HashSet::new();
+    let mut new_accounts = Vec::new();
+    for pool in pools {
+      let pool_unlocked = pool.read().unwrap();
+      if pool_unlocked.is_hydrated() {
+        continue;
+      }
+      for account in pool_unlocked.dependent_accounts() {
+        if !requested_accounts.contains(&account) && seen.insert(account) {
+          pools_map.entry(account).or_insert_with(|| pool.clone());
+          new_accounts.push(account);
+        }
+      }
+    }
+    drop(pools_map);
+    for pool in pools {
+      let dependent_accounts = pool.read().unwrap().dependent_accounts();
+      self.add_account_dependent(dependent_accounts, pool);
+    }
+    new_accounts
+  }
+
+  /// Mark the unhydrated pools whose dependent accounts are all in `fetched_accounts` as hydrated
+  /// and publish their prices
+  pub(crate) fn mark_pools_hydrated(
+    &self,
+    pools: &[Arc<RwLock<dyn PoolTrait>>],
+    fetched_accounts: &HashSet<Pubkey>,
+  ) {
+    for pool in pools {
+      let market_update = {
+        let mut pool_unlocked = pool.write().unwrap();
+        if pool_unlocked.is_hydrated()
+          || !pool_unlocked
+            .dependent_accounts()
+            .iter()
+            .all(|account| fetched_accounts.contains(account))
+        {
+          continue;
+        }
+        pool_unlocked.set_hydrated();
+        MarketUpdate::from_pool(&*pool_unlocked)
+      };
+      if let Some(market_update) = market_update {
+        self.publish_market_update(&market_update);
+      }
+    }
+  }
+}
+
+/// Get the deduplicated dependent accounts of every pool
+fn get_pools_dependent_accounts(pools: &[Arc<RwLock<dyn PoolTrait>>]) -> Vec<Pubkey> {
+  let mut seen = HashSet::new();
+  pools
+    .iter()
+    .flat_map(|pool| pool.read().unwrap().dependent_accounts())
+    .filter(|account| seen.insert(*account))
+    .collect()
+}
@@ -0,0 +1,97 @@
+use crate::central_context::central_context::CentralContext;
+use crate::types::pool::PoolTrait;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::{Arc, RwLock};
+
+impl CentralContext {
+  /// Remove a pool from the central context
+  ///
+  /// Removes the pool from both sides of its bidirectional market entry (dropping the token pair,
+  /// and a token with no pairs left, once the last pool of the pair is gone), from every
+  /// `pools_map` key that maps to it along with their update versions, and from the bonding curve
+  /// caches. Accounts shared with other pools (e.g. Meteora vaults) are handed over to one of those
+  /// pools in `pools_map` and keep their update versions.
+  ///
+  /// Returns the removed pool, or `None` if no pool is registered at `pool_address`.
+  pub fn remove_pool(&self, pool_address: &Pubkey) -> Option<Arc<RwLock<dyn PoolTrait>>> {
+    let pool = {
+      let pools_map = self.pools_map.read().unwrap();
+      let pool = pools_map.get(pool_address)?;
+      // The key may be a dependent account of the pool rather than the pool itself
+      if pool.read().unwrap().pool_address() != pool_address {
+        return None;
+      }
+      pool.clone()
+    };
+    let (token_a_address, token_b_address, dependent_accounts) = {
+      let pool_unlocked = pool.read().unwrap();
+      (
+        *pool_unlocked.token_a_address(),
+        *pool_unlocked.token_b_address(),
+        pool_unlocked.dependent_accounts(),
+      )
+    };
+
+    // Both sides of the market entry share the same vec, removing from it once is enough
+    let mut markets = self.markets.write().unwrap();
+    let pair_markets = markets
+      .get(&token_a_address)
+      .and_then(|markets_a| markets_a.get(&token_b_address))
+      .cloned();
+    if let Some(pair_markets) = pair_markets {
+      let mut pair_markets = pair_markets.write().unwrap();
+      pair_markets.retain(|market| market.read().unwrap().pool_address() != pool_address);
+      if pair_markets.is_empty() {
+        for (token, other_token) in [
+          (token_a_address, token_b_address),
+          (token_b_address, token_a_address),
+        ] {
+          if let Some(token_markets) = markets.get_mut(&token) {
+            token_markets.remove(&other_token);
+            if token_markets.is_empty() {
+              markets.remove(&token);
+            }
+          }
+        }
+      }
+    }
+    drop(markets);
+
+    let mut pools_map = self.pools_map.write().unwrap();
+    let mut account_dependents = self.account_dependents.write().unwrap();
+    let mut account_update_versions = self.account_update_versions.write().unwrap();
+    for account in dependent_accounts.iter().chain([pool_address]) {
+      let remaining_dependent = account_dependents.get_mut(account).and_then(|dependents| {
+        dependents.retain(|dependent| !Arc::ptr_eq(dependent, &pool));
+        dependents.first().cloned()
+      });
+      if pools_map
+        .get(account)
+        .is_some_and(|mapped_pool| Arc::ptr_eq(mapped_pool, &pool))
+      {
+        // Hand a shared account over to another pool depending on it
+        match remaining_dependent.clone() {
+          Some(remaining_dependent) => {
+            pools_map.insert(*account, remaining_dependent);
+          }
+          None => {
+            pools_map.remove(account);
+          }
+        }
+      }
+      if remaining_dependent.is_none() {
+        account_dependents.remove(account);
+        account_update_versions.remove(account);
+      }
+    }
+    drop(account_update_versions);
+    drop(account_dependents);
+    drop(pools_map);
+
+    self.pf_bonding_curves.lock().unwrap().remove(pool_address);
+    self.raydium_launchpads.lock().unwrap().remove(pool_address);
+    self.meteora_dbcs.lock().unwrap().remove(pool_address);
+    self.closed_pools.write().unwrap().remove(pool_address);
+    Some(pool)
+  }
+}
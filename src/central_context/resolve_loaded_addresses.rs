@@ -0,0 +1,77 @@
+use crate::central_context::central_context::CentralContext;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::message::v0::LoadedAddresses;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+
+/// Lookup table accounts start with a 56 byte metadata header followed by the stored addresses
+const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+impl CentralContext {
+  /// Resolve the addresses a v0 message loads from address lookup tables
+  ///
+  /// Used when the transaction status meta isn't available, e.g. for transactions seen before they
+  /// land. Lookup tables are read from the `address_lookup_tables` cache and fetched from JSON RPC
+  /// when missing. Tables can be extended, so a cached table that is too short for an index is
+  /// fetched again. Returns empty loaded addresses for legacy messages and `None` if a table can't
+  /// be fetched or doesn't contain an index.
+  pub fn resolve_loaded_addresses(&self, message: &VersionedMessage) -> Option<LoadedAddresses> {
+    let mut loaded_addresses = LoadedAddresses::default();
+    let Some(lookups) = message.address_table_lookups() else {
+      return Some(loaded_addresses);
+    };
+    let mut readonly = Vec::new();
+    for lookup in lookups {
+      let max_index = lookup
+        .writable_indexes
+        .iter()
+        .chain(&lookup.readonly_indexes)
+        .max()
+        .map_or(0, |index| *index as usize);
+      let mut table = self.get_address_lookup_table(&lookup.account_key, false)?;
+      if max_index >= table.len() {
+        table = self.get_address_lookup_table(&lookup.account_key, true)?;
+      }
+      for index in &lookup.writable_indexes {
+        loaded_addresses.writable.push(*table.get(*index as usize)?);
+      }
+      for index in &lookup.readonly_indexes {
+        readonly.push(*table.get(*index as usize)?);
+      }
+    }
+    // All writable addresses across tables come before all readonly addresses
+    loaded_addresses.readonly = readonly;
+    Some(loaded_addresses)
+  }
+
+  /// Get the addresses stored in a lookup table, from cache unless `refresh` is set
+  fn get_address_lookup_table(
+    &self,
+    table_address: &Pubkey,
+    refresh: bool,
+  ) -> Option<Arc<Vec<Pubkey>>> {
+    if !refresh
+      && let Some(table) = self
+        .address_lookup_tables
+        .read()
+        .unwrap()
+        .get(table_address)
+    {
+      return Some(table.clone());
+    }
+    let data = self.json_rpc_client.get_account(table_address).ok()?.data;
+    let table = Arc::new(
+      data
+        .get(LOOKUP_TABLE_META_SIZE..)?
+        .chunks_exact(32)
+        .map(|address| Pubkey::new_from_array(address.try_into().unwrap()))
+        .collect::<Vec<Pubkey>>(),
+    );
+    self
+      .address_lookup_tables
+      .write()
+      .unwrap()
+      .insert(*table_address, table.clone());
+    Some(table)
+  }
+}
@@ -0,0 +1,174 @@
+use crate::central_context::central_context::CentralContext;
+use crate::constants::{SNAPSHOT_MAGIC, SNAPSHOT_VERSION};
+use crate::types::meteora_amm_pool::MeteoraAmmPool;
+use crate::types::meteora_dammv2_pool::MeteoraDammV2Pool;
+use crate::types::meteora_dbc::MeteoraDbc;
+use crate::types::meteora_vault::MeteoraVault;
+use crate::types::pf_bonding_curve::PfBondingCurve;
+use crate::types::pool::PoolTrait;
+use crate::types::pools::Pools;
+use crate::types::pumpswap_pool::PumpswapPool;
+use crate::types::raydium_ammv4_pool::RaydiumAmmV4Pool;
+use crate::types::raydium_cpmm_pool::RaydiumCpmmPool;
+use crate::types::raydium_launchpad::RaydiumLaunchpad;
+use borsh::BorshDeserialize;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+/// A pool read from a snapshot, before it is inserted into the context
+pub(super) enum RestoredPool {
+  MeteoraAmm(MeteoraAmmPool),
+  MeteoraDbc(MeteoraDbc),
+  PfBondingCurve(PfBondingCurve),
+  RaydiumLaunchpad(RaydiumLaunchpad),
+  Other(Arc<RwLock<dyn PoolTrait>>),
+}
+
+/// Everything read from a snapshot file
+struct RestoredState {
+  slot: u64,
+  meteora_vaults: Vec<MeteoraVault>,
+  pools: Vec<RestoredPool>,
+  raydium_cpmm_fee_rates_lp: HashMap<Pubkey, u64>,
+  legit_tokens: HashMap<Pubkey, bool>,
+  account_update_versions: HashMap<Pubkey, (u64, u64)>,
+}
+
+impl CentralContext {
+  /// Restore the state written by `save_snapshot` into this context
+  ///
+  /// Pools are inserted with `insert_pool` and added back to the bonding curve, launchpad and DBC
+  /// caches, Meteora pools are linked to the restored `meteora_vault_cache` vaults, and
+  /// `current_slot` advances to the snapshot slot. The pools' state is as of the snapshot slot,
+  /// catch up on what changed since with `refresh_pools` or an account stream, whose updates older
+  /// than the restored update versions are rejected.
+  ///
+  /// The whole file is decoded before anything is applied, so a missing, corrupt or incompatible
+  /// (other `SNAPSHOT_VERSION`) snapshot returns an error and leaves the context untouched. Callers
+  /// should fall back to a full load in that case, see `load_pools_from_snapshot`. Returns the
+  /// slot of the snapshot.
+  pub fn restore_snapshot(&mut self, path: &Path) -> io::Result<u64> {
+    let data = fs::read(path)?;
+    let state = read_snapshot(&mut data.as_slice())?;
+
+    self.apply_slot_update(state.slot);
+
+    {
+      let mut vault_cache = self.meteora_vault_cache.lock().unwrap();
+      for vault in state.meteora_vaults {
+        vault_cache
+          .entry(vault.token_address)
+          .or_insert_with(|| Arc::new(RwLock::new(vault)));
+      }
+    }
+
+    for pool in state.pools {
+      let pool: Arc<RwLock<dyn PoolTrait>> = match pool {
+        RestoredPool::MeteoraAmm(mut pool) => {
+          // Share the vaults with the other pools of the same tokens again
+          let mut vault_cache = self.meteora_vault_cache.lock().unwrap();
+          for vault in [&mut pool.token_a_vault, &mut pool.token_b_vault] {
+            let token_address = vault.read().unwrap().token_address;
+            *vault = vault_cache.entry(token_address).or_insert_with(|| vault.clone()).clone();
+          }
+          Arc::new(RwLock::new(pool))
+        }
+        RestoredPool::MeteoraDbc(pool) => {
+          let address = *pool.pool_address();
+          let pool = Arc::new(RwLock::new(pool));
+          self.meteora_dbcs.lock().unwrap().insert(address, pool.clone());
+          pool
+        }
+        RestoredPool::PfBondingCurve(pool) => {
+          let address = pool.bonding_curve_address;
+          let pool = Arc::new(RwLock::new(pool));
+          self.pf_bonding_curves.lock().unwrap().insert(address, pool.clone());
+          pool
+        }
+        RestoredPool::RaydiumLaunchpad(pool) => {
+          let address = *pool.pool_address();
+          let pool = Arc::new(RwLock::new(pool));
+          self.raydium_launchpads.lock().unwrap().insert(address, pool.clone());
+          pool
+        }
+        RestoredPool::Other(pool) => pool,
+      };
+      self.insert_pool(pool);
+    }
+
+    self.raydium_cpmm_fee_rates_lp.extend(state.raydium_cpmm_fee_rates_lp);
+    self.legit_tokens.lock().unwrap().extend(state.legit_tokens);
+    self.account_update_versions.write().unwrap().extend(state.account_update_versions);
+    Ok(state.slot)
+  }
+}
+
+/// Decode a whole snapshot, rejecting other versions and trailing data
+fn read_snapshot(reader: &mut &[u8]) -> io::Result<RestoredState> {
+  let magic = <[u8; 8]>::deserialize_reader(reader)?;
+  if magic != SNAPSHOT_MAGIC {
+    return Err(io::Error::new(io::ErrorKind::InvalidData, "Not a snapshot file"));
+  }
+  let version = u32::deserialize_reader(reader)?;
+  if version != SNAPSHOT_VERSION {
+    return Err(io::Error::new(
+      io::ErrorKind::InvalidData,
+      format!("Unsupported snapshot version {}, expected {}", version, SNAPSHOT_VERSION),
+    ));
+  }
+  let slot = u64::deserialize_reader(reader)?;
+  let meteora_vaults = Vec::<MeteoraVault>::deserialize_reader(reader)?;
+  let pool_count = u32::deserialize_reader(reader)?;
+  let pools = (0..pool_count)
+    .map(|_| deserialize_pool(reader))
+    .collect::<io::Result<Vec<_>>>()?;
+  let state = RestoredState {
+    slot,
+    meteora_vaults,
+    pools,
+    raydium_cpmm_fee_rates_lp: HashMap::deserialize_reader(reader)?,
+    legit_tokens: HashMap::deserialize_reader(reader)?,
+    account_update_versions: HashMap::deserialize_reader(reader)?,
+  };
+  if reader.read(&mut [0])? != 0 {
+    return Err(io::Error::new(io::ErrorKind::InvalidData, "Trailing data after snapshot"));
+  }
+  Ok(state)
+}
+
+/// Read the pool type written by `save_snapshot` followed by the concrete pool
+pub(super) fn deserialize_pool(reader: &mut &[u8]) -> io::Result<RestoredPool> {
+  let pool_type = Pools::deserialize_reader(reader)?;
+  Ok(match pool_type {
+    Pools::MeteoraAmm => RestoredPool::MeteoraAmm(MeteoraAmmPool::deserialize_reader(reader)?),
+    Pools::MeteoraDbc => RestoredPool::MeteoraDbc(MeteoraDbc::deserialize_reader(reader)?),
+    Pools::PfBondingCurve => {
+      RestoredPool::PfBondingCurve(PfBondingCurve::deserialize_reader(reader)?)
+    }
+    Pools::RaydiumLaunchpad => {
+      RestoredPool::RaydiumLaunchpad(RaydiumLaunchpad::deserialize_reader(reader)?)
+    }
+    Pools::MeteoraDammV2 => RestoredPool::Other(Arc::new(RwLock::new(
+      MeteoraDammV2Pool::deserialize_reader(reader)?,
+    ))),
+    Pools::PumpswapAmm => RestoredPool::Other(Arc::new(RwLock::new(
+      PumpswapPool::deserialize_reader(reader)?,
+    ))),
+    Pools::RaydiumAmmV4 => RestoredPool::Other(Arc::new(RwLock::new(
+      RaydiumAmmV4Pool::deserialize_reader(reader)?,
+    ))),
+    Pools::RaydiumCpmm => RestoredPool::Other(Arc::new(RwLock::new(
+      RaydiumCpmmPool::deserialize_reader(reader)?,
+    ))),
+    _ => {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Unsupported pool type {:?} in snapshot", pool_type),
+      ));
+    }
+  })
+}
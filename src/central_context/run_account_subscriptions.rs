@@ -0,0 +1,141 @@
+use crate::central_context::central_context::CentralContext;
+use futures::future::BoxFuture;
+use futures::stream::{self, SelectAll, StreamExt};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::rpc_config::RpcAccountInfoConfig;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::time::Duration;
+
+pub(crate) const MIN_RECONNECT_DELAY: Duration = Duration::from_millis(500);
+pub(crate) const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Ends one subscription, as returned by `PubsubClient::account_subscribe`
+type UnsubscribeFn = Box<dyn FnOnce() -> BoxFuture<'static, ()> + Send>;
+
+impl CentralContext {
+  /**
+  Keep every pool in `pools_map` and every mint in `token_registry` current from websocket
+  `accountSubscribe` notifications, for setups without a Geyser gRPC endpoint. Each dependent
+  account (pool state, vaults, bonding curves, ...) and tracked mint gets a subscription on one
+  shared connection, and notifications are fed through `apply_account_update`.
+
+  Subscriptions only deliver changes, so right after subscribing the current state of the accounts
+  is fetched with getMultipleAccounts. This also covers the gap when the connection drops: the
+  client reconnects with exponential backoff, resubscribes everything and refetches. The backoff
+  only resets once a connection delivered notifications. Accounts of pools inserted and mints
+  tracked later are subscribed, and accounts no longer needed unsubscribed, every
+  `new_account_poll_interval`, which must be non-zero.
+
+  Runs until the returned future is dropped, which requires a tokio runtime. Websocket providers
+  limit subscriptions per connection, so very large `pools_map`s may need a gRPC stream instead.
+  */
+  pub async fn run_account_subscriptions(&self, ws_url: &str, new_account_poll_interval: Duration) {
+    let mut reconnect_delay = MIN_RECONNECT_DELAY;
+    loop {
+      match PubsubClient::new(ws_url).await {
+        Ok(client) => {
+          // Only a connection that delivered notifications counts as healthy, otherwise a server
+          // accepting connections but failing subscriptions would be reconnected to in a loop
+          if self
+            .stream_account_updates(&client, new_account_poll_interval)
+            .await
+          {
+            reconnect_delay = MIN_RECONNECT_DELAY;
+          }
+          println!("run_account_subscriptions: connection dropped, reconnecting");
+        }
+        Err(error) => println!("run_account_subscriptions: failed to connect: {}", error),
+      }
+      tokio::time::sleep(reconnect_delay).await;
+      reconnect_delay = (reconnect_delay * 2).min(MAX_RECONNECT_DELAY);
+    }
+  }
+
+  /// Subscribe to every streamed account (see `get_streamed_accounts`) on one connection and apply
+  /// notifications until the connection drops or a subscription fails. Accounts that aren't
+  /// streamed anymore are unsubscribed. Returns whether any notification was received.
+  async fn stream_account_updates(
+    &self,
+    client: &PubsubClient,
+    new_account_poll_interval: Duration,
+  ) -> bool {
+    let config = RpcAccountInfoConfig {
+      encoding: Some(UiAccountEncoding::Base64),
+      data_slice: None,
+      commitment: Some(self.json_rpc_client_async.commitment()),
+      min_context_slot: None,
+    };
+    let mut subscriptions = HashMap::new();
+    let mut notifications = SelectAll::new();
+    let mut poll_new_accounts = tokio::time::interval(new_account_poll_interval);
+    let mut delivered = false;
+
+    loop {
+      tokio::select! {
+        _ = poll_new_accounts.tick() => {
+          let streamed_accounts = self.get_streamed_accounts();
+          let new_accounts: Vec<Pubkey> = streamed_accounts
+            .iter()
+            .filter(|account| !subscriptions.contains_key(*account))
+            .copied()
+            .collect();
+          let removed_accounts: Vec<Pubkey> = subscriptions
+            .keys()
+            .filter(|account| !streamed_accounts.contains(*account))
+            .copied()
+            .collect();
+          for account in removed_accounts {
+            let unsubscribe: UnsubscribeFn = subscriptions.remove(&account).unwrap();
+            unsubscribe().await;
+          }
+          for account in &new_accounts {
+            match client.account_subscribe(account, Some(config.clone())).await {
+              Ok((notification_stream, unsubscribe)) => {
+                let account = *account;
+                // Each stream ends with a `None` marker, telling an unsubscribed account apart
+                // from a dropped connection
+                notifications.push(
+                  notification_stream
+                    .map(move |response| (account, Some(response)))
+                    .chain(stream::iter([(account, None)])),
+                );
+                subscriptions.insert(account, unsubscribe);
+              }
+              Err(error) => {
+                println!("run_account_subscriptions: failed to subscribe to {}: {}", account, error);
+                return delivered;
+              }
+            }
+          }
+          // Catch up on anything that changed before the subscriptions were live
+          self.refetch_accounts_async(&new_accounts).await;
+        }
+        notification = notifications.next(), if !notifications.is_empty() => {
+          let Some((address, response)) = notification else {
+            return delivered;
+          };
+          let Some(response) = response else {
+            // Every subscription stream ends together when the connection drops
+            if subscriptions.contains_key(&address) {
+              return delivered;
+            }
+            continue;
+          };
+          delivered = true;
+          if let Some(account) = response.value.decode::<Account>() {
+            self.apply_account_update(
+              &address,
+              &account.owner,
+              &account.data,
+              response.context.slot,
+              None,
+            );
+          }
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,218 @@
+use crate::central_context::central_context::CentralContext;
+use crate::central_context::run_account_subscriptions::{MAX_RECONNECT_DELAY, MIN_RECONNECT_DELAY};
+use crate::constants::SUPPORTED_PROGRAMS;
+use crate::types::decoded_transaction::DecodedTransaction;
+use futures::{SinkExt, StreamExt};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::SyncSender;
+use std::time::Duration;
+use yellowstone_grpc_client::GeyserGrpcClient;
+use yellowstone_grpc_proto::prelude::subscribe_update::UpdateOneof;
+use yellowstone_grpc_proto::prelude::{
+  CommitmentLevel, SubscribeRequest, SubscribeRequestFilterAccounts, SubscribeRequestFilterSlots,
+  SubscribeRequestFilterTransactions, SubscribeRequestPing,
+};
+
+impl CentralContext {
+  /**
+  Keep every pool in `pools_map` and every mint in `token_registry` current from a Yellowstone
+  geyser gRPC stream at `endpoint`, authenticated with `x_token` if set. Requires the `geyser`
+  feature.
+
+  The stream subscribes to the accounts owned by `SUPPORTED_PROGRAMS`, to the dependent accounts
+  of every pool (vaults, mints, ...) and to the tracked mints, which are fed through
+  `apply_account_update` with their write versions, and to slots, which advance `current_slot`
+  through `apply_slot_update`.
+  With a `transactions` sender, successful non vote transactions mentioning a supported program
+  are decoded with `DecodedTransaction::from_geyser_transaction` and sent to it. Transactions that
+  don't fit in the channel are dropped.
+
+  Dependent accounts of pools inserted and mints tracked later are added to the subscription every
+  `new_account_poll_interval`, which must be non-zero. When the stream ends the client reconnects
+  with exponential backoff, which only resets once a stream delivered updates. Runs until the
+  returned future is dropped, which requires a tokio runtime.
+  */
+  pub async fn run_geyser_subscription(
+    &self,
+    endpoint: &str,
+    x_token: Option<&str>,
+    transactions: Option<SyncSender<DecodedTransaction>>,
+    new_account_poll_interval: Duration,
+  ) {
+    let mut reconnect_delay = MIN_RECONNECT_DELAY;
+    loop {
+      let client = async {
+        GeyserGrpcClient::build_from_shared(endpoint.to_string())?
+          .x_token(x_token)?
+          .connect()
+          .await
+      };
+      match client.await {
+        Ok(client) => {
+          if self
+            .stream_geyser_updates(client, transactions.as_ref(), new_account_poll_interval)
+            .await
+          {
+            reconnect_delay = MIN_RECONNECT_DELAY;
+          }
+          println!("run_geyser_subscription: stream ended, reconnecting");
+        }
+        Err(error) => println!("run_geyser_subscription: failed to connect: {}", error),
+      }
+      tokio::time::sleep(reconnect_delay).await;
+      reconnect_delay = (reconnect_delay * 2).min(MAX_RECONNECT_DELAY);
+    }
+  }
+
+  /// Subscribe on one connection and apply updates until the stream ends. Returns whether any
+  /// update was received.
+  async fn stream_geyser_updates(
+    &self,
+    mut client: GeyserGrpcClient,
+    transactions: Option<&SyncSender<DecodedTransaction>>,
+    new_account_poll_interval: Duration,
+  ) -> bool {
+    let mut subscribed_accounts = self.get_streamed_accounts();
+    let request = self.geyser_subscribe_request(&subscribed_accounts, transactions.is_some());
+    let (mut sink, mut stream) = match client.subscribe_with_request(Some(request)).await {
+      Ok(subscription) => subscription,
+      Err(error) => {
+        println!("run_geyser_subscription: failed to subscribe: {}", error);
+        return false;
+      }
+    };
+    let mut poll_new_accounts = tokio::time::interval(new_account_poll_interval);
+    // The first tick completes immediately
+    poll_new_accounts.tick().await;
+    let mut delivered = false;
+
+    loop {
+      tokio::select! {
+        _ = poll_new_accounts.tick() => {
+          let accounts = self.get_streamed_accounts();
+          if accounts == subscribed_accounts {
+            continue;
+          }
+          // A new request replaces every filter of the stream
+          let request = self.geyser_subscribe_request(&accounts, transactions.is_some());
+          if let Err(error) = sink.send(request).await {
+            println!("run_geyser_subscription: failed to update the subscription: {}", error);
+            return delivered;
+          }
+          subscribed_accounts = accounts;
+        }
+        update = stream.next() => {
+          let update = match update {
+            Some(Ok(update)) => update,
+            Some(Err(error)) => {
+              println!("run_geyser_subscription: stream failed: {}", error);
+              return delivered;
+            }
+            None => return delivered,
+          };
+          delivered = true;
+          match update.update_oneof {
+            Some(UpdateOneof::Account(update)) => {
+              let Some(account) = update.account else {
+                continue;
+              };
+              let (Ok(address), Ok(owner)) = (
+                Pubkey::try_from(account.pubkey.as_slice()),
+                Pubkey::try_from(account.owner.as_slice()),
+              ) else {
+                continue;
+              };
+              self.apply_account_update(
+                &address,
+                &owner,
+                &account.data,
+                update.slot,
+                Some(account.write_version),
+              );
+            }
+            Some(UpdateOneof::Slot(update)) => self.apply_slot_update(update.slot),
+            Some(UpdateOneof::Transaction(update)) => {
+              if let (Some(transactions), Some(info)) = (transactions, &update.transaction)
+                && let Some(transaction) =
+                  DecodedTransaction::from_geyser_transaction(update.slot, info)
+              {
+                let _ = transactions.try_send(transaction);
+              }
+            }
+            // Answer server pings so load balancers keep an idle stream open
+            Some(UpdateOneof::Ping(_)) => {
+              let ping = SubscribeRequest {
+                ping: Some(SubscribeRequestPing { id: 1 }),
+                ..Default::default()
+              };
+              if let Err(error) = sink.send(ping).await {
+                println!("run_geyser_subscription: failed to answer a ping: {}", error);
+                return delivered;
+              }
+            }
+            _ => {}
+          }
+        }
+      }
+    }
+  }
+
+  /// Subscription to the supported programs' accounts, the streamed `accounts`, slots and
+  /// optionally transactions, at the commitment of `json_rpc_client_async`
+  fn geyser_subscribe_request(
+    &self,
+    accounts: &HashSet<Pubkey>,
+    with_transactions: bool,
+  ) -> SubscribeRequest {
+    let supported_programs: Vec<String> = SUPPORTED_PROGRAMS
+      .iter()
+      .map(|program| program.to_string())
+      .collect();
+    let commitment = match self.json_rpc_client_async.commitment().commitment {
+      solana_commitment_config::CommitmentLevel::Processed => CommitmentLevel::Processed,
+      solana_commitment_config::CommitmentLevel::Confirmed => CommitmentLevel::Confirmed,
+      solana_commitment_config::CommitmentLevel::Finalized => CommitmentLevel::Finalized,
+    };
+    let mut transactions = HashMap::new();
+    if with_transactions {
+      transactions.insert(
+        "supported_programs".to_string(),
+        SubscribeRequestFilterTransactions {
+          vote: Some(false),
+          failed: Some(false),
+          account_include: supported_programs.clone(),
+          ..Default::default()
+        },
+      );
+    }
+    SubscribeRequest {
+      accounts: HashMap::from([
+        (
+          "supported_programs".to_string(),
+          SubscribeRequestFilterAccounts {
+            owner: supported_programs,
+            ..Default::default()
+          },
+        ),
+        (
+          "dependent_accounts".to_string(),
+          SubscribeRequestFilterAccounts {
+            account: accounts.iter().map(|account| account.to_string()).collect(),
+            ..Default::default()
+          },
+        ),
+      ]),
+      slots: HashMap::from([(
+        "slots".to_string(),
+        SubscribeRequestFilterSlots {
+          filter_by_commitment: Some(true),
+          ..Default::default()
+        },
+      )]),
+      transactions,
+      commitment: Some(commitment as i32),
+      ..Default::default()
+    }
+  }
+}
@@ -0,0 +1,119 @@
+use crate::central_context::central_context::CentralContext;
+use crate::constants::{SNAPSHOT_MAGIC, SNAPSHOT_VERSION};
+use crate::types::meteora_amm_pool::MeteoraAmmPool;
+use crate::types::meteora_dammv2_pool::MeteoraDammV2Pool;
+use crate::types::meteora_dbc::MeteoraDbc;
+use crate::types::pf_bonding_curve::PfBondingCurve;
+use crate::types::pool::PoolTrait;
+use crate::types::pools::Pools;
+use crate::types::pumpswap_pool::PumpswapPool;
+use crate::types::raydium_ammv4_pool::RaydiumAmmV4Pool;
+use crate::types::raydium_cpmm_pool::RaydiumCpmmPool;
+use crate::types::raydium_launchpad::RaydiumLaunchpad;
+use borsh::BorshSerialize;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+impl CentralContext {
+  /// Write the state of the context to a versioned binary snapshot at `path`
+  ///
+  /// The snapshot holds every pool with its current reserves, the Meteora vault cache, the
+  /// Raydium CPMM fee rates, the legit token cache and the per account update versions, tagged
+  /// with `current_slot`. Bonding curve, launchpad and DBC caches are rebuilt from the pools. Load
+  /// it back with `restore_snapshot`.
+  ///
+  /// The file is written next to `path` first and renamed over it, so a crash mid write never
+  /// leaves a truncated snapshot behind. Returns the slot of the snapshot.
+  pub fn save_snapshot(&self, path: &Path) -> io::Result<u64> {
+    let slot = *self.current_slot.read().unwrap();
+    let temporary_path = path.with_extension("tmp");
+    let mut writer = BufWriter::new(File::create(&temporary_path)?);
+
+    SNAPSHOT_MAGIC.serialize(&mut writer)?;
+    SNAPSHOT_VERSION.serialize(&mut writer)?;
+    slot.serialize(&mut writer)?;
+
+    let vaults: Vec<_> = self.meteora_vault_cache.lock().unwrap().values().cloned().collect();
+    (vaults.len() as u32).serialize(&mut writer)?;
+    for vault in vaults {
+      vault.read().unwrap().serialize(&mut writer)?;
+    }
+
+    // Pools are in pools_map once per dependent account
+    let pools: Vec<_> = {
+      let pools_map = self.pools_map.read().unwrap();
+      let mut seen = HashSet::new();
+      pools_map
+        .values()
+        .filter(|pool| {
+          let pool = pool.read().unwrap();
+          is_snapshot_pool_type(pool.pool_type()) && seen.insert(*pool.pool_address())
+        })
+        .cloned()
+        .collect()
+    };
+    (pools.len() as u32).serialize(&mut writer)?;
+    for pool in pools {
+      serialize_pool(&*pool.read().unwrap(), &mut writer)?;
+    }
+
+    self.raydium_cpmm_fee_rates_lp.serialize(&mut writer)?;
+    self.legit_tokens.lock().unwrap().serialize(&mut writer)?;
+    self.account_update_versions.read().unwrap().serialize(&mut writer)?;
+
+    writer.into_inner().map_err(|error| error.into_error())?.sync_all()?;
+    fs::rename(&temporary_path, path)?;
+    Ok(slot)
+  }
+}
+
+/// Check if pools of this type can be written to a snapshot
+fn is_snapshot_pool_type(pool_type: &Pools) -> bool {
+  matches!(
+    pool_type,
+    Pools::MeteoraAmm
+      | Pools::MeteoraDammV2
+      | Pools::MeteoraDbc
+      | Pools::PfBondingCurve
+      | Pools::PumpswapAmm
+      | Pools::RaydiumAmmV4
+      | Pools::RaydiumCpmm
+      | Pools::RaydiumLaunchpad
+  )
+}
+
+/// Write the pool type followed by the concrete pool
+pub(super) fn serialize_pool<W: Write>(pool: &dyn PoolTrait, writer: &mut W) -> io::Result<()> {
+  let pool_type = pool.pool_type();
+  pool_type.serialize(writer)?;
+  let any = pool.as_any();
+  let serialized = match pool_type {
+    Pools::MeteoraAmm => any.downcast_ref::<MeteoraAmmPool>().map(|pool| pool.serialize(writer)),
+    Pools::MeteoraDammV2 => any
+      .downcast_ref::<MeteoraDammV2Pool>()
+      .map(|pool| pool.serialize(writer)),
+    Pools::MeteoraDbc => any.downcast_ref::<MeteoraDbc>().map(|pool| pool.serialize(writer)),
+    Pools::PfBondingCurve => any
+      .downcast_ref::<PfBondingCurve>()
+      .map(|pool| pool.serialize(writer)),
+    Pools::PumpswapAmm => any.downcast_ref::<PumpswapPool>().map(|pool| pool.serialize(writer)),
+    Pools::RaydiumAmmV4 => any
+      .downcast_ref::<RaydiumAmmV4Pool>()
+      .map(|pool| pool.serialize(writer)),
+    Pools::RaydiumCpmm => any
+      .downcast_ref::<RaydiumCpmmPool>()
+      .map(|pool| pool.serialize(writer)),
+    Pools::RaydiumLaunchpad => any
+      .downcast_ref::<RaydiumLaunchpad>()
+      .map(|pool| pool.serialize(writer)),
+    _ => None,
+  };
+  serialized.unwrap_or_else(|| {
+    Err(io::Error::new(
+      io::ErrorKind::InvalidInput,
+      format!("Pool {} can't be snapshotted", pool.pool_address()),
+    ))
+  })
+}
@@ -0,0 +1,19 @@
+use crate::central_context::central_context::CentralContext;
+use crate::types::graduation::Graduation;
+use std::sync::mpsc::{Receiver, sync_channel};
+
+impl CentralContext {
+  /// Subscribe to bonding curve graduations
+  ///
+  /// A `Graduation` is published when a curve is seen completed or migrated, from
+  /// `apply_account_update`, `insert_pool` or `sweep_pools` (before the curve is swept), and again
+  /// once its successor pool is inserted if it wasn't known at graduation, or its successor address
+  /// is learned from `process_migration_instruction`. Events are delivered
+  /// over a bounded channel holding up to `capacity` events, events that don't fit are dropped.
+  /// Drop the receiver to unsubscribe.
+  pub fn subscribe_graduations(&self, capacity: usize) -> Receiver<Graduation> {
+    let (sender, receiver) = sync_channel(capacity);
+    self.graduation_subscribers.lock().unwrap().push(sender);
+    receiver
+  }
+}
@@ -0,0 +1,37 @@
+use crate::central_context::central_context::CentralContext;
+use crate::types::backpressure_policy::BackpressurePolicy;
+use crate::types::market_subscription::MarketSubscription;
+use crate::types::market_update::MarketUpdate;
+use crate::types::market_update_subscriber::MarketUpdateSubscriber;
+use std::sync::Arc;
+use std::sync::mpsc::{Receiver, sync_channel};
+
+impl CentralContext {
+  /// Subscribe to `MarketUpdate`s pushed whenever a market's prices or reserves change
+  ///
+  /// Updates are published from every update path that goes through the context: account updates
+  /// applied with `apply_account_update`, pools fetched with `fetch_market_state_from_rpc` (sync
+  /// or async) or `refresh_pools`, and markets created by `process_pool_creation_instruction` or
+  /// `process_token_creation_instruction`. Only updates
+  /// matching `subscription` are delivered, over a bounded channel holding up to `capacity`
+  /// updates. `backpressure_policy` decides what happens when the channel is full. Drop the
+  /// receiver to unsubscribe.
+  pub fn subscribe_market_updates(
+    &self,
+    subscription: MarketSubscription,
+    capacity: usize,
+    backpressure_policy: BackpressurePolicy,
+  ) -> Receiver<MarketUpdate> {
+    let (sender, receiver) = sync_channel(capacity);
+    self
+      .market_update_subscribers
+      .lock()
+      .unwrap()
+      .push(Arc::new(MarketUpdateSubscriber {
+        subscription,
+        sender,
+        backpressure_policy,
+      }));
+    receiver
+  }
+}
@@ -0,0 +1,36 @@
+use crate::central_context::central_context::CentralContext;
+use crate::types::pool::PoolTrait;
+use std::sync::{Arc, RwLock};
+
+impl CentralContext {
+  /// Remove every pool whose lifecycle state the `sweep_policy` says to remove
+  ///
+  /// `apply_account_update` already sweeps pools as their updates arrive. This catches pools that
+  /// changed state without an account update, e.g. pools loaded before the policy was set.
+  /// Returns the removed pools.
+  pub fn sweep_pools(&self) -> Vec<Arc<RwLock<dyn PoolTrait>>> {
+    let sweep_policy = self.sweep_policy.read().unwrap().clone();
+    // Collect first, `remove_pool` needs the pools map write lock
+    let pool_addresses: Vec<_> = self
+      .pools_map
+      .read()
+      .unwrap()
+      .iter()
+      .filter_map(|(address, pool)| {
+        let pool_unlocked = pool.read().unwrap();
+        (pool_unlocked.pool_address() == address
+          && sweep_policy.should_remove(self.get_pool_lifecycle(&*pool_unlocked)))
+        .then_some(*address)
+      })
+      .collect();
+    pool_addresses
+      .iter()
+      .filter_map(|pool_address| {
+        // Track graduations before the curve is gone
+        let pool = self.pools_map.read().unwrap().get(pool_address).cloned()?;
+        self.track_graduation(&pool);
+        self.remove_pool(pool_address)
+      })
+      .collect()
+  }
+}
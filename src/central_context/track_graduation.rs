@@ -0,0 +1,78 @@
+use crate::central_context::central_context::CentralContext;
+use crate::types::graduation::Graduation;
+use crate::types::pool::PoolTrait;
+use crate::types::pool_lifecycle::PoolLifecycle;
+use std::collections::hash_map::Entry;
+use std::sync::{Arc, RwLock};
+
+impl CentralContext {
+  /// Record the graduation of a bonding curve that completed or migrated, and publish it
+  ///
+  /// When the successor address can be derived from the curve (Pumpfun) the successor is looked
+  /// up in `pools_map`, if it isn't known yet it is linked later by `insert_pool`. Other curves get
+  /// their successor address from their migration instruction, see
+  /// `process_migration_instruction`. Does nothing for pools that don't graduate, curves still
+  /// trading, or curves already tracked.
+  pub(crate) fn track_graduation(&self, pool: &Arc<RwLock<dyn PoolTrait>>) {
+    let (pool_address, pool_type, token_a_address, token_b_address, successor) = {
+      let pool_unlocked = pool.read().unwrap();
+      // Cheapest checks first, this runs for every account update and inserted pool
+      if !matches!(
+        self.get_pool_lifecycle(&*pool_unlocked),
+        PoolLifecycle::Completed | PoolLifecycle::Migrated
+      ) || self
+        .graduations
+        .read()
+        .unwrap()
+        .contains_key(pool_unlocked.pool_address())
+      {
+        return;
+      }
+      let Some(successor) = pool_unlocked.graduation_successor() else {
+        return;
+      };
+      (
+        *pool_unlocked.pool_address(),
+        *pool_unlocked.pool_type(),
+        *pool_unlocked.token_a_address(),
+        *pool_unlocked.token_b_address(),
+        successor,
+      )
+    };
+    let (successor_pool_type, derived_successor_address) = successor;
+
+    // Only a pool at the derived address is the successor, others are linked later from the
+    // migration instruction (see `process_migration_instruction`)
+    let successor_inserted = derived_successor_address.is_some_and(|address| {
+      self
+        .pools_map
+        .read()
+        .unwrap()
+        .get(&address)
+        .is_some_and(|successor| {
+          let successor = successor.read().unwrap();
+          *successor.pool_address() == address && *successor.pool_type() == successor_pool_type
+        })
+    });
+
+    let graduation = Graduation {
+      pool_address,
+      pool_type,
+      token_a_address,
+      token_b_address,
+      successor_pool_type,
+      successor_pool_address: derived_successor_address,
+      successor_inserted,
+      slot: *self.current_slot.read().unwrap(),
+    };
+    // Another thread, or the curve's migration instruction, may have tracked it between the
+    // check and here
+    match self.graduations.write().unwrap().entry(pool_address) {
+      Entry::Occupied(_) => return,
+      Entry::Vacant(entry) => {
+        entry.insert(graduation.clone());
+      }
+    }
+    self.publish_graduation(&graduation);
+  }
+}
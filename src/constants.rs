@@ -2,6 +2,20 @@ use solana_sdk::pubkey::Pubkey;
 
 pub const LAMPORTS_PER_SOL: u128 = 1_000_000_000;
 
+/// Maximum number of accounts a single getMultipleAccounts request accepts
+pub const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+/// Magic bytes at the start of every `CentralContext` snapshot file
+pub const SNAPSHOT_MAGIC: [u8; 8] = *b"SOLCTXSN";
+/// Version of the snapshot format, bumped whenever a snapshotted type's layout changes. Snapshots
+/// from other versions are rejected by `CentralContext::restore_snapshot`.
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+/// Cap of the total Meteora DAMMv2 fee numerator for version 0 pools, 50%
+pub const METEORA_DAMMV2_MAX_FEE_NUMERATOR_V0: u64 = 500_000_000;
+/// Cap of the total Meteora DAMMv2 fee numerator for version 1 pools, 99%
+pub const METEORA_DAMMV2_MAX_FEE_NUMERATOR_V1: u64 = 990_000_000;
+
 pub struct Tokens {
   pub wsol: Pubkey,
   pub usdc: Pubkey,
@@ -37,11 +51,13 @@ pub struct PumpConstants {
   pub create_pool_instruction_discriminator: [u8; 8],
   pub bonding_curve_event_discriminator: [u8; 16],
   pub bonding_curve_create_instruction_discriminator: [u8; 8],
+  pub bonding_curve_create_v2_instruction_discriminator: [u8; 8],
   pub bonding_curve_buy_exact_sol_in_instruction_discriminator: [u8; 8],
   pub pumpswap_buy_exact_quote_in_instruction_discriminator: [u8; 8],
   pub pumpswap_swap_event_discriminator: [u8; 8],
   pub pumpswap_buy_swap_event_discriminator: [u8; 8],
   pub bc_init_virtual_sol_reserves: u64,
+  pub bc_init_virtual_token_reserves: u64,
   pub bc_init_virtual_token_reserve_diff: u64,
 }
 
@@ -76,6 +92,8 @@ pub const PUMP_CONSTANTS: PumpConstants = PumpConstants {
     228, 69, 165, 46, 81, 203, 154, 29, 189, 219, 127, 211, 78, 230, 97, 238,
   ],
   bonding_curve_create_instruction_discriminator: [24, 30, 200, 40, 5, 28, 7, 119],
+  // Token-2022 create instruction, same args as create with a trailing mayhem mode flag
+  bonding_curve_create_v2_instruction_discriminator: [214, 144, 76, 236, 95, 139, 49, 180],
   bonding_curve_buy_exact_sol_in_instruction_discriminator: [56, 252, 116, 8, 158, 223, 205, 95],
   pumpswap_buy_exact_quote_in_instruction_discriminator: [198, 46, 21, 82, 180, 217, 232, 112],
   pumpswap_swap_event_discriminator: [228, 69, 165, 46, 81, 203, 154, 29],
@@ -84,6 +102,7 @@ pub const PUMP_CONSTANTS: PumpConstants = PumpConstants {
   Initial virtual reserves and liquidity offsets used in bonding curve in raw token units
   */
   bc_init_virtual_sol_reserves: 30_000_000_000,
+  bc_init_virtual_token_reserves: 1_073_000_000_000_000,
   bc_init_virtual_token_reserve_diff: 279_900_000_000_000,
 };
 
@@ -110,6 +129,9 @@ pub struct RaydiumConstants {
   pub launchpad_authority: Pubkey,
   pub cpmm_swap_discriminators: [[u8; 8]; 2],
   pub launchpad_swap_discriminators: [[u8; 8]; 4],
+  pub launchpad_initialize_discriminators: [[u8; 8]; 3],
+  pub launchpad_migrate_to_amm_discriminator: [u8; 8],
+  pub launchpad_migrate_to_cpswap_discriminator: [u8; 8],
   pub cpmm_create_pool_instruction_discriminator: [u8; 8],
   pub ammv4_create_pool_instruction_discriminator: u8,
   pub ammv4_swap_discriminators: [u8; 2],
@@ -151,6 +173,21 @@ pub const RAYDIUM_CONSTANTS: RaydiumConstants = RaydiumConstants {
     // Sell exact out
     [95, 200, 71, 34, 8, 9, 11, 166],
   ],
+  /*
+  All launchpad pool initialize instructions. They share the mint and curve params prefix, later
+  versions append creator fee and token 2022 params.
+  */
+  launchpad_initialize_discriminators: [
+    // Initialize
+    [175, 175, 109, 31, 13, 152, 155, 237],
+    // Initialize v2
+    [67, 153, 175, 39, 218, 16, 38, 32],
+    // Initialize with token 2022
+    [37, 190, 126, 222, 44, 154, 171, 17],
+  ],
+  // Migrations of a completed launchpad pool to the AMMv4 or CPMM pool chosen by its migrate_type
+  launchpad_migrate_to_amm_discriminator: [207, 82, 192, 145, 254, 207, 145, 223],
+  launchpad_migrate_to_cpswap_discriminator: [136, 92, 200, 103, 28, 218, 144, 140],
 };
 
 pub struct MeteoraConstants {
@@ -167,6 +204,9 @@ pub struct MeteoraConstants {
   pub dbc_pool_authority: Pubkey,
   pub dbc_event_authority: Pubkey,
   pub dbc_swap_discriminator: [u8; 8],
+  pub dbc_initialize_virtual_pool_discriminators: [[u8; 8]; 2],
+  pub dbc_migrate_damm_discriminator: [u8; 8],
+  pub dbc_migrate_dammv2_discriminator: [u8; 8],
 }
 
 pub const METEORA_CONSTANTS: MeteoraConstants = MeteoraConstants {
@@ -183,6 +223,15 @@ pub const METEORA_CONSTANTS: MeteoraConstants = MeteoraConstants {
   dbc_pool_authority: Pubkey::from_str_const("FhVo3mqL8PW5pH5U2CN4XE33DokiyZnUwuGpH2hmHLuM"),
   dbc_event_authority: Pubkey::from_str_const("8Ks12pbrD6PXxfty1hVQiE9sc289zgU1zHkvXhrSdriF"),
   dbc_swap_discriminator: [248, 198, 158, 145, 225, 117, 135, 200],
+  dbc_initialize_virtual_pool_discriminators: [
+    // Initialize virtual pool with spl token
+    [140, 85, 215, 176, 102, 54, 104, 79],
+    // Initialize virtual pool with token 2022
+    [169, 118, 51, 78, 145, 110, 220, 155],
+  ],
+  // Migrations of a completed virtual pool to a DAMM v1 or DAMM v2 pool
+  dbc_migrate_damm_discriminator: [27, 1, 48, 22, 180, 63, 118, 217],
+  dbc_migrate_dammv2_discriminator: [156, 169, 230, 103, 53, 228, 80, 64],
 };
 
 pub struct SolanaPrograms {
@@ -210,6 +259,9 @@ pub struct PoolsAccountSizes {
   pub meteora_dammv2: usize,
   pub raydium_ammv4: usize,
   pub raydium_cpmm: usize,
+  pub meteora_dbc: usize,
+  pub raydium_launchpad: usize,
+  pub pf_bonding_curve: usize,
 }
 
 pub const POOLS_ACCOUNT_SIZES: PoolsAccountSizes = PoolsAccountSizes {
@@ -218,4 +270,21 @@ pub const POOLS_ACCOUNT_SIZES: PoolsAccountSizes = PoolsAccountSizes {
   meteora_dammv2: 1112,
   raydium_ammv4: 752,
   raydium_cpmm: 637,
+  meteora_dbc: 472,
+  raydium_launchpad: 429,
+  pf_bonding_curve: 150,
 };
+
+/// Programs owning the pool state accounts this library can decode. Used to build account owner
+/// and transaction account include filters for geyser gRPC or websocket program subscriptions.
+pub const SUPPORTED_PROGRAMS: [Pubkey; 9] = [
+  PUMP_CONSTANTS.bonding_curve_program,
+  PUMP_CONSTANTS.pump_swap_program,
+  RAYDIUM_CONSTANTS.amm_program,
+  RAYDIUM_CONSTANTS.cpmm_program,
+  RAYDIUM_CONSTANTS.launchpad_program,
+  METEORA_CONSTANTS.amm_program,
+  METEORA_CONSTANTS.vault_program,
+  METEORA_CONSTANTS.dammv2_program,
+  METEORA_CONSTANTS.dbc_program,
+];
@@ -7,14 +7,16 @@
 //!
 //! This library also provides:
 //! - A shared context (`CentralContext`) for managing DEX liquidity pools for trading pairs, RPC
-//! clients, blockchain state, etc.
+//!   clients, blockchain state, etc.
 //! - Protocol-specific pool parsing and raw on-chain account data decoding utilities. Every
-//! protocol type has an accessible `from_account_info` method that can be used from raw on chain
-//! account data.
+//!   protocol type has an accessible `from_account_info` method that can be used from raw on chain
+//!   account data.
 //! - Common types and traits for abstractions with liquidity pool fees, liquidity, etc.
 //!
-//! Environment variables required:
+//! Environment variables read by `CentralContext::new` (see `CentralContextBuilder` to configure
+//! the context without them):
 //! - RPC_NODE_URL: URL of the Solana JSON RPC node to use for on-chain data fetching
+//! - RPC_FALLBACK_URLS: optional comma separated fallback RPC nodes
 
 mod central_context;
 pub mod constants;
@@ -28,26 +30,57 @@ mod utilities;
 
 // Re-exports
 pub use central_context::central_context::CentralContext;
+pub use central_context::central_context_builder::CentralContextBuilder;
 pub use meteora::get_meteora_vault_from_token_address::get_meteora_vault_from_token_address;
 pub use pumpfun::derive_bonding_curve::derive_bonding_curve;
 pub use raydium::get_cpmm_fee_amount_from_config_account::get_cpmm_fee_amount_from_config_account;
+pub use types::backend_rpc_sender::BackendRpcSender;
+pub use types::backpressure_policy::BackpressurePolicy;
+pub use types::decoded_transaction::DecodedTransaction;
+pub use types::error::Error;
+pub use types::failover_rpc_sender::FailoverRpcSender;
+pub use types::field_mismatch::FieldMismatch;
+pub use types::fixture_rpc_backend::FixtureRpcBackend;
+pub use types::graduation::Graduation;
 pub use types::instruction::Instruction;
 pub use types::link::Link;
+pub use types::market_subscription::MarketSubscription;
 pub use types::market_update::MarketUpdate;
 pub use types::meteora_amm_pool::MeteoraAmmPool;
 pub use types::meteora_dammv2_pool::MeteoraDammV2Pool;
 pub use types::meteora_dbc::MeteoraDbc;
 pub use types::meteora_vault::MeteoraVault;
+pub use types::pair_filter::PairFilter;
+pub use types::pair_ranking::PairRanking;
 pub use types::pf_bonding_curve::PfBondingCurve;
 pub use types::pool::{Pool, PoolTrait};
+pub use types::pool_account_field::PoolAccountField;
+pub use types::pool_audit::PoolAudit;
+pub use types::pool_lifecycle::PoolLifecycle;
+pub use types::pool_load_spec::PoolLoadSpec;
+pub use types::pool_snapshot::PoolSnapshot;
 pub use types::pools::Pools;
 pub use types::pumpswap_pool::PumpswapPool;
 pub use types::raydium_ammv4_pool::RaydiumAmmV4Pool;
 pub use types::raydium_cpmm_pool::RaydiumCpmmPool;
 pub use types::raydium_launchpad::RaydiumLaunchpad;
+pub use types::retry_policy::RetryPolicy;
+pub use types::rpc_backend::RpcBackend;
 pub use types::swap_direction::SwapDirection;
 pub use types::swap_tx::SwapTx;
+pub use types::sweep_policy::SweepPolicy;
 pub use types::token_creation::TokenCreation;
-pub use utilities::is_legit_token::is_legit_token;
-pub use utilities::load_pools::load_pools;
+pub use types::token_metadata::TokenMetadata;
+pub use utilities::extract_links::extract_links;
+pub use utilities::get_mint_supply::get_mint_supply;
+pub use utilities::get_pool_accounts::get_pool_accounts;
+pub use utilities::get_token_account_amount::get_token_account_amount;
+pub use utilities::get_token_account_owners::get_token_account_owners;
+pub use utilities::get_tx_account_keys::get_tx_account_keys;
+pub use utilities::get_tx_instructions::get_tx_instructions;
+pub use utilities::is_legit_token::{is_legit_token, is_legit_token_async};
+pub use utilities::load_graduation_successors::load_graduation_successors;
+pub use utilities::load_pf_bonding_curves::load_pf_bonding_curves;
+pub use utilities::load_pools::{load_pools, load_pools_with_specs};
+pub use utilities::load_pools_from_snapshot::load_pools_from_snapshot;
 pub use utilities::process_get_program_accounts_pool::process_get_program_accounts_pool;
@@ -0,0 +1,46 @@
+use crate::protocol_idls::meteora::{VaultIdlBig, VaultIdlSmall};
+use crate::types::meteora_vault::MeteoraVault;
+use borsh::BorshDeserialize;
+
+impl MeteoraVault {
+  /// Update the vault state from raw vault account data
+  ///
+  /// Updates the locked profit tracker, total amount, and LP mint address. The LP supply lives in
+  /// the LP mint account so it is not touched here. Supports both big (10240 bytes) and small
+  /// (1232 bytes) vault account formats, returns false for any other data.
+  pub(crate) fn apply_vault_data(&mut self, vault_data: &[u8]) -> bool {
+    // if it is a big vault and the data length is 10240, then we need to use the big vault idl
+    let (locked_profit_tracker, total_amount, lp_mint) = if vault_data.len() == 10240 {
+      let Ok(decoded) = VaultIdlBig::try_from_slice(vault_data) else {
+        return false;
+      };
+      (
+        decoded.locked_profit_tracker,
+        decoded.total_amount,
+        decoded.lp_mint,
+      )
+    } else if vault_data.len() == 1232 {
+      let Ok(decoded) = VaultIdlSmall::try_from_slice(vault_data) else {
+        return false;
+      };
+      (
+        decoded.locked_profit_tracker,
+        decoded.total_amount,
+        decoded.lp_mint,
+      )
+    } else {
+      return false;
+    };
+
+    self.last_updated_locked_profit = locked_profit_tracker.last_updated_locked_profit;
+    self.last_report = locked_profit_tracker.last_report;
+    self.locked_profit_degradation = locked_profit_tracker.locked_profit_degradation;
+    self.total_amount = total_amount;
+    /*
+    There are some cases where if you deterministically derive a popular token like USDC or USDT or
+    WSOL then the actual address will be different. In this case, lookup the actual address.
+    */
+    self.lp_token_address = lp_mint;
+    true
+  }
+}
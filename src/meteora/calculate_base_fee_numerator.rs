@@ -53,12 +53,8 @@ impl MeteoraDammV2Pool {
         match self.base_fee_mode {
           0 => {
             // Linear: cliff_fee_numerator - (period * reduction_factor)
-            let reduction = periods_passed * reduction_factor;
-            if reduction >= self.cliff_fee_numerator {
-              0
-            } else {
-              self.cliff_fee_numerator - reduction
-            }
+            let reduction = periods_passed.saturating_mul(reduction_factor);
+            self.cliff_fee_numerator.saturating_sub(reduction)
           }
           1 => {
             // Exponential: cliff_fee_numerator * (1 - reduction_factor/BASIS_POINT_MAX)^period
@@ -0,0 +1,46 @@
+use crate::meteora::sqrt_price_math::{
+  get_delta_amount_base, get_delta_amount_quote, get_next_sqrt_price_from_base_input,
+  get_next_sqrt_price_from_quote_input, to_u64_saturating,
+};
+use crate::types::error::Error;
+use crate::types::meteora_dammv2_pool::MeteoraDammV2Pool;
+use crate::types::swap_direction::SwapDirection;
+use primitive_types::U256;
+
+impl MeteoraDammV2Pool {
+  /// Calculate the output of swapping `amount_in` (after any input fee) in `direction` on the
+  /// pool's liquidity range, before any output fee
+  ///
+  /// Selling token A moves the sqrt price down towards `sqrt_min_price`, selling token B moves it
+  /// up towards `sqrt_max_price`. Swaps that would leave the range are filled up to its bound.
+  /// Fails with `Error::Arithmetic` for a pool without liquidity or priced outside of its range.
+  pub fn calculate_swap_amount_out(
+    &self,
+    amount_in: u64,
+    direction: SwapDirection,
+  ) -> Result<u64, Error> {
+    if self.liquidity == 0
+      || self.sqrt_price == 0
+      || self.sqrt_price < self.sqrt_min_price
+      || self.sqrt_price > self.sqrt_max_price
+    {
+      return Err(Error::Arithmetic { operation: "quote" });
+    }
+    let amount_in = U256::from(amount_in);
+    let amount_out = match direction {
+      SwapDirection::AToB => {
+        let next_sqrt_price =
+          get_next_sqrt_price_from_base_input(self.sqrt_price, self.liquidity, amount_in)
+            .max(self.sqrt_min_price);
+        get_delta_amount_quote(next_sqrt_price, self.sqrt_price, self.liquidity, false)
+      }
+      SwapDirection::BToA => {
+        let next_sqrt_price =
+          get_next_sqrt_price_from_quote_input(self.sqrt_price, self.liquidity, amount_in)
+            .min(self.sqrt_max_price);
+        get_delta_amount_base(self.sqrt_price, next_sqrt_price, self.liquidity, false)
+      }
+    };
+    Ok(to_u64_saturating(amount_out))
+  }
+}
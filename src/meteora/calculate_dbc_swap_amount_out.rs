@@ -0,0 +1,83 @@
+use crate::meteora::sqrt_price_math::{
+  get_delta_amount_base, get_delta_amount_quote, get_next_sqrt_price_from_base_input,
+  get_next_sqrt_price_from_quote_input, to_u64_saturating,
+};
+use crate::types::error::Error;
+use crate::types::meteora_dbc::MeteoraDbc;
+use crate::types::swap_direction::SwapDirection;
+use primitive_types::U256;
+
+impl MeteoraDbc {
+  /// Calculate the output of swapping `amount_in` (after any input fee) in `direction` along the
+  /// pool's price curve, before any output fee
+  ///
+  /// Walks the curve segments from the current sqrt price like the program does: up for buys
+  /// (quote in), down for sells (base in). Buys past the last point, the migration price, are
+  /// filled up to it and sells can't go below `sqrt_start_price`. Fails with `Error::Arithmetic`
+  /// for a pool without a price or curve.
+  pub fn calculate_swap_amount_out(
+    &self,
+    amount_in: u64,
+    direction: SwapDirection,
+  ) -> Result<u64, Error> {
+    if self.sqrt_price == 0 || self.curve.is_empty() {
+      return Err(Error::Arithmetic { operation: "quote" });
+    }
+    let mut sqrt_price = self.sqrt_price;
+    let mut amount_left = U256::from(amount_in);
+    let mut amount_out = U256::zero();
+    match direction {
+      SwapDirection::BToA => {
+        for point in &self.curve {
+          if amount_left.is_zero() {
+            break;
+          }
+          if point.sqrt_price <= sqrt_price {
+            continue;
+          }
+          let max_amount_in =
+            get_delta_amount_quote(sqrt_price, point.sqrt_price, point.liquidity, true);
+          let next_sqrt_price = if amount_left < max_amount_in {
+            get_next_sqrt_price_from_quote_input(sqrt_price, point.liquidity, amount_left)
+              .min(point.sqrt_price)
+          } else {
+            point.sqrt_price
+          };
+          amount_out += get_delta_amount_base(sqrt_price, next_sqrt_price, point.liquidity, false);
+          amount_left = amount_left.saturating_sub(max_amount_in);
+          sqrt_price = next_sqrt_price;
+        }
+      }
+      SwapDirection::AToB => {
+        // Each segment ends at a point and uses the liquidity of the point above it, the lowest
+        // segment goes down to the start price with the first point's liquidity
+        let lower_bounds = self
+          .curve
+          .iter()
+          .map(|point| point.sqrt_price)
+          .zip(self.curve.iter().skip(1).map(|point| point.liquidity))
+          .rev()
+          .chain([(self.sqrt_start_price, self.curve[0].liquidity)]);
+        for (lower_sqrt_price, liquidity) in lower_bounds {
+          if amount_left.is_zero() {
+            break;
+          }
+          if lower_sqrt_price >= sqrt_price {
+            continue;
+          }
+          let max_amount_in = get_delta_amount_base(lower_sqrt_price, sqrt_price, liquidity, true);
+          let next_sqrt_price = if amount_left < max_amount_in {
+            get_next_sqrt_price_from_base_input(sqrt_price, liquidity, amount_left)
+              .max(lower_sqrt_price)
+          } else {
+            lower_sqrt_price
+          };
+          amount_out += get_delta_amount_quote(next_sqrt_price, sqrt_price, liquidity, false);
+          amount_left = amount_left.saturating_sub(max_amount_in);
+          sqrt_price = next_sqrt_price;
+        }
+      }
+    }
+    Ok(to_u64_saturating(amount_out))
+  }
+}
@@ -15,12 +15,14 @@ impl MeteoraDammV2Pool {
     }
 
     // dynamic_fee_numerator = ((volatility_accumulator * bin_step)^2 * variable_fee_control + 99_999_999_999) / 100_000_000_000
-    let volatility_bin_product = self.volatility_accumulator * self.bin_step as u128;
-    let square_vfa_bin = volatility_bin_product * volatility_bin_product;
+    let volatility_bin_product = self
+      .volatility_accumulator
+      .saturating_mul(self.bin_step as u128);
+    let square_vfa_bin = volatility_bin_product.saturating_mul(volatility_bin_product);
 
     // Handle potential overflow by using saturating operations
     let v_fee = square_vfa_bin.saturating_mul(self.variable_fee_control as u128);
-    let dynamic_fee_numerator = (v_fee + 99_999_999_999) / 100_000_000_000;
+    let dynamic_fee_numerator = v_fee.div_ceil(100_000_000_000);
 
     // Convert to u64, capping at max value if necessary
     cmp::min(dynamic_fee_numerator, u64::MAX as u128) as u64
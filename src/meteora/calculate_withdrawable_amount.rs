@@ -6,20 +6,22 @@ impl MeteoraVault {
   /// Calculate the withdrawable amount from a Meteora vault
   ///
   /// Accounts for locked profit degradation over time. The locked profit gradually
-  /// becomes available based on the degradation rate and time since last report.
+  /// becomes available based on the degradation rate and time since last report (unix seconds).
+  /// A `last_report` in the future (clock skew) counts as no time elapsed, and a locked profit
+  /// bigger than the total amount leaves nothing withdrawable.
   pub fn calculate_withdrawable_amount(&self) -> u64 {
     let current_time: u128 = SystemTime::now()
       .duration_since(UNIX_EPOCH)
       .unwrap()
-      .as_millis();
-    let duration: u128 = current_time - self.last_report as u128;
-    let locked_fund_ratio: u128 = duration * self.locked_profit_degradation as u128;
+      .as_secs() as u128;
+    let duration: u128 = current_time.saturating_sub(self.last_report as u128);
+    let locked_fund_ratio: u128 = duration.saturating_mul(self.locked_profit_degradation as u128);
     if locked_fund_ratio > METEORA_CONSTANTS.locked_profit_degradation_denominator {
       return self.total_amount;
     }
     let locked_profit: u128 = (self.last_updated_locked_profit as u128)
       * (METEORA_CONSTANTS.locked_profit_degradation_denominator - locked_fund_ratio)
       / METEORA_CONSTANTS.locked_profit_degradation_denominator;
-    self.total_amount - locked_profit as u64
+    self.total_amount.saturating_sub(locked_profit as u64)
   }
 }
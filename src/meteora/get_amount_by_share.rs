@@ -0,0 +1,19 @@
+use crate::types::meteora_vault::MeteoraVault;
+
+impl MeteoraVault {
+  /// Amount of tokens that can be withdrawn from the vault with `lp_amount` vault LP tokens, i.e.
+  /// their share of `calculate_withdrawable_amount`. 0 for a vault without LP supply, capped at
+  /// the withdrawable amount if `lp_amount` exceeds the LP supply.
+  pub fn get_amount_by_share(&self, lp_amount: u128) -> u64 {
+    if self.lp_supply == 0 {
+      return 0;
+    }
+    let withdrawable_amount = self.calculate_withdrawable_amount();
+    lp_amount
+      .checked_mul(withdrawable_amount as u128)
+      .map(|amount| amount / self.lp_supply as u128)
+      .map_or(withdrawable_amount, |amount| {
+        amount.min(withdrawable_amount as u128) as u64
+      })
+  }
+}
@@ -1,6 +1,7 @@
 use crate::CentralContext;
 use crate::meteora::get_meteora_vault_from_token_address::get_meteora_vault_from_token_address;
 use crate::protocol_idls::meteora::MeteoraAmmPoolIdl;
+use crate::types::error::Error;
 use crate::types::meteora_amm_pool::MeteoraAmmPool;
 use crate::types::pool::Pool;
 use crate::types::pools::Pools;
@@ -13,14 +14,25 @@ impl MeteoraAmmPool {
   ///
   /// Parses the account buffer using the Meteora AMM pool IDL structure and creates
   /// vault references for both tokens. The vaults are looked up or created via the
-  /// central context cache.
+  /// central context cache. Panics if the data can't be decoded, see `try_from_account_info`.
   pub fn from_account_info(
     pubkey: Pubkey,
-    account_buffer: &Vec<u8>,
+    account_buffer: &[u8],
     central_context: Arc<CentralContext>,
   ) -> Self {
-    let decoded_layout: MeteoraAmmPoolIdl =
-      MeteoraAmmPoolIdl::try_from_slice(account_buffer).unwrap();
+    Self::try_from_account_info(pubkey, account_buffer, central_context)
+      .unwrap_or_else(|error| panic!("{}", error))
+  }
+
+  /// Fallible version of `from_account_info`. No vault is added to the cache when the data can't
+  /// be decoded.
+  pub fn try_from_account_info(
+    pubkey: Pubkey,
+    account_buffer: &[u8],
+    central_context: Arc<CentralContext>,
+  ) -> Result<Self, Error> {
+    let decoded_layout = MeteoraAmmPoolIdl::try_from_slice(account_buffer)
+      .map_err(|error| Error::decode(pubkey, error))?;
     let token_a_address: Pubkey = decoded_layout.token_a_mint;
     let token_b_address: Pubkey = decoded_layout.token_b_mint;
     let token_a_vault =
@@ -30,12 +42,14 @@ impl MeteoraAmmPool {
     let token_a_vault_address = token_a_vault.read().unwrap().vault;
     let token_b_vault_address = token_b_vault.read().unwrap().vault;
 
-    Self {
+    Ok(Self {
       info: Pool {
         pool_address: pubkey,
         token_a_address,
         token_b_address,
         pool_type: Pools::MeteoraAmm,
+        last_updated_slot: 0,
+        hydrated: false,
         token_a_vault_address,
         token_b_vault_address,
       },
@@ -57,6 +71,6 @@ impl MeteoraAmmPool {
       */
       token_a_lp_amount: 0,
       token_b_lp_amount: 0,
-    }
+    })
   }
 }
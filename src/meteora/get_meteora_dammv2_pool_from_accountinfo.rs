@@ -1,4 +1,5 @@
 use crate::protocol_idls::meteora::MeteoraDammv2PoolIdl;
+use crate::types::error::Error;
 use crate::types::meteora_dammv2_pool::MeteoraDammV2Pool;
 use crate::types::pool::Pool;
 use crate::types::pools::Pools;
@@ -9,17 +10,27 @@ impl MeteoraDammV2Pool {
   /// Create a Meteora DAMMv2 pool from on-chain account data
   ///
   /// Parses the account buffer using the Meteora DAMMv2 pool IDL structure.
-  /// DAMMv2 pools support dynamic fees based on volatility and time-based schedules.
+  /// DAMMv2 pools support dynamic fees based on volatility and time-based schedules. Panics if the
+  /// data can't be decoded, see `try_from_account_info`.
   pub fn from_account_info(pubkey: Pubkey, account_buffer: &[u8]) -> Self {
-    let decoded_layout: MeteoraDammv2PoolIdl =
-      MeteoraDammv2PoolIdl::try_from_slice(account_buffer).unwrap();
+    Self::try_from_account_info(pubkey, account_buffer).unwrap_or_else(|error| panic!("{}", error))
+  }
+
+  /// Fallible version of `from_account_info`. Pools with a `version` or `collect_fee_mode` the fee
+  /// calculations don't know are rejected with `Error::UnsupportedLayout`.
+  pub fn try_from_account_info(pubkey: Pubkey, account_buffer: &[u8]) -> Result<Self, Error> {
+    let decoded_layout = MeteoraDammv2PoolIdl::try_from_slice(account_buffer)
+      .map_err(|error| Error::decode(pubkey, error))?;
+    check_supported_layout(pubkey, &decoded_layout)?;
 
-    Self {
+    Ok(Self {
       info: Pool {
         pool_address: pubkey,
         token_a_address: decoded_layout.token_a_mint,
         token_b_address: decoded_layout.token_b_mint,
         pool_type: Pools::MeteoraDammV2,
+        last_updated_slot: 0,
+        hydrated: true,
         token_a_vault_address: decoded_layout.token_a_vault,
         token_b_vault_address: decoded_layout.token_b_vault,
       },
@@ -49,6 +60,27 @@ impl MeteoraDammV2Pool {
       sqrt_max_price: decoded_layout.sqrt_max_price,
       sqrt_min_price: decoded_layout.sqrt_min_price,
       version: decoded_layout.version,
-    }
+    })
+  }
+}
+
+/// Check that the pool's `version` (0 or 1) and `collect_fee_mode` (0 BothToken or 1 OnlyB) are
+/// ones the fee calculations support
+pub(crate) fn check_supported_layout(
+  pubkey: Pubkey,
+  decoded_layout: &MeteoraDammv2PoolIdl,
+) -> Result<(), Error> {
+  if decoded_layout.version > 1 {
+    return Err(Error::unsupported_layout(
+      pubkey,
+      format!("unknown DAMMv2 pool version {}", decoded_layout.version),
+    ));
+  }
+  if decoded_layout.collect_fee_mode > 1 {
+    return Err(Error::unsupported_layout(
+      pubkey,
+      format!("unknown DAMMv2 collect fee mode {}", decoded_layout.collect_fee_mode),
+    ));
   }
+  Ok(())
 }
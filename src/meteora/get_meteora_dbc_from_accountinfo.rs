@@ -1,4 +1,5 @@
-use crate::protocol_idls::meteora::{DbcPoolConfig, DbcVirtualPool};
+use crate::protocol_idls::meteora::{DbcPoolConfig, DbcVirtualPool, LiquidityDistributionConfig};
+use crate::types::error::Error;
 use crate::types::meteora_dbc::ActivationType;
 use crate::types::meteora_dbc::{BaseFeeMode, MeteoraDbc};
 use crate::types::pool::Pool;
@@ -7,30 +8,68 @@ use borsh::BorshDeserialize;
 use solana_sdk::pubkey::Pubkey;
 
 impl MeteoraDbc {
+  /// Create a Meteora DBC pool from its on-chain virtual pool data and its decoded config. Panics
+  /// if the data can't be decoded, see `try_from_account_info`.
   pub fn from_account_info(
     pubkey: Pubkey,
     account_buffer: &[u8],
     dbc_pool_config: &DbcPoolConfig,
     config_address: Pubkey,
   ) -> Self {
-    let dbc_virtual_pool = DbcVirtualPool::try_from_slice(account_buffer).unwrap();
-    Self {
-      pool: Pool {
+    Self::try_from_account_info(pubkey, account_buffer, dbc_pool_config, config_address)
+      .unwrap_or_else(|error| panic!("{}", error))
+  }
+
+  /// Fallible version of `from_account_info`
+  pub fn try_from_account_info(
+    pubkey: Pubkey,
+    account_buffer: &[u8],
+    dbc_pool_config: &DbcPoolConfig,
+    config_address: Pubkey,
+  ) -> Result<Self, Error> {
+    let dbc_virtual_pool = DbcVirtualPool::try_from_slice(account_buffer)
+      .map_err(|error| Error::decode(pubkey, error))?;
+    let mut dbc = Self::from_config(
+      Pool {
         pool_address: pubkey,
         token_a_address: dbc_virtual_pool.base_mint,
         token_b_address: dbc_pool_config.quote_mint,
         token_a_vault_address: dbc_virtual_pool.base_vault,
         token_b_vault_address: dbc_virtual_pool.quote_vault,
         pool_type: Pools::MeteoraDbc,
+        last_updated_slot: 0,
+        hydrated: true,
       },
+      config_address,
+      dbc_pool_config,
+    );
 
-      config: config_address,
+    dbc.sqrt_price = dbc_virtual_pool.sqrt_price;
+    // Reserve amounts from the virtual pool
+    dbc.base_reserve = dbc_virtual_pool.base_reserve;
+    dbc.quote_reserve = dbc_virtual_pool.quote_reserve;
+    dbc.activation_point = dbc_virtual_pool.activation_point;
+    dbc.volatility_accumulator = dbc_virtual_pool.volatility_tracker.volatility_accumulator;
+    dbc.migration_progress = dbc_virtual_pool.migration_progress;
+    dbc.is_migrated = dbc_virtual_pool.is_migrated != 0;
+    Ok(dbc)
+  }
 
-      sqrt_price: dbc_virtual_pool.sqrt_price,
+  /// Build a DBC pool with the fee settings of its config. Pool state (price, reserves, activation
+  /// point, volatility and migration) is left at 0 for the caller to fill in.
+  pub(crate) fn from_config(
+    pool: Pool,
+    config_address: Pubkey,
+    dbc_pool_config: &DbcPoolConfig,
+  ) -> Self {
+    Self {
+      pool,
+
+      config: config_address,
 
-      // Reserve amounts from the virtual pool
-      base_reserve: dbc_virtual_pool.base_reserve,
-      quote_reserve: dbc_virtual_pool.quote_reserve,
+      sqrt_price: 0,
+      base_reserve: 0,
+      quote_reserve: 0,
 
       cliff_fee_numerator: dbc_pool_config.pool_fees.base_fee.cliff_fee_numerator,
       base_fee_number_of_periods: dbc_pool_config.pool_fees.base_fee.first_factor,
@@ -44,14 +83,29 @@ impl MeteoraDbc {
       } else {
         BaseFeeMode::Linear
       },
-      activation_point: dbc_virtual_pool.activation_point,
-      volatility_accumulator: dbc_virtual_pool.volatility_tracker.volatility_accumulator,
+      activation_point: 0,
+      volatility_accumulator: 0,
       variable_fee_control: dbc_pool_config.pool_fees.dynamic_fee.variable_fee_control,
       activation_type: if dbc_pool_config.activation_type == 0 {
         ActivationType::Slot
       } else {
         ActivationType::Time
       },
+      migration_option: dbc_pool_config.migration_option,
+      migration_progress: 0,
+      is_migrated: false,
+      collect_fee_mode: dbc_pool_config.collect_fee_mode,
+      sqrt_start_price: dbc_pool_config.sqrt_start_price,
+      // Unused points are zeroed
+      curve: dbc_pool_config
+        .curve
+        .iter()
+        .take_while(|point| point.sqrt_price != 0 && point.liquidity != 0)
+        .map(|point| LiquidityDistributionConfig {
+          sqrt_price: point.sqrt_price,
+          liquidity: point.liquidity,
+        })
+        .collect(),
     }
   }
 }
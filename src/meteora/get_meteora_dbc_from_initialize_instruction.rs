@@ -0,0 +1,138 @@
+use crate::constants::METEORA_CONSTANTS;
+use crate::protocol_idls::meteora::{DbcInitializeVirtualPoolInstructionDataIdl, DbcPoolConfig};
+use crate::types::instruction::Instruction;
+use crate::types::meteora_dbc::{ActivationType, MeteoraDbc};
+use crate::types::pool::Pool;
+use crate::types::pools::Pools;
+use crate::types::token_creation::TokenCreation;
+use borsh::BorshDeserialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+impl MeteoraDbc {
+  /**
+  Decode a Meteora DBC initialize_virtual_pool_with_spl_token or
+  initialize_virtual_pool_with_token2022 instruction into the token creation event and the newly
+  created virtual pool. The pool starts at the config's start price with the whole initial base
+  supply in reserve. Returns `None` if the instruction is not a DBC pool initialize instruction or
+  is malformed.
+
+  Both instructions start their accounts with: config, pool_authority, creator, base_mint,
+  quote_mint, pool, base_vault, quote_vault.
+  */
+  pub fn from_initialize_instruction(
+    instruction: &Instruction,
+    dbc_pool_config: &DbcPoolConfig,
+    block_time: u64,
+    slot: u64,
+    index: u64,
+    atomic_instruction_index: u8,
+    signature: Signature,
+  ) -> Option<(Self, TokenCreation)> {
+    let token_creation = Self::token_creation_from_initialize_instruction(
+      instruction,
+      block_time,
+      slot,
+      index,
+      atomic_instruction_index,
+      signature,
+    )?;
+    let pool_address = token_creation.market_address;
+    let token_address = token_creation.address;
+    let mut dbc = Self::from_config(
+      Pool {
+        pool_address,
+        token_a_address: token_address,
+        token_b_address: dbc_pool_config.quote_mint,
+        token_a_vault_address: *instruction.account(6)?,
+        token_b_vault_address: *instruction.account(7)?,
+        pool_type: Pools::MeteoraDbc,
+        last_updated_slot: slot,
+        hydrated: true,
+      },
+      *instruction.account(0)?,
+      dbc_pool_config,
+    );
+    dbc.sqrt_price = dbc_pool_config.sqrt_start_price;
+    dbc.base_reserve = Self::initial_base_supply(dbc_pool_config)?;
+    // The program activates the pool at the point it is initialized
+    dbc.activation_point = match dbc.activation_type {
+      ActivationType::Slot => slot,
+      ActivationType::Time => block_time,
+    };
+
+    Some((dbc, token_creation))
+  }
+
+  /// Decode the token creation event of a DBC pool initialize instruction. Unlike
+  /// `from_initialize_instruction` this doesn't need the pool's config, so the event can be
+  /// reported before the config is loaded.
+  pub fn token_creation_from_initialize_instruction(
+    instruction: &Instruction,
+    block_time: u64,
+    slot: u64,
+    index: u64,
+    atomic_instruction_index: u8,
+    signature: Signature,
+  ) -> Option<TokenCreation> {
+    if !Self::is_initialize_instruction(instruction) {
+      return None;
+    }
+    let decoded_layout =
+      DbcInitializeVirtualPoolInstructionDataIdl::deserialize(&mut &instruction.data[..]).ok()?;
+    Some(TokenCreation {
+      address: *instruction.account(3)?,
+      creator: *instruction.account(2)?,
+      market_address: *instruction.account(5)?,
+      name: decoded_layout.name,
+      symbol: decoded_layout.symbol,
+      uri: decoded_layout.uri,
+      // Only available in the off chain metadata behind the uri
+      description: String::new(),
+      twitter: String::new(),
+      website: String::new(),
+      block_time,
+      slot,
+      index,
+      atomic_instruction_index,
+      signature,
+    })
+  }
+
+  /// Get the config account of a DBC pool initialize instruction without decoding the rest of it,
+  /// so the caller can look up the config before calling `from_initialize_instruction`.
+  pub fn initialize_instruction_config(instruction: &Instruction) -> Option<Pubkey> {
+    if !Self::is_initialize_instruction(instruction) {
+      return None;
+    }
+    instruction.account(0).copied()
+  }
+
+  fn is_initialize_instruction(instruction: &Instruction) -> bool {
+    instruction.program_id() == Some(&METEORA_CONSTANTS.dbc_program)
+      && METEORA_CONSTANTS
+        .dbc_initialize_virtual_pool_discriminators
+        .iter()
+        .any(|discriminator| instruction.data.starts_with(discriminator))
+  }
+
+  /*
+  Mirrors get_initial_base_supply in the DBC program: fixed supply configs mint their whole pre
+  migration supply, otherwise the amount sold on the curve plus the amount reserved for migration
+  and locked vesting is minted into the base vault.
+  */
+  fn initial_base_supply(dbc_pool_config: &DbcPoolConfig) -> Option<u64> {
+    if dbc_pool_config.fixed_token_supply_flag == 1 {
+      return Some(dbc_pool_config.pre_migration_token_supply);
+    }
+    let vesting = &dbc_pool_config.locked_vesting_config;
+    let locked_vesting_amount = vesting
+      .amount_per_period
+      .checked_mul(vesting.number_of_period)?
+      .checked_add(vesting.cliff_unlock_amount)?;
+    dbc_pool_config
+      .swap_base_amount
+      .checked_add(dbc_pool_config.migration_base_threshold)?
+      .checked_add(locked_vesting_amount)
+  }
+}
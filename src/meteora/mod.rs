@@ -1,8 +1,15 @@
 pub mod calculate_base_fee_numerator;
+pub mod calculate_dammv2_swap_amount_out;
+pub mod calculate_dbc_swap_amount_out;
 pub mod calculate_dynamic_fee_numerator;
 pub mod calculate_withdrawable_amount;
+pub mod get_amount_by_share;
 pub mod get_meteora_ammpool_from_accountinfo;
 pub mod get_meteora_dammv2_pool_from_accountinfo;
 pub mod get_meteora_vault_from_token_address;
 pub mod update_meteora_vaultinfo;
+mod apply_meteora_vault_data;
 mod get_meteora_dbc_from_accountinfo;
+mod get_meteora_dbc_from_initialize_instruction;
+mod serialize_shared_meteora_vault;
+mod sqrt_price_math;
@@ -0,0 +1,22 @@
+use crate::types::meteora_vault::MeteoraVault;
+use borsh::io::{Error, Read, Write};
+use borsh::{BorshDeserialize, BorshSerialize};
+use std::sync::{Arc, RwLock};
+
+impl MeteoraVault {
+  /// Serialize a copy of a shared vault, used by the `MeteoraAmmPool` borsh derive
+  pub(crate) fn serialize_shared<W: Write>(
+    vault: &Arc<RwLock<MeteoraVault>>,
+    writer: &mut W,
+  ) -> Result<(), Error> {
+    vault.read().unwrap().serialize(writer)
+  }
+
+  /// Deserialize a vault into a new shared vault, used by the `MeteoraAmmPool` borsh derive. The
+  /// vault isn't shared with other pools until it is linked to `meteora_vault_cache`.
+  pub(crate) fn deserialize_shared<R: Read>(
+    reader: &mut R,
+  ) -> Result<Arc<RwLock<MeteoraVault>>, Error> {
+    Ok(Arc::new(RwLock::new(MeteoraVault::deserialize_reader(reader)?)))
+  }
+}
@@ -0,0 +1,85 @@
+//! Concentrated liquidity math shared by Meteora DAMMv2 and DBC pools, following the programs' own
+//! implementation. Sqrt prices are Q64.64 square roots of the price of the base token (A) in quote
+//! token (B) units, and liquidity is Q64.64 as well.
+use primitive_types::U256;
+
+/// Amount of base token between the `lower` and `upper` sqrt prices for `liquidity`:
+/// `liquidity * (upper - lower) / (lower * upper)`
+pub(crate) fn get_delta_amount_base(
+  lower: u128,
+  upper: u128,
+  liquidity: u128,
+  round_up: bool,
+) -> U256 {
+  let numerator = U256::from(liquidity) * U256::from(upper.saturating_sub(lower));
+  let denominator = U256::from(lower) * U256::from(upper);
+  if denominator.is_zero() {
+    return U256::zero();
+  }
+  let (quotient, remainder) = numerator.div_mod(denominator);
+  if round_up && !remainder.is_zero() {
+    quotient + 1
+  } else {
+    quotient
+  }
+}
+
+/// Amount of quote token between the `lower` and `upper` sqrt prices for `liquidity`:
+/// `liquidity * (upper - lower) >> 128`
+pub(crate) fn get_delta_amount_quote(
+  lower: u128,
+  upper: u128,
+  liquidity: u128,
+  round_up: bool,
+) -> U256 {
+  let product = U256::from(liquidity) * U256::from(upper.saturating_sub(lower));
+  let mask = (U256::one() << 128) - 1;
+  if round_up && !(product & mask).is_zero() {
+    (product >> 128) + 1
+  } else {
+    product >> 128
+  }
+}
+
+/// Sqrt price after `amount` of base token is swapped in, moving the price down:
+/// `liquidity * sqrt_price / (liquidity + amount * sqrt_price)`, rounded up
+pub(crate) fn get_next_sqrt_price_from_base_input(
+  sqrt_price: u128,
+  liquidity: u128,
+  amount: U256,
+) -> u128 {
+  let numerator = U256::from(liquidity) * U256::from(sqrt_price);
+  let denominator =
+    U256::from(liquidity).saturating_add(amount.saturating_mul(U256::from(sqrt_price)));
+  if denominator.is_zero() {
+    return sqrt_price;
+  }
+  let (quotient, remainder) = numerator.div_mod(denominator);
+  let next_sqrt_price = if remainder.is_zero() {
+    quotient
+  } else {
+    quotient + 1
+  };
+  // Never above the starting sqrt price, which fits in a u128
+  next_sqrt_price.min(U256::from(sqrt_price)).as_u128()
+}
+
+/// Sqrt price after `amount` of quote token is swapped in, moving the price up:
+/// `sqrt_price + (amount << 128) / liquidity`. Saturates at `u128::MAX`.
+pub(crate) fn get_next_sqrt_price_from_quote_input(
+  sqrt_price: u128,
+  liquidity: u128,
+  amount: U256,
+) -> u128 {
+  if liquidity == 0 {
+    return u128::MAX;
+  }
+  let quotient = amount.saturating_mul(U256::one() << 128) / U256::from(liquidity);
+  let next_sqrt_price = quotient.saturating_add(U256::from(sqrt_price));
+  next_sqrt_price.min(U256::from(u128::MAX)).as_u128()
+}
+
+/// Clamp an amount to a u64
+pub(crate) fn to_u64_saturating(amount: U256) -> u64 {
+  amount.min(U256::from(u64::MAX)).as_u64()
+}
@@ -1,68 +1,71 @@
 use crate::CentralContext;
+use crate::types::error::Error;
 use crate::types::meteora_vault::MeteoraVault;
-use borsh::BorshDeserialize;
-use solana_sdk::account::ReadableAccount;
-use std::sync::Arc;
-use crate::protocol_idls::meteora::{VaultIdlBig, VaultIdlSmall};
+use solana_sdk::pubkey::Pubkey;
+use std::sync::{Arc, RwLock};
 
 impl MeteoraVault {
   /// Update vault information from on-chain account data
   ///
   /// Fetches the vault account and updates all vault state including locked profit
   /// tracker, total amount, and LP token supply. Supports both big (10240 bytes)
-  /// and small (1232 bytes) vault account formats.
+  /// and small (1232 bytes) vault account formats. Errors are logged, see `try_update_vault_info`
+  /// to handle them.
   pub fn update_vault_info(&mut self, central_context: Arc<CentralContext>) {
-    let vault_address = self.vault;
-    let binding = central_context
-      .json_rpc_client
-      .get_account(&vault_address)
-      .unwrap();
-    let vault_data = binding.data();
+    if let Err(error) = self.try_update_vault_info(&central_context) {
+      println!("update_meteora_vaultinfo: {}", error);
+    }
+  }
 
-    // if it is a big vault and the data length is 10240, then we need to use the big vault idl
-    if vault_data.len() == 10240 {
-      let decoded = VaultIdlBig::try_from_slice(&vault_data).unwrap();
+  /// Fallible version of `update_vault_info`. Vault accounts of an unknown size are rejected with
+  /// `Error::UnsupportedLayout`.
+  pub fn try_update_vault_info(&mut self, central_context: &CentralContext) -> Result<(), Error> {
+    let vault_account = central_context.json_rpc_client.get_account(&self.vault)?;
+    if !self.apply_vault_data(&vault_account.data) {
+      return Err(unknown_vault_size(self.vault, vault_account.data.len()));
+    }
 
-      self.last_updated_locked_profit = decoded.locked_profit_tracker.last_updated_locked_profit;
-      self.last_report = decoded.locked_profit_tracker.last_report;
-      self.locked_profit_degradation = decoded.locked_profit_tracker.locked_profit_degradation;
-      self.total_amount = decoded.total_amount;
-      /*
-      There are some cases where if you deterministically derive a popular token like USDC or USDT or
-      WSOL then the actual address will be different. In this case, lookup the actual address.
-      */
-      self.lp_token_address = decoded.lp_mint;
-      self.lp_supply = central_context
-        .json_rpc_client
-        .get_token_supply(&decoded.lp_mint)
-        .unwrap()
-        .amount
-        .parse()
-        .unwrap();
-    } else if vault_data.len() == 1232 {
-      let decoded = VaultIdlSmall::try_from_slice(&vault_data).unwrap();
+    let lp_supply = central_context
+      .json_rpc_client
+      .get_token_supply(&self.lp_token_address)?;
+    self.lp_supply = lp_supply
+      .amount
+      .parse()
+      .map_err(|error| Error::decode(self.lp_token_address, error))?;
+    Ok(())
+  }
 
-      self.last_updated_locked_profit = decoded.locked_profit_tracker.last_updated_locked_profit;
-      self.last_report = decoded.locked_profit_tracker.last_report;
-      self.locked_profit_degradation = decoded.locked_profit_tracker.locked_profit_degradation;
-      self.total_amount = decoded.total_amount;
-      /*
-      There are some cases where if you deterministically derive a popular token like USDC or USDT or
-      WSOL then the actual address will be different. In this case, lookup the actual address.
-      */
-      self.lp_token_address = decoded.lp_mint;
-      self.lp_supply = central_context
-        .json_rpc_client
-        .get_token_supply(&decoded.lp_mint)
-        .unwrap()
-        .amount
-        .parse()
-        .unwrap();
-    } else {
-      println!(
-        "update_meteora_vaultinfo: Unknown vault data length for vault: {:?}",
-        vault_address
-      );
+  /// Async version of `try_update_vault_info` using `json_rpc_client_async`
+  ///
+  /// Takes the shared vault rather than `&mut self` so no lock is held across the RPC calls. The
+  /// vault is only written once each fetch succeeds.
+  pub async fn update_vault_info_async(
+    vault: &RwLock<MeteoraVault>,
+    central_context: &CentralContext,
+  ) -> Result<(), Error> {
+    let vault_address = vault.read().unwrap().vault;
+    let vault_account = central_context
+      .json_rpc_client_async
+      .get_account(&vault_address)
+      .await?;
+    if !vault.write().unwrap().apply_vault_data(&vault_account.data) {
+      return Err(unknown_vault_size(vault_address, vault_account.data.len()));
     }
+
+    let lp_token_address = vault.read().unwrap().lp_token_address;
+    let lp_supply = central_context
+      .json_rpc_client_async
+      .get_token_supply(&lp_token_address)
+      .await?
+      .amount
+      .parse()
+      .map_err(|error| Error::decode(lp_token_address, error))?;
+    vault.write().unwrap().lp_supply = lp_supply;
+    Ok(())
   }
 }
+
+/// Error for a vault account that is neither the big nor the small vault format
+fn unknown_vault_size(vault_address: Pubkey, size: usize) -> Error {
+  Error::unsupported_layout(vault_address, format!("unknown vault data length {}", size))
+}
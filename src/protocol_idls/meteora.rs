@@ -77,6 +77,7 @@ pub struct SwapResult {
 }
 
 // For big vaults like this one of length 10240: mPWBpKzzchEjitz7x4Q2d7cbQ3fHibF2BHWbWk8YGnH
+#[allow(dead_code)]
 #[derive(BorshDeserialize)]
 pub struct VaultIdlBig {
   pub discriminator: [u8; 8],
@@ -96,6 +97,7 @@ pub struct VaultIdlBig {
 }
 
 // For small vaults like this one of length 1232: 12Q6qfukBF7KwbwxRvLnxhEnPdZPb7vjd8bPckCChf8
+#[allow(dead_code)]
 #[derive(BorshDeserialize)]
 pub struct VaultIdlSmall {
   pub discriminator: [u8; 8],
@@ -131,7 +133,7 @@ pub struct VaultBumps {
 https://github.com/MeteoraAg/cp-amm/blob/main/programs/cp-amm/src/state/pool.rs
 Pulled directly of of Meteora DammV2 program src. All supporting types included below:
 */
-#[derive(BorshDeserialize, Debug)]
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct MeteoraDammv2PoolIdl {
   pub discriminator: [u8; 8],
   /// Pool fee
@@ -200,9 +202,20 @@ pub struct MeteoraDammv2PoolIdl {
   pub reward_infos: [RewardInfo; NUM_REWARDS],
 }
 
+impl MeteoraDammv2PoolIdl {
+  /// Anchor account discriminator of `Pool`
+  pub const DISCRIMINATOR: [u8; 8] = [241, 154, 109, 4, 17, 177, 109, 188];
+  // `PoolFeesStruct` is the 40 byte base fee, 8 bytes of fee percents, the 96 byte dynamic fee
+  // and 16 bytes of padding
+  /// Byte offset of `token_a_mint`
+  pub const TOKEN_A_MINT_OFFSET: usize = 8 + (40 + 8 + 96 + 16);
+  /// Byte offset of `token_b_mint`
+  pub const TOKEN_B_MINT_OFFSET: usize = Self::TOKEN_A_MINT_OFFSET + 32;
+}
+
 const NUM_REWARDS: usize = 2;
 
-#[derive(BorshDeserialize, Debug)]
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct PoolFeesStruct {
   /// Trade fees are extra token amounts that are held inside the token
   /// accounts during a trade, making the value of liquidity tokens rise.
@@ -228,7 +241,7 @@ pub struct PoolFeesStruct {
   pub padding_1: [u64; 2],
 }
 
-#[derive(BorshDeserialize, Debug)]
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct PoolMetrics {
   pub total_lp_a_fee: u128,
   pub total_lp_b_fee: u128,
@@ -240,7 +253,7 @@ pub struct PoolMetrics {
   pub padding: u64,
 }
 
-#[derive(BorshDeserialize, Debug)]
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct RewardInfo {
   /// Indicates if the reward has been initialized
   pub initialized: u8,
@@ -271,7 +284,7 @@ pub struct RewardInfo {
   pub cumulative_seconds_with_empty_liquidity_reward: u64,
 }
 
-#[derive(BorshDeserialize, Debug)]
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct BaseFeeStruct {
   pub cliff_fee_numerator: u64,
   // In fee scheduler first_factor: number_of_period, second_factor: period_frequency, third_factor: reduction_factor
@@ -284,7 +297,7 @@ pub struct BaseFeeStruct {
   pub padding_1: u64,
 }
 
-#[derive(BorshDeserialize, Debug)]
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct DynamicFeeStruct {
   pub initialized: u8, // 0, ignore for dynamic fee
   pub padding: [u8; 7],
@@ -306,7 +319,7 @@ This layout is pulled from this guy's sdk but using his sdk creates manu cargo c
 copied over the chunks needed manually
 https://github.com/regolith-labs/meteora-pools-sdk/tree/master/src/types
 */
-#[derive(BorshDeserialize)]
+#[derive(BorshSerialize, BorshDeserialize)]
 pub struct AmmPoolFees {
   /// Trade fees are extra token amounts that are held inside the token
   /// accounts during a trade, making the value of liquidity tokens rise.
@@ -323,6 +336,7 @@ pub struct AmmPoolFees {
   pub protocol_trade_fee_denominator: u64,
 }
 
+#[allow(dead_code)]
 #[derive(BorshDeserialize)]
 struct Padding {
   /// Padding 0
@@ -333,6 +347,7 @@ struct Padding {
   pub padding2: [u64; 21],
 }
 
+#[allow(dead_code)]
 #[derive(BorshDeserialize)]
 enum CurveType {
   ConstantProduct,
@@ -348,6 +363,7 @@ enum CurveType {
   },
 }
 
+#[allow(dead_code)]
 #[derive(BorshDeserialize)]
 struct TokenMultiplier {
   /// Multiplier for token A of the pool.
@@ -358,6 +374,7 @@ struct TokenMultiplier {
   pub precision_factor: u8,
 }
 
+#[allow(dead_code)]
 #[derive(BorshDeserialize)]
 struct Depeg {
   /// The virtual price of staking / interest bearing token
@@ -368,6 +385,7 @@ struct Depeg {
   pub depeg_type: DepegType,
 }
 
+#[allow(dead_code)]
 #[derive(BorshDeserialize)]
 enum DepegType {
   None,
@@ -414,7 +432,7 @@ pub struct DbcPoolConfig {
   pub curve: [LiquidityDistributionConfig; 20],
 }
 
-#[derive(BorshDeserialize, Debug)]
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct DbcVirtualPool {
   pub discriminator: [u8; 8],
   pub volatility_tracker: VolatilityTracker,
@@ -446,7 +464,16 @@ pub struct DbcVirtualPool {
   pub _padding_1: [u64; 7],
 }
 
-#[derive(BorshDeserialize)]
+impl DbcVirtualPool {
+  /// Anchor account discriminator of `VirtualPool`
+  pub const DISCRIMINATOR: [u8; 8] = [213, 224, 5, 209, 98, 69, 119, 92];
+  /// Byte offset of `config`, after the discriminator and the 64 byte `VolatilityTracker`
+  pub const CONFIG_OFFSET: usize = 8 + 64;
+  /// Byte offset of `base_mint`, after the config and creator
+  pub const BASE_MINT_OFFSET: usize = Self::CONFIG_OFFSET + 2 * 32;
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
 pub struct MeteoraAmmPoolIdl {
   pub discriminator: [u8; 8],
   /// LP token mint of the pool
@@ -478,3 +505,24 @@ pub struct MeteoraAmmPoolIdl {
   pub fees: AmmPoolFees,
   pub garbage: [u8; 590],
 }
+
+impl MeteoraAmmPoolIdl {
+  /// Anchor account discriminator of `Pool`
+  pub const DISCRIMINATOR: [u8; 8] = [241, 154, 109, 4, 17, 177, 109, 188];
+  /// Byte offset of `token_a_mint`, after the discriminator and LP mint
+  pub const TOKEN_A_MINT_OFFSET: usize = 8 + 32;
+  /// Byte offset of `token_b_mint`
+  pub const TOKEN_B_MINT_OFFSET: usize = Self::TOKEN_A_MINT_OFFSET + 32;
+}
+
+/*
+Args of initialize_virtual_pool_with_spl_token and initialize_virtual_pool_with_token2022, both take
+the same InitializePoolParameters.
+*/
+#[derive(BorshDeserialize, Debug)]
+pub struct DbcInitializeVirtualPoolInstructionDataIdl {
+  pub discriminator: [u8; 8],
+  pub name: String,
+  pub symbol: String,
+  pub uri: String,
+}
@@ -1,6 +1,7 @@
 use borsh::BorshDeserialize;
 use solana_sdk::pubkey::Pubkey;
 
+#[allow(dead_code)]
 #[derive(BorshDeserialize)]
 pub struct PfBondingCurveIdl {
   pub discriminator: [u8; 8],
@@ -70,3 +71,15 @@ pub struct PfTradeEventIdlOld {
   pub virtual_token_reserves: u64,
 }
 
+/**
+Data of the bonding curve create and create_v2 instructions. create_v2 (Token-2022 mints) appends
+an is_mayhem_mode flag which we don't need, so deserialize without requiring the whole buffer.
+*/
+#[derive(BorshDeserialize)]
+pub struct PfCreateInstructionDataIdl {
+  pub discriminator: [u8; 8],
+  pub name: String,
+  pub symbol: String,
+  pub uri: String,
+  pub creator: Pubkey,
+}
@@ -1,4 +1,4 @@
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_sdk::pubkey::Pubkey;
 
 /*
@@ -6,7 +6,7 @@ Anchor IDL extracted representation of the PumpSwap pool account. Only the subse
 actually need to reconstruct our high-level `PumpswapPool` type are included here. The layout order
 MUST match the on-chain structure so that `BorshDeserialize` can succeed.
 */
-#[derive(BorshDeserialize)]
+#[derive(BorshSerialize, BorshDeserialize)]
 pub struct PumpAmmPoolAccount {
   pub padding1: u64,
   pub pool_bump: u8,
@@ -22,6 +22,15 @@ pub struct PumpAmmPoolAccount {
   pub padding: [u8; 57],
 }
 
+impl PumpAmmPoolAccount {
+  /// Anchor account discriminator of `Pool`
+  pub const DISCRIMINATOR: [u8; 8] = [241, 154, 109, 4, 17, 177, 109, 188];
+  /// Byte offset of `base_mint`, after the discriminator, bump, index and creator
+  pub const BASE_MINT_OFFSET: usize = 8 + 1 + 2 + 32;
+  /// Byte offset of `quote_mint`
+  pub const QUOTE_MINT_OFFSET: usize = Self::BASE_MINT_OFFSET + 32;
+}
+
 #[derive(BorshDeserialize)]
 pub struct PumpAmmCreatePoolInstructionDataIdl {
   pub discriminator: [u8; 8],
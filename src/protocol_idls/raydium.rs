@@ -3,10 +3,11 @@ This is pulled from Raydium io Raydium SDK:
 https://github.com/raydium-io/raydium-sdk/blob/f4b7f47e744c12a8b0119b85e16d0d8274aa5ba9/src/liquidity/layout.ts
 The official Raydium IDL on their site did not contain all this info
 */
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_sdk::pubkey::Pubkey;
 
-#[derive(BorshDeserialize)]
+#[allow(dead_code)]
+#[derive(BorshSerialize, BorshDeserialize)]
 pub struct AmmV4PoolInfoIdl {
   status: u64,
   nonce: u64,
@@ -36,7 +37,7 @@ pub struct AmmV4PoolInfoIdl {
   quote_need_take_pnl: u64,
   quote_total_pnl: u64,
   base_total_pnl: u64,
-  pool_open_time: u64,
+  pub pool_open_time: u64,
   punish_pc_amount: u64,
   punish_coin_amount: u64,
   orderbook_to_init_time: u64,
@@ -65,6 +66,14 @@ pub struct AmmV4PoolInfoIdl {
   padding: [u64; 3],
 }
 
+impl AmmV4PoolInfoIdl {
+  // 32 u64 fields, then the swap amounts (4 u128 and 2 u64) and both vaults
+  /// Byte offset of `base_mint`
+  pub const BASE_MINT_OFFSET: usize = 32 * 8 + (4 * 16 + 2 * 8) + 2 * 32;
+  /// Byte offset of `quote_mint`
+  pub const QUOTE_MINT_OFFSET: usize = Self::BASE_MINT_OFFSET + 32;
+}
+
 /*
 Pulled from Raydium SDK V2. Confirmed by the IDL to be accurate minus some discriminator and buffer
 value differences.
@@ -101,7 +110,7 @@ Directly from the protocol source code:
 https://github.com/raydium-io/raydium-cp-swap/blob/master/programs/cp-swap/src/states/pool.rs
 Added in instruction discriminator for anchor accounts
 */
-#[derive(BorshDeserialize)]
+#[derive(BorshSerialize, BorshDeserialize)]
 pub struct CpmmPoolInfoIdl {
   pub discriminator: [u8; 8],
   /// Which config the pool belongs
@@ -168,7 +177,19 @@ pub struct CpmmPoolInfoIdl {
   pub padding: [u64; 28],
 }
 
-#[derive(BorshDeserialize)]
+impl CpmmPoolInfoIdl {
+  /// Anchor account discriminator of `PoolState`
+  pub const DISCRIMINATOR: [u8; 8] = [247, 237, 227, 245, 215, 195, 222, 70];
+  /// Byte offset of `amm_config`
+  pub const AMM_CONFIG_OFFSET: usize = 8;
+  /// Byte offset of `token_0_mint`, after the config, creator, both vaults and the LP mint
+  pub const TOKEN_0_MINT_OFFSET: usize = Self::AMM_CONFIG_OFFSET + 5 * 32;
+  /// Byte offset of `token_1_mint`
+  pub const TOKEN_1_MINT_OFFSET: usize = Self::TOKEN_0_MINT_OFFSET + 32;
+}
+
+#[allow(dead_code)]
+#[derive(BorshSerialize, BorshDeserialize)]
 pub struct LaunchpadPoolIdl {
   discriminator: [u8; 8],
   pub epoch: u64,
@@ -199,6 +220,21 @@ pub struct LaunchpadPoolIdl {
   padding: [u8; 64],
 }
 
+impl LaunchpadPoolIdl {
+  /// Anchor account discriminator of `PoolState`
+  pub const DISCRIMINATOR: [u8; 8] = [247, 237, 227, 245, 215, 195, 222, 70];
+  // Discriminator, epoch, 5 u8 fields, 10 u64 fields and the vesting schedule
+  /// Byte offset of `global_config`
+  pub const GLOBAL_CONFIG_OFFSET: usize = 8 + 8 + 5 + 10 * 8 + 5 * 8;
+  /// Byte offset of `platform_config`
+  pub const PLATFORM_CONFIG_OFFSET: usize = Self::GLOBAL_CONFIG_OFFSET + 32;
+  /// Byte offset of `base_mint`
+  pub const BASE_MINT_OFFSET: usize = Self::PLATFORM_CONFIG_OFFSET + 32;
+  /// Byte offset of `quote_mint`
+  pub const QUOTE_MINT_OFFSET: usize = Self::BASE_MINT_OFFSET + 32;
+}
+
+#[allow(dead_code)]
 #[derive(BorshDeserialize)]
 pub struct LaunchpadTradeEventIdl {
   discriminator: [u8; 16],
@@ -223,6 +259,7 @@ pub struct LaunchpadTradeEventIdl {
 }
 
 
+#[allow(dead_code)]
 #[derive(BorshDeserialize)]
 pub struct CpmmInitializeInstructionDataIdl {
   discriminator: [u8; 8],
@@ -239,3 +276,52 @@ pub struct AmmV4Initialize2InstructionDataIdl {
   pub init_pc_amount: u64,
   pub init_coin_amount: u64,
 }
+
+/*
+Launchpad initialize instruction args, from the Raydium Launchlab IDL. Only the prefix shared by
+initialize, initialize_v2 and initialize_with_token_2022 is included, the creator fee and transfer
+fee params that follow are not needed.
+*/
+#[derive(BorshDeserialize)]
+pub struct LaunchpadInitializeInstructionDataIdl {
+  pub discriminator: [u8; 8],
+  pub base_mint_param: LaunchpadMintParamsIdl,
+  pub curve_param: LaunchpadCurveParamsIdl,
+  pub vesting_param: LaunchpadVestingParamsIdl,
+}
+
+#[derive(BorshDeserialize)]
+pub struct LaunchpadMintParamsIdl {
+  pub decimals: u8,
+  pub name: String,
+  pub symbol: String,
+  pub uri: String,
+}
+
+/// Base tokens locked for the creator's vesting, kept out of both the sale and the migration pool
+#[derive(BorshDeserialize)]
+pub struct LaunchpadVestingParamsIdl {
+  pub total_locked_amount: u64,
+  pub cliff_period: u64,
+  pub unlock_period: u64,
+}
+
+#[derive(BorshDeserialize)]
+pub enum LaunchpadCurveParamsIdl {
+  Constant {
+    supply: u64,
+    total_base_sell: u64,
+    total_quote_fund_raising: u64,
+    migrate_type: u8,
+  },
+  Fixed {
+    supply: u64,
+    total_quote_fund_raising: u64,
+    migrate_type: u8,
+  },
+  Linear {
+    supply: u64,
+    total_quote_fund_raising: u64,
+    migrate_type: u8,
+  },
+}
@@ -1,6 +1,7 @@
 use crate::constants::PUMP_CONSTANTS;
 use crate::protocol_idls::pumpfun::PfBondingCurveIdl;
 use crate::pumpfun::derive_bonding_curve::derive_bonding_curve;
+use crate::types::error::Error;
 use crate::types::pf_bonding_curve::PfBondingCurve;
 use borsh::BorshDeserialize;
 use solana_sdk::pubkey::Pubkey;
@@ -13,7 +14,10 @@ impl PfBondingCurve {
   from the token address using derive_bonding_curve.
   
   USAGE:
-  ```rust
+  ```rust,no_run
+  # use solana_central::{CentralContext, PfBondingCurve, derive_bonding_curve};
+  # use solana_sdk::pubkey::Pubkey;
+  # let context = CentralContext::new();
   let token_address = Pubkey::from_str_const("ERHiB4WJQX1WQXc88hXXcie3uCStYH5Uzz3MPJW4rwKe");
   let bonding_curve_address = derive_bonding_curve(&token_address);
   let pf_bonding_curve = PfBondingCurve::from_account_info(
@@ -26,18 +30,39 @@ impl PfBondingCurve {
   );
   println!("{:?}", pf_bonding_curve);
   ```
+
+  Panics if the data can't be decoded, see `try_from_account_info`.
   */
   pub fn from_account_info(token_address: Pubkey, account_buffer: &[u8]) -> Self {
-    let decoded_layout = PfBondingCurveIdl::try_from_slice(&account_buffer[..150])
-      .expect("Failed to deserialize Pf Bonding Curve account");
+    Self::try_from_account_info(token_address, account_buffer)
+      .unwrap_or_else(|error| panic!("{}", error))
+  }
+
+  /// Fallible version of `from_account_info`
+  pub fn try_from_account_info(
+    token_address: Pubkey,
+    account_buffer: &[u8],
+  ) -> Result<Self, Error> {
+    let bonding_curve_address = derive_bonding_curve(&token_address);
+    let decoded_layout = account_buffer
+      .get(..150)
+      .ok_or_else(|| {
+        Error::decode(
+          bonding_curve_address,
+          format!("expected 150 bytes, got {}", account_buffer.len()),
+        )
+      })
+      .and_then(|data| {
+        PfBondingCurveIdl::try_from_slice(data)
+          .map_err(|error| Error::decode(bonding_curve_address, error))
+      })?;
 
     let (creator_vault_address, _) = Pubkey::find_program_address(
       &[b"creator-vault", decoded_layout.creator.as_array()],
       &PUMP_CONSTANTS.bonding_curve_program,
     );
-    let bonding_curve_address = derive_bonding_curve(&token_address);
 
-    Self {
+    Ok(Self {
       virtual_sol_reserves: decoded_layout.virtual_sol_reserves,
       virtual_token_reserves: decoded_layout.virtual_token_reserves,
       complete: decoded_layout.complete,
@@ -48,6 +73,7 @@ impl PfBondingCurve {
         &token_address,
       ),
       creator_vault_address,
-    }
+      last_updated_slot: 0,
+    })
   }
 }
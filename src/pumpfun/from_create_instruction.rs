@@ -0,0 +1,78 @@
+use crate::constants::PUMP_CONSTANTS;
+use crate::protocol_idls::pumpfun::PfCreateInstructionDataIdl;
+use crate::types::instruction::Instruction;
+use crate::types::pf_bonding_curve::PfBondingCurve;
+use crate::types::token_creation::TokenCreation;
+use borsh::BorshDeserialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+impl PfBondingCurve {
+  /**
+  Decode a Pumpfun bonding curve create or create_v2 instruction into the token creation event and
+  the newly created bonding curve. A new bonding curve always starts at the initial virtual
+  reserves. Returns `None` if the instruction is not a bonding curve create instruction or is
+  malformed.
+
+  Both instructions start their accounts with: mint, mint_authority, bonding_curve,
+  associated_bonding_curve, global. The associated bonding curve is read from the instruction
+  rather than derived because create_v2 mints are owned by Token-2022.
+  */
+  pub fn from_create_instruction(
+    instruction: &Instruction,
+    block_time: u64,
+    slot: u64,
+    index: u64,
+    atomic_instruction_index: u8,
+    signature: Signature,
+  ) -> Option<(Self, TokenCreation)> {
+    if *instruction.program_id()? != PUMP_CONSTANTS.bonding_curve_program
+      || !(instruction
+        .data
+        .starts_with(&PUMP_CONSTANTS.bonding_curve_create_instruction_discriminator)
+        || instruction
+          .data
+          .starts_with(&PUMP_CONSTANTS.bonding_curve_create_v2_instruction_discriminator))
+    {
+      return None;
+    }
+    let decoded_layout =
+      PfCreateInstructionDataIdl::deserialize(&mut &instruction.data[..]).ok()?;
+
+    let token_address = *instruction.account(0)?;
+    let bonding_curve_address = *instruction.account(2)?;
+    let (creator_vault_address, _) = Pubkey::find_program_address(
+      &[b"creator-vault", decoded_layout.creator.as_array()],
+      &PUMP_CONSTANTS.bonding_curve_program,
+    );
+
+    let bonding_curve = Self {
+      virtual_sol_reserves: PUMP_CONSTANTS.bc_init_virtual_sol_reserves,
+      virtual_token_reserves: PUMP_CONSTANTS.bc_init_virtual_token_reserves,
+      complete: false,
+      token_address,
+      bonding_curve_address,
+      bonding_curve_associated_token_account_address: *instruction.account(3)?,
+      creator_vault_address,
+      last_updated_slot: slot,
+    };
+    let token_creation = TokenCreation {
+      address: token_address,
+      creator: decoded_layout.creator,
+      market_address: bonding_curve_address,
+      name: decoded_layout.name,
+      symbol: decoded_layout.symbol,
+      uri: decoded_layout.uri,
+      // Only available in the off chain metadata behind the uri
+      description: String::new(),
+      twitter: String::new(),
+      website: String::new(),
+      block_time,
+      slot,
+      index,
+      atomic_instruction_index,
+      signature,
+    };
+    Some((bonding_curve, token_creation))
+  }
+}
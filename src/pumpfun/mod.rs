@@ -1,7 +1,8 @@
 //! Pumpfun protocol utilities
 //!
 //! This module provides functions for working with Pumpfun bonding curves,
-//! including PDA derivation and state updates.
+//! including PDA derivation, state updates and create instruction parsing.
 
 pub mod derive_bonding_curve;
 mod from_account_info;
+mod from_create_instruction;
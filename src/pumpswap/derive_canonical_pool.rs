@@ -0,0 +1,24 @@
+use crate::constants::{PUMP_CONSTANTS, TOKENS};
+use crate::types::pumpswap_pool::PumpswapPool;
+use solana_sdk::pubkey::{Pubkey, PubkeyError};
+
+impl PumpswapPool {
+  /// Derive the canonical Pumpswap pool a completed Pumpfun bonding curve migrates to
+  ///
+  /// Uses the seeds `["pool", index, creator, base_mint, quote_mint]` with index 0, the pump pool
+  /// authority PDA (see `pump_pool_authority_pda`) as creator and WSOL as quote mint.
+  pub fn derive_canonical_pool(base_mint: &Pubkey) -> Result<Pubkey, PubkeyError> {
+    let pump_pool_authority = Self::pump_pool_authority_pda(base_mint)?;
+    let (pda, _) = Pubkey::find_program_address(
+      &[
+        b"pool",
+        &0u16.to_le_bytes(),
+        pump_pool_authority.as_ref(),
+        base_mint.as_ref(),
+        TOKENS.wsol.as_ref(),
+      ],
+      &PUMP_CONSTANTS.pump_swap_program,
+    );
+    Ok(pda)
+  }
+}
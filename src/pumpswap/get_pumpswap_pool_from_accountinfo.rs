@@ -1,6 +1,7 @@
 use crate::constants::PUMP_CONSTANTS;
 use crate::constants::PUMP_SWAP_FEE_VAULTS;
 use crate::protocol_idls::pumpswap::PumpAmmPoolAccount;
+use crate::types::error::Error;
 use crate::types::pool::Pool;
 use crate::types::pools::Pools;
 use crate::types::pumpswap_pool::PumpswapPool;
@@ -11,12 +12,42 @@ use spl_associated_token_account::get_associated_token_address;
 impl PumpswapPool {
   /// Create a Pumpswap pool from on-chain account data
   ///
-  /// Parses the account buffer and derives associated fee vault and creator vault addresses.
+  /// Parses the account buffer and derives associated fee vault and creator vault addresses. Panics
+  /// if the data can't be decoded, see `try_from_account_info`.
   pub fn from_account_info(pubkey: Pubkey, account_buffer: &[u8]) -> Self {
-    let decoded_layout: PumpAmmPoolAccount =
-      PumpAmmPoolAccount::try_from_slice(&account_buffer[0..300])
-        .expect("Failed to deserialize PumpSwap pool account");
+    Self::try_from_account_info(pubkey, account_buffer).unwrap_or_else(|error| panic!("{}", error))
+  }
+
+  /// Fallible version of `from_account_info`
+  pub fn try_from_account_info(pubkey: Pubkey, account_buffer: &[u8]) -> Result<Self, Error> {
+    let decoded_layout = account_buffer
+      .get(..300)
+      .ok_or_else(|| {
+        Error::decode(pubkey, format!("expected 300 bytes, got {}", account_buffer.len()))
+      })
+      .and_then(|data| {
+        PumpAmmPoolAccount::try_from_slice(data).map_err(|error| Error::decode(pubkey, error))
+      })?;
+
+    Ok(Self::from_parts(
+      Pool {
+        pool_address: pubkey,
+        token_a_address: decoded_layout.base_mint,
+        token_b_address: decoded_layout.quote_mint,
+        pool_type: Pools::PumpswapAmm,
+        last_updated_slot: 0,
+        hydrated: false,
+        token_a_vault_address: decoded_layout.pool_base_token_account,
+        token_b_vault_address: decoded_layout.pool_quote_token_account,
+      },
+      decoded_layout.creator,
+      decoded_layout.coin_creator,
+    ))
+  }
 
+  /// Build a Pumpswap pool from its decoded addresses and derive the fee vault and creator vault
+  /// accounts. Vault amounts are left at 0 for the caller to fill in.
+  pub(crate) fn from_parts(info: Pool, pool_creator: Pubkey, coin_creator: Pubkey) -> Self {
     // Randomly pick one of the known fee vaults – same logic as the TS code.
 
     /*
@@ -24,26 +55,19 @@ impl PumpswapPool {
     collected in the quote token, so we use the quote mint.
     */
     let fee_vault_token_account =
-      get_associated_token_address(&PUMP_SWAP_FEE_VAULTS[0], &decoded_layout.quote_mint);
+      get_associated_token_address(&PUMP_SWAP_FEE_VAULTS[0], &info.token_b_address);
 
     let (coin_creator_vault_authority, _) = Pubkey::find_program_address(
-      &[b"creator_vault", decoded_layout.coin_creator.as_array()],
+      &[b"creator_vault", coin_creator.as_array()],
       &PUMP_CONSTANTS.pump_swap_program,
     );
     let coin_creator_vault_authority_token_account =
-      get_associated_token_address(&coin_creator_vault_authority, &decoded_layout.quote_mint);
+      get_associated_token_address(&coin_creator_vault_authority, &info.token_b_address);
 
     Self {
-      info: Pool {
-        pool_address: pubkey,
-        token_a_address: decoded_layout.base_mint,
-        token_b_address: decoded_layout.quote_mint,
-        pool_type: Pools::PumpswapAmm,
-        token_a_vault_address: decoded_layout.pool_base_token_account,
-        token_b_vault_address: decoded_layout.pool_quote_token_account,
-      },
-      pool_creator: decoded_layout.creator,
-      coin_creator: decoded_layout.coin_creator,
+      info,
+      pool_creator,
+      coin_creator,
       fee_vault: PUMP_SWAP_FEE_VAULTS[0],
       fee_vault_token_account,
       // These are fetched lazily later.
@@ -0,0 +1,52 @@
+use crate::constants::PUMP_CONSTANTS;
+use crate::protocol_idls::pumpswap::PumpAmmCreatePoolInstructionDataIdl;
+use crate::types::instruction::Instruction;
+use crate::types::pool::Pool;
+use crate::types::pools::Pools;
+use crate::types::pumpswap_pool::PumpswapPool;
+use borsh::BorshDeserialize;
+
+impl PumpswapPool {
+  /// Create a Pumpswap pool from a Pumpswap `create_pool` instruction
+  ///
+  /// The pool addresses are read from the instruction accounts and the initial reserves are the
+  /// base and quote amounts deposited by the creator. Returns `None` if the instruction is not a
+  /// Pumpswap create pool instruction or is malformed.
+  ///
+  /// `slot` is the slot of the creating transaction, recorded as the pool's `last_updated_slot`.
+  pub fn from_create_pool_instruction(instruction: &Instruction, slot: u64) -> Option<Self> {
+    if *instruction.program_id()? != PUMP_CONSTANTS.pump_swap_program
+      || !instruction
+        .data
+        .starts_with(&PUMP_CONSTANTS.create_pool_instruction_discriminator)
+    {
+      return None;
+    }
+    // Newer versions of the instruction append fields, so don't require the whole buffer be read
+    let decoded_layout =
+      PumpAmmCreatePoolInstructionDataIdl::deserialize(&mut &instruction.data[..]).ok()?;
+
+    /*
+    Accounts: pool, global_config, creator, base_mint, quote_mint, lp_mint, user_base_token_account,
+    user_quote_token_account, user_pool_token_account, pool_base_token_account,
+    pool_quote_token_account, ...
+    */
+    let mut pool = Self::from_parts(
+      Pool {
+        pool_address: *instruction.account(0)?,
+        token_a_address: *instruction.account(3)?,
+        token_b_address: *instruction.account(4)?,
+        pool_type: Pools::PumpswapAmm,
+        last_updated_slot: slot,
+        hydrated: true,
+        token_a_vault_address: *instruction.account(9)?,
+        token_b_vault_address: *instruction.account(10)?,
+      },
+      *instruction.account(2)?,
+      decoded_layout.coin_creator,
+    );
+    pool.token_a_vault_amount = decoded_layout.base_amount_in;
+    pool.token_b_vault_amount = decoded_layout.quote_amount_in;
+    Some(pool)
+  }
+}
@@ -2,4 +2,6 @@
 //!
 //! This module provides functions for parsing and working with Pumpswap AMM pools.
 
+pub mod derive_canonical_pool;
 pub mod get_pumpswap_pool_from_accountinfo;
+pub mod get_pumpswap_pool_from_create_pool_instruction;
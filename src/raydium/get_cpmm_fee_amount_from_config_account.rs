@@ -15,21 +15,20 @@ const SEVEN: Pubkey = Pubkey::from_str_const("C7Cx2pMLtjybS3mDKSfsBj4zQ3PRZGkKt7
 /// and logs a warning.
 pub fn get_cpmm_fee_amount_from_config_account(config_account: Pubkey, pool_account: &Pubkey) -> u64 {
   if config_account == ONE {
-    return 15000000;
+    15000000
   } else if config_account == TWO {
-    return 3000000;
+    3000000
   } else if config_account == THREE {
-    return 5000000;
+    5000000
   } else if config_account == FOUR {
-    return 2500000;
+    2500000
   } else if config_account == FIVE {
-    return 20000000;
+    20000000
   } else if config_account == SIX {
-    return 10000000;
+    10000000
   } else if config_account == SEVEN {
-    return 4000000;
-  }
-  else {
+    4000000
+  } else {
     println!(
       "get_cpmm_fee_amount_from_config_account: Found a config account not defined in constants, config_account: {}, pool account: {}. Reporting a 100% swap fee rate.",
       config_account,
@@ -1,5 +1,6 @@
 use crate::constants::RAYDIUM_CONSTANTS;
 use crate::protocol_idls::raydium::LaunchpadPoolIdl;
+use crate::types::error::Error;
 use crate::types::pool::Pool;
 use crate::types::pools::Pools;
 use crate::types::raydium_launchpad::RaydiumLaunchpad;
@@ -9,40 +10,63 @@ use solana_sdk::pubkey::Pubkey;
 impl RaydiumLaunchpad {
   /// Create a Raydium launchpad pool from on-chain account data
   ///
-  /// Parses the launchpad pool account and derives platform and creator vault PDAs.
+  /// Parses the launchpad pool account and derives platform and creator vault PDAs. Panics if the
+  /// data can't be decoded, see `try_from_account_info`.
   pub fn from_account_info(pubkey: Pubkey, account_buffer: &[u8]) -> Self {
-    let decoded_layout = LaunchpadPoolIdl::try_from_slice(account_buffer).unwrap();
-    let platform_config = decoded_layout.platform_config;
-    let token_b_address = decoded_layout.quote_mint;
+    Self::try_from_account_info(pubkey, account_buffer).unwrap_or_else(|error| panic!("{}", error))
+  }
+
+  /// Fallible version of `from_account_info`
+  pub fn try_from_account_info(pubkey: Pubkey, account_buffer: &[u8]) -> Result<Self, Error> {
+    let decoded_layout = LaunchpadPoolIdl::try_from_slice(account_buffer)
+      .map_err(|error| Error::decode(pubkey, error))?;
 
+    let mut launchpad = Self::from_parts(
+      Pool {
+        pool_address: pubkey,
+        token_a_address: decoded_layout.base_mint,
+        token_b_address: decoded_layout.quote_mint,
+        pool_type: Pools::RaydiumLaunchpad,
+        last_updated_slot: 0,
+        hydrated: true,
+        token_a_vault_address: decoded_layout.base_vault,
+        token_b_vault_address: decoded_layout.quote_vault,
+      },
+      decoded_layout.platform_config,
+      &decoded_layout.creator,
+    );
+    launchpad.virtual_token_a_reserve = decoded_layout.virtual_base;
+    launchpad.virtual_token_b_reserve = decoded_layout.virtual_quote;
+    launchpad.real_token_a_reserve = decoded_layout.real_base;
+    launchpad.real_token_b_reserve = decoded_layout.real_quote;
+    launchpad.status = decoded_layout.status;
+    launchpad.migrate_type = decoded_layout.migrate_type;
+    Ok(launchpad)
+  }
+
+  /// Build a launchpad pool from its decoded addresses and derive the platform and creator vault
+  /// PDAs. Reserves, status and migrate type are left at 0 for the caller to fill in.
+  pub(crate) fn from_parts(info: Pool, platform_config: Pubkey, creator: &Pubkey) -> Self {
     let (platform_vault, _) = Pubkey::find_program_address(
-      &[platform_config.as_array(), token_b_address.as_array()],
+      &[platform_config.as_array(), info.token_b_address.as_array()],
       &RAYDIUM_CONSTANTS.launchpad_program,
     );
     let (creator_vault, _) = Pubkey::find_program_address(
-      &[
-        decoded_layout.creator.as_array(),
-        token_b_address.as_array(),
-      ],
+      &[creator.as_array(), info.token_b_address.as_array()],
       &RAYDIUM_CONSTANTS.launchpad_program,
     );
 
     Self {
-      info: Pool {
-        pool_address: pubkey,
-        token_a_address: decoded_layout.base_mint,
-        token_b_address,
-        pool_type: Pools::RaydiumLaunchpad,
-        token_a_vault_address: decoded_layout.base_vault,
-        token_b_vault_address: decoded_layout.quote_vault,
-      },
+      info,
       platform_config,
       platform_vault,
       creator_vault,
-      virtual_token_a_reserve: decoded_layout.virtual_base,
-      virtual_token_b_reserve: decoded_layout.virtual_quote,
-      real_token_a_reserve: decoded_layout.real_base,
-      real_token_b_reserve: decoded_layout.real_quote,
+      virtual_token_a_reserve: 0,
+      virtual_token_b_reserve: 0,
+      real_token_a_reserve: 0,
+      real_token_b_reserve: 0,
+      status: 0,
+      migrate_type: 0,
     }
   }
 }
@@ -0,0 +1,108 @@
+use crate::constants::RAYDIUM_CONSTANTS;
+use crate::protocol_idls::raydium::{
+  LaunchpadCurveParamsIdl, LaunchpadInitializeInstructionDataIdl,
+};
+use crate::types::instruction::Instruction;
+use crate::types::pool::Pool;
+use crate::types::pools::Pools;
+use crate::types::raydium_launchpad::RaydiumLaunchpad;
+use crate::types::token_creation::TokenCreation;
+use borsh::BorshDeserialize;
+use solana_sdk::signature::Signature;
+
+impl RaydiumLaunchpad {
+  /**
+  Decode a Raydium launchpad initialize instruction (initialize, initialize_v2 or
+  initialize_with_token_2022) into the token creation event and the newly created launchpad pool.
+  Returns `None` if the instruction is not a launchpad initialize instruction, is malformed, or does
+  not use a constant product curve.
+
+  Accounts: payer, creator, global_config, platform_config, authority, pool_state, base_mint,
+  quote_mint, base_vault, quote_vault, ...
+  */
+  pub fn from_initialize_instruction(
+    instruction: &Instruction,
+    block_time: u64,
+    slot: u64,
+    index: u64,
+    atomic_instruction_index: u8,
+    signature: Signature,
+  ) -> Option<(Self, TokenCreation)> {
+    if *instruction.program_id()? != RAYDIUM_CONSTANTS.launchpad_program
+      || !RAYDIUM_CONSTANTS
+        .launchpad_initialize_discriminators
+        .iter()
+        .any(|discriminator| instruction.data.starts_with(discriminator))
+    {
+      return None;
+    }
+    let decoded_layout =
+      LaunchpadInitializeInstructionDataIdl::deserialize(&mut &instruction.data[..]).ok()?;
+
+    /*
+    Only constant product curves are in use. The program picks the initial virtual reserves so that
+    selling total_base_sell tokens raises total_quote_fund_raising while keeping k constant, and the
+    final price matches the migration pool made of the remaining supply and the raised quote:
+    virtual_base = sell^2 / (sell - remaining), virtual_quote = raised * (virtual_base - sell -
+    remaining) / remaining. Tokens locked for vesting go to neither the sale nor the migration pool,
+    so remaining = supply - sell - locked.
+    */
+    let LaunchpadCurveParamsIdl::Constant {
+      supply,
+      total_base_sell,
+      total_quote_fund_raising,
+      migrate_type,
+    } = decoded_layout.curve_param
+    else {
+      return None;
+    };
+    let sell = total_base_sell as u128;
+    let remaining = supply
+      .checked_sub(total_base_sell)?
+      .checked_sub(decoded_layout.vesting_param.total_locked_amount)? as u128;
+    let virtual_base = (sell * sell).checked_div(sell.checked_sub(remaining)?)?;
+    let virtual_quote = (total_quote_fund_raising as u128)
+      .checked_mul(virtual_base.checked_sub(sell + remaining)?)?
+      .checked_div(remaining)?;
+
+    let creator = *instruction.account(1)?;
+    let pool_address = *instruction.account(5)?;
+    let token_address = *instruction.account(6)?;
+    let mut launchpad = Self::from_parts(
+      Pool {
+        pool_address,
+        token_a_address: token_address,
+        token_b_address: *instruction.account(7)?,
+        pool_type: Pools::RaydiumLaunchpad,
+        last_updated_slot: slot,
+        hydrated: true,
+        token_a_vault_address: *instruction.account(8)?,
+        token_b_vault_address: *instruction.account(9)?,
+      },
+      *instruction.account(3)?,
+      &creator,
+    );
+    launchpad.virtual_token_a_reserve = u64::try_from(virtual_base).ok()?;
+    launchpad.virtual_token_b_reserve = u64::try_from(virtual_quote).ok()?;
+    launchpad.migrate_type = migrate_type;
+
+    let token_creation = TokenCreation {
+      address: token_address,
+      creator,
+      market_address: pool_address,
+      name: decoded_layout.base_mint_param.name,
+      symbol: decoded_layout.base_mint_param.symbol,
+      uri: decoded_layout.base_mint_param.uri,
+      // Only available in the off chain metadata behind the uri
+      description: String::new(),
+      twitter: String::new(),
+      website: String::new(),
+      block_time,
+      slot,
+      index,
+      atomic_instruction_index,
+      signature,
+    };
+    Some((launchpad, token_creation))
+  }
+}
@@ -1,4 +1,5 @@
 use crate::protocol_idls::raydium::AmmV4PoolInfoIdl;
+use crate::types::error::Error;
 use crate::types::pool::Pool;
 use crate::types::pools::Pools;
 use crate::types::raydium_ammv4_pool::RaydiumAmmV4Pool;
@@ -8,24 +9,33 @@ use solana_sdk::pubkey::Pubkey;
 impl RaydiumAmmV4Pool {
   /// Create a Raydium AMMv4 pool from on-chain account data
   ///
-  /// Parses the account buffer using the Raydium AMMv4 pool IDL structure.
+  /// Parses the account buffer using the Raydium AMMv4 pool IDL structure. Panics if the data
+  /// can't be decoded, see `try_from_account_info`.
   pub fn from_account_info(pubkey: Pubkey, account_buffer: &[u8]) -> Self {
-    let decoded_layout: AmmV4PoolInfoIdl =
-      AmmV4PoolInfoIdl::try_from_slice(account_buffer).unwrap();
-    Self {
+    Self::try_from_account_info(pubkey, account_buffer).unwrap_or_else(|error| panic!("{}", error))
+  }
+
+  /// Fallible version of `from_account_info`
+  pub fn try_from_account_info(pubkey: Pubkey, account_buffer: &[u8]) -> Result<Self, Error> {
+    let decoded_layout = AmmV4PoolInfoIdl::try_from_slice(account_buffer)
+      .map_err(|error| Error::decode(pubkey, error))?;
+    Ok(Self {
       info: Pool {
         pool_address: pubkey,
         token_a_address: decoded_layout.base_mint,
         token_b_address: decoded_layout.quote_mint,
         pool_type: Pools::RaydiumAmmV4,
+        last_updated_slot: 0,
+        hydrated: false,
         token_a_vault_address: decoded_layout.base_vault,
         token_b_vault_address: decoded_layout.quote_vault,
       },
       swap_fee_numerator: decoded_layout.swap_fee_numerator,
       swap_fee_denominator: decoded_layout.swap_fee_denominator,
+      open_time: decoded_layout.pool_open_time,
       // Account layout doesn't contain these balances, so we set them to 0
       token_a_vault_amount: 0,
       token_b_vault_amount: 0,
-    }
+    })
   }
 }
@@ -0,0 +1,49 @@
+use crate::constants::RAYDIUM_CONSTANTS;
+use crate::protocol_idls::raydium::AmmV4Initialize2InstructionDataIdl;
+use crate::types::instruction::Instruction;
+use crate::types::pool::Pool;
+use crate::types::pools::Pools;
+use crate::types::raydium_ammv4_pool::RaydiumAmmV4Pool;
+use borsh::BorshDeserialize;
+
+impl RaydiumAmmV4Pool {
+  /// Create a Raydium AMMv4 pool from an AMMv4 `initialize2` instruction
+  ///
+  /// Coin is the base token (token A) and pc is the quote token (token B). New AMMv4 pools always
+  /// use the standard 0.25% swap fee. Returns `None` if the instruction is not an AMMv4 initialize2
+  /// instruction or is malformed.
+  ///
+  /// `slot` is the slot of the creating transaction, recorded as the pool's `last_updated_slot`.
+  pub fn from_initialize2_instruction(instruction: &Instruction, slot: u64) -> Option<Self> {
+    if *instruction.program_id()? != RAYDIUM_CONSTANTS.amm_program
+      || instruction.data.first()
+        != Some(&RAYDIUM_CONSTANTS.ammv4_create_pool_instruction_discriminator)
+    {
+      return None;
+    }
+    let decoded_layout =
+      AmmV4Initialize2InstructionDataIdl::deserialize(&mut &instruction.data[..]).ok()?;
+
+    /*
+    Accounts: token_program, associated_token_program, system_program, rent, amm, amm_authority,
+    amm_open_orders, lp_mint, coin_mint, pc_mint, pool_coin_token_account, pool_pc_token_account, ...
+    */
+    Some(Self {
+      info: Pool {
+        pool_address: *instruction.account(4)?,
+        token_a_address: *instruction.account(8)?,
+        token_b_address: *instruction.account(9)?,
+        pool_type: Pools::RaydiumAmmV4,
+        last_updated_slot: slot,
+        hydrated: true,
+        token_a_vault_address: *instruction.account(10)?,
+        token_b_vault_address: *instruction.account(11)?,
+      },
+      token_a_vault_amount: decoded_layout.init_coin_amount,
+      token_b_vault_amount: decoded_layout.init_pc_amount,
+      swap_fee_numerator: 25,
+      swap_fee_denominator: 10000,
+      open_time: decoded_layout.open_time,
+    })
+  }
+}
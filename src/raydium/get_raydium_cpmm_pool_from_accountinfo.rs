@@ -1,4 +1,5 @@
 use crate::protocol_idls::raydium::CpmmPoolInfoIdl;
+use crate::types::error::Error;
 use crate::raydium::get_cpmm_fee_amount_from_config_account::get_cpmm_fee_amount_from_config_account;
 use crate::types::pool::Pool;
 use crate::types::pools::Pools;
@@ -9,16 +10,25 @@ use solana_sdk::pubkey::Pubkey;
 impl RaydiumCpmmPool {
   /// Create a Raydium CPMM pool from on-chain account data
   ///
-  /// Parses the account buffer and looks up the fee rate from the config account.
+  /// Parses the account buffer and looks up the fee rate from the config account. Panics if the
+  /// data can't be decoded, see `try_from_account_info`.
   pub fn from_account_info(pubkey: Pubkey, account_buffer: &[u8]) -> Self {
-    let decoded_layout: CpmmPoolInfoIdl = CpmmPoolInfoIdl::try_from_slice(account_buffer).unwrap();
+    Self::try_from_account_info(pubkey, account_buffer).unwrap_or_else(|error| panic!("{}", error))
+  }
+
+  /// Fallible version of `from_account_info`
+  pub fn try_from_account_info(pubkey: Pubkey, account_buffer: &[u8]) -> Result<Self, Error> {
+    let decoded_layout = CpmmPoolInfoIdl::try_from_slice(account_buffer)
+      .map_err(|error| Error::decode(pubkey, error))?;
     let fee_fraction_lp = get_cpmm_fee_amount_from_config_account(decoded_layout.amm_config, &pubkey);
-    Self {
+    Ok(Self {
       info: Pool {
         pool_address: pubkey,
         token_a_address: decoded_layout.token_0_mint,
         token_b_address: decoded_layout.token_1_mint,
         pool_type: Pools::RaydiumCpmm,
+        last_updated_slot: 0,
+        hydrated: false,
         token_a_vault_address: decoded_layout.token_0_vault,
         token_b_vault_address: decoded_layout.token_1_vault,
       },
@@ -35,6 +45,7 @@ impl RaydiumCpmmPool {
       creator_fees_token_a: decoded_layout.creator_fees_token_0,
       creator_fees_token_b: decoded_layout.creator_fees_token_1,
       fee_fraction_lp,
-    }
+      open_time: decoded_layout.open_time,
+    })
   }
 }
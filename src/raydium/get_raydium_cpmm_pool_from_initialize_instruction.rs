@@ -0,0 +1,61 @@
+use crate::constants::RAYDIUM_CONSTANTS;
+use crate::protocol_idls::raydium::CpmmInitializeInstructionDataIdl;
+use crate::raydium::get_cpmm_fee_amount_from_config_account::get_cpmm_fee_amount_from_config_account;
+use crate::types::instruction::Instruction;
+use crate::types::pool::Pool;
+use crate::types::pools::Pools;
+use crate::types::raydium_cpmm_pool::RaydiumCpmmPool;
+use borsh::BorshDeserialize;
+
+impl RaydiumCpmmPool {
+  /// Create a Raydium CPMM pool from a CPMM `initialize` instruction
+  ///
+  /// The initial vault balances are the amounts deposited by the creator and no fees have been
+  /// accumulated yet. Returns `None` if the instruction is not a CPMM initialize instruction or is
+  /// malformed.
+  ///
+  /// `slot` is the slot of the creating transaction, recorded as the pool's `last_updated_slot`.
+  pub fn from_initialize_instruction(instruction: &Instruction, slot: u64) -> Option<Self> {
+    if *instruction.program_id()? != RAYDIUM_CONSTANTS.cpmm_program
+      || !instruction
+        .data
+        .starts_with(&RAYDIUM_CONSTANTS.cpmm_create_pool_instruction_discriminator)
+    {
+      return None;
+    }
+    let decoded_layout =
+      CpmmInitializeInstructionDataIdl::deserialize(&mut &instruction.data[..]).ok()?;
+
+    /*
+    Accounts: creator, amm_config, authority, pool_state, token_0_mint, token_1_mint, lp_mint,
+    creator_token_0, creator_token_1, creator_lp_token, token_0_vault, token_1_vault,
+    create_pool_fee, observation_state, ...
+    */
+    let pool_address = *instruction.account(3)?;
+    let pool_config_account = *instruction.account(1)?;
+    Some(Self {
+      info: Pool {
+        pool_address,
+        token_a_address: *instruction.account(4)?,
+        token_b_address: *instruction.account(5)?,
+        pool_type: Pools::RaydiumCpmm,
+        last_updated_slot: slot,
+        hydrated: true,
+        token_a_vault_address: *instruction.account(10)?,
+        token_b_vault_address: *instruction.account(11)?,
+      },
+      pool_config_account,
+      observation_state_account: *instruction.account(13)?,
+      fee_fraction_lp: get_cpmm_fee_amount_from_config_account(pool_config_account, &pool_address),
+      token_a_vault_amount: decoded_layout.init_amount_0,
+      token_b_vault_amount: decoded_layout.init_amount_1,
+      protocol_fees_token_a: 0,
+      protocol_fees_token_b: 0,
+      fund_fees_token_a: 0,
+      fund_fees_token_b: 0,
+      creator_fees_token_a: 0,
+      creator_fees_token_b: 0,
+      open_time: decoded_layout.open_time,
+    })
+  }
+}
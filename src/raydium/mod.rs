@@ -5,8 +5,12 @@
 //! - CPMM (Concentrated Liquidity Market Maker) pool parsing
 //! - Launchpad pool parsing
 //! - Fee configuration lookups
+//! - Pool creation and launchpad initialize instruction parsing
 
 pub mod get_raydium_ammv4_pool_from_accountinfo;
+pub mod get_raydium_ammv4_pool_from_initialize2_instruction;
 pub mod get_raydium_cpmm_pool_from_accountinfo;
+pub mod get_raydium_cpmm_pool_from_initialize_instruction;
 pub mod get_launchpad_pool_from_accountinfo;
-pub mod get_cpmm_fee_amount_from_config_account;
\ No newline at end of file
+pub mod get_launchpad_pool_from_initialize_instruction;
+pub mod get_cpmm_fee_amount_from_config_account;
@@ -0,0 +1,173 @@
+use crate::types::rpc_backend::RpcBackend;
+use async_trait::async_trait;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use solana_account_decoder::{UiAccountEncoding, encode_ui_account};
+use solana_client::client_error::{ClientError, Result};
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_request::{RpcError, RpcRequest};
+use solana_client::rpc_response::{Response, RpcBlockhash, RpcKeyedAccount, RpcResponseContext};
+use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// RPC transport answering JSON RPC requests from an `RpcBackend` instead of a node
+///
+/// Supports getAccountInfo, getMultipleAccounts, getProgramAccounts, getTokenAccountBalance,
+/// getTokenSupply, getSlot and getLatestBlockhash, encoded like a node would so `RpcClient`
+/// parses them as usual. Accounts are returned in the requested encoding and data slice, with the
+/// backend's slot as context slot. Other requests fail with an `RpcRequestError`.
+///
+/// Used by `CentralContextBuilder::rpc_backend` for both RPC clients of the context, wrap it with
+/// `RpcClient::new_sender` to use it elsewhere.
+pub struct BackendRpcSender {
+  backend: Arc<dyn RpcBackend>,
+  request_count: AtomicUsize,
+}
+
+impl BackendRpcSender {
+  /// Create a sender answering from `backend`
+  pub fn new(backend: Arc<dyn RpcBackend>) -> Self {
+    Self {
+      backend,
+      request_count: AtomicUsize::new(0),
+    }
+  }
+
+  /// Wrap `value` in a response context at the backend's slot
+  fn with_context(&self, value: impl Serialize) -> Result<Value> {
+    Ok(serde_json::to_value(Response {
+      context: RpcResponseContext::new(self.backend.get_slot()?),
+      value,
+    })?)
+  }
+}
+
+#[async_trait]
+impl RpcSender for BackendRpcSender {
+  async fn send(&self, request: RpcRequest, params: Value) -> Result<Value> {
+    self.request_count.fetch_add(1, Ordering::Relaxed);
+    match request {
+      RpcRequest::GetSlot => Ok(json!(self.backend.get_slot()?)),
+      RpcRequest::GetLatestBlockhash => {
+        let (blockhash, last_valid_block_height) = self.backend.get_latest_blockhash()?;
+        self.with_context(RpcBlockhash {
+          blockhash: blockhash.to_string(),
+          last_valid_block_height,
+        })
+      }
+      RpcRequest::GetAccountInfo => {
+        let pubkey = parse_pubkey(&params[0])?;
+        let config: RpcAccountInfoConfig = parse_config(&params[1])?;
+        let account = self.backend.get_account(&pubkey)?.map(|account| {
+          encode_ui_account(
+            &pubkey,
+            &account,
+            config.encoding.unwrap_or(UiAccountEncoding::Base64),
+            None,
+            config.data_slice,
+          )
+        });
+        self.with_context(account)
+      }
+      RpcRequest::GetMultipleAccounts => {
+        let pubkeys = params[0]
+          .as_array()
+          .ok_or_else(|| invalid_params("expected an array of pubkeys"))?
+          .iter()
+          .map(parse_pubkey)
+          .collect::<Result<Vec<_>>>()?;
+        let config: RpcAccountInfoConfig = parse_config(&params[1])?;
+        let encoding = config.encoding.unwrap_or(UiAccountEncoding::Base64);
+        let accounts: Vec<_> = self
+          .backend
+          .get_multiple_accounts(&pubkeys)?
+          .into_iter()
+          .zip(&pubkeys)
+          .map(|(account, pubkey)| {
+            account
+              .map(|account| encode_ui_account(pubkey, &account, encoding, None, config.data_slice))
+          })
+          .collect();
+        self.with_context(accounts)
+      }
+      RpcRequest::GetProgramAccounts => {
+        let program_id = parse_pubkey(&params[0])?;
+        let config: RpcProgramAccountsConfig = parse_config(&params[1])?;
+        let encoding = config
+          .account_config
+          .encoding
+          .unwrap_or(UiAccountEncoding::Base64);
+        let accounts: Vec<_> = self
+          .backend
+          .get_program_accounts_with_config(&program_id, &config)?
+          .into_iter()
+          .map(|(pubkey, account)| RpcKeyedAccount {
+            pubkey: pubkey.to_string(),
+            account: encode_ui_account(
+              &pubkey,
+              &account,
+              encoding,
+              None,
+              config.account_config.data_slice,
+            ),
+          })
+          .collect();
+        if config.with_context.unwrap_or_default() {
+          self.with_context(accounts)
+        } else {
+          Ok(serde_json::to_value(accounts)?)
+        }
+      }
+      RpcRequest::GetTokenAccountBalance => {
+        let balance = self
+          .backend
+          .get_token_account_balance(&parse_pubkey(&params[0])?)?;
+        self.with_context(balance)
+      }
+      RpcRequest::GetTokenSupply => {
+        let supply = self.backend.get_token_supply(&parse_pubkey(&params[0])?)?;
+        self.with_context(supply)
+      }
+      _ => Err(
+        RpcError::RpcRequestError(format!("{} is not supported by the RPC backend", request))
+          .into(),
+      ),
+    }
+  }
+
+  fn get_transport_stats(&self) -> RpcTransportStats {
+    RpcTransportStats {
+      request_count: self.request_count.load(Ordering::Relaxed),
+      ..RpcTransportStats::default()
+    }
+  }
+
+  fn url(&self) -> String {
+    "rpc-backend".to_string()
+  }
+}
+
+/// Parse a base58 pubkey param
+fn parse_pubkey(param: &Value) -> Result<Pubkey> {
+  param
+    .as_str()
+    .and_then(|pubkey| Pubkey::from_str(pubkey).ok())
+    .ok_or_else(|| invalid_params(&format!("invalid pubkey {}", param)))
+}
+
+/// Parse an optional config param, missing configs are the default config
+fn parse_config<T: Default + DeserializeOwned>(param: &Value) -> Result<T> {
+  if param.is_null() {
+    return Ok(T::default());
+  }
+  serde_json::from_value(param.clone()).map_err(|error| invalid_params(&error.to_string()))
+}
+
+/// Error for a request whose params can't be parsed
+fn invalid_params(message: &str) -> ClientError {
+  RpcError::RpcRequestError(format!("Invalid params: {}", message)).into()
+}
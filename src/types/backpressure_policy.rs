@@ -0,0 +1,10 @@
+/// What to do when a market update subscriber's bounded channel is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackpressurePolicy {
+  /// Block the publishing thread until the subscriber makes room. Every update is delivered, but
+  /// a slow subscriber stalls the update path (and every other subscriber) until it catches up.
+  Block,
+  /// Drop the update for this subscriber and keep going. The subscriber may miss intermediate
+  /// prices but never slows down the update path.
+  DropNewest,
+}
@@ -0,0 +1,355 @@
+use crate::types::instruction::Instruction;
+use crate::utilities::get_token_account_owners::get_token_account_owners;
+use crate::utilities::get_tx_account_keys::get_tx_account_keys;
+use crate::utilities::get_tx_instructions::get_tx_instructions;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::message::compiled_instruction::CompiledInstruction;
+use solana_sdk::message::v0::LoadedAddresses;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
+use solana_transaction_status_client_types::option_serializer::OptionSerializer;
+use solana_transaction_status_client_types::{
+  EncodedConfirmedTransactionWithStatusMeta, EncodedTransactionWithStatusMeta, InnerInstruction,
+  InnerInstructions, TransactionStatusMeta, TransactionTokenBalance, UiInnerInstructions,
+  UiInstruction, UiLoadedAddresses, UiTransactionError, UiTransactionTokenBalance,
+};
+use std::collections::HashMap;
+use std::str::FromStr;
+#[cfg(feature = "geyser")]
+use {
+  solana_account_decoder::parse_token::UiTokenAmount,
+  solana_sdk::hash::Hash,
+  solana_sdk::message::v0::MessageAddressTableLookup,
+  solana_sdk::message::{MessageHeader, legacy, v0},
+  solana_sdk::transaction::TransactionError,
+  yellowstone_grpc_proto::prelude as geyser,
+  yellowstone_grpc_proto::prelude::SubscribeUpdateTransactionInfo,
+};
+
+/**
+An owned, decoded transaction built from the RPC `getTransaction` / `getBlock` formats (or saved
+JSON fixtures of them), or from a geyser stream's transaction and status meta. Holds everything
+needed to drive the instruction parsers: the full account key list with loaded addresses merged
+in, inner instructions with their stack heights, token balances and log messages. Use
+`instructions` to get the ordered `Instruction` views.
+
+Only binary RPC encodings (`base58`, `base64`) are supported. `json` and `jsonParsed` encodings do not
+carry the raw instruction data so they are rejected.
+*/
+#[derive(Debug, Clone)]
+pub struct DecodedTransaction {
+  pub slot: u64,
+  pub block_time: Option<i64>,
+  pub transaction: VersionedTransaction,
+  /// Static account keys followed by the writable and readonly loaded addresses
+  pub tx_account_keys: Vec<Pubkey>,
+  pub inner_instructions: Vec<InnerInstructions>,
+  pub pre_token_balances: Vec<UiTransactionTokenBalance>,
+  pub post_token_balances: Vec<UiTransactionTokenBalance>,
+  pub log_messages: Vec<String>,
+  /// The error the transaction failed with, `None` if it succeeded or no meta was provided
+  pub err: Option<UiTransactionError>,
+}
+
+impl DecodedTransaction {
+  /// Decode a transaction returned by RPC `getTransaction`
+  pub fn from_encoded_confirmed_transaction(
+    encoded: &EncodedConfirmedTransactionWithStatusMeta,
+  ) -> Option<Self> {
+    Self::from_encoded_transaction(encoded.slot, encoded.block_time, &encoded.transaction)
+  }
+
+  /// Decode one transaction of an RPC `getBlock` result. Returns `None` if the transaction is
+  /// not binary encoded, its meta uses parsed instructions, or it is a v0 transaction using
+  /// lookup tables without `loaded_addresses` in its meta.
+  pub fn from_encoded_transaction(
+    slot: u64,
+    block_time: Option<i64>,
+    encoded: &EncodedTransactionWithStatusMeta,
+  ) -> Option<Self> {
+    let transaction = encoded.transaction.decode()?;
+    let meta = encoded.meta.as_ref();
+
+    let loaded_addresses = match meta.map(|meta| &meta.loaded_addresses) {
+      Some(OptionSerializer::Some(loaded_addresses)) => parse_loaded_addresses(loaded_addresses)?,
+      _ => match transaction.message.address_table_lookups() {
+        Some(lookups) if !lookups.is_empty() => return None,
+        _ => LoadedAddresses::default(),
+      },
+    };
+    let tx_account_keys = get_tx_account_keys(&transaction.message, &loaded_addresses);
+
+    let inner_instructions = match meta.map(|meta| &meta.inner_instructions) {
+      Some(OptionSerializer::Some(inner_instructions)) => inner_instructions
+        .iter()
+        .map(parse_inner_instructions)
+        .collect::<Option<Vec<_>>>()?,
+      _ => Vec::new(),
+    };
+
+    Some(Self {
+      slot,
+      block_time,
+      pre_token_balances: option_vec(meta.map(|meta| &meta.pre_token_balances)),
+      post_token_balances: option_vec(meta.map(|meta| &meta.post_token_balances)),
+      log_messages: option_vec(meta.map(|meta| &meta.log_messages)),
+      err: meta.and_then(|meta| meta.err.clone()),
+      transaction,
+      tx_account_keys,
+      inner_instructions,
+    })
+  }
+
+  /// Build from an already decoded transaction and its status meta, as produced by geyser gRPC
+  /// streams (e.g. Yellowstone's `create_tx_with_meta` conversion) or a ledger reader
+  pub fn from_status_meta(
+    slot: u64,
+    block_time: Option<i64>,
+    transaction: VersionedTransaction,
+    meta: TransactionStatusMeta,
+  ) -> Self {
+    let tx_account_keys = get_tx_account_keys(&transaction.message, &meta.loaded_addresses);
+    let into_ui_balances = |balances: Option<Vec<TransactionTokenBalance>>| {
+      balances
+        .unwrap_or_default()
+        .into_iter()
+        .map(UiTransactionTokenBalance::from)
+        .collect()
+    };
+    Self {
+      slot,
+      block_time,
+      transaction,
+      tx_account_keys,
+      inner_instructions: meta.inner_instructions.unwrap_or_default(),
+      pre_token_balances: into_ui_balances(meta.pre_token_balances),
+      post_token_balances: into_ui_balances(meta.post_token_balances),
+      log_messages: meta.log_messages.unwrap_or_default(),
+      err: meta.status.err().map(UiTransactionError::from),
+    }
+  }
+
+  /// Build from a transaction update of a Yellowstone geyser gRPC stream. Returns `None` if the
+  /// update has no transaction or meta, or holds malformed keys, indexes or errors.
+  #[cfg(feature = "geyser")]
+  pub fn from_geyser_transaction(slot: u64, info: &SubscribeUpdateTransactionInfo) -> Option<Self> {
+    let transaction = parse_geyser_transaction(info.transaction.as_ref()?)?;
+    let meta = info.meta.as_ref()?;
+    let loaded_addresses = LoadedAddresses {
+      writable: parse_pubkeys(&meta.loaded_writable_addresses)?,
+      readonly: parse_pubkeys(&meta.loaded_readonly_addresses)?,
+    };
+    let tx_account_keys = get_tx_account_keys(&transaction.message, &loaded_addresses);
+
+    let inner_instructions = meta
+      .inner_instructions
+      .iter()
+      .map(|inner_instructions| {
+        Some(InnerInstructions {
+          index: u8::try_from(inner_instructions.index).ok()?,
+          instructions: inner_instructions
+            .instructions
+            .iter()
+            .map(|instruction| {
+              Some(InnerInstruction {
+                instruction: CompiledInstruction {
+                  program_id_index: u8::try_from(instruction.program_id_index).ok()?,
+                  accounts: instruction.accounts.clone(),
+                  data: instruction.data.clone(),
+                },
+                stack_height: instruction.stack_height,
+              })
+            })
+            .collect::<Option<Vec<_>>>()?,
+        })
+      })
+      .collect::<Option<Vec<_>>>()?;
+    // Geyser serializes the transaction error with bincode
+    let err = match &meta.err {
+      Some(err) => Some(UiTransactionError::from(
+        bincode::deserialize::<TransactionError>(&err.err).ok()?,
+      )),
+      None => None,
+    };
+
+    Some(Self {
+      slot,
+      block_time: None,
+      transaction,
+      tx_account_keys,
+      inner_instructions,
+      pre_token_balances: parse_geyser_token_balances(&meta.pre_token_balances)?,
+      post_token_balances: parse_geyser_token_balances(&meta.post_token_balances)?,
+      log_messages: meta.log_messages.clone(),
+      err,
+    })
+  }
+
+  /// The transaction's first signature
+  pub fn signature(&self) -> Option<&Signature> {
+    self.transaction.signatures.first()
+  }
+
+  /// The transaction's message. Its instructions index into `tx_account_keys`, not the message's
+  /// static keys alone
+  pub fn message(&self) -> &VersionedMessage {
+    &self.transaction.message
+  }
+
+  /// Views over every instruction in execution order, see `get_tx_instructions`
+  pub fn instructions(&self) -> Vec<Instruction<'_>> {
+    get_tx_instructions(
+      &self.tx_account_keys,
+      &self.transaction.message,
+      &self.inner_instructions,
+    )
+  }
+
+  /// Token account to owner map from the pre and post token balances, see
+  /// `get_token_account_owners`
+  pub fn token_account_owners(&self) -> HashMap<Pubkey, Pubkey> {
+    get_token_account_owners(
+      &self.tx_account_keys,
+      &self.pre_token_balances,
+      &self.post_token_balances,
+    )
+  }
+}
+
+fn option_vec<T: Clone>(value: Option<&OptionSerializer<Vec<T>>>) -> Vec<T> {
+  match value {
+    Some(OptionSerializer::Some(vec)) => vec.clone(),
+    _ => Vec::new(),
+  }
+}
+
+fn parse_loaded_addresses(loaded_addresses: &UiLoadedAddresses) -> Option<LoadedAddresses> {
+  let parse = |addresses: &[String]| {
+    addresses
+      .iter()
+      .map(|address| Pubkey::from_str(address).ok())
+      .collect::<Option<Vec<_>>>()
+  };
+  Some(LoadedAddresses {
+    writable: parse(&loaded_addresses.writable)?,
+    readonly: parse(&loaded_addresses.readonly)?,
+  })
+}
+
+fn parse_inner_instructions(inner_instructions: &UiInnerInstructions) -> Option<InnerInstructions> {
+  let instructions = inner_instructions
+    .instructions
+    .iter()
+    .map(|instruction| match instruction {
+      UiInstruction::Compiled(compiled) => Some(InnerInstruction {
+        instruction: CompiledInstruction {
+          program_id_index: compiled.program_id_index,
+          accounts: compiled.accounts.clone(),
+          data: bs58::decode(&compiled.data).into_vec().ok()?,
+        },
+        stack_height: compiled.stack_height,
+      }),
+      UiInstruction::Parsed(_) => None,
+    })
+    .collect::<Option<Vec<_>>>()?;
+  Some(InnerInstructions {
+    index: inner_instructions.index,
+    instructions,
+  })
+}
+
+#[cfg(feature = "geyser")]
+fn parse_pubkeys(addresses: &[Vec<u8>]) -> Option<Vec<Pubkey>> {
+  addresses
+    .iter()
+    .map(|address| Pubkey::try_from(address.as_slice()).ok())
+    .collect()
+}
+
+#[cfg(feature = "geyser")]
+fn parse_geyser_transaction(transaction: &geyser::Transaction) -> Option<VersionedTransaction> {
+  let message = transaction.message.as_ref()?;
+  let header = message.header.as_ref()?;
+  let header = MessageHeader {
+    num_required_signatures: u8::try_from(header.num_required_signatures).ok()?,
+    num_readonly_signed_accounts: u8::try_from(header.num_readonly_signed_accounts).ok()?,
+    num_readonly_unsigned_accounts: u8::try_from(header.num_readonly_unsigned_accounts).ok()?,
+  };
+  let account_keys = parse_pubkeys(&message.account_keys)?;
+  let recent_blockhash = Hash::new_from_array(message.recent_blockhash.as_slice().try_into().ok()?);
+  let instructions = message
+    .instructions
+    .iter()
+    .map(|instruction| {
+      Some(CompiledInstruction {
+        program_id_index: u8::try_from(instruction.program_id_index).ok()?,
+        accounts: instruction.accounts.clone(),
+        data: instruction.data.clone(),
+      })
+    })
+    .collect::<Option<Vec<_>>>()?;
+  let message = if message.versioned {
+    VersionedMessage::V0(v0::Message {
+      header,
+      account_keys,
+      recent_blockhash,
+      instructions,
+      address_table_lookups: message
+        .address_table_lookups
+        .iter()
+        .map(|lookup| {
+          Some(MessageAddressTableLookup {
+            account_key: Pubkey::try_from(lookup.account_key.as_slice()).ok()?,
+            writable_indexes: lookup.writable_indexes.clone(),
+            readonly_indexes: lookup.readonly_indexes.clone(),
+          })
+        })
+        .collect::<Option<Vec<_>>>()?,
+    })
+  } else {
+    VersionedMessage::Legacy(legacy::Message {
+      header,
+      account_keys,
+      recent_blockhash,
+      instructions,
+    })
+  };
+  Some(VersionedTransaction {
+    signatures: transaction
+      .signatures
+      .iter()
+      .map(|signature| Signature::try_from(signature.as_slice()).ok())
+      .collect::<Option<Vec<_>>>()?,
+    message,
+  })
+}
+
+#[cfg(feature = "geyser")]
+fn parse_geyser_token_balances(
+  balances: &[geyser::TokenBalance],
+) -> Option<Vec<UiTransactionTokenBalance>> {
+  // Geyser sends empty strings for missing owners and program ids
+  let optional = |value: &String| match value.is_empty() {
+    true => OptionSerializer::None,
+    false => OptionSerializer::Some(value.clone()),
+  };
+  balances
+    .iter()
+    .map(|balance| {
+      let ui_token_amount = balance.ui_token_amount.as_ref()?;
+      Some(UiTransactionTokenBalance {
+        account_index: u8::try_from(balance.account_index).ok()?,
+        mint: balance.mint.clone(),
+        ui_token_amount: UiTokenAmount {
+          ui_amount: Some(ui_token_amount.ui_amount),
+          decimals: u8::try_from(ui_token_amount.decimals).ok()?,
+          amount: ui_token_amount.amount.clone(),
+          ui_amount_string: ui_token_amount.ui_amount_string.clone(),
+        },
+        owner: optional(&balance.owner),
+        program_id: optional(&balance.program_id),
+      })
+    })
+    .collect()
+}
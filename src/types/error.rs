@@ -0,0 +1,70 @@
+use solana_client::client_error::ClientError;
+use solana_sdk::pubkey::Pubkey;
+use std::fmt;
+
+/// Error returned by the fallible APIs of the library (`try_from_account_info`,
+/// `PoolTrait::try_fetch_market_state_from_rpc`, ...)
+#[derive(Debug)]
+pub enum Error {
+  /// Account data that doesn't decode into the expected layout (too short, corrupt, ...)
+  Decode { account: Pubkey, reason: String },
+  /// A failed RPC request, or an account missing on chain
+  Rpc(ClientError),
+  /// Account data that decodes but uses a version, mode or size the library doesn't support
+  UnsupportedLayout { account: Pubkey, reason: String },
+  /// Overflow, underflow or division by zero in a pool calculation
+  Arithmetic { operation: &'static str },
+  /// An operation that isn't valid for the pool or its current state
+  InvalidPool { pool: Pubkey, reason: String },
+}
+
+impl Error {
+  /// Build a `Decode` error for `account`
+  pub(crate) fn decode(account: Pubkey, reason: impl fmt::Display) -> Self {
+    Self::Decode {
+      account,
+      reason: reason.to_string(),
+    }
+  }
+
+  /// Build an `UnsupportedLayout` error for `account`
+  pub(crate) fn unsupported_layout(account: Pubkey, reason: impl fmt::Display) -> Self {
+    Self::UnsupportedLayout {
+      account,
+      reason: reason.to_string(),
+    }
+  }
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Decode { account, reason } => {
+        write!(f, "Failed to decode account {}: {}", account, reason)
+      }
+      Self::Rpc(error) => write!(f, "RPC error: {}", error),
+      Self::UnsupportedLayout { account, reason } => {
+        write!(f, "Unsupported layout for account {}: {}", account, reason)
+      }
+      Self::Arithmetic { operation } => {
+        write!(f, "Arithmetic overflow or division by zero in {}", operation)
+      }
+      Self::InvalidPool { pool, reason } => write!(f, "Invalid pool {}: {}", pool, reason),
+    }
+  }
+}
+
+impl std::error::Error for Error {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      Self::Rpc(error) => Some(error),
+      _ => None,
+    }
+  }
+}
+
+impl From<ClientError> for Error {
+  fn from(error: ClientError) -> Self {
+    Self::Rpc(error)
+  }
+}
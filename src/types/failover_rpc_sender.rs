@@ -0,0 +1,111 @@
+use crate::types::retry_policy::RetryPolicy;
+use async_trait::async_trait;
+use solana_client::client_error::{ClientError, ClientErrorKind, Result};
+use solana_client::rpc_custom_error::{
+  JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED, JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY,
+};
+use solana_client::rpc_request::{RpcError, RpcRequest};
+use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+use solana_rpc_client::http_sender::HttpSender;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// RPC transport over several endpoints that fails over to the next endpoint when one errors
+///
+/// Requests go to the endpoint that last answered, starting with the first (primary) one. On a
+/// transport error, an HTTP error status (including rate limiting once the HTTP sender's own 429
+/// retries run out), an unparsable response, an unhealthy node or a node behind the requested
+/// minimum context slot, the request moves on to the next endpoint. Other RPC errors, like invalid
+/// params, are returned right away as every endpoint would give the same answer. After a full pass
+/// over the endpoints fails, the request is retried following the `RetryPolicy`.
+///
+/// Used by `CentralContextBuilder` for both RPC clients of the context, wrap it with
+/// `RpcClient::new_sender` to use it elsewhere.
+pub struct FailoverRpcSender {
+  senders: Vec<HttpSender>,
+  active_sender: AtomicUsize,
+  retry_policy: RetryPolicy,
+}
+
+impl FailoverRpcSender {
+  /// Create a sender over `urls`, in order of preference. `urls` must not be empty.
+  pub fn new(urls: &[String], timeout: Duration, retry_policy: RetryPolicy) -> Self {
+    assert!(!urls.is_empty(), "FailoverRpcSender needs at least one url");
+    Self {
+      senders: urls
+        .iter()
+        .map(|url| HttpSender::new_with_timeout(url, timeout))
+        .collect(),
+      active_sender: AtomicUsize::new(0),
+      retry_policy,
+    }
+  }
+}
+
+#[async_trait]
+impl RpcSender for FailoverRpcSender {
+  async fn send(&self, request: RpcRequest, params: serde_json::Value) -> Result<serde_json::Value> {
+    let mut backoff = self.retry_policy.initial_backoff;
+    let mut retries = 0;
+    loop {
+      let first_sender = self.active_sender.load(Ordering::Relaxed);
+      let mut last_error = None;
+      for offset in 0..self.senders.len() {
+        let index = (first_sender + offset) % self.senders.len();
+        let sender = &self.senders[index];
+        match sender.send(request, params.clone()).await {
+          Ok(response) => {
+            self.active_sender.store(index, Ordering::Relaxed);
+            return Ok(response);
+          }
+          Err(error) if should_fail_over(&error) => {
+            println!(
+              "FailoverRpcSender: {} failed on {}: {}",
+              request,
+              sender.url(),
+              error
+            );
+            last_error = Some(error);
+          }
+          Err(error) => return Err(error),
+        }
+      }
+      if retries >= self.retry_policy.max_retries {
+        return Err(last_error.unwrap());
+      }
+      retries += 1;
+      tokio::time::sleep(backoff).await;
+      backoff = (backoff * 2).min(self.retry_policy.max_backoff);
+    }
+  }
+
+  fn get_transport_stats(&self) -> RpcTransportStats {
+    let mut stats = RpcTransportStats::default();
+    for sender in &self.senders {
+      let sender_stats = sender.get_transport_stats();
+      stats.request_count += sender_stats.request_count;
+      stats.elapsed_time += sender_stats.elapsed_time;
+      stats.rate_limited_time += sender_stats.rate_limited_time;
+    }
+    stats
+  }
+
+  fn url(&self) -> String {
+    self.senders[self.active_sender.load(Ordering::Relaxed)].url()
+  }
+}
+
+/// Check if another endpoint could answer the request that failed with this error
+fn should_fail_over(error: &ClientError) -> bool {
+  match error.kind() {
+    ClientErrorKind::Io(_)
+    | ClientErrorKind::Reqwest(_)
+    | ClientErrorKind::Middleware(_)
+    | ClientErrorKind::SerdeJson(_) => true,
+    ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => matches!(
+      *code,
+      JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY | JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED
+    ),
+    _ => false,
+  }
+}
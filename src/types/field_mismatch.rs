@@ -0,0 +1,22 @@
+/// A pool field whose in-memory value differs from the value decoded from chain, see `PoolAudit`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldMismatch {
+  /// Name of the field from `PoolTrait::state_fields`, or of the derived value
+  /// (`token_a_amount_units`, `price_a_over_b_lp`, `total_swap_fee_lp`, `a_to_b_fee_a_lp`, ...)
+  pub field: &'static str,
+  pub in_memory: u128,
+  pub on_chain: u128,
+}
+
+impl FieldMismatch {
+  /// Absolute difference between the in-memory and on-chain values
+  pub fn magnitude(&self) -> u128 {
+    self.in_memory.abs_diff(self.on_chain)
+  }
+
+  /// Difference relative to the on-chain value, e.g. 0.01 for a 1% drift. Infinite when the
+  /// on-chain value is 0.
+  pub fn relative_magnitude(&self) -> f64 {
+    self.magnitude() as f64 / self.on_chain as f64
+  }
+}
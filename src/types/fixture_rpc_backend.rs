@@ -0,0 +1,163 @@
+use crate::types::rpc_backend::RpcBackend;
+use solana_client::client_error::Result;
+use solana_client::rpc_config::RpcProgramAccountsConfig;
+use solana_client::rpc_filter::RpcFilterType;
+use solana_client::rpc_response::RpcKeyedAccount;
+use solana_sdk::account::Account;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// In memory `RpcBackend` serving a fixed set of accounts, to run the library without a node
+///
+/// Accounts are inserted one by one with `set_account` or loaded from a fixture directory with
+/// `from_directory`. The slot and blockhash are whatever was last set, 0 and the default hash
+/// until then. Accounts can be changed at any time, e.g. to simulate a swap between two
+/// `refresh_pools` calls.
+///
+/// ```no_run
+/// use solana_central::{CentralContextBuilder, FixtureRpcBackend};
+/// use std::path::Path;
+/// use std::sync::Arc;
+///
+/// let backend = FixtureRpcBackend::from_directory(Path::new("tests/fixtures")).unwrap();
+/// backend.set_slot(350_000_000);
+/// let central_context = CentralContextBuilder::new()
+///   .rpc_backend(Arc::new(backend))
+///   .build()
+///   .unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct FixtureRpcBackend {
+  accounts: RwLock<HashMap<Pubkey, Account>>,
+  slot: AtomicU64,
+  latest_blockhash: RwLock<Hash>,
+}
+
+impl FixtureRpcBackend {
+  /// Create a backend without accounts
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Load every `.json` file of `directory` as an account. Files are in the format written by
+  /// `solana account <address> --output json`: `{"pubkey": ..., "account": {...}}` with the account
+  /// in a binary encoding (base64, base64+zstd or base58). Other files and subdirectories are
+  /// skipped.
+  pub fn from_directory(directory: &Path) -> io::Result<Self> {
+    let backend = Self::new();
+    for entry in fs::read_dir(directory)? {
+      let path = entry?.path();
+      if !path.is_file() || path.extension().is_none_or(|extension| extension != "json") {
+        continue;
+      }
+      let invalid_fixture = |reason: String| {
+        io::Error::new(
+          io::ErrorKind::InvalidData,
+          format!("Invalid account fixture {}: {}", path.display(), reason),
+        )
+      };
+      let keyed_account: RpcKeyedAccount = serde_json::from_slice(&fs::read(&path)?)
+        .map_err(|error| invalid_fixture(error.to_string()))?;
+      let pubkey = Pubkey::from_str(&keyed_account.pubkey)
+        .map_err(|error| invalid_fixture(error.to_string()))?;
+      let account = keyed_account
+        .account
+        .decode()
+        .ok_or_else(|| invalid_fixture("account data isn't in a binary encoding".to_string()))?;
+      backend.set_account(pubkey, account);
+    }
+    Ok(backend)
+  }
+
+  /// Insert or replace an account
+  pub fn set_account(&self, pubkey: Pubkey, account: Account) {
+    self.accounts.write().unwrap().insert(pubkey, account);
+  }
+
+  /// Remove an account, it is then reported as not existing
+  pub fn remove_account(&self, pubkey: &Pubkey) -> Option<Account> {
+    self.accounts.write().unwrap().remove(pubkey)
+  }
+
+  /// Set the slot reported by the backend
+  pub fn set_slot(&self, slot: u64) {
+    self.slot.store(slot, Ordering::Relaxed);
+  }
+
+  /// Set the blockhash reported by the backend
+  pub fn set_latest_blockhash(&self, blockhash: Hash) {
+    *self.latest_blockhash.write().unwrap() = blockhash;
+  }
+}
+
+impl RpcBackend for FixtureRpcBackend {
+  fn get_slot(&self) -> Result<u64> {
+    Ok(self.slot.load(Ordering::Relaxed))
+  }
+
+  fn get_latest_blockhash(&self) -> Result<(Hash, u64)> {
+    // Blockhashes are valid for 150 blocks
+    Ok((
+      *self.latest_blockhash.read().unwrap(),
+      self.slot.load(Ordering::Relaxed) + 150,
+    ))
+  }
+
+  fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>> {
+    Ok(self.accounts.read().unwrap().get(pubkey).cloned())
+  }
+
+  fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+    let accounts = self.accounts.read().unwrap();
+    Ok(
+      pubkeys
+        .iter()
+        .map(|pubkey| accounts.get(pubkey).cloned())
+        .collect(),
+    )
+  }
+
+  fn get_program_accounts_with_config(
+    &self,
+    program_id: &Pubkey,
+    config: &RpcProgramAccountsConfig,
+  ) -> Result<Vec<(Pubkey, Account)>> {
+    let filters = config.filters.as_deref().unwrap_or_default();
+    Ok(
+      self
+        .accounts
+        .read()
+        .unwrap()
+        .iter()
+        .filter(|(_, account)| {
+          account.owner == *program_id
+            && filters
+              .iter()
+              .all(|filter| filter_matches(filter, &account.data))
+        })
+        .map(|(pubkey, account)| (*pubkey, account.clone()))
+        .collect(),
+    )
+  }
+}
+
+/// Check if account data passes a getProgramAccounts filter
+fn filter_matches(filter: &RpcFilterType, data: &[u8]) -> bool {
+  match filter {
+    RpcFilterType::DataSize(size) => data.len() as u64 == *size,
+    RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(data),
+    // Initialized or frozen token account, token-2022 accounts with extensions have their account
+    // type (2) right after the base account
+    RpcFilterType::TokenAccountState => {
+      (data.len() == 165 || data.get(165) == Some(&2))
+        && data.get(108).is_some_and(|state| *state != 0)
+    }
+  }
+}
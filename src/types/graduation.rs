@@ -0,0 +1,23 @@
+use crate::types::pools::Pools;
+use solana_sdk::pubkey::Pubkey;
+
+/// A bonding curve that graduated (completed or migrated) and the AMM pool its liquidity moves to.
+/// Tracked in `CentralContext::graduations` and published to `subscribe_graduations` subscribers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Graduation {
+  /// Address of the bonding curve pool
+  pub pool_address: Pubkey,
+  pub pool_type: Pools,
+  pub token_a_address: Pubkey,
+  pub token_b_address: Pubkey,
+  /// Type of the AMM pool the curve migrates to, see `PoolTrait::graduation_successor`
+  pub successor_pool_type: Pools,
+  /// Address of the AMM pool, derived from the curve when possible (Pumpfun), otherwise `None`
+  /// until the curve's migration instruction is seen, see
+  /// `CentralContext::process_migration_instruction`
+  pub successor_pool_address: Option<Pubkey>,
+  /// Whether the successor pool is inserted in the context and can be traded
+  pub successor_inserted: bool,
+  /// Slot the graduation was detected at
+  pub slot: u64,
+}
@@ -3,9 +3,10 @@ use solana_sdk::pubkey::Pubkey;
 /**
 A general independent Solana instruction type agnostic of inner or top level instructions. Designed
 to conveniently work with the format used in Solana SDKs and yellowstone grpc. Does not own any of
-the data just lifetimes to it.
+the data just lifetimes to it. Build one with `Instruction::new`, fields may be added.
 */
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct Instruction<'a> {
   /// The ordered list of account keys of the tx that this instruction belongs to
   pub tx_account_keys: &'a [Pubkey],
@@ -15,4 +16,39 @@ pub struct Instruction<'a> {
   pub data: &'a [u8],
   /// The index of the program id in the tx_account_keys vector
   pub program_id_index: u8,
+  /// The invocation stack height, 1 for top level instructions and 2 or more for inner
+  /// instructions. Inner instructions from before stack heights were recorded default to 2
+  pub stack_height: u32,
+}
+
+impl<'a> Instruction<'a> {
+  /// View over an instruction of a tx whose account keys are `tx_account_keys`
+  pub fn new(
+    tx_account_keys: &'a [Pubkey],
+    accounts: &'a [u8],
+    data: &'a [u8],
+    program_id_index: u8,
+    stack_height: u32,
+  ) -> Self {
+    Self {
+      tx_account_keys,
+      accounts,
+      data,
+      program_id_index,
+      stack_height,
+    }
+  }
+
+  /// Get the program id that this instruction invokes
+  pub fn program_id(&self) -> Option<&'a Pubkey> {
+    self.tx_account_keys.get(self.program_id_index as usize)
+  }
+
+  /// Get the address of the account at `position` in this instruction's account list. Returns
+  /// `None` if the instruction has fewer accounts or the index points outside the tx account keys.
+  pub fn account(&self, position: usize) -> Option<&'a Pubkey> {
+    self
+      .tx_account_keys
+      .get(*self.accounts.get(position)? as usize)
+  }
 }
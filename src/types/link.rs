@@ -1,4 +1,5 @@
 use solana_sdk::pubkey::Pubkey;
+use std::fmt;
 
 /**
 Struct used to track a "link" between two accounts. For there to be a link, both accounts must be
@@ -52,14 +53,17 @@ impl Link {
       atomic_instruction_index,
     })
   }
+}
 
+impl fmt::Display for Link {
   /// Debug tostring for link
-  pub fn to_string(&self) -> String {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     let wallet_1 = Pubkey::new_from_array(self.link[0..32].try_into().expect("slice with incorrect length"));
     let wallet_2 = Pubkey::new_from_array(self.link[32..64].try_into().expect("slice with incorrect length"));
-    format!(
+    write!(
+      f,
       "Wallet 1: {}, Wallet 2: {}, Block Time: {}, Slot: {}, Index: {}, Atomic Instruction Index: {}",
       wallet_1, wallet_2, self.block_time, self.slot, self.index, self.atomic_instruction_index
     )
   }
-}
\ No newline at end of file
+}
@@ -0,0 +1,27 @@
+use crate::types::market_update::MarketUpdate;
+use solana_sdk::pubkey::Pubkey;
+
+/// Filter for `CentralContext::subscribe_market_updates` deciding which `MarketUpdate`s a
+/// subscriber receives.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MarketSubscription {
+  /// Updates for a single market, keyed by market (pool) address
+  Market(Pubkey),
+  /// Updates for every market that trades the token, on either side
+  Token(Pubkey),
+  /// Every market update
+  All,
+}
+
+impl MarketSubscription {
+  /// Check if the update passes this subscription's filter
+  pub fn matches(&self, update: &MarketUpdate) -> bool {
+    match self {
+      Self::Market(market_address) => update.market_address == *market_address,
+      Self::Token(token_address) => {
+        update.token_a_address == *token_address || update.token_b_address == *token_address
+      }
+      Self::All => true,
+    }
+  }
+}
@@ -1,14 +1,45 @@
+use crate::types::pool::PoolTrait;
 use solana_sdk::pubkey::Pubkey;
 
 /// Used to deliver high frequency market price updates without having to do read locks on the pool
 /// struct that holds the current market state. It's going to be more meant to be used for
-/// "pushing" market updates throughout the system.
+/// "pushing" market updates throughout the system. Published by `CentralContext` whenever a
+/// market's prices or reserves change, see `CentralContext::subscribe_market_updates`.
 #[repr(C)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct MarketUpdate {
   pub market_address: Pubkey,
   pub token_a_address: Pubkey,
   pub token_b_address: Pubkey,
+  /// Units of token A for 1 unit of token B in lamports. Always defined, updates for pools without
+  /// a price aren't published (see `from_pool`), but 0 if the price is below 1 lamport.
   pub price_a_b: u128,
+  /// Units of token B for 1 unit of token A in lamports, like `price_a_b`
   pub price_b_a: u128,
+  /// Actual amount of token A in the pool in token units, see `PoolTrait::token_a_amount_units`.
+  /// Bonding curves are priced from virtual reserves, so the price doesn't follow from it.
+  pub token_a_amount: u64,
+  /// Actual amount of token B in the pool in token units, like `token_a_amount`
+  pub token_b_amount: u64,
+}
+
+impl MarketUpdate {
+  /// Snapshot the current prices and reserves of a pool. Returns `None` while the pool isn't
+  /// hydrated or either price is undefined (see `PoolTrait::try_price_a_over_b_lp`), e.g. an AMM
+  /// pool with an empty side, since there is no meaningful price to push. Bonding curves are
+  /// priced from their virtual reserves, so a fresh curve without real reserves still has a price.
+  pub fn from_pool(pool: &(impl PoolTrait + ?Sized)) -> Option<Self> {
+    if !pool.is_hydrated() {
+      return None;
+    }
+    Some(Self {
+      market_address: *pool.pool_address(),
+      token_a_address: *pool.token_a_address(),
+      token_b_address: *pool.token_b_address(),
+      price_a_b: pool.try_price_a_over_b_lp().ok()?,
+      price_b_a: pool.try_price_b_over_a_lp().ok()?,
+      token_a_amount: pool.token_a_amount_units(),
+      token_b_amount: pool.token_b_amount_units(),
+    })
+  }
 }
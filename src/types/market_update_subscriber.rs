@@ -0,0 +1,11 @@
+use crate::types::backpressure_policy::BackpressurePolicy;
+use crate::types::market_subscription::MarketSubscription;
+use crate::types::market_update::MarketUpdate;
+use std::sync::mpsc::SyncSender;
+
+/// A registered market update subscriber, see `CentralContext::subscribe_market_updates`.
+pub(crate) struct MarketUpdateSubscriber {
+  pub subscription: MarketSubscription,
+  pub sender: SyncSender<MarketUpdate>,
+  pub backpressure_policy: BackpressurePolicy,
+}
@@ -1,10 +1,17 @@
 use crate::CentralContext;
 use crate::constants::LAMPORTS_PER_SOL;
+use crate::protocol_idls::meteora::MeteoraAmmPoolIdl;
 use crate::types::meteora_vault::MeteoraVault;
+use crate::types::pool_lifecycle::PoolLifecycle;
 use crate::types::swap_direction::SwapDirection;
 use crate::types::pool::Pool;
 use crate::types::pool::PoolTrait;
+use crate::types::pool::constant_product_quote;
 use crate::types::pools::Pools;
+use crate::utilities::get_mint_supply::get_mint_supply;
+use crate::utilities::get_token_account_amount::get_token_account_amount;
+use crate::types::error::Error;
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_sdk::pubkey::Pubkey;
 use std::any::Any;
 use std::sync::{Arc, RwLock};
@@ -16,11 +23,24 @@ such as vaults for tokens, because they are shared across all AMM pools in the M
 they will be stored in shared MeteoraVault objects. References to those shared objects will be
 stored in these pool info objects.
 */
+#[derive(BorshSerialize, BorshDeserialize)]
 pub struct MeteoraAmmPool {
   pub info: Pool,
   // Is the pool currently enabled for trading, looks like this is a meteora specific field
   pub enabled: bool,
+  /*
+  Snapshots store a copy of the shared vaults, `CentralContext::restore_snapshot` links the restored
+  pool back to the vaults in `meteora_vault_cache`
+  */
+  #[borsh(
+    serialize_with = "MeteoraVault::serialize_shared",
+    deserialize_with = "MeteoraVault::deserialize_shared"
+  )]
   pub token_a_vault: Arc<RwLock<MeteoraVault>>,
+  #[borsh(
+    serialize_with = "MeteoraVault::serialize_shared",
+    deserialize_with = "MeteoraVault::deserialize_shared"
+  )]
   pub token_b_vault: Arc<RwLock<MeteoraVault>>,
   // The address of the token account that receives the swap fees for token A
   pub token_a_fee_address: Pubkey,
@@ -41,22 +61,18 @@ pub struct MeteoraAmmPool {
 
 impl PoolTrait for MeteoraAmmPool {
   fn token_a_amount_units(&self) -> u64 {
-    let token_a_vault_guard = self.token_a_vault.read().unwrap();
-    if token_a_vault_guard.lp_supply == 0 {
-      0
-    } else {
-      (self.token_a_lp_amount * token_a_vault_guard.calculate_withdrawable_amount() as u128
-        / token_a_vault_guard.lp_supply as u128) as u64
-    }
+    self
+      .token_a_vault
+      .read()
+      .unwrap()
+      .get_amount_by_share(self.token_a_lp_amount)
   }
   fn token_b_amount_units(&self) -> u64 {
-    let token_b_vault_guard = self.token_b_vault.read().unwrap();
-    if token_b_vault_guard.lp_supply == 0 {
-      0
-    } else {
-      (self.token_b_lp_amount * token_b_vault_guard.calculate_withdrawable_amount() as u128
-        / token_b_vault_guard.lp_supply as u128) as u64
-    }
+    self
+      .token_b_vault
+      .read()
+      .unwrap()
+      .get_amount_by_share(self.token_b_lp_amount)
   }
 
   fn pool_address(&self) -> &Pubkey {
@@ -77,11 +93,79 @@ impl PoolTrait for MeteoraAmmPool {
   fn pool_type(&self) -> &Pools {
     &self.info.pool_type
   }
+  fn last_updated_slot(&self) -> u64 {
+    self.info.last_updated_slot
+  }
+  fn set_last_updated_slot(&mut self, slot: u64) {
+    self.info.last_updated_slot = slot;
+  }
+  fn is_hydrated(&self) -> bool {
+    self.info.hydrated
+  }
+  fn set_hydrated(&mut self) {
+    self.info.hydrated = true;
+  }
+  fn state_fields(&self) -> Vec<(&'static str, u128)> {
+    let token_a_vault = self.token_a_vault.read().unwrap();
+    let token_b_vault = self.token_b_vault.read().unwrap();
+    vec![
+      ("enabled", self.enabled as u128),
+      ("token_a_lp_amount", self.token_a_lp_amount),
+      ("token_b_lp_amount", self.token_b_lp_amount),
+      ("trade_fee_numerator", self.trade_fee_numerator),
+      ("trade_fee_denominator", self.trade_fee_denominator),
+      ("protocol_trade_fee_numerator", self.protocol_trade_fee_numerator),
+      ("protocol_trade_fee_denominator", self.protocol_trade_fee_denominator),
+      (
+        "token_a_vault.last_updated_locked_profit",
+        token_a_vault.last_updated_locked_profit as u128,
+      ),
+      ("token_a_vault.last_report", token_a_vault.last_report as u128),
+      ("token_a_vault.locked_profit_degradation", token_a_vault.locked_profit_degradation as u128),
+      ("token_a_vault.total_amount", token_a_vault.total_amount as u128),
+      ("token_a_vault.lp_supply", token_a_vault.lp_supply as u128),
+      (
+        "token_b_vault.last_updated_locked_profit",
+        token_b_vault.last_updated_locked_profit as u128,
+      ),
+      ("token_b_vault.last_report", token_b_vault.last_report as u128),
+      ("token_b_vault.locked_profit_degradation", token_b_vault.locked_profit_degradation as u128),
+      ("token_b_vault.total_amount", token_b_vault.total_amount as u128),
+      ("token_b_vault.lp_supply", token_b_vault.lp_supply as u128),
+    ]
+  }
+  fn lifecycle(&self, _current_slot: u64) -> PoolLifecycle {
+    if self.enabled {
+      PoolLifecycle::Active
+    } else {
+      PoolLifecycle::Closed
+    }
+  }
+
+  fn total_swap_fee_lp(&self, central_context: &Arc<CentralContext>) -> u64 {
+    // A zero fee denominator is an uninitialized pool, report the whole input as fee so the pool
+    // is never used
+    self
+      .try_total_swap_fee_lp(central_context)
+      .unwrap_or(LAMPORTS_PER_SOL as u64)
+  }
 
-  fn total_swap_fee_lp(&self, _: &Arc<CentralContext>) -> u64 {
-    (self.trade_fee_numerator * LAMPORTS_PER_SOL / self.trade_fee_denominator
-      + self.protocol_trade_fee_numerator * LAMPORTS_PER_SOL / self.protocol_trade_fee_denominator)
-      as u64
+  fn try_total_swap_fee_lp(&self, _: &Arc<CentralContext>) -> Result<u64, Error> {
+    let fee_lp = |numerator: u128, denominator: u128| {
+      numerator
+        .checked_mul(LAMPORTS_PER_SOL)
+        .and_then(|fee| fee.checked_div(denominator))
+    };
+    fee_lp(self.trade_fee_numerator, self.trade_fee_denominator)
+      .zip(fee_lp(
+        self.protocol_trade_fee_numerator,
+        self.protocol_trade_fee_denominator,
+      ))
+      .and_then(|(trade_fee, protocol_trade_fee)| trade_fee.checked_add(protocol_trade_fee))
+      .and_then(|fee| u64::try_from(fee).ok())
+      .ok_or(Error::Arithmetic {
+        operation: "total_swap_fee_lp",
+      })
   }
 
   fn as_any(&self) -> &dyn Any {
@@ -90,52 +174,102 @@ impl PoolTrait for MeteoraAmmPool {
   fn as_any_mut(&mut self) -> &mut dyn Any {
     self
   }
-
-  fn price_a_over_b_lp(&self) -> u128 {
-    if self.token_a_amount_units() == 0 {
-      return 0;
-    }
-    self.token_a_amount_units() as u128 * LAMPORTS_PER_SOL / self.token_b_amount_units() as u128
-  }
-
-  fn price_b_over_a_lp(&self) -> u128 {
-    if self.token_a_amount_units() == 0 {
-      return 0;
-    }
-    self.token_b_amount_units() as u128 * LAMPORTS_PER_SOL / self.token_a_amount_units() as u128
-  }
-  fn fetch_market_state_from_rpc(&mut self, context: &Arc<CentralContext>) {
+  fn try_fetch_market_state_from_rpc(
+    &mut self,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<(), Error> {
     // Update vaults
     self
       .token_a_vault
       .write()
       .unwrap()
-      .update_vault_info(context.clone());
+      .try_update_vault_info(central_context)?;
     self
       .token_b_vault
       .write()
       .unwrap()
-      .update_vault_info(context.clone());
+      .try_update_vault_info(central_context)?;
 
     // Update this pools liquidity amounts
-    self.token_a_lp_amount = context
-      .json_rpc_client
-      .get_token_account_balance(&self.token_a_lp_vault_address)
-      .unwrap()
-      .amount
-      .parse()
-      .unwrap();
-    self.token_b_lp_amount = context
-      .json_rpc_client
-      .get_token_account_balance(&self.token_b_lp_vault_address)
-      .unwrap()
-      .amount
-      .parse()
-      .unwrap();
+    self.token_a_lp_amount =
+      central_context.fetch_token_account_amount(&self.token_a_lp_vault_address)? as u128;
+    self.token_b_lp_amount =
+      central_context.fetch_token_account_amount(&self.token_b_lp_vault_address)? as u128;
+    self.info.hydrated = true;
+    Ok(())
+  }
+
+
+  fn dependent_accounts(&self) -> Vec<Pubkey> {
+    let token_a_vault = self.token_a_vault.read().unwrap();
+    let token_b_vault = self.token_b_vault.read().unwrap();
+    vec![
+      self.info.pool_address,
+      token_a_vault.vault,
+      token_b_vault.vault,
+      token_a_vault.lp_token_address,
+      token_b_vault.lp_token_address,
+      self.token_a_lp_vault_address,
+      self.token_b_lp_vault_address,
+    ]
+  }
+
+  fn apply_account_update(&mut self, address: &Pubkey, data: &[u8]) -> bool {
+    // Vaults and their LP mints are shared, updating them updates every pool referencing them
+    for vault in [&self.token_a_vault, &self.token_b_vault] {
+      let mut vault = vault.write().unwrap();
+      if *address == vault.vault {
+        return vault.apply_vault_data(data);
+      }
+      if *address == vault.lp_token_address {
+        let Some(lp_supply) = get_mint_supply(data) else {
+          return false;
+        };
+        vault.lp_supply = lp_supply;
+        return true;
+      }
+    }
+
+    if *address == self.token_a_lp_vault_address
+      && let Some(amount) = get_token_account_amount(data)
+    {
+      self.token_a_lp_amount = amount as u128;
+    } else if *address == self.token_b_lp_vault_address
+      && let Some(amount) = get_token_account_amount(data)
+    {
+      self.token_b_lp_amount = amount as u128;
+    } else if *address == self.info.pool_address
+      && let Ok(decoded_layout) = MeteoraAmmPoolIdl::try_from_slice(data)
+    {
+      self.enabled = decoded_layout.enabled;
+      self.trade_fee_numerator = decoded_layout.fees.trade_fee_numerator as u128;
+      self.trade_fee_denominator = decoded_layout.fees.trade_fee_denominator as u128;
+      self.protocol_trade_fee_numerator = decoded_layout.fees.protocol_trade_fee_numerator as u128;
+      self.protocol_trade_fee_denominator =
+        decoded_layout.fees.protocol_trade_fee_denominator as u128;
+    } else {
+      return false;
+    }
+    true
   }
 
-  fn directional_fees(&self, swap_direction: SwapDirection, _: &Arc<CentralContext>) -> (f64, f64) {
+  fn directional_fees(&self, _: SwapDirection, _: &Arc<CentralContext>) -> (f64, f64) {
     // TODO implement properly but Meteora Ammv1 not actively used anymore
     (1.0, 1.0)
   }
+
+  fn try_quote(
+    &self,
+    amount_in: u64,
+    direction: SwapDirection,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<u64, Error> {
+    // The fee is taken from the input token
+    let fee_lp = self.try_total_swap_fee_lp(central_context)?;
+    let (reserve_a, reserve_b) = (self.token_a_amount_units(), self.token_b_amount_units());
+    match direction {
+      SwapDirection::AToB => constant_product_quote(amount_in, reserve_a, reserve_b, fee_lp, 0),
+      SwapDirection::BToA => constant_product_quote(amount_in, reserve_b, reserve_a, fee_lp, 0),
+    }
+  }
 }
@@ -1,17 +1,22 @@
 use crate::CentralContext;
-use crate::constants::LAMPORTS_PER_SOL;
+use crate::constants::{METEORA_DAMMV2_MAX_FEE_NUMERATOR_V0, METEORA_DAMMV2_MAX_FEE_NUMERATOR_V1};
+use crate::meteora::get_meteora_dammv2_pool_from_accountinfo::check_supported_layout;
 use crate::protocol_idls::meteora::MeteoraDammv2PoolIdl;
 use crate::types::pool::Pool;
 use crate::types::pool::PoolTrait;
+use crate::types::pool::deduct_fee;
+use crate::types::pool_lifecycle::PoolLifecycle;
+use crate::types::error::Error;
 use crate::types::pools::Pools;
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use primitive_types::U256;
 use solana_sdk::pubkey::Pubkey;
 use std::any::Any;
 use std::cmp;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-#[derive(Debug)]
+#[derive(Debug, BorshSerialize, BorshDeserialize)]
 /// Struct to hold data and PoolTrait implementation for Meteora Ammv2/Dammv2 pools.
 pub struct MeteoraDammV2Pool {
   pub info: Pool,
@@ -148,8 +153,68 @@ impl PoolTrait for MeteoraDammV2Pool {
   fn pool_type(&self) -> &Pools {
     &self.info.pool_type
   }
+  fn last_updated_slot(&self) -> u64 {
+    self.info.last_updated_slot
+  }
+  fn set_last_updated_slot(&mut self, slot: u64) {
+    self.info.last_updated_slot = slot;
+  }
+  fn state_fields(&self) -> Vec<(&'static str, u128)> {
+    vec![
+      ("cliff_fee_numerator", self.cliff_fee_numerator as u128),
+      ("base_fee_mode", self.base_fee_mode as u128),
+      ("first_factor", self.first_factor as u128),
+      ("second_factor", u64::from_le_bytes(self.second_factor) as u128),
+      ("third_factor", self.third_factor as u128),
+      ("protocol_fee_percent", self.protocol_fee_percent as u128),
+      ("referral_fee_percent", self.referral_fee_percent as u128),
+      ("initialized", self.initialized as u128),
+      ("variable_fee_control", self.variable_fee_control as u128),
+      ("bin_step", self.bin_step as u128),
+      ("last_update_timestamp", self.last_update_timestamp as u128),
+      ("sqrt_price_reference", self.sqrt_price_reference),
+      ("volatility_accumulator", self.volatility_accumulator),
+      ("volatility_reference", self.volatility_reference),
+      ("activation_point", self.activation_point as u128),
+      ("activation_type", self.activation_type as u128),
+      ("liquidity", self.liquidity),
+      ("sqrt_price", self.sqrt_price),
+      ("collect_fee_mode", self.collect_fee_mode as u128),
+      ("protocol_a_fee", self.protocol_a_fee as u128),
+      ("protocol_b_fee", self.protocol_b_fee as u128),
+      ("partner_a_fee", self.partner_a_fee as u128),
+      ("partner_b_fee", self.partner_b_fee as u128),
+      ("sqrt_min_price", self.sqrt_min_price),
+      ("sqrt_max_price", self.sqrt_max_price),
+      ("version", self.version as u128),
+    ]
+  }
+  fn lifecycle(&self, current_slot: u64) -> PoolLifecycle {
+    let current_point = match self.activation_type {
+      // Timestamp-based timing (in seconds)
+      1 => SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs(),
+      // Slot-based timing
+      _ => current_slot,
+    };
+    if current_point < self.activation_point {
+      PoolLifecycle::PendingActivation
+    } else {
+      PoolLifecycle::Active
+    }
+  }
 
   fn total_swap_fee_lp(&self, central_context: &Arc<CentralContext>) -> u64 {
+    // Unknown versions are rejected when decoding, assume the highest cap if one slips through so
+    // the fee is never underestimated
+    self
+      .try_total_swap_fee_lp(central_context)
+      .unwrap_or(METEORA_DAMMV2_MAX_FEE_NUMERATOR_V1)
+  }
+
+  fn try_total_swap_fee_lp(&self, central_context: &Arc<CentralContext>) -> Result<u64, Error> {
     // Step 1: Calculate base fee numerator
     let base_fee_numerator = self.calculate_base_fee_numerator(central_context);
 
@@ -161,7 +226,8 @@ impl PoolTrait for MeteoraDammV2Pool {
     );
 
     // Step 3: Calculate total effective fee numerator
-    let total_fee_numerator_uncapped = base_fee_numerator + dynamic_fee_numerator;
+    let total_fee_numerator_uncapped =
+      base_fee_numerator.saturating_add(dynamic_fee_numerator);
     println!(
       "[MeteoraDammV2Pool] Total uncapped fee numerator: {}",
       total_fee_numerator_uncapped
@@ -171,16 +237,17 @@ impl PoolTrait for MeteoraDammV2Pool {
     // Version 0: max 50% (500_000_000)
     // Version 1: max 99% (990_000_000)
     let max_fee_numerator = match self.version {
-      0 => 500_000_000_u64, // 50%
-      1 => 990_000_000_u64, // 99%
-      _ => panic!(
-        "Invalid pool version: {} for metoera dammv2: pool address: {}",
-        self.version, self.info.pool_address
-      ),
+      0 => METEORA_DAMMV2_MAX_FEE_NUMERATOR_V0,
+      1 => METEORA_DAMMV2_MAX_FEE_NUMERATOR_V1,
+      _ => {
+        return Err(Error::unsupported_layout(
+          self.info.pool_address,
+          format!("unknown DAMMv2 pool version {}", self.version),
+        ));
+      }
     };
 
-    let total_fee_numerator_capped = cmp::min(total_fee_numerator_uncapped, max_fee_numerator);
-    total_fee_numerator_capped
+    Ok(cmp::min(total_fee_numerator_uncapped, max_fee_numerator))
   }
 
   fn as_any(&self) -> &dyn Any {
@@ -190,29 +257,30 @@ impl PoolTrait for MeteoraDammV2Pool {
     self
   }
 
-  fn price_a_over_b_lp(&self) -> u128 {
-    if self.token_b_amount_units() == 0 {
-      return 0;
-    }
-    self.token_a_amount_units() as u128 * LAMPORTS_PER_SOL / self.token_b_amount_units() as u128
+  fn try_fetch_market_state_from_rpc(
+    &mut self,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<(), Error> {
+    let pool_address = self.info.pool_address;
+    let data = central_context.json_rpc_client.get_account(&pool_address)?.data;
+    let decoded_layout = MeteoraDammv2PoolIdl::try_from_slice(&data)
+      .map_err(|error| Error::decode(pool_address, error))?;
+    check_supported_layout(pool_address, &decoded_layout)?;
+    self.apply_account_update(&pool_address, &data);
+    Ok(())
   }
 
-  fn price_b_over_a_lp(&self) -> u128 {
-    if self.token_a_amount_units() == 0 {
-      return 0;
+  fn apply_account_update(&mut self, address: &Pubkey, data: &[u8]) -> bool {
+    // Liquidity and prices live in the pool account, the vault balances are not used
+    if *address != self.info.pool_address {
+      return false;
+    }
+    let Ok(decoded_layout) = MeteoraDammv2PoolIdl::try_from_slice(data) else {
+      return false;
+    };
+    if check_supported_layout(*address, &decoded_layout).is_err() {
+      return false;
     }
-    self.token_b_amount_units() as u128 * LAMPORTS_PER_SOL / self.token_a_amount_units() as u128
-  }
-
-  fn fetch_market_state_from_rpc(&mut self, central_context: &Arc<CentralContext>) {
-    let decoded_layout: MeteoraDammv2PoolIdl = MeteoraDammv2PoolIdl::try_from_slice(
-      &central_context
-        .json_rpc_client
-        .get_account(&self.info.pool_address)
-        .unwrap()
-        .data,
-    )
-    .unwrap();
 
     self.cliff_fee_numerator = decoded_layout.pool_fees.base_fee.cliff_fee_numerator;
     self.base_fee_mode = decoded_layout.pool_fees.base_fee.base_fee_mode;
@@ -244,13 +312,14 @@ impl PoolTrait for MeteoraDammV2Pool {
     self.sqrt_max_price = decoded_layout.sqrt_max_price;
     self.sqrt_min_price = decoded_layout.sqrt_min_price;
     self.version = decoded_layout.version;
+    true
   }
 
   /**
   Returns the directional fees for a given swap direction as fractions.
   Meteora DAMMV2 charges fees on ONLY ONE token at a time:
   - In BothToken mode (collect_fee_mode=0): fees are charged on the OUTPUT token. It says this in
-  the program source code
+    the program source code
   - In OnlyB mode (collect_fee_mode=1): fees are always charged on token B (input or output)
   */
   fn directional_fees(
@@ -258,16 +327,31 @@ impl PoolTrait for MeteoraDammV2Pool {
     direction: crate::types::swap_direction::SwapDirection,
     central_context: &Arc<CentralContext>,
   ) -> (f64, f64) {
+    // Unknown collect fee modes are rejected when decoding, charge the fee on both tokens if one
+    // slips through so the fee is never underestimated
+    self
+      .try_directional_fees(direction, central_context)
+      .unwrap_or_else(|_| {
+        let fee_fraction = self.total_swap_fee_lp(central_context) as f64 / 1_000_000_000.0;
+        (fee_fraction, fee_fraction)
+      })
+  }
+
+  fn try_directional_fees(
+    &self,
+    direction: crate::types::swap_direction::SwapDirection,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<(f64, f64), Error> {
     use crate::types::swap_direction::SwapDirection;
 
     // Get total fee in lamports (10^9 = 1.0)
-    let total_fee_lp = self.total_swap_fee_lp(central_context);
+    let total_fee_lp = self.try_total_swap_fee_lp(central_context)?;
 
     // Convert lamports to fraction (divide by 10^9)
     let fee_fraction = total_fee_lp as f64 / 1_000_000_000.0;
 
     // CollectFeeMode::BothToken = 0, CollectFeeMode::OnlyB = 1
-    match (self.collect_fee_mode, direction) {
+    Ok(match (self.collect_fee_mode, direction) {
       // BothToken mode: fees on output token (see source code line 44: "only out token is collected")
       (0, SwapDirection::AToB) => {
         // A → B: fee on output (token B)
@@ -287,10 +371,31 @@ impl PoolTrait for MeteoraDammV2Pool {
         (0.0, fee_fraction)
       }
       // Invalid collect_fee_mode
-      _ => panic!(
-        "Invalid collect_fee_mode: {} for metoera dammv2: pool address: {}",
-        self.collect_fee_mode, self.info.pool_address
-      ),
+      _ => {
+        return Err(Error::unsupported_layout(
+          self.info.pool_address,
+          format!("unknown DAMMv2 collect fee mode {}", self.collect_fee_mode),
+        ));
+      }
+    })
+  }
+
+  fn try_quote(
+    &self,
+    amount_in: u64,
+    direction: crate::types::swap_direction::SwapDirection,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<u64, Error> {
+    use crate::types::swap_direction::SwapDirection;
+
+    let fee_lp = self.try_total_swap_fee_lp(central_context)?;
+    // OnlyB pools take the fee from token B, on the way in when selling it. Every other fee is
+    // taken from the output, see `try_directional_fees`
+    if self.collect_fee_mode == 1 && direction == SwapDirection::BToA {
+      self.calculate_swap_amount_out(deduct_fee(amount_in, fee_lp), direction)
+    } else {
+      let amount_out = self.calculate_swap_amount_out(amount_in, direction)?;
+      Ok(deduct_fee(amount_out, fee_lp))
     }
   }
 }
@@ -1,10 +1,12 @@
 use crate::CentralContext;
 use crate::constants::LAMPORTS_PER_SOL;
-use crate::protocol_idls::meteora::DbcVirtualPool;
-use crate::types::pool::{Pool, PoolTrait};
+use crate::protocol_idls::meteora::{DbcVirtualPool, LiquidityDistributionConfig};
+use crate::types::pool::{Pool, PoolTrait, deduct_fee};
+use crate::types::pool_lifecycle::PoolLifecycle;
 use crate::types::pools::Pools;
 use crate::types::swap_direction::SwapDirection;
-use borsh::BorshDeserialize;
+use crate::types::error::Error;
+use borsh::{BorshDeserialize, BorshSerialize};
 use primitive_types::U512;
 use solana_sdk::pubkey::Pubkey;
 use std::any::Any;
@@ -12,7 +14,6 @@ use std::cmp::min;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-const FEE_DENOMINATOR: u64 = 1_000_000_000;
 const MAX_FEE_NUMERATOR: u64 = 990_000_000;
 /// The denominator for the exponential fee reduction factor.
 const EXPONENTIAL_FEE_REDUCTION_DENOMINATOR: f64 = 10000.0;
@@ -22,12 +23,15 @@ const DYNAMIC_FEE_SCALING_DENOMINATOR: u128 = 100_000_000_000;
 /**
 The base fee on meteora dbc can be either a linear fee or an exponential fee.
 */
+#[derive(BorshSerialize, BorshDeserialize)]
+#[borsh(use_discriminant = true)]
 pub enum BaseFeeMode {
   Linear = 0,
   Exponential = 1,
   RateLimiter = 2,
 }
 
+#[derive(BorshSerialize, BorshDeserialize)]
 pub enum ActivationType {
   Slot,
   Time,
@@ -36,6 +40,7 @@ pub enum ActivationType {
 // Like in all other protocols token A is base and token B is quote.
 // TODO use our own sol token account in the referral fee if we are trading with sol
 /// Struct to hold data and PoolTrait implementation for Meteora Dynamic Bonding Curve pools.
+#[derive(BorshSerialize, BorshDeserialize)]
 pub struct MeteoraDbc {
   pub pool: Pool,
   pub config: Pubkey,
@@ -72,6 +77,19 @@ pub struct MeteoraDbc {
   pub base_fee_reduction_factor: u64,
   pub base_fee_period_frequency: u64,
   pub base_fee_number_of_periods: u16,
+  /// Destination of the migration from the config: 0 DAMM v1 (`MeteoraAmmPool`), 1 DAMMv2
+  pub migration_option: u8,
+  /// 0 PreBondingCurve, 1 PostBondingCurve, 2 LockedVesting, 3 CreatedPool
+  pub migration_progress: u8,
+  /// Whether the liquidity has moved to the destination AMM pool
+  pub is_migrated: bool,
+  /// How the fee is collected, from the config: 0 in the quote token, 1 in the output token
+  pub collect_fee_mode: u8,
+  /// Lowest sqrt price of the curve, from the config
+  pub sqrt_start_price: u128,
+  /// Points of the config's price curve by ascending sqrt price. The liquidity of a point applies
+  /// from the previous point's sqrt price (`sqrt_start_price` for the first point) up to its own.
+  pub curve: Vec<LiquidityDistributionConfig>,
 }
 
 impl PoolTrait for MeteoraDbc {
@@ -98,6 +116,52 @@ impl PoolTrait for MeteoraDbc {
   fn pool_type(&self) -> &Pools {
     &Pools::MeteoraDbc
   }
+  fn last_updated_slot(&self) -> u64 {
+    self.pool.last_updated_slot
+  }
+  fn set_last_updated_slot(&mut self, slot: u64) {
+    self.pool.last_updated_slot = slot;
+  }
+  fn state_fields(&self) -> Vec<(&'static str, u128)> {
+    vec![
+      ("sqrt_price", self.sqrt_price),
+      ("base_reserve", self.base_reserve as u128),
+      ("quote_reserve", self.quote_reserve as u128),
+      ("cliff_fee_numerator", self.cliff_fee_numerator as u128),
+      ("activation_point", self.activation_point as u128),
+      ("volatility_accumulator", self.volatility_accumulator),
+      ("variable_fee_control", self.variable_fee_control as u128),
+      ("migration_progress", self.migration_progress as u128),
+      ("is_migrated", self.is_migrated as u128),
+    ]
+  }
+  fn lifecycle(&self, current_slot: u64) -> PoolLifecycle {
+    let current_point = match self.activation_type {
+      ActivationType::Slot => current_slot,
+      ActivationType::Time => SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs(),
+    };
+    if self.is_migrated {
+      PoolLifecycle::Migrated
+    } else if self.migration_progress > 0 {
+      // Past PreBondingCurve, the curve is complete and the pool is waiting to migrate
+      PoolLifecycle::Completed
+    } else if current_point < self.activation_point {
+      PoolLifecycle::PendingActivation
+    } else {
+      PoolLifecycle::Active
+    }
+  }
+  fn graduation_successor(&self) -> Option<(Pools, Option<Pubkey>)> {
+    // The DAMM pool address depends on the migration fee config, so it isn't derived here
+    match self.migration_option {
+      0 => Some((Pools::MeteoraAmm, None)),
+      1 => Some((Pools::MeteoraDammV2, None)),
+      _ => None,
+    }
+  }
 
   /**
   Calculates the total trading fee numerator.
@@ -127,10 +191,11 @@ impl PoolTrait for MeteoraDbc {
 
     let base_fee_numerator = match self.base_fee_mode {
       BaseFeeMode::Linear => {
-        if period * self.base_fee_reduction_factor >= self.cliff_fee_numerator {
+        let reduction = period.saturating_mul(self.base_fee_reduction_factor);
+        if reduction >= self.cliff_fee_numerator {
           return 0;
         }
-        self.cliff_fee_numerator - (period * self.base_fee_reduction_factor)
+        self.cliff_fee_numerator - reduction
       }
       BaseFeeMode::Exponential => {
         // Use f64 for this as it typically will not get larger than cliff fee numerator
@@ -146,18 +211,21 @@ impl PoolTrait for MeteoraDbc {
       _ => self.cliff_fee_numerator,
     };
 
-    // Dynamic fee calculations
-    let dynamic_fee_numerator = ((U512::from(self.volatility_accumulator).pow(U512::from(2))
-      * U512::from(self.variable_fee_control))
-      / U512::from(DYNAMIC_FEE_SCALING_DENOMINATOR))
+    // Dynamic fee calculations, capped before the conversion as extreme volatility doesn't fit in
+    // a u64
+    let dynamic_fee_numerator = min(
+      (U512::from(self.volatility_accumulator).pow(U512::from(2))
+        * U512::from(self.variable_fee_control))
+        / U512::from(DYNAMIC_FEE_SCALING_DENOMINATOR),
+      U512::from(MAX_FEE_NUMERATOR),
+    )
     .as_u64();
 
     // Cap at max fee numerator
-    let total_fee_numerator = min(
-      base_fee_numerator + dynamic_fee_numerator,
+    min(
+      base_fee_numerator.saturating_add(dynamic_fee_numerator),
       MAX_FEE_NUMERATOR,
-    );
-    total_fee_numerator
+    )
   }
 
   fn as_any(&self) -> &dyn Any {
@@ -169,43 +237,64 @@ impl PoolTrait for MeteoraDbc {
   }
 
   /**
-  The reciprocal of the price_b_over_a_lp.
+  The reciprocal of the price_b_over_a_lp. Undefined for a zero sqrt price.
   */
-  fn price_a_over_b_lp(&self) -> u128 {
+  fn try_price_a_over_b_lp(&self) -> Result<u128, Error> {
     let price = U512::from(self.sqrt_price).pow(U512::from(2));
     let two_pow_128 = U512::from(1) << 128; // Represents (2^64)^2
-    (two_pow_128 * U512::from(LAMPORTS_PER_SOL) / price).as_u128()
+    (two_pow_128 * U512::from(LAMPORTS_PER_SOL))
+      .checked_div(price)
+      .and_then(|price| u128::try_from(price).ok())
+      .ok_or(Error::Arithmetic {
+        operation: "price_a_over_b_lp",
+      })
   }
 
   /**
   The sqrt price of the protocol will be in terms of quote over base which for us is b over a.
   */
-  fn price_b_over_a_lp(&self) -> u128 {
+  fn try_price_b_over_a_lp(&self) -> Result<u128, Error> {
     let price = U512::from(self.sqrt_price).pow(U512::from(2));
     let two_pow_128 = U512::from(1) << 128; // Represents (2^64)^2
-    (price * U512::from(LAMPORTS_PER_SOL) / two_pow_128).as_u128()
+    u128::try_from(price * U512::from(LAMPORTS_PER_SOL) / two_pow_128).map_err(|_| {
+      Error::Arithmetic {
+        operation: "price_b_over_a_lp",
+      }
+    })
   }
 
   // TODO implement these 3
-  fn fetch_market_state_from_rpc(&mut self, central_context: &Arc<CentralContext>) {
-    // Fetch current pool state from RPC
-    let pool_account = central_context
-      .json_rpc_client
-      .get_account(&self.pool.pool_address)
-      .expect("Failed to fetch pool account");
+  fn try_fetch_market_state_from_rpc(
+    &mut self,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<(), Error> {
+    let pool_address = self.pool.pool_address;
+    let data = central_context.json_rpc_client.get_account(&pool_address)?.data;
+    if !self.apply_account_update(&pool_address, &data) {
+      return Err(Error::decode(pool_address, "account doesn't match the pool layout"));
+    }
+    Ok(())
+  }
 
-    // Deserialize the fresh pool data
-    let dbc_virtual_pool = DbcVirtualPool::try_from_slice(&pool_account.data)
-      .expect("Failed to deserialize DbcVirtualPool");
+  fn apply_account_update(&mut self, address: &Pubkey, data: &[u8]) -> bool {
+    if *address != self.pool.pool_address {
+      return false;
+    }
+    let Ok(dbc_virtual_pool) = DbcVirtualPool::try_from_slice(data) else {
+      return false;
+    };
 
     // Update all dynamic pool state fields with fresh on-chain data
     self.sqrt_price = dbc_virtual_pool.sqrt_price;
     self.base_reserve = dbc_virtual_pool.base_reserve;
     self.quote_reserve = dbc_virtual_pool.quote_reserve;
     self.volatility_accumulator = dbc_virtual_pool.volatility_tracker.volatility_accumulator;
+    self.migration_progress = dbc_virtual_pool.migration_progress;
+    self.is_migrated = dbc_virtual_pool.is_migrated != 0;
 
     // Note: activation_point is static config, so we don't update it
     // Note: Fee config fields (cliff_fee_numerator, etc.) come from DbcPoolConfig, not DbcVirtualPool
+    true
   }
 
   fn token_a_amount_units(&self) -> u64 {
@@ -215,7 +304,29 @@ impl PoolTrait for MeteoraDbc {
     self.quote_reserve
   }
 
-  fn directional_fees(&self, _: SwapDirection, __: &Arc<CentralContext>) -> (f64, f64) {
+  fn directional_fees(&self, _: SwapDirection, _central_context: &Arc<CentralContext>) -> (f64, f64) {
     (1.0, 1.0)
   }
+
+  fn try_quote(
+    &self,
+    amount_in: u64,
+    direction: SwapDirection,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<u64, Error> {
+    if self.is_migrated || self.migration_progress > 0 {
+      return Err(Error::InvalidPool {
+        pool: self.pool.pool_address,
+        reason: "the bonding curve is complete".to_string(),
+      });
+    }
+    let fee_lp = self.total_swap_fee_lp(central_context);
+    // Quote token fees are taken from the input on buys, every other fee from the output
+    if self.collect_fee_mode == 0 && direction == SwapDirection::BToA {
+      self.calculate_swap_amount_out(deduct_fee(amount_in, fee_lp), direction)
+    } else {
+      let amount_out = self.calculate_swap_amount_out(amount_in, direction)?;
+      Ok(deduct_fee(amount_out, fee_lp))
+    }
+  }
 }
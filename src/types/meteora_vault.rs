@@ -1,8 +1,9 @@
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_sdk::pubkey::Pubkey;
 
 /// Struct to hold data for Meteora vaults, which are used in Meteora AMMv1 and potentially Meteora
 /// DLMM pools.
+#[derive(BorshSerialize, BorshDeserialize)]
 pub struct MeteoraVault {
   // shows up as aVault/ A Vault
   pub vault: Pubkey,
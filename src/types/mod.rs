@@ -10,16 +10,38 @@ pub mod meteora_amm_pool;
 pub mod meteora_dammv2_pool;
 pub mod meteora_vault;
 pub mod pool;
+pub mod pool_account_field;
+pub mod pool_lifecycle;
+pub mod pool_load_spec;
 pub mod pools;
 pub mod pumpswap_pool;
 pub mod raydium_ammv4_pool;
 pub mod raydium_cpmm_pool;
 pub mod swap_direction;
+pub mod sweep_policy;
 pub mod pf_bonding_curve;
 pub mod swap_tx;
 pub mod market_update;
+pub mod market_subscription;
+pub mod market_update_subscriber;
+pub mod backpressure_policy;
 pub mod meteora_dbc;
 pub mod raydium_launchpad;
 pub mod instruction;
 pub mod link;
 pub mod token_creation;
+pub mod decoded_transaction;
+pub mod graduation;
+pub mod field_mismatch;
+pub mod pool_audit;
+pub mod failover_rpc_sender;
+pub mod retry_policy;
+pub mod rpc_backend;
+pub mod backend_rpc_sender;
+pub mod fixture_rpc_backend;
+pub mod error;
+pub mod pair_filter;
+pub mod pair_ranking;
+pub mod pool_snapshot;
+pub mod token_metadata;
+pub mod pending_dbc_pool;
@@ -0,0 +1,20 @@
+use crate::types::pair_ranking::PairRanking;
+use crate::types::pool_lifecycle::PoolLifecycle;
+use crate::types::pools::Pools;
+
+/// Which pools `CentralContext::pools_for_pair` returns and in which order. The default keeps every
+/// hydrated pool of the pair and ranks them by deepest liquidity.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PairFilter {
+  /// Minimum reserve of the first token of the pair, in token units
+  pub min_liquidity: u64,
+  /// Allowed pool types, `None` allows every type
+  pub pool_types: Option<Vec<Pools>>,
+  /// Allowed lifecycle states (see `CentralContext::get_pool_lifecycle`), `None` allows every
+  /// state. Use `Some(vec![PoolLifecycle::Active])` to only get tradeable pools.
+  pub lifecycles: Option<Vec<PoolLifecycle>>,
+  /// Maximum `total_swap_fee_lp`, `None` allows any fee. Pools whose fee is unknown are excluded
+  /// when set.
+  pub max_fee_lp: Option<u64>,
+  pub ranking: PairRanking,
+}
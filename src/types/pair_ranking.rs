@@ -0,0 +1,12 @@
+/// How `CentralContext::pools_for_pair` orders the pools of a pair, best first
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PairRanking {
+  /// Most of the second token received for selling `amount_in` units of the first token, see
+  /// `PoolSnapshot::amount_out`. Pools that can't quote come last.
+  BestPrice { amount_in: u64 },
+  /// Largest reserve of the first token
+  #[default]
+  DeepestLiquidity,
+  /// Lowest `total_swap_fee_lp`, pools whose fee is unknown last
+  LowestFee,
+}
@@ -0,0 +1,54 @@
+use crate::types::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+/// A Meteora DBC pool initialize instruction seen before its config was cached, kept with owned
+/// data until `CentralContext::load_dbc_pool_configs` loads the config and builds the pool
+pub(crate) struct PendingDbcPool {
+  pub tx_account_keys: Vec<Pubkey>,
+  pub accounts: Vec<u8>,
+  pub data: Vec<u8>,
+  pub program_id_index: u8,
+  pub stack_height: u32,
+  pub block_time: u64,
+  pub slot: u64,
+  pub index: u64,
+  pub atomic_instruction_index: u8,
+  pub signature: Signature,
+}
+
+impl PendingDbcPool {
+  /// Copy the instruction and its position in the chain
+  pub fn new(
+    instruction: &Instruction,
+    block_time: u64,
+    slot: u64,
+    index: u64,
+    atomic_instruction_index: u8,
+    signature: Signature,
+  ) -> Self {
+    Self {
+      tx_account_keys: instruction.tx_account_keys.to_vec(),
+      accounts: instruction.accounts.to_vec(),
+      data: instruction.data.to_vec(),
+      program_id_index: instruction.program_id_index,
+      stack_height: instruction.stack_height,
+      block_time,
+      slot,
+      index,
+      atomic_instruction_index,
+      signature,
+    }
+  }
+
+  /// Borrow the stored instruction
+  pub fn instruction(&self) -> Instruction<'_> {
+    Instruction {
+      tx_account_keys: &self.tx_account_keys,
+      accounts: &self.accounts,
+      data: &self.data,
+      program_id_index: self.program_id_index,
+      stack_height: self.stack_height,
+    }
+  }
+}
@@ -1,17 +1,19 @@
 use crate::CentralContext;
-use crate::constants::LAMPORTS_PER_SOL;
 use crate::constants::PUMP_CONSTANTS;
 use crate::constants::TOKENS;
 use crate::protocol_idls::pumpfun::PfBondingCurveIdl;
-use crate::types::pool::PoolTrait;
+use crate::types::pool::{PoolTrait, constant_product_quote, reserve_price};
+use crate::types::pool_lifecycle::PoolLifecycle;
 use crate::types::pools::Pools;
+use crate::types::pumpswap_pool::PumpswapPool;
 use crate::types::swap_direction::SwapDirection;
-use borsh::BorshDeserialize;
+use crate::types::error::Error;
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_sdk::pubkey::Pubkey;
 use std::any::Any;
 use std::sync::Arc;
 
-#[derive(Debug)]
+#[derive(Debug, BorshSerialize, BorshDeserialize)]
 /// Struct to hold data and PoolTrait implementation for Pumpfun Bonding Curve pools/markets.
 pub struct PfBondingCurve {
   pub virtual_sol_reserves: u64,
@@ -22,6 +24,8 @@ pub struct PfBondingCurve {
   pub bonding_curve_associated_token_account_address: Pubkey,
   // Pubkey::find_program_address(&[b"creator-vault", creator.as_array()], &PUMP_CONSTANTS.bonding_curve_program,);
   pub creator_vault_address: Pubkey,
+  // Slot of the newest state applied to this bonding curve, 0 if unknown
+  pub last_updated_slot: u64,
 }
 
 impl PoolTrait for PfBondingCurve {
@@ -43,6 +47,32 @@ impl PoolTrait for PfBondingCurve {
   fn pool_type(&self) -> &Pools {
     &Pools::PfBondingCurve
   }
+  fn last_updated_slot(&self) -> u64 {
+    self.last_updated_slot
+  }
+  fn set_last_updated_slot(&mut self, slot: u64) {
+    self.last_updated_slot = slot;
+  }
+  fn state_fields(&self) -> Vec<(&'static str, u128)> {
+    vec![
+      ("virtual_sol_reserves", self.virtual_sol_reserves as u128),
+      ("virtual_token_reserves", self.virtual_token_reserves as u128),
+      ("complete", self.complete as u128),
+    ]
+  }
+  fn lifecycle(&self, _current_slot: u64) -> PoolLifecycle {
+    if self.complete {
+      PoolLifecycle::Completed
+    } else {
+      PoolLifecycle::Active
+    }
+  }
+  fn graduation_successor(&self) -> Option<(Pools, Option<Pubkey>)> {
+    Some((
+      Pools::PumpswapAmm,
+      PumpswapPool::derive_canonical_pool(&self.token_address).ok(),
+    ))
+  }
   /*
   Never going to call this likely but the fee is 1.25% with 0.95% protocol fee and 0.30% creator fee
   */
@@ -57,44 +87,96 @@ impl PoolTrait for PfBondingCurve {
     self
   }
 
-  fn price_a_over_b_lp(&self) -> u128 {
-    self.virtual_token_reserves as u128 * LAMPORTS_PER_SOL / self.virtual_sol_reserves as u128
+  fn try_price_a_over_b_lp(&self) -> Result<u128, Error> {
+    reserve_price(
+      self.virtual_token_reserves,
+      self.virtual_sol_reserves,
+      "price_a_over_b_lp",
+    )
   }
 
-  fn price_b_over_a_lp(&self) -> u128 {
-    self.virtual_sol_reserves as u128 * LAMPORTS_PER_SOL / self.virtual_token_reserves as u128
+  fn try_price_b_over_a_lp(&self) -> Result<u128, Error> {
+    reserve_price(
+      self.virtual_sol_reserves,
+      self.virtual_token_reserves,
+      "price_b_over_a_lp",
+    )
   }
 
-  fn fetch_market_state_from_rpc(&mut self, central_context: &Arc<CentralContext>) {
-    // Fetch data from json rpc before calling updater
-    let data = central_context
-      .json_rpc_client
-      .get_account(&self.bonding_curve_address)
-      .unwrap()
-      .data;
+  fn try_fetch_market_state_from_rpc(
+    &mut self,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<(), Error> {
+    let bonding_curve_address = self.bonding_curve_address;
+    let data = central_context.json_rpc_client.get_account(&bonding_curve_address)?.data;
+    if !self.apply_account_update(&bonding_curve_address, &data) {
+      return Err(Error::decode(bonding_curve_address, "account doesn't match the pool layout"));
+    }
+    Ok(())
+  }
 
+  fn apply_account_update(&mut self, address: &Pubkey, data: &[u8]) -> bool {
+    // Reserves are virtual and tracked in the bonding curve account, not the token account
+    if *address != self.bonding_curve_address || data.len() < 150 {
+      return false;
+    }
     // Deserialize the account buffer into a BondingCurveAccount
-    let decoded_layout: PfBondingCurveIdl = PfBondingCurveIdl::try_from_slice(&data[..150]).unwrap();
+    let Ok(decoded_layout) = PfBondingCurveIdl::try_from_slice(&data[..150]) else {
+      return false;
+    };
     // Update the bonding curve state
     self.virtual_sol_reserves = decoded_layout.virtual_sol_reserves;
     self.virtual_token_reserves = decoded_layout.virtual_token_reserves;
     self.complete = decoded_layout.complete;
+    true
   }
   /**
   This will get real token reserves metric
   */
   fn token_a_amount_units(&self) -> u64 {
-    self.virtual_token_reserves - PUMP_CONSTANTS.bc_init_virtual_token_reserve_diff
+    self
+      .virtual_token_reserves
+      .saturating_sub(PUMP_CONSTANTS.bc_init_virtual_token_reserve_diff)
   }
 
   /**
   This will get real sol reserves metric
   */
   fn token_b_amount_units(&self) -> u64 {
-    self.virtual_sol_reserves - PUMP_CONSTANTS.bc_init_virtual_sol_reserves
+    self
+      .virtual_sol_reserves
+      .saturating_sub(PUMP_CONSTANTS.bc_init_virtual_sol_reserves)
   }
 
-  fn directional_fees(&self, _: SwapDirection, __: &Arc<CentralContext>) -> (f64, f64) {
+  fn directional_fees(&self, _: SwapDirection, _central_context: &Arc<CentralContext>) -> (f64, f64) {
     (1.0, 1.0)
   }
+
+  fn try_quote(
+    &self,
+    amount_in: u64,
+    direction: SwapDirection,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<u64, Error> {
+    if self.complete {
+      return Err(Error::InvalidPool {
+        pool: self.bonding_curve_address,
+        reason: "the bonding curve is complete".to_string(),
+      });
+    }
+    // Constant product on the virtual reserves, capped at the real reserves. The fee is taken in
+    // SOL, from the input on buys and the output on sells
+    let fee_lp = self.total_swap_fee_lp(central_context);
+    let (virtual_token, virtual_sol) = (self.virtual_token_reserves, self.virtual_sol_reserves);
+    match direction {
+      SwapDirection::AToB => Ok(
+        constant_product_quote(amount_in, virtual_token, virtual_sol, 0, fee_lp)?
+          .min(self.token_b_amount_units()),
+      ),
+      SwapDirection::BToA => Ok(
+        constant_product_quote(amount_in, virtual_sol, virtual_token, fee_lp, 0)?
+          .min(self.token_a_amount_units()),
+      ),
+    }
+  }
 }
@@ -1,6 +1,11 @@
 use crate::CentralContext;
+use crate::constants::LAMPORTS_PER_SOL;
+use crate::types::error::Error;
+use crate::types::market_update::MarketUpdate;
+use crate::types::pool_lifecycle::PoolLifecycle;
 use crate::types::pools::Pools;
 use crate::types::swap_direction::SwapDirection;
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_sdk::pubkey::Pubkey;
 use std::any::Any;
 use std::sync::Arc;
@@ -8,7 +13,7 @@ use std::sync::Arc;
 /// Base pool information shared by all pool types. Contains the essential addresses and
 /// identifiers that every pool implementation requires. Protocol-specific pool structs will embed
 /// this as an `info` field.
-#[derive(Debug)]
+#[derive(Debug, BorshSerialize, BorshDeserialize)]
 pub struct Pool {
   pub pool_address: Pubkey,
   pub token_a_address: Pubkey,
@@ -16,6 +21,10 @@ pub struct Pool {
   pub token_a_vault_address: Pubkey,
   pub token_b_vault_address: Pubkey,
   pub pool_type: Pools,
+  /// Slot of the newest state applied to the pool, 0 if unknown
+  pub last_updated_slot: u64,
+  /// Whether the reserves have been loaded, see `PoolTrait::is_hydrated`
+  pub hydrated: bool,
 }
 
 /// Trait for all pool types, providing a unified interface for DEX pools. All pools implement this
@@ -34,10 +43,53 @@ pub trait PoolTrait: Any + Send + Sync {
   fn token_b_vault_address(&self) -> &Pubkey;
   /// Get the pool type enum variant
   fn pool_type(&self) -> &Pools;
+  /// Get the slot of the newest state applied to the pool: the newest account update applied
+  /// through the context (`apply_account_update`, `refresh_pools`,
+  /// `fetch_market_state_from_rpc_async`), or the creation slot for pools built from instructions.
+  /// 0 if unknown. Strategies can use this to skip pools with stale state.
+  fn last_updated_slot(&self) -> u64;
+  /// Set the slot of the newest account update applied to the pool
+  fn set_last_updated_slot(&mut self, slot: u64);
+  /// Check if the pool's reserves have been loaded. Pools whose reserves live in other accounts
+  /// (AMMv4, CPMM and Pumpswap vault balances, Meteora vaults and LP accounts) are not hydrated
+  /// when built from their pool account alone, their reserves are 0 until every dependent account
+  /// is fetched, e.g. with `CentralContext::refresh_pools`. Never query prices on unhydrated pools.
+  /// Defaults to true for pools whose account holds the reserves.
+  fn is_hydrated(&self) -> bool {
+    true
+  }
+  /// Mark the pool's reserves as loaded. No-op for pools that are always hydrated.
+  fn set_hydrated(&mut self) {}
+  /// The state decoded from the pool's accounts as named integers (bools are 0 or 1), used by
+  /// `CentralContext::audit_pools` to diff the pool against chain state
+  fn state_fields(&self) -> Vec<(&'static str, u128)> {
+    Vec::new()
+  }
   
+  /// Get the lifecycle state derived from the pool's own state. Defaults to `Active` for pools
+  /// without activation or migration state. Closed accounts are tracked by the context, see
+  /// `CentralContext::get_pool_lifecycle`.
+  /// * `current_slot` - Needed for pools activating at a slot
+  fn lifecycle(&self, _current_slot: u64) -> PoolLifecycle {
+    PoolLifecycle::Active
+  }
+
+  /// Get the type of AMM pool a bonding curve graduates to, and the successor pool's address if it
+  /// can be derived from the curve. `None` for pools that don't graduate. See
+  /// `CentralContext::graduations`.
+  fn graduation_successor(&self) -> Option<(Pools, Option<Pubkey>)> {
+    None
+  }
+
   /// Get the total swap fee in lamports (10^9 lamports = 1 SOL)
   fn total_swap_fee_lp(&self, central_context: &Arc<CentralContext>) -> u64;
 
+  /// Fallible version of `total_swap_fee_lp`, for pools whose fee depends on state the library
+  /// may not support. Defaults to `total_swap_fee_lp`.
+  fn try_total_swap_fee_lp(&self, central_context: &Arc<CentralContext>) -> Result<u64, Error> {
+    Ok(self.total_swap_fee_lp(central_context))
+  }
+
   /// Get a reference to the pool as `Any` for type downcasting
   fn as_any(&self) -> &dyn Any;
   /// Get a mutable reference to the pool as `Any` for type downcasting
@@ -45,17 +97,90 @@ pub trait PoolTrait: Any + Send + Sync {
 
   /// Calculate the price of token A in terms of token B. Fx ticker equivalent: B/A. Returns how
   /// many units of token A are needed to buy 1 unit of token B, in lamports. For AMMs, typically
-  /// calculated as: (A reserves * LAMPORTS_PER_SOL) / B reserves
-  fn price_a_over_b_lp(&self) -> u128;
+  /// calculated as: (A reserves * LAMPORTS_PER_SOL) / B reserves. Returns 0 when the price is
+  /// undefined (e.g. empty pool), which can't be told apart from a price below 1 lamport. Use
+  /// `try_price_a_over_b_lp` where the difference matters.
+  fn price_a_over_b_lp(&self) -> u128 {
+    self.try_price_a_over_b_lp().unwrap_or(0)
+  }
 
   /// Calculate the price of token B in terms of token A. Fx ticker equivalent: A/B. Returns how
   /// many units of token A are needed to buy 1 unit of token B, in lamports. For AMMs, typically
   /// calculated as: (B reserves * LAMPORTS_PER_SOL) / A reserves. Inverse of `price_a_over_b_lp`.
-  fn price_b_over_a_lp(&self) -> u128;
+  /// Returns 0 when the price is undefined (e.g. empty pool), like `price_a_over_b_lp`. See
+  /// `try_price_b_over_a_lp`.
+  fn price_b_over_a_lp(&self) -> u128 {
+    self.try_price_b_over_a_lp().unwrap_or(0)
+  }
+
+  /// Same as `price_a_over_b_lp` but fails with `Error::Arithmetic` when the price is undefined or
+  /// doesn't fit in a u128. Defaults to the ratio of `token_a_amount_units` over
+  /// `token_b_amount_units`.
+  fn try_price_a_over_b_lp(&self) -> Result<u128, Error> {
+    reserve_price(
+      self.token_a_amount_units(),
+      self.token_b_amount_units(),
+      "price_a_over_b_lp",
+    )
+  }
+
+  /// Same as `price_b_over_a_lp` but fails with `Error::Arithmetic` when the price is undefined or
+  /// doesn't fit in a u128. Defaults to the ratio of `token_b_amount_units` over
+  /// `token_a_amount_units`.
+  fn try_price_b_over_a_lp(&self) -> Result<u128, Error> {
+    reserve_price(
+      self.token_b_amount_units(),
+      self.token_a_amount_units(),
+      "price_b_over_a_lp",
+    )
+  }
 
   /// Fetch and update pool state from JSON RPC and immediately overrides the in-memory pool state.
-  /// Should not be used in production in favor of using gRPC streams.
-  fn fetch_market_state_from_rpc(&mut self, central_context: &Arc<CentralContext>);
+  /// Should not be used in production in favor of using gRPC streams. To refresh many pools at once
+  /// use `CentralContext::refresh_pools`, which batches the RPC calls. A `MarketUpdate` is
+  /// published through `central_context` if the prices or reserves changed. Errors are logged, see
+  /// `try_fetch_market_state_from_rpc` to handle them.
+  fn fetch_market_state_from_rpc(&mut self, central_context: &Arc<CentralContext>) {
+    let previous_update = MarketUpdate::from_pool(self);
+    if let Err(error) = self.try_fetch_market_state_from_rpc(central_context) {
+      println!(
+        "fetch_market_state_from_rpc: Failed to fetch pool {}: {}",
+        self.pool_address(),
+        error
+      );
+      return;
+    }
+    if let Some(update) = MarketUpdate::from_pool(self)
+      && previous_update.as_ref() != Some(&update)
+    {
+      central_context.publish_market_update(&update);
+    }
+  }
+
+  /// Fallible version of `fetch_market_state_from_rpc`. Returns an error if an RPC request fails or
+  /// a fetched account can't be decoded, in which case the pool may be partially updated. Doesn't
+  /// publish a `MarketUpdate`, use `fetch_market_state_from_rpc` or
+  /// `CentralContext::publish_market_update` for that.
+  fn try_fetch_market_state_from_rpc(
+    &mut self,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<(), Error>;
+
+  /// Get every account whose on-chain data the pool state is derived from. These are the keys the
+  /// pool is registered under in `CentralContext::pools_map`. Defaults to the pool address and
+  /// both vault addresses.
+  fn dependent_accounts(&self) -> Vec<Pubkey> {
+    vec![
+      *self.pool_address(),
+      *self.token_a_vault_address(),
+      *self.token_b_vault_address(),
+    ]
+  }
+
+  /// Decode the raw data of one of the `dependent_accounts` (e.g. from a gRPC or websocket
+  /// account stream) and update only the fields derived from it. Returns false if the account is
+  /// not one the pool reads state from or the data could not be decoded.
+  fn apply_account_update(&mut self, address: &Pubkey, data: &[u8]) -> bool;
 
   /// Get the actual amount of token A in the pool, in token units. This is calculated dynamically
   /// because some protocols (like Meteora) derive real token balances from LP token balances
@@ -73,10 +198,85 @@ pub trait PoolTrait: Any + Send + Sync {
   /// (e.g., 0.003 = 0.3%). The fees may differ based on swap direction for protocols
   /// with asymmetric fee structures.
   /// * `central_context` with updated current slot value - Needed for time-based fee calculations
-  /// in Meteora DAMMv2 and DBC
+  ///   in Meteora DAMMv2 and DBC
   fn directional_fees(
     &self,
     direction: SwapDirection,
     central_context: &Arc<CentralContext>,
   ) -> (f64, f64);
+
+  /// Fallible version of `directional_fees`. Defaults to `directional_fees`.
+  fn try_directional_fees(
+    &self,
+    direction: SwapDirection,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<(f64, f64), Error> {
+    Ok(self.directional_fees(direction, central_context))
+  }
+
+  /// Get the amount of the output token received for swapping `amount_in` units of the input token
+  /// in `direction`, after fees. Simulated on the pool's own curve from its in-memory state:
+  /// constant product for AMMs and bonding curves on their virtual reserves, the concentrated
+  /// liquidity range of DAMMv2 pools and the price curve of DBC pools. Swaps running past the end
+  /// of a curve or range are filled up to it. Returns 0 when the pool can't quote, see
+  /// `try_quote`.
+  /// * `central_context` with updated current slot value - Needed for time-based fee calculations
+  ///   in Meteora DAMMv2 and DBC
+  fn quote(
+    &self,
+    amount_in: u64,
+    direction: SwapDirection,
+    central_context: &Arc<CentralContext>,
+  ) -> u64 {
+    self
+      .try_quote(amount_in, direction, central_context)
+      .unwrap_or(0)
+  }
+
+  /// Fallible version of `quote`. Fails with `Error::Arithmetic` on empty reserves and
+  /// `Error::InvalidPool` for pools that can't be traded, e.g. completed bonding curves, or whose
+  /// swap fee is unknown (see `try_total_swap_fee_lp`).
+  fn try_quote(
+    &self,
+    amount_in: u64,
+    direction: SwapDirection,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<u64, Error>;
+}
+
+/// Output of a constant product swap of `amount_in` against `reserve_in` and `reserve_out`, with a
+/// fee of `fee_in_lp` lamports per SOL taken from the input and `fee_out_lp` from the output. Fails
+/// on empty reserves.
+pub(crate) fn constant_product_quote(
+  amount_in: u64,
+  reserve_in: u64,
+  reserve_out: u64,
+  fee_in_lp: u64,
+  fee_out_lp: u64,
+) -> Result<u64, Error> {
+  if reserve_in == 0 || reserve_out == 0 {
+    return Err(Error::Arithmetic { operation: "quote" });
+  }
+  let amount_in = deduct_fee(amount_in, fee_in_lp) as u128;
+  // Below reserve_out, fits in a u64
+  let amount_out = reserve_out as u128 * amount_in / (reserve_in as u128 + amount_in);
+  Ok(deduct_fee(amount_out as u64, fee_out_lp))
+}
+
+/// `amount` minus a fee of `fee_lp` lamports per SOL of it, 0 for fees of 100% or more
+pub(crate) fn deduct_fee(amount: u64, fee_lp: u64) -> u64 {
+  (amount as u128 * LAMPORTS_PER_SOL.saturating_sub(fee_lp as u128) / LAMPORTS_PER_SOL) as u64
+}
+
+/// Price of `numerator` reserves over `denominator` reserves in lamports, i.e.
+/// `numerator * LAMPORTS_PER_SOL / denominator`. Fails on empty `denominator` reserves.
+pub(crate) fn reserve_price(
+  numerator: u64,
+  denominator: u64,
+  operation: &'static str,
+) -> Result<u128, Error> {
+  (numerator as u128)
+    .checked_mul(LAMPORTS_PER_SOL)
+    .and_then(|amount| amount.checked_div(denominator as u128))
+    .ok_or(Error::Arithmetic { operation })
 }
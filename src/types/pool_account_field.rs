@@ -0,0 +1,53 @@
+use crate::protocol_idls::meteora::{DbcVirtualPool, MeteoraAmmPoolIdl, MeteoraDammv2PoolIdl};
+use crate::protocol_idls::pumpswap::PumpAmmPoolAccount;
+use crate::protocol_idls::raydium::{AmmV4PoolInfoIdl, CpmmPoolInfoIdl, LaunchpadPoolIdl};
+use crate::types::pools::Pools;
+use solana_account_decoder::UiDataSliceConfig;
+
+/// Pubkey fields of pool accounts that a `PoolLoadSpec` can filter on or slice. Offsets come from
+/// the protocol IDL structs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolAccountField {
+  /// Token A mint, in the same orientation as the pool types: base mint for Pumpswap, AMMv4,
+  /// launchpads and DBC, token 0 for CPMM
+  TokenAMint,
+  /// Token B mint, quote mint or token 1. Not stored in DBC pools, it is in their config.
+  TokenBMint,
+  /// Config the pool was created with: CPMM `amm_config`, DBC `config` and launchpad
+  /// `platform_config`
+  Config,
+}
+
+impl PoolAccountField {
+  /// Get the byte offset of the field in the pool account of `pool_type`, `None` if the account
+  /// doesn't have the field or the pool type can't be loaded
+  pub fn offset(&self, pool_type: Pools) -> Option<usize> {
+    match (pool_type, self) {
+      (Pools::RaydiumAmmV4, Self::TokenAMint) => Some(AmmV4PoolInfoIdl::BASE_MINT_OFFSET),
+      (Pools::RaydiumAmmV4, Self::TokenBMint) => Some(AmmV4PoolInfoIdl::QUOTE_MINT_OFFSET),
+      (Pools::RaydiumCpmm, Self::TokenAMint) => Some(CpmmPoolInfoIdl::TOKEN_0_MINT_OFFSET),
+      (Pools::RaydiumCpmm, Self::TokenBMint) => Some(CpmmPoolInfoIdl::TOKEN_1_MINT_OFFSET),
+      (Pools::RaydiumCpmm, Self::Config) => Some(CpmmPoolInfoIdl::AMM_CONFIG_OFFSET),
+      (Pools::RaydiumLaunchpad, Self::TokenAMint) => Some(LaunchpadPoolIdl::BASE_MINT_OFFSET),
+      (Pools::RaydiumLaunchpad, Self::TokenBMint) => Some(LaunchpadPoolIdl::QUOTE_MINT_OFFSET),
+      (Pools::RaydiumLaunchpad, Self::Config) => Some(LaunchpadPoolIdl::PLATFORM_CONFIG_OFFSET),
+      (Pools::PumpswapAmm, Self::TokenAMint) => Some(PumpAmmPoolAccount::BASE_MINT_OFFSET),
+      (Pools::PumpswapAmm, Self::TokenBMint) => Some(PumpAmmPoolAccount::QUOTE_MINT_OFFSET),
+      (Pools::MeteoraAmm, Self::TokenAMint) => Some(MeteoraAmmPoolIdl::TOKEN_A_MINT_OFFSET),
+      (Pools::MeteoraAmm, Self::TokenBMint) => Some(MeteoraAmmPoolIdl::TOKEN_B_MINT_OFFSET),
+      (Pools::MeteoraDammV2, Self::TokenAMint) => Some(MeteoraDammv2PoolIdl::TOKEN_A_MINT_OFFSET),
+      (Pools::MeteoraDammV2, Self::TokenBMint) => Some(MeteoraDammv2PoolIdl::TOKEN_B_MINT_OFFSET),
+      (Pools::MeteoraDbc, Self::TokenAMint) => Some(DbcVirtualPool::BASE_MINT_OFFSET),
+      (Pools::MeteoraDbc, Self::Config) => Some(DbcVirtualPool::CONFIG_OFFSET),
+      _ => None,
+    }
+  }
+
+  /// Get a data slice covering only this field, for `PoolLoadSpec::data_slice`
+  pub fn data_slice(&self, pool_type: Pools) -> Option<UiDataSliceConfig> {
+    Some(UiDataSliceConfig {
+      offset: self.offset(pool_type)?,
+      length: 32,
+    })
+  }
+}
@@ -0,0 +1,27 @@
+use crate::types::field_mismatch::FieldMismatch;
+use crate::types::pools::Pools;
+use solana_sdk::pubkey::Pubkey;
+
+/// Result of comparing one in-memory pool with its accounts fetched from chain, see
+/// `CentralContext::audit_pools`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoolAudit {
+  pub pool_address: Pubkey,
+  pub pool_type: Pools,
+  /// Slot the accounts were fetched at
+  pub slot: u64,
+  /// `last_updated_slot` of the in-memory pool when it was compared. Mismatches are only drift when
+  /// this is not newer than `slot`, otherwise the in-memory state may just be ahead of the RPC node.
+  pub in_memory_slot: u64,
+  /// Dependent accounts that don't exist on chain
+  pub missing_accounts: Vec<Pubkey>,
+  /// Fields and derived values that differ from chain
+  pub mismatches: Vec<FieldMismatch>,
+}
+
+impl PoolAudit {
+  /// Check if the in-memory pool matches chain
+  pub fn is_consistent(&self) -> bool {
+    self.missing_accounts.is_empty() && self.mismatches.is_empty()
+  }
+}
@@ -0,0 +1,14 @@
+/// Where a pool is in its life, see `PoolTrait::lifecycle` and `CentralContext::get_pool_lifecycle`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PoolLifecycle {
+  /// Created but swaps are not allowed yet (open time or activation point in the future)
+  PendingActivation,
+  /// Tradeable
+  Active,
+  /// A bonding curve that sold out and is waiting to migrate
+  Completed,
+  /// A bonding curve whose liquidity moved to an AMM pool
+  Migrated,
+  /// Trading disabled, or the pool account was closed
+  Closed,
+}
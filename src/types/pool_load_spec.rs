@@ -0,0 +1,121 @@
+use crate::constants::{METEORA_CONSTANTS, POOLS_ACCOUNT_SIZES, PUMP_CONSTANTS, RAYDIUM_CONSTANTS};
+use crate::protocol_idls::meteora::{DbcVirtualPool, MeteoraAmmPoolIdl, MeteoraDammv2PoolIdl};
+use crate::protocol_idls::pumpswap::PumpAmmPoolAccount;
+use crate::protocol_idls::raydium::{CpmmPoolInfoIdl, LaunchpadPoolIdl};
+use crate::types::pool_account_field::PoolAccountField;
+use crate::types::pools::Pools;
+use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::pubkey::Pubkey;
+
+/**
+Which pool accounts of one protocol to fetch with getProgramAccounts, see `load_pools_with_specs`
+and `get_pool_accounts`. Accounts are always filtered by the pool account size and, for Anchor
+programs, the account discriminator.
+
+USAGE:
+```rust,no_run
+# use solana_central::{PoolAccountField, PoolLoadSpec, Pools};
+# use solana_central::constants::TOKENS;
+// Only Pumpswap pools quoted in WSOL
+let spec = PoolLoadSpec {
+  filters: vec![(PoolAccountField::TokenBMint, TOKENS.wsol)],
+  ..PoolLoadSpec::new(Pools::PumpswapAmm)
+};
+```
+*/
+#[derive(Clone, Debug)]
+pub struct PoolLoadSpec {
+  pub pool_type: Pools,
+  /// Fields that must equal the given address
+  pub filters: Vec<(PoolAccountField, Pubkey)>,
+  /// Only download this part of each account, e.g. `PoolAccountField::data_slice`. Sliced
+  /// accounts can't be built into pools, use them with `get_pool_accounts`.
+  pub data_slice: Option<UiDataSliceConfig>,
+}
+
+impl PoolLoadSpec {
+  /// Load every pool of `pool_type`
+  pub fn new(pool_type: Pools) -> Self {
+    Self {
+      pool_type,
+      filters: Vec::new(),
+      data_slice: None,
+    }
+  }
+
+  /// Get the program owning the pool accounts, `None` if the pool type can't be loaded
+  pub fn program(&self) -> Option<Pubkey> {
+    match self.pool_type {
+      Pools::MeteoraAmm => Some(METEORA_CONSTANTS.amm_program),
+      Pools::MeteoraDammV2 => Some(METEORA_CONSTANTS.dammv2_program),
+      Pools::MeteoraDbc => Some(METEORA_CONSTANTS.dbc_program),
+      Pools::RaydiumAmmV4 => Some(RAYDIUM_CONSTANTS.amm_program),
+      Pools::RaydiumCpmm => Some(RAYDIUM_CONSTANTS.cpmm_program),
+      Pools::RaydiumLaunchpad => Some(RAYDIUM_CONSTANTS.launchpad_program),
+      Pools::PumpswapAmm => Some(PUMP_CONSTANTS.pump_swap_program),
+      _ => None,
+    }
+  }
+
+  /// Build the getProgramAccounts config. Returns `None` if the pool type can't be loaded or a
+  /// filter uses a field its accounts don't have.
+  pub fn rpc_config(&self) -> Option<RpcProgramAccountsConfig> {
+    let (data_size, discriminator) = match self.pool_type {
+      Pools::MeteoraAmm => (
+        POOLS_ACCOUNT_SIZES.meteora_amm,
+        Some(MeteoraAmmPoolIdl::DISCRIMINATOR),
+      ),
+      Pools::MeteoraDammV2 => (
+        POOLS_ACCOUNT_SIZES.meteora_dammv2,
+        Some(MeteoraDammv2PoolIdl::DISCRIMINATOR),
+      ),
+      Pools::MeteoraDbc => (
+        POOLS_ACCOUNT_SIZES.meteora_dbc,
+        Some(DbcVirtualPool::DISCRIMINATOR),
+      ),
+      // AMMv4 is not an Anchor program, its pool accounts have no discriminator
+      Pools::RaydiumAmmV4 => (POOLS_ACCOUNT_SIZES.raydium_ammv4, None),
+      Pools::RaydiumCpmm => (
+        POOLS_ACCOUNT_SIZES.raydium_cpmm,
+        Some(CpmmPoolInfoIdl::DISCRIMINATOR),
+      ),
+      Pools::RaydiumLaunchpad => (
+        POOLS_ACCOUNT_SIZES.raydium_launchpad,
+        Some(LaunchpadPoolIdl::DISCRIMINATOR),
+      ),
+      Pools::PumpswapAmm => (
+        POOLS_ACCOUNT_SIZES.pump_swap,
+        Some(PumpAmmPoolAccount::DISCRIMINATOR),
+      ),
+      _ => return None,
+    };
+
+    let mut filters = vec![RpcFilterType::DataSize(data_size as u64)];
+    if let Some(discriminator) = discriminator {
+      filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+        0,
+        &discriminator,
+      )));
+    }
+    for (field, value) in &self.filters {
+      filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+        field.offset(self.pool_type)?,
+        value.as_ref(),
+      )));
+    }
+
+    Some(RpcProgramAccountsConfig {
+      filters: Some(filters),
+      account_config: RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        data_slice: self.data_slice,
+        commitment: None,
+        min_context_slot: None,
+      },
+      with_context: None,
+      sort_results: None,
+    })
+  }
+}
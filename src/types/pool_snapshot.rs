@@ -0,0 +1,33 @@
+use crate::types::pool_lifecycle::PoolLifecycle;
+use crate::types::pools::Pools;
+use solana_sdk::pubkey::Pubkey;
+
+/// Owned copy of a pool's state for one pair, returned by `CentralContext::pools_for_pair` so
+/// callers can compare pools without holding their locks. Token A and B are the first and second
+/// token of the queried pair, not necessarily the pool's own token A and B.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoolSnapshot {
+  pub pool_address: Pubkey,
+  pub pool_type: Pools,
+  pub token_a_address: Pubkey,
+  pub token_b_address: Pubkey,
+  /// Whether the pool stores the pair as (token B, token A). Swapping token A for token B is then
+  /// `SwapDirection::BToA` on the pool.
+  pub reversed: bool,
+  pub token_a_amount_units: u64,
+  pub token_b_amount_units: u64,
+  /// Units of token A for 1 unit of token B in lamports, `None` if undefined (e.g. an empty side)
+  /// rather than the 0 of `PoolTrait::price_a_over_b_lp`. See `PoolTrait::try_price_a_over_b_lp`.
+  pub price_a_over_b_lp: Option<u128>,
+  /// Units of token B for 1 unit of token A in lamports, `None` if undefined
+  pub price_b_over_a_lp: Option<u128>,
+  /// `None` if the fee is unknown, see `PoolTrait::try_total_swap_fee_lp`. Such pools are ranked
+  /// last by `PairRanking::LowestFee` and excluded by `PairFilter::max_fee_lp`.
+  pub total_swap_fee_lp: Option<u64>,
+  pub lifecycle: PoolLifecycle,
+  pub last_updated_slot: u64,
+  /// Amount of token B received for the `amount_in` of `PairRanking::BestPrice` after fees, see
+  /// `PoolTrait::quote`. `None` with other rankings or if the pool can't quote, e.g. a completed
+  /// bonding curve or a pool whose fee is unknown.
+  pub amount_out: Option<u64>,
+}
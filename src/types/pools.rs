@@ -1,6 +1,9 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
 /// Enumeration of all supported DEX pool types
 #[repr(u8)]
-#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[derive(Debug, Eq, PartialEq, Clone, Copy, BorshSerialize, BorshDeserialize)]
+#[borsh(use_discriminant = true)]
 pub enum Pools {
   MeteoraAmm = 0,
   RaydiumCpmm = 1,
@@ -1,14 +1,18 @@
 use crate::CentralContext;
-use crate::constants::{LAMPORTS_PER_SOL, PUMP_CONSTANTS};
+use crate::constants::PUMP_CONSTANTS;
 use crate::types::pool::Pool;
 use crate::types::pool::PoolTrait;
+use crate::types::pool::constant_product_quote;
 use crate::types::pools::Pools;
 use crate::types::swap_direction::SwapDirection;
+use crate::utilities::get_token_account_amount::get_token_account_amount;
+use crate::types::error::Error;
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_sdk::pubkey::{Pubkey, PubkeyError};
 use std::any::Any;
 use std::sync::Arc;
 
-#[derive(Debug)]
+#[derive(Debug, BorshSerialize, BorshDeserialize)]
 
 /// Struct to hold data and PoolTrait implementation for Pumpswap pools.
 pub struct PumpswapPool {
@@ -57,13 +61,11 @@ impl PumpswapPool {
   */
   pub fn is_canonical_pool(&self) -> bool {
     if let Ok(pump_authority_pda) = Self::pump_pool_authority_pda(&self.info.token_a_address) {
-      let is_canonical = self.pool_creator == pump_authority_pda;
       // println!("[PumpSwap] Pool: {}", self.info.pool_address);
       // println!("[PumpSwap] Base mint (token_a): {}", self.info.token_a_address);
       // println!("[PumpSwap] Pool creator: {}", self.pool_creator);
       // println!("[PumpSwap] Derived PDA: {}", pump_authority_pda);
-      // println!("[PumpSwap] Is canonical: {}", is_canonical);
-      is_canonical
+      self.pool_creator == pump_authority_pda
     } else {
       // println!("[PumpSwap] Failed to derive PDA for base mint: {}", self.info.token_a_address);
       false
@@ -193,6 +195,24 @@ impl PoolTrait for PumpswapPool {
   fn pool_type(&self) -> &Pools {
     &self.info.pool_type
   }
+  fn last_updated_slot(&self) -> u64 {
+    self.info.last_updated_slot
+  }
+  fn set_last_updated_slot(&mut self, slot: u64) {
+    self.info.last_updated_slot = slot;
+  }
+  fn is_hydrated(&self) -> bool {
+    self.info.hydrated
+  }
+  fn set_hydrated(&mut self) {
+    self.info.hydrated = true;
+  }
+  fn state_fields(&self) -> Vec<(&'static str, u128)> {
+    vec![
+      ("token_a_vault_amount", self.token_a_vault_amount as u128),
+      ("token_b_vault_amount", self.token_b_vault_amount as u128),
+    ]
+  }
   /*
   PumpSwap uses two different fee structures:
 
@@ -215,30 +235,33 @@ impl PoolTrait for PumpswapPool {
     self
   }
 
-  fn price_a_over_b_lp(&self) -> u128 {
-    self.token_a_vault_amount as u128 * LAMPORTS_PER_SOL / self.token_b_vault_amount as u128
+  fn try_fetch_market_state_from_rpc(
+    &mut self,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<(), Error> {
+    self.token_a_vault_amount =
+      central_context.fetch_token_account_amount(&self.info.token_a_vault_address)?;
+    self.token_b_vault_amount =
+      central_context.fetch_token_account_amount(&self.info.token_b_vault_address)?;
+    self.info.hydrated = true;
+    Ok(())
   }
 
-  fn price_b_over_a_lp(&self) -> u128 {
-    self.token_b_vault_amount as u128 * LAMPORTS_PER_SOL / self.token_a_vault_amount as u128
-  }
 
-  fn fetch_market_state_from_rpc(&mut self, central_context: &Arc<CentralContext>) {
-    // Update token vaults fetch from rpc
-    self.token_a_vault_amount = central_context
-      .json_rpc_client
-      .get_token_account_balance(&self.info.token_a_vault_address)
-      .unwrap()
-      .amount
-      .parse()
-      .unwrap();
-    self.token_b_vault_amount = central_context
-      .json_rpc_client
-      .get_token_account_balance(&self.info.token_b_vault_address)
-      .unwrap()
-      .amount
-      .parse()
-      .unwrap();
+  fn apply_account_update(&mut self, address: &Pubkey, data: &[u8]) -> bool {
+    // The pool account itself holds no state that changes after creation
+    if *address == self.info.token_a_vault_address
+      && let Some(amount) = get_token_account_amount(data)
+    {
+      self.token_a_vault_amount = amount;
+    } else if *address == self.info.token_b_vault_address
+      && let Some(amount) = get_token_account_amount(data)
+    {
+      self.token_b_vault_amount = amount;
+    } else {
+      return false;
+    }
+    true
   }
 
   fn directional_fees(&self, _: SwapDirection, central_context: &Arc<CentralContext>) -> (f64, f64) {
@@ -249,4 +272,19 @@ impl PoolTrait for PumpswapPool {
     println!("[PumpSwap] Total fee: {}%", total_fee_fraction * 100.0);
     (0.0, total_fee_fraction)
   }
+
+  fn try_quote(
+    &self,
+    amount_in: u64,
+    direction: SwapDirection,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<u64, Error> {
+    // The fee is taken in the quote token, from the input on buys and the output on sells
+    let fee_lp = self.total_swap_fee_lp(central_context);
+    let (reserve_a, reserve_b) = (self.token_a_amount_units(), self.token_b_amount_units());
+    match direction {
+      SwapDirection::AToB => constant_product_quote(amount_in, reserve_a, reserve_b, 0, fee_lp),
+      SwapDirection::BToA => constant_product_quote(amount_in, reserve_b, reserve_a, fee_lp, 0),
+    }
+  }
 }
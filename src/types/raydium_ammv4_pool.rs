@@ -1,12 +1,19 @@
 use crate::CentralContext;
 use crate::constants::LAMPORTS_PER_SOL;
+use crate::protocol_idls::raydium::AmmV4PoolInfoIdl;
+use crate::types::error::Error;
 use crate::types::pool::Pool;
 use crate::types::pool::PoolTrait;
+use crate::types::pool::constant_product_quote;
+use crate::types::pool_lifecycle::PoolLifecycle;
 use crate::types::pools::Pools;
 use crate::types::swap_direction::SwapDirection;
+use crate::utilities::get_token_account_amount::get_token_account_amount;
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_sdk::pubkey::Pubkey;
 use std::any::Any;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /**
 Struct to hold data and PoolTrait implementation for Raydium AmmV4 pools. The only things you
@@ -14,13 +21,15 @@ actually need to trade on a Raydium AmmV4 at least are these things: the liquidi
 address, baseVault, quoteVault. The rest of the fields can be constant blasted as random values and
 the program will not care because Serum and Openbook are no longer in use for these AMMs.
 */
-#[derive(Debug)]
+#[derive(Debug, BorshSerialize, BorshDeserialize)]
 pub struct RaydiumAmmV4Pool {
   pub info: Pool,
   pub token_a_vault_amount: u64,
   pub token_b_vault_amount: u64,
   pub swap_fee_numerator: u64,
   pub swap_fee_denominator: u64,
+  // Unix timestamp in seconds after which swaps are allowed on the pool
+  pub open_time: u64,
 }
 
 impl PoolTrait for RaydiumAmmV4Pool {
@@ -49,9 +58,55 @@ impl PoolTrait for RaydiumAmmV4Pool {
   fn pool_type(&self) -> &Pools {
     &self.info.pool_type
   }
+  fn last_updated_slot(&self) -> u64 {
+    self.info.last_updated_slot
+  }
+  fn set_last_updated_slot(&mut self, slot: u64) {
+    self.info.last_updated_slot = slot;
+  }
+  fn is_hydrated(&self) -> bool {
+    self.info.hydrated
+  }
+  fn set_hydrated(&mut self) {
+    self.info.hydrated = true;
+  }
+  fn state_fields(&self) -> Vec<(&'static str, u128)> {
+    vec![
+      ("token_a_vault_amount", self.token_a_vault_amount as u128),
+      ("token_b_vault_amount", self.token_b_vault_amount as u128),
+      ("swap_fee_numerator", self.swap_fee_numerator as u128),
+      ("swap_fee_denominator", self.swap_fee_denominator as u128),
+      ("open_time", self.open_time as u128),
+    ]
+  }
+  fn lifecycle(&self, _current_slot: u64) -> PoolLifecycle {
+    let now = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .unwrap()
+      .as_secs();
+    if self.open_time > now {
+      PoolLifecycle::PendingActivation
+    } else {
+      PoolLifecycle::Active
+    }
+  }
+
+  fn total_swap_fee_lp(&self, central_context: &Arc<CentralContext>) -> u64 {
+    // A zero fee denominator is an uninitialized pool, report the whole input as fee so the pool
+    // is never used
+    self
+      .try_total_swap_fee_lp(central_context)
+      .unwrap_or(LAMPORTS_PER_SOL as u64)
+  }
 
-  fn total_swap_fee_lp(&self, _: &Arc<CentralContext>) -> u64 {
-    self.swap_fee_numerator * LAMPORTS_PER_SOL as u64 / self.swap_fee_denominator
+  fn try_total_swap_fee_lp(&self, _: &Arc<CentralContext>) -> Result<u64, Error> {
+    self
+      .swap_fee_numerator
+      .checked_mul(LAMPORTS_PER_SOL as u64)
+      .and_then(|fee| fee.checked_div(self.swap_fee_denominator))
+      .ok_or(Error::Arithmetic {
+        operation: "total_swap_fee_lp",
+      })
   }
 
   fn as_any(&self) -> &dyn Any {
@@ -61,29 +116,37 @@ impl PoolTrait for RaydiumAmmV4Pool {
     self
   }
 
-  fn price_a_over_b_lp(&self) -> u128 {
-    self.token_a_vault_amount as u128 * LAMPORTS_PER_SOL / self.token_b_vault_amount as u128
-  }
-
-  fn price_b_over_a_lp(&self) -> u128 {
-    self.token_b_vault_amount as u128 * LAMPORTS_PER_SOL / self.token_a_vault_amount as u128
+  fn try_fetch_market_state_from_rpc(
+    &mut self,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<(), Error> {
+    self.token_a_vault_amount =
+      central_context.fetch_token_account_amount(&self.info.token_a_vault_address)?;
+    self.token_b_vault_amount =
+      central_context.fetch_token_account_amount(&self.info.token_b_vault_address)?;
+    self.info.hydrated = true;
+    Ok(())
   }
 
-  fn fetch_market_state_from_rpc(&mut self, central_context: &Arc<CentralContext>) {
-    self.token_a_vault_amount = central_context
-      .json_rpc_client
-      .get_token_account_balance(&self.info.token_a_vault_address)
-      .unwrap()
-      .amount
-      .parse()
-      .unwrap();
-    self.token_b_vault_amount = central_context
-      .json_rpc_client
-      .get_token_account_balance(&self.info.token_b_vault_address)
-      .unwrap()
-      .amount
-      .parse()
-      .unwrap();
+  fn apply_account_update(&mut self, address: &Pubkey, data: &[u8]) -> bool {
+    if *address == self.info.token_a_vault_address
+      && let Some(amount) = get_token_account_amount(data)
+    {
+      self.token_a_vault_amount = amount;
+    } else if *address == self.info.token_b_vault_address
+      && let Some(amount) = get_token_account_amount(data)
+    {
+      self.token_b_vault_amount = amount;
+    } else if *address == self.info.pool_address
+      && let Ok(decoded_layout) = AmmV4PoolInfoIdl::try_from_slice(data)
+    {
+      self.swap_fee_numerator = decoded_layout.swap_fee_numerator;
+      self.swap_fee_denominator = decoded_layout.swap_fee_denominator;
+      self.open_time = decoded_layout.pool_open_time;
+    } else {
+      return false;
+    }
+    true
   }
 
   fn directional_fees(&self, swap_direction: SwapDirection, _: &Arc<CentralContext>) -> (f64, f64) {
@@ -94,4 +157,19 @@ impl PoolTrait for RaydiumAmmV4Pool {
       (0.0, 0.0025)
     }
   }
+
+  fn try_quote(
+    &self,
+    amount_in: u64,
+    direction: SwapDirection,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<u64, Error> {
+    // The fee is taken from the input token
+    let fee_lp = self.try_total_swap_fee_lp(central_context)?;
+    let (reserve_a, reserve_b) = (self.token_a_amount_units(), self.token_b_amount_units());
+    match direction {
+      SwapDirection::AToB => constant_product_quote(amount_in, reserve_a, reserve_b, fee_lp, 0),
+      SwapDirection::BToA => constant_product_quote(amount_in, reserve_b, reserve_a, fee_lp, 0),
+    }
+  }
 }
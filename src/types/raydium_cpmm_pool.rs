@@ -1,16 +1,20 @@
 use crate::CentralContext;
-use crate::constants::LAMPORTS_PER_SOL;
 use crate::protocol_idls::raydium::CpmmPoolInfoIdl;
+use crate::types::error::Error;
 use crate::types::pool::Pool;
 use crate::types::pool::PoolTrait;
+use crate::types::pool::constant_product_quote;
+use crate::types::pool_lifecycle::PoolLifecycle;
 use crate::types::pools::Pools;
 use crate::types::swap_direction::SwapDirection;
-use borsh::BorshDeserialize;
+use crate::utilities::get_token_account_amount::get_token_account_amount;
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_sdk::pubkey::Pubkey;
 use std::any::Any;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-#[derive(Debug)]
+#[derive(Debug, BorshSerialize, BorshDeserialize)]
 /// Struct to hold data and PoolTrait implementation for Raydium Cpmm pools.
 pub struct RaydiumCpmmPool {
   pub info: Pool,
@@ -35,6 +39,8 @@ pub struct RaydiumCpmmPool {
   pub fund_fees_token_b: u64,
   pub creator_fees_token_a: u64,
   pub creator_fees_token_b: u64,
+  // Unix timestamp in seconds after which swaps are allowed on the pool
+  pub open_time: u64,
 }
 
 impl PoolTrait for RaydiumCpmmPool {
@@ -71,6 +77,42 @@ impl PoolTrait for RaydiumCpmmPool {
   fn pool_type(&self) -> &Pools {
     &self.info.pool_type
   }
+  fn last_updated_slot(&self) -> u64 {
+    self.info.last_updated_slot
+  }
+  fn set_last_updated_slot(&mut self, slot: u64) {
+    self.info.last_updated_slot = slot;
+  }
+  fn is_hydrated(&self) -> bool {
+    self.info.hydrated
+  }
+  fn set_hydrated(&mut self) {
+    self.info.hydrated = true;
+  }
+  fn state_fields(&self) -> Vec<(&'static str, u128)> {
+    vec![
+      ("token_a_vault_amount", self.token_a_vault_amount as u128),
+      ("token_b_vault_amount", self.token_b_vault_amount as u128),
+      ("protocol_fees_token_a", self.protocol_fees_token_a as u128),
+      ("protocol_fees_token_b", self.protocol_fees_token_b as u128),
+      ("fund_fees_token_a", self.fund_fees_token_a as u128),
+      ("fund_fees_token_b", self.fund_fees_token_b as u128),
+      ("creator_fees_token_a", self.creator_fees_token_a as u128),
+      ("creator_fees_token_b", self.creator_fees_token_b as u128),
+      ("open_time", self.open_time as u128),
+    ]
+  }
+  fn lifecycle(&self, _current_slot: u64) -> PoolLifecycle {
+    let now = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .unwrap()
+      .as_secs();
+    if self.open_time > now {
+      PoolLifecycle::PendingActivation
+    } else {
+      PoolLifecycle::Active
+    }
+  }
 
   fn total_swap_fee_lp(&self, _central_context: &Arc<CentralContext>) -> u64 {
     self.fee_fraction_lp
@@ -83,44 +125,21 @@ impl PoolTrait for RaydiumCpmmPool {
     self
   }
 
-  fn price_a_over_b_lp(&self) -> u128 {
-    // Use tradeable amounts (excluding fees) for accurate pricing
-    let token_a_tradeable = self.token_a_amount_units();
-    let token_b_tradeable = self.token_b_amount_units();
-    token_a_tradeable as u128 * LAMPORTS_PER_SOL / token_b_tradeable as u128
-  }
-
-  fn price_b_over_a_lp(&self) -> u128 {
-    // Use tradeable amounts (excluding fees) for accurate pricing
-    let token_a_tradeable = self.token_a_amount_units();
-    let token_b_tradeable = self.token_b_amount_units();
-    token_b_tradeable as u128 * LAMPORTS_PER_SOL / token_a_tradeable as u128
-  }
-
-  fn fetch_market_state_from_rpc(&mut self, central_context: &Arc<CentralContext>) {
+  fn try_fetch_market_state_from_rpc(
+    &mut self,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<(), Error> {
     // Fetch raw vault balances
-    self.token_a_vault_amount = central_context
-      .json_rpc_client
-      .get_token_account_balance(&self.info.token_a_vault_address)
-      .unwrap()
-      .amount
-      .parse()
-      .unwrap();
-    self.token_b_vault_amount = central_context
-      .json_rpc_client
-      .get_token_account_balance(&self.info.token_b_vault_address)
-      .unwrap()
-      .amount
-      .parse()
-      .unwrap();
+    self.token_a_vault_amount =
+      central_context.fetch_token_account_amount(&self.info.token_a_vault_address)?;
+    self.token_b_vault_amount =
+      central_context.fetch_token_account_amount(&self.info.token_b_vault_address)?;
 
     // Fetch pool state account to get accumulated fees
-    let pool_account = central_context
-      .json_rpc_client
-      .get_account(&self.info.pool_address)
-      .unwrap();
-
-    let pool_state: CpmmPoolInfoIdl = CpmmPoolInfoIdl::try_from_slice(&pool_account.data).unwrap();
+    let pool_address = self.info.pool_address;
+    let pool_account = central_context.json_rpc_client.get_account(&pool_address)?;
+    let pool_state = CpmmPoolInfoIdl::try_from_slice(&pool_account.data)
+      .map_err(|error| Error::decode(pool_address, error))?;
 
     // Update accumulated fees
     self.protocol_fees_token_a = pool_state.protocol_fees_token_0;
@@ -129,6 +148,32 @@ impl PoolTrait for RaydiumCpmmPool {
     self.fund_fees_token_b = pool_state.fund_fees_token_1;
     self.creator_fees_token_a = pool_state.creator_fees_token_0;
     self.creator_fees_token_b = pool_state.creator_fees_token_1;
+    self.info.hydrated = true;
+    Ok(())
+  }
+
+  fn apply_account_update(&mut self, address: &Pubkey, data: &[u8]) -> bool {
+    if *address == self.info.token_a_vault_address
+      && let Some(amount) = get_token_account_amount(data)
+    {
+      self.token_a_vault_amount = amount;
+    } else if *address == self.info.token_b_vault_address
+      && let Some(amount) = get_token_account_amount(data)
+    {
+      self.token_b_vault_amount = amount;
+    } else if *address == self.info.pool_address
+      && let Ok(pool_state) = CpmmPoolInfoIdl::try_from_slice(data)
+    {
+      self.protocol_fees_token_a = pool_state.protocol_fees_token_0;
+      self.protocol_fees_token_b = pool_state.protocol_fees_token_1;
+      self.fund_fees_token_a = pool_state.fund_fees_token_0;
+      self.fund_fees_token_b = pool_state.fund_fees_token_1;
+      self.creator_fees_token_a = pool_state.creator_fees_token_0;
+      self.creator_fees_token_b = pool_state.creator_fees_token_1;
+    } else {
+      return false;
+    }
+    true
   }
 
   fn directional_fees(&self, swap_direction: SwapDirection, _: &Arc<CentralContext>) -> (f64, f64) {
@@ -139,4 +184,19 @@ impl PoolTrait for RaydiumCpmmPool {
       (0.0, self.fee_fraction_lp as f64 / 1_000_000_000.0)
     }
   }
+
+  fn try_quote(
+    &self,
+    amount_in: u64,
+    direction: SwapDirection,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<u64, Error> {
+    // The fee is taken from the input token
+    let fee_lp = self.try_total_swap_fee_lp(central_context)?;
+    let (reserve_a, reserve_b) = (self.token_a_amount_units(), self.token_b_amount_units());
+    match direction {
+      SwapDirection::AToB => constant_product_quote(amount_in, reserve_a, reserve_b, fee_lp, 0),
+      SwapDirection::BToA => constant_product_quote(amount_in, reserve_b, reserve_a, fee_lp, 0),
+    }
+  }
 }
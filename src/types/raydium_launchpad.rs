@@ -1,11 +1,13 @@
 use crate::CentralContext;
-use crate::constants::LAMPORTS_PER_SOL;
 use crate::protocol_idls::raydium::LaunchpadPoolIdl;
 use crate::types::pool::Pool;
 use crate::types::pool::PoolTrait;
+use crate::types::pool::constant_product_quote;
+use crate::types::pool_lifecycle::PoolLifecycle;
 use crate::types::pools::Pools;
 use crate::types::swap_direction::SwapDirection;
-use borsh::BorshDeserialize;
+use crate::types::error::Error;
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_sdk::pubkey::Pubkey;
 use std::any::Any;
 use std::sync::Arc;
@@ -15,6 +17,7 @@ Struct to hold data and PoolTrait implementation for Raydium AmmV4 pools. As of
 global config account for Raydium Launchlab uses the constant product pool. We use do token a is
 base and token b is quote here.
 */
+#[derive(BorshSerialize, BorshDeserialize)]
 pub struct RaydiumLaunchpad {
   pub info: Pool,
   pub platform_config: Pubkey,
@@ -28,16 +31,24 @@ pub struct RaydiumLaunchpad {
   pub virtual_token_b_reserve: u64,
   pub real_token_a_reserve: u64,
   pub real_token_b_reserve: u64,
+  // 0 trading on the curve, 1 curve complete and waiting to migrate, 2 migrated
+  pub status: u8,
+  // Destination of the migration: 0 AMMv4, 1 CPMM
+  pub migrate_type: u8,
 }
 
 impl PoolTrait for RaydiumLaunchpad {
   fn token_a_amount_units(&self) -> u64 {
     // A is the base token which is being sold as the launchpad progresses
-    self.virtual_token_a_reserve - self.real_token_a_reserve
+    self
+      .virtual_token_a_reserve
+      .saturating_sub(self.real_token_a_reserve)
   }
   fn token_b_amount_units(&self) -> u64 {
     // B is the quote token which fills up as the launchpad progresses
-    self.virtual_token_b_reserve + self.real_token_b_reserve
+    self
+      .virtual_token_b_reserve
+      .saturating_add(self.real_token_b_reserve)
   }
 
   fn pool_address(&self) -> &Pubkey {
@@ -58,14 +69,46 @@ impl PoolTrait for RaydiumLaunchpad {
   fn pool_type(&self) -> &Pools {
     &self.info.pool_type
   }
+  fn last_updated_slot(&self) -> u64 {
+    self.info.last_updated_slot
+  }
+  fn set_last_updated_slot(&mut self, slot: u64) {
+    self.info.last_updated_slot = slot;
+  }
+  fn state_fields(&self) -> Vec<(&'static str, u128)> {
+    vec![
+      ("virtual_token_a_reserve", self.virtual_token_a_reserve as u128),
+      ("virtual_token_b_reserve", self.virtual_token_b_reserve as u128),
+      ("real_token_a_reserve", self.real_token_a_reserve as u128),
+      ("real_token_b_reserve", self.real_token_b_reserve as u128),
+      ("status", self.status as u128),
+    ]
+  }
+  fn lifecycle(&self, _current_slot: u64) -> PoolLifecycle {
+    match self.status {
+      0 => PoolLifecycle::Active,
+      1 => PoolLifecycle::Completed,
+      _ => PoolLifecycle::Migrated,
+    }
+  }
+  fn graduation_successor(&self) -> Option<(Pools, Option<Pubkey>)> {
+    // AMMv4 pools depend on an OpenBook market and CPMM pools on an AMM config chosen at migration
+    match self.migrate_type {
+      0 => Some((Pools::RaydiumAmmV4, None)),
+      1 => Some((Pools::RaydiumCpmm, None)),
+      _ => None,
+    }
+  }
 
   /*
   We never would use this because we'd never do arbitrage on raydium launchpad and we do not take
   into account for fees or price impact when doing slippage calculation in order to avoid
-  fraudulent platform configs or fee configs that are 100% or something.
+  fraudulent platform configs or fee configs that are 100% or something. Reported as 0 like
+  `directional_fees`.
   */
   fn total_swap_fee_lp(&self, _: &Arc<CentralContext>) -> u64 {
-    panic!("total_swap_fee_lp: Called for Raydium Launchpad, should not be used in prod");
+    // Unknown, see `try_total_swap_fee_lp`
+    0
   }
 
   fn as_any(&self) -> &dyn Any {
@@ -75,30 +118,63 @@ impl PoolTrait for RaydiumLaunchpad {
     self
   }
 
-  fn price_a_over_b_lp(&self) -> u128 {
-    self.token_a_amount_units() as u128 * LAMPORTS_PER_SOL / self.token_b_amount_units() as u128
+  fn try_fetch_market_state_from_rpc(
+    &mut self,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<(), Error> {
+    let pool_address = self.info.pool_address;
+    let data = central_context.json_rpc_client.get_account(&pool_address)?.data;
+    if !self.apply_account_update(&pool_address, &data) {
+      return Err(Error::decode(pool_address, "account doesn't match the pool layout"));
+    }
+    Ok(())
   }
 
-  fn price_b_over_a_lp(&self) -> u128 {
-    self.token_b_amount_units() as u128 * LAMPORTS_PER_SOL / self.token_a_amount_units() as u128
-  }
-
-  fn fetch_market_state_from_rpc(&mut self, central_context: &Arc<CentralContext>) {
+  fn apply_account_update(&mut self, address: &Pubkey, data: &[u8]) -> bool {
     // The only values that change are real reserves, and those are stored in the pool account.
-    let current_pool_state = LaunchpadPoolIdl::try_from_slice(
-      central_context
-        .json_rpc_client
-        .get_account(&self.info.pool_address)
-        .unwrap()
-        .data
-        .as_slice(),
-    )
-    .unwrap();
+    if *address != self.info.pool_address {
+      return false;
+    }
+    let Ok(current_pool_state) = LaunchpadPoolIdl::try_from_slice(data) else {
+      return false;
+    };
     self.real_token_a_reserve = current_pool_state.real_base;
     self.real_token_b_reserve = current_pool_state.real_quote;
+    self.status = current_pool_state.status;
+    true
   }
 
-  fn directional_fees(&self, _: SwapDirection, __: &Arc<CentralContext>) -> (f64, f64) {
+  fn directional_fees(&self, _: SwapDirection, _central_context: &Arc<CentralContext>) -> (f64, f64) {
     (0.0, 0.0)
   }
+
+  fn try_total_swap_fee_lp(&self, _: &Arc<CentralContext>) -> Result<u64, Error> {
+    Err(Error::InvalidPool {
+      pool: self.info.pool_address,
+      reason: "the swap fee is set by the global and platform configs, which aren't loaded"
+        .to_string(),
+    })
+  }
+
+  fn try_quote(
+    &self,
+    amount_in: u64,
+    direction: SwapDirection,
+    central_context: &Arc<CentralContext>,
+  ) -> Result<u64, Error> {
+    if self.status != 0 {
+      return Err(Error::InvalidPool {
+        pool: self.info.pool_address,
+        reason: "the launchpad curve is complete".to_string(),
+      });
+    }
+    // Constant product on the virtual reserves, the fee is taken in the quote token, from the
+    // input on buys and the output on sells
+    let fee_lp = self.try_total_swap_fee_lp(central_context)?;
+    let (reserve_a, reserve_b) = (self.token_a_amount_units(), self.token_b_amount_units());
+    match direction {
+      SwapDirection::AToB => constant_product_quote(amount_in, reserve_a, reserve_b, 0, fee_lp),
+      SwapDirection::BToA => constant_product_quote(amount_in, reserve_b, reserve_a, fee_lp, 0),
+    }
+  }
 }
@@ -0,0 +1,22 @@
+use std::time::Duration;
+
+/// How RPC requests are retried once every endpoint failed, see `FailoverRpcSender`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+  /// Number of extra passes over all endpoints before the error is returned
+  pub max_retries: u32,
+  /// Wait before the first retry pass, doubled after every pass
+  pub initial_backoff: Duration,
+  /// Upper bound of the wait between passes
+  pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+  fn default() -> Self {
+    Self {
+      max_retries: 2,
+      initial_backoff: Duration::from_millis(500),
+      max_backoff: Duration::from_secs(10),
+    }
+  }
+}
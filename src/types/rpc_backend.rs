@@ -0,0 +1,98 @@
+use crate::utilities::get_mint_supply::get_mint_supply;
+use crate::utilities::get_token_account_amount::get_token_account_amount;
+use solana_account_decoder::parse_account_data::SplTokenAdditionalDataV2;
+use solana_account_decoder::parse_token::{UiTokenAmount, token_amount_to_ui_amount_v3};
+use solana_client::client_error::{ClientError, ClientErrorKind, Result};
+use solana_client::rpc_config::RpcProgramAccountsConfig;
+use solana_sdk::account::Account;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+
+/// Source of the chain state read by the context's RPC clients
+///
+/// Covers the RPC methods the library uses. Wrap a backend in a `BackendRpcSender` (or pass it to
+/// `CentralContextBuilder::rpc_backend`) to serve the context's `RpcClient`s from it instead of a
+/// node, e.g. with a `FixtureRpcBackend` to load and refresh pools offline. The token balance and
+/// supply methods are derived from `get_account` by default.
+///
+/// Methods are called from the async RPC client, so they shouldn't block for long.
+pub trait RpcBackend: Send + Sync {
+  /// Get the slot the backend's state is at, used as the context slot of every response
+  fn get_slot(&self) -> Result<u64>;
+
+  /// Get the latest blockhash and the last block height it is valid at
+  fn get_latest_blockhash(&self) -> Result<(Hash, u64)>;
+
+  /// Get an account, `None` if it doesn't exist
+  fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>>;
+
+  /// Get many accounts at once, in the order of `pubkeys`
+  fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+    pubkeys
+      .iter()
+      .map(|pubkey| self.get_account(pubkey))
+      .collect()
+  }
+
+  /// Get the accounts owned by `program_id` that match the filters of `config`. The data slice and
+  /// encoding of `config` are applied by the caller.
+  fn get_program_accounts_with_config(
+    &self,
+    program_id: &Pubkey,
+    config: &RpcProgramAccountsConfig,
+  ) -> Result<Vec<(Pubkey, Account)>>;
+
+  /// Get the balance of an SPL token account
+  fn get_token_account_balance(&self, pubkey: &Pubkey) -> Result<UiTokenAmount> {
+    let account = get_existing_account(self, pubkey)?;
+    let (Some(mint_address), Some(amount)) = (
+      account
+        .data
+        .get(..32)
+        .and_then(|data| Pubkey::try_from(data).ok()),
+      get_token_account_amount(&account.data),
+    ) else {
+      return Err(invalid_account(pubkey, "token account"));
+    };
+    let decimals = get_mint_decimals(self, &mint_address)?;
+    Ok(token_amount_to_ui_amount_v3(
+      amount,
+      &SplTokenAdditionalDataV2::with_decimals(decimals),
+    ))
+  }
+
+  /// Get the total supply of an SPL token mint
+  fn get_token_supply(&self, mint_address: &Pubkey) -> Result<UiTokenAmount> {
+    let account = get_existing_account(self, mint_address)?;
+    let Some(supply) = get_mint_supply(&account.data) else {
+      return Err(invalid_account(mint_address, "mint"));
+    };
+    let decimals = get_mint_decimals(self, mint_address)?;
+    Ok(token_amount_to_ui_amount_v3(
+      supply,
+      &SplTokenAdditionalDataV2::with_decimals(decimals),
+    ))
+  }
+}
+
+/// Get an account from the backend, failing if it doesn't exist
+fn get_existing_account<B: RpcBackend + ?Sized>(backend: &B, pubkey: &Pubkey) -> Result<Account> {
+  backend
+    .get_account(pubkey)?
+    .ok_or_else(|| ClientErrorKind::Custom(format!("Account {} not found", pubkey)).into())
+}
+
+/// Read the decimals of a mint, the byte after its supply
+fn get_mint_decimals<B: RpcBackend + ?Sized>(backend: &B, mint_address: &Pubkey) -> Result<u8> {
+  let account = get_existing_account(backend, mint_address)?;
+  account
+    .data
+    .get(44)
+    .copied()
+    .ok_or_else(|| invalid_account(mint_address, "mint"))
+}
+
+/// Error for an account whose data isn't the `expected` SPL account
+fn invalid_account(pubkey: &Pubkey, expected: &str) -> ClientError {
+  ClientErrorKind::Custom(format!("Account {} is not a valid {}", pubkey, expected)).into()
+}
@@ -0,0 +1,22 @@
+use crate::types::pool_lifecycle::PoolLifecycle;
+
+/// Which lifecycle states get pools removed from `CentralContext`, see
+/// `CentralContext::sweep_policy`. The default never removes anything.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SweepPolicy {
+  pub remove_completed: bool,
+  pub remove_migrated: bool,
+  pub remove_closed: bool,
+}
+
+impl SweepPolicy {
+  /// Check if a pool in the given state should be removed under this policy
+  pub fn should_remove(&self, lifecycle: PoolLifecycle) -> bool {
+    match lifecycle {
+      PoolLifecycle::PendingActivation | PoolLifecycle::Active => false,
+      PoolLifecycle::Completed => self.remove_completed,
+      PoolLifecycle::Migrated => self.remove_migrated,
+      PoolLifecycle::Closed => self.remove_closed,
+    }
+  }
+}
@@ -0,0 +1,66 @@
+use crate::constants::SOLANA_PROGRAMS;
+use crate::types::error::Error;
+use solana_sdk::pubkey::Pubkey;
+
+/// Metadata of an SPL token or token-2022 mint, decoded from the mint account. Cached in
+/// `CentralContext::token_registry`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenMetadata {
+  pub mint: Pubkey,
+  /// SPL token or token-2022 program owning the mint
+  pub token_program: Pubkey,
+  pub decimals: u8,
+  pub supply: u64,
+  pub mint_authority: Option<Pubkey>,
+  pub freeze_authority: Option<Pubkey>,
+  /// Slot of the mint state, 0 if unknown
+  pub last_updated_slot: u64,
+}
+
+impl TokenMetadata {
+  /// Decode the base mint layout shared by SPL token and token-2022 (extensions are ignored):
+  /// optional mint authority at 0..36, supply at 36..44, decimals at 44, initialized flag at 45
+  /// and optional freeze authority at 46..82.
+  pub fn try_from_mint_account(
+    mint: Pubkey,
+    owner: &Pubkey,
+    data: &[u8],
+    slot: u64,
+  ) -> Result<Self, Error> {
+    if *owner != SOLANA_PROGRAMS.token_program && *owner != SOLANA_PROGRAMS.token_2022_program {
+      return Err(Error::decode(
+        mint,
+        format!("owner {} isn't a token program", owner),
+      ));
+    }
+    let data = data
+      .get(..82)
+      .ok_or_else(|| Error::decode(mint, "account is too short for a mint"))?;
+    if data[45] != 1 {
+      return Err(Error::decode(mint, "mint isn't initialized"));
+    }
+    Ok(Self {
+      mint,
+      token_program: *owner,
+      decimals: data[44],
+      supply: u64::from_le_bytes(data[36..44].try_into().unwrap()),
+      mint_authority: decode_optional_pubkey(mint, &data[0..36])?,
+      freeze_authority: decode_optional_pubkey(mint, &data[46..82])?,
+      last_updated_slot: slot,
+    })
+  }
+
+  /// Convert a raw amount of the token to UI units, e.g. 1_500_000 of a 6 decimals token is 1.5
+  pub fn ui_amount(&self, amount: u64) -> f64 {
+    amount as f64 / 10f64.powi(self.decimals as i32)
+  }
+}
+
+/// Decode a `COption<Pubkey>`: a u32 tag (0 for none, 1 for some) followed by the pubkey
+fn decode_optional_pubkey(mint: Pubkey, data: &[u8]) -> Result<Option<Pubkey>, Error> {
+  match u32::from_le_bytes(data[0..4].try_into().unwrap()) {
+    0 => Ok(None),
+    1 => Ok(Some(Pubkey::try_from(&data[4..36]).unwrap())),
+    tag => Err(Error::decode(mint, format!("invalid option tag {}", tag))),
+  }
+}
@@ -0,0 +1,126 @@
+use crate::constants::SOLANA_PROGRAMS;
+use crate::types::instruction::Instruction;
+use crate::types::link::Link;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+
+const SYSTEM_TRANSFER: u32 = 2;
+const SYSTEM_TRANSFER_WITH_SEED: u32 = 11;
+const TOKEN_TRANSFER: u8 = 3;
+const TOKEN_TRANSFER_CHECKED: u8 = 12;
+const TOKEN_2022_TRANSFER_FEE_EXTENSION: u8 = 26;
+const TOKEN_2022_TRANSFER_CHECKED_WITH_FEE: u8 = 1;
+
+/**
+Find all links created by SOL and SPL token transfers in a transaction. `instructions` must be the
+transaction's instructions in execution order, every top level instruction followed by its inner
+instructions, and a link's `atomic_instruction_index` is the position of its transfer in that list.
+This is the same ordering used for `SwapTx` and `TokenCreation`.
+
+System Program transfer and transfer with seed link the sender and recipient wallets. SPL Token and
+Token-2022 transfer and transfer checked, and Token-2022 transfer checked with fee, link the owners
+of the source and destination token accounts, resolved with `token_account_owners` (see `get_token_account_owners`). A source token
+account without a known owner falls back to the transfer authority. Zero amount transfers are
+skipped as they're commonly used for address poisoning and never show a real relationship.
+*/
+pub fn extract_links(
+  instructions: &[Instruction],
+  token_account_owners: &HashMap<Pubkey, Pubkey>,
+  block_time: u64,
+  slot: u64,
+  index: u64,
+) -> Vec<Link> {
+  let mut links = Vec::new();
+  for (atomic_instruction_index, instruction) in instructions.iter().enumerate() {
+    // Links can only record the first 256 instructions of a tx
+    let Ok(atomic_instruction_index) = u8::try_from(atomic_instruction_index) else {
+      break;
+    };
+    let Some((sender, recipient)) = get_transfer_wallets(instruction, token_account_owners) else {
+      continue;
+    };
+    if let Some(link) = Link::check_link(
+      sender,
+      recipient,
+      block_time,
+      slot,
+      index,
+      atomic_instruction_index,
+    ) {
+      links.push(link);
+    }
+  }
+  links
+}
+
+/// Get the sending and receiving wallets of a non zero SOL or SPL token transfer instruction
+fn get_transfer_wallets<'a>(
+  instruction: &Instruction<'a>,
+  token_account_owners: &'a HashMap<Pubkey, Pubkey>,
+) -> Option<(&'a Pubkey, &'a Pubkey)> {
+  let program_id = instruction.program_id()?;
+  let data = instruction.data;
+
+  if *program_id == SOLANA_PROGRAMS.system_program {
+    // System instructions use a 4 byte little endian tag followed by the lamports
+    let tag = u32::from_le_bytes(data.get(0..4)?.try_into().ok()?);
+    let lamports = u64::from_le_bytes(data.get(4..12)?.try_into().ok()?);
+    if lamports == 0 {
+      return None;
+    }
+    return match tag {
+      // Accounts: from, to
+      SYSTEM_TRANSFER => Some((instruction.account(0)?, instruction.account(1)?)),
+      // Accounts: from, base, to
+      SYSTEM_TRANSFER_WITH_SEED => Some((instruction.account(0)?, instruction.account(2)?)),
+      _ => None,
+    };
+  }
+
+  if *program_id == SOLANA_PROGRAMS.token_program
+    || *program_id == SOLANA_PROGRAMS.token_2022_program
+  {
+    // Token instructions use a 1 byte tag followed by the amount, extension instructions add a
+    // second tag for the extension's own instruction
+    let (tag, amount) = match *data.first()? {
+      TOKEN_2022_TRANSFER_FEE_EXTENSION => (
+        (TOKEN_2022_TRANSFER_FEE_EXTENSION, *data.get(1)?),
+        data.get(2..10)?,
+      ),
+      tag => ((tag, 0), data.get(1..9)?),
+    };
+    if u64::from_le_bytes(amount.try_into().ok()?) == 0 {
+      return None;
+    }
+    let (source, destination, authority) = match tag {
+      // Accounts: source, destination, authority
+      (TOKEN_TRANSFER, _) => (
+        instruction.account(0)?,
+        instruction.account(1)?,
+        instruction.account(2)?,
+      ),
+      // Accounts: source, mint, destination, authority
+      (TOKEN_TRANSFER_CHECKED, _) => (
+        instruction.account(0)?,
+        instruction.account(2)?,
+        instruction.account(3)?,
+      ),
+      // Accounts: source, mint, destination, authority
+      (TOKEN_2022_TRANSFER_FEE_EXTENSION, TOKEN_2022_TRANSFER_CHECKED_WITH_FEE)
+        if *program_id == SOLANA_PROGRAMS.token_2022_program =>
+      {
+        (
+          instruction.account(0)?,
+          instruction.account(2)?,
+          instruction.account(3)?,
+        )
+      }
+      _ => return None,
+    };
+    let sender = token_account_owners.get(source).unwrap_or(authority);
+    let recipient = token_account_owners.get(destination)?;
+    return Some((sender, recipient));
+  }
+
+  None
+}
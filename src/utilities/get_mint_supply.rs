@@ -0,0 +1,5 @@
+/// Read the total supply out of raw SPL token (or token-2022) mint data. The supply is the u64 at
+/// bytes 36..44, after the optional mint authority. Returns `None` if the data is too short.
+pub fn get_mint_supply(data: &[u8]) -> Option<u64> {
+  Some(u64::from_le_bytes(data.get(36..44)?.try_into().ok()?))
+}
@@ -0,0 +1,27 @@
+use crate::CentralContext;
+use crate::types::pool_load_spec::PoolLoadSpec;
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+
+/// Fetch the raw pool accounts matching a `PoolLoadSpec` with an async getProgramAccounts JSON RPC
+/// call, without building pools. With a data slice only that part of each account is returned,
+/// e.g. to list the quote mint of every pool without downloading the full accounts.
+pub async fn get_pool_accounts(
+  spec: &PoolLoadSpec,
+  central_context: &CentralContext,
+) -> Result<Vec<(Pubkey, Account)>, ClientError> {
+  let (Some(program), Some(config)) = (spec.program(), spec.rpc_config()) else {
+    return Err(ClientErrorKind::Custom(format!("Unsupported pool load spec {:?}", spec)).into());
+  };
+  let accounts = central_context
+    .json_rpc_client_async
+    .get_program_ui_accounts_with_config(&program, config)
+    .await?;
+  Ok(
+    accounts
+      .into_iter()
+      .filter_map(|(pubkey, account)| account.decode::<Account>().map(|account| (pubkey, account)))
+      .collect(),
+  )
+}
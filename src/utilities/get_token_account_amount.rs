@@ -0,0 +1,5 @@
+/// Read the token amount out of raw SPL token (or token-2022) account data. The amount is the u64
+/// at bytes 64..72, after the mint and owner. Returns `None` if the data is too short.
+pub fn get_token_account_amount(data: &[u8]) -> Option<u64> {
+  Some(u64::from_le_bytes(data.get(64..72)?.try_into().ok()?))
+}
@@ -0,0 +1,24 @@
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status_client_types::UiTransactionTokenBalance;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Map the token accounts of a transaction to the wallets that own them using the pre and post
+/// token balance metadata. Pre balances cover token accounts closed by the transaction and post
+/// balances cover token accounts created by it. Balances without an owner are skipped.
+pub fn get_token_account_owners(
+  tx_account_keys: &[Pubkey],
+  pre_token_balances: &[UiTransactionTokenBalance],
+  post_token_balances: &[UiTransactionTokenBalance],
+) -> HashMap<Pubkey, Pubkey> {
+  pre_token_balances
+    .iter()
+    .chain(post_token_balances)
+    .filter_map(|balance| {
+      let token_account = tx_account_keys.get(balance.account_index as usize)?;
+      let owner = Option::<&String>::from(balance.owner.as_ref())
+        .and_then(|owner| Pubkey::from_str(owner).ok())?;
+      Some((*token_account, owner))
+    })
+    .collect()
+}
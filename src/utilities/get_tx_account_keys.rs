@@ -0,0 +1,25 @@
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::message::v0::LoadedAddresses;
+use solana_sdk::pubkey::Pubkey;
+
+/// Build the full ordered account key list of a transaction, which is what instruction account
+/// indices point into. For v0 transactions this is the static keys, then the writable addresses
+/// loaded from lookup tables, then the readonly loaded addresses. Legacy transactions only have
+/// static keys and `loaded_addresses` is ignored.
+///
+/// Loaded addresses come from the transaction status meta (RPC or gRPC) or from
+/// `CentralContext::resolve_loaded_addresses`.
+pub fn get_tx_account_keys(
+  message: &VersionedMessage,
+  loaded_addresses: &LoadedAddresses,
+) -> Vec<Pubkey> {
+  let static_account_keys = message.static_account_keys();
+  if let VersionedMessage::Legacy(_) = message {
+    return static_account_keys.to_vec();
+  }
+  let mut tx_account_keys = Vec::with_capacity(static_account_keys.len() + loaded_addresses.len());
+  tx_account_keys.extend_from_slice(static_account_keys);
+  tx_account_keys.extend_from_slice(&loaded_addresses.writable);
+  tx_account_keys.extend_from_slice(&loaded_addresses.readonly);
+  tx_account_keys
+}
@@ -0,0 +1,48 @@
+use crate::types::instruction::Instruction;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status_client_types::InnerInstructions;
+
+/**
+Get views over every instruction of a transaction in execution order: each top level instruction
+followed by its inner instructions. An instruction's position in the returned list is its atomic
+instruction index as used by `SwapTx`, `TokenCreation` and `Link`, and it is stable regardless of
+the order `inner_instructions` are listed in.
+
+`tx_account_keys` must be the full account key list from `get_tx_account_keys` so that accounts
+loaded from lookup tables resolve correctly.
+*/
+pub fn get_tx_instructions<'a>(
+  tx_account_keys: &'a [Pubkey],
+  message: &'a VersionedMessage,
+  inner_instructions: &'a [InnerInstructions],
+) -> Vec<Instruction<'a>> {
+  let mut instructions = Vec::new();
+  for (top_level_index, compiled_instruction) in message.instructions().iter().enumerate() {
+    instructions.push(Instruction {
+      tx_account_keys,
+      accounts: &compiled_instruction.accounts,
+      data: &compiled_instruction.data,
+      program_id_index: compiled_instruction.program_id_index,
+      stack_height: 1,
+    });
+    for inner in inner_instructions
+      .iter()
+      .filter(|inner| inner.index as usize == top_level_index)
+    {
+      instructions.extend(
+        inner
+          .instructions
+          .iter()
+          .map(|inner_instruction| Instruction {
+            tx_account_keys,
+            accounts: &inner_instruction.instruction.accounts,
+            data: &inner_instruction.instruction.data,
+            program_id_index: inner_instruction.instruction.program_id_index,
+            stack_height: inner_instruction.stack_height.unwrap_or(2),
+          }),
+      );
+    }
+  }
+  instructions
+}
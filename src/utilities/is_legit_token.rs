@@ -16,28 +16,15 @@ pub fn is_legit_token(token_address: &Pubkey, central_context: &Arc<CentralConte
     return *legit_token;
   }
 
-  // Derive metaplex pda for token
-  let (metaplex_pda, _) = Pubkey::find_program_address(
-    &[
-      b"metadata",
-      SOLANA_PROGRAMS.metaplex_program.as_ref(),
-      token_address.as_ref(),
-    ],
-    &SOLANA_PROGRAMS.metaplex_program,
-  );
-
   // Get account data for metaplex pda
-  if let Ok(metaplex_pda_data) = central_context.json_rpc_client.get_account(&metaplex_pda) {
-    let metaplex_pda_data = metaplex_pda_data.data;
-    // Must have discriminator and update authority
-    if metaplex_pda_data.len() < 33 {
+  if let Ok(metaplex_pda_data) = central_context
+    .json_rpc_client
+    .get_account(&get_metaplex_pda(token_address))
+  {
+    let Some(legit) = is_legit_metadata(&metaplex_pda_data.data, central_context) else {
       return false;
-    }
-    let update_authority = Pubkey::new_from_array(metaplex_pda_data[1..33].try_into().unwrap());
+    };
     // Cache and return result
-    let legit = central_context
-      .legit_update_authorities
-      .contains(&update_authority);
     legit_tokens.insert(*token_address, legit);
     return legit;
   }
@@ -47,3 +34,64 @@ pub fn is_legit_token(token_address: &Pubkey, central_context: &Arc<CentralConte
   */
   false
 }
+
+/// Async version of `is_legit_token` using `json_rpc_client_async`
+///
+/// The cache lock is not held while the metadata is fetched, so concurrent checks for the same
+/// uncached token may both hit the RPC.
+pub async fn is_legit_token_async(
+  token_address: &Pubkey,
+  central_context: &Arc<CentralContext>,
+) -> bool {
+  if let Some(legit_token) = central_context
+    .legit_tokens
+    .lock()
+    .unwrap()
+    .get(token_address)
+  {
+    return *legit_token;
+  }
+
+  let Ok(metaplex_pda_data) = central_context
+    .json_rpc_client_async
+    .get_account(&get_metaplex_pda(token_address))
+    .await
+  else {
+    // Same as the sync version, no metadata (or a failed request) is not legit and not cached
+    return false;
+  };
+  let Some(legit) = is_legit_metadata(&metaplex_pda_data.data, central_context) else {
+    return false;
+  };
+  central_context
+    .legit_tokens
+    .lock()
+    .unwrap()
+    .insert(*token_address, legit);
+  legit
+}
+
+/// Derive metaplex pda for token
+fn get_metaplex_pda(token_address: &Pubkey) -> Pubkey {
+  let (metaplex_pda, _) = Pubkey::find_program_address(
+    &[
+      b"metadata",
+      SOLANA_PROGRAMS.metaplex_program.as_ref(),
+      token_address.as_ref(),
+    ],
+    &SOLANA_PROGRAMS.metaplex_program,
+  );
+  metaplex_pda
+}
+
+/// Check the update authority of raw metaplex metadata against the legit update authorities.
+/// Returns `None` if the data is too short to hold one.
+fn is_legit_metadata(metaplex_pda_data: &[u8], central_context: &CentralContext) -> Option<bool> {
+  // Must have discriminator and update authority
+  let update_authority = Pubkey::new_from_array(metaplex_pda_data.get(1..33)?.try_into().ok()?);
+  Some(
+    central_context
+      .legit_update_authorities
+      .contains(&update_authority),
+  )
+}
@@ -0,0 +1,81 @@
+use crate::CentralContext;
+use crate::constants::MAX_ACCOUNTS_PER_REQUEST;
+use crate::types::pools::Pools;
+use crate::utilities::process_get_program_accounts_pool::process_pool_account;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+
+/// Fetch and insert the successor pools of graduations whose successor address is known but that
+/// isn't inserted yet, e.g. the DAMMv2 or Meteora AMM pool a DBC migrates to (see
+/// `CentralContext::process_migration_instruction`). The successors are fetched using async
+/// getMultipleAccounts JSON RPC calls in chunks of 100, inserted with `insert_pool`, which marks
+/// their graduations `successor_inserted`, and hydrated with `refresh_pools_async`.
+///
+/// Successors that don't exist yet (the migration isn't confirmed), aren't of the expected pool
+/// type or can't be decoded, and failed requests, are skipped and picked up by the next call. Call
+/// it off the ingestion thread after migration instructions were processed.
+pub async fn load_graduation_successors(central_context: Arc<CentralContext>) {
+  let pending_successors: Vec<(Pubkey, Pools)> = {
+    let pools_map = central_context.pools_map.read().unwrap();
+    let graduations = central_context.graduations.read().unwrap();
+    graduations
+      .values()
+      .filter(|graduation| !graduation.successor_inserted)
+      .filter_map(|graduation| {
+        let successor_pool_address = graduation.successor_pool_address?;
+        (!pools_map.contains_key(&successor_pool_address))
+          .then_some((successor_pool_address, graduation.successor_pool_type))
+      })
+      .collect()
+  };
+
+  let mut inserted_successors = Vec::new();
+  for pending_successors in pending_successors.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+    let successor_addresses: Vec<Pubkey> = pending_successors
+      .iter()
+      .map(|(successor_pool_address, _)| *successor_pool_address)
+      .collect();
+    let accounts = match central_context
+      .json_rpc_client_async
+      .get_multiple_accounts(&successor_addresses)
+      .await
+    {
+      Ok(accounts) => accounts,
+      Err(error) => {
+        println!(
+          "load_graduation_successors: getMultipleAccounts failed: {}",
+          error
+        );
+        continue;
+      }
+    };
+
+    for ((successor_pool_address, successor_pool_type), account) in
+      pending_successors.iter().zip(accounts)
+    {
+      let Some(account) = account else {
+        continue;
+      };
+      let successor = match process_pool_account(successor_pool_address, &account, &central_context)
+      {
+        Ok(Some(successor)) => successor,
+        Ok(None) => continue,
+        Err(error) => {
+          println!(
+            "load_graduation_successors: Skipping successor {}: {}",
+            successor_pool_address, error
+          );
+          continue;
+        }
+      };
+      if *successor.read().unwrap().pool_type() != *successor_pool_type {
+        continue;
+      }
+      central_context.insert_pool(successor);
+      inserted_successors.push(*successor_pool_address);
+    }
+  }
+  central_context
+    .refresh_pools_async(&inserted_successors)
+    .await;
+}
@@ -0,0 +1,62 @@
+use crate::CentralContext;
+use crate::constants::{MAX_ACCOUNTS_PER_REQUEST, POOLS_ACCOUNT_SIZES, PUMP_CONSTANTS};
+use crate::pumpfun::derive_bonding_curve::derive_bonding_curve;
+use crate::types::pf_bonding_curve::PfBondingCurve;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::{Arc, RwLock};
+
+/// Load the Pumpfun bonding curves of the given tokens using async getMultipleAccounts JSON RPC
+/// calls in chunks of 100. Bonding curve accounts don't store their token address so they can't be
+/// loaded by program like `load_pools` does, the curves are derived from the token addresses
+/// instead. Loaded curves are added to `pf_bonding_curves` and inserted into the central context.
+/// Tokens without a bonding curve, curves that can't be decoded and failed requests are skipped.
+pub async fn load_pf_bonding_curves(
+  token_addresses: &[Pubkey],
+  central_context: Arc<CentralContext>,
+) {
+  for token_addresses in token_addresses.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+    let bonding_curve_addresses: Vec<Pubkey> =
+      token_addresses.iter().map(derive_bonding_curve).collect();
+    let accounts = match central_context
+      .json_rpc_client_async
+      .get_multiple_accounts(&bonding_curve_addresses)
+      .await
+    {
+      Ok(accounts) => accounts,
+      Err(error) => {
+        println!(
+          "load_pf_bonding_curves: getMultipleAccounts failed: {}",
+          error
+        );
+        continue;
+      }
+    };
+
+    for (token_address, account) in token_addresses.iter().zip(accounts) {
+      let Some(account) = account else {
+        continue;
+      };
+      if account.owner != PUMP_CONSTANTS.bonding_curve_program
+        || account.data.len() < POOLS_ACCOUNT_SIZES.pf_bonding_curve
+      {
+        continue;
+      }
+      let bonding_curve = match PfBondingCurve::try_from_account_info(*token_address, &account.data)
+      {
+        Ok(bonding_curve) => Arc::new(RwLock::new(bonding_curve)),
+        Err(error) => {
+          println!("load_pf_bonding_curves: Skipping bonding curve: {}", error);
+          continue;
+        }
+      };
+      let bonding_curve_address = bonding_curve.read().unwrap().bonding_curve_address;
+      central_context
+        .pf_bonding_curves
+        .lock()
+        .unwrap()
+        .insert(bonding_curve_address, bonding_curve.clone());
+      central_context.insert_pool(bonding_curve);
+    }
+  }
+  println!("load_pf_bonding_curves: Loaded bonding curves from rpc");
+}
@@ -1,44 +1,114 @@
 use crate::CentralContext;
+use crate::constants::{METEORA_CONSTANTS, POOLS_ACCOUNT_SIZES};
+use crate::protocol_idls::meteora::DbcVirtualPool;
+use crate::types::pool_load_spec::PoolLoadSpec;
 use crate::utilities::process_get_program_accounts_pool::process_get_program_accounts_pool;
 use futures::future::join_all;
 use solana_account_decoder::UiAccountEncoding;
 use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
 use solana_client::rpc_filter::RpcFilterType;
 use solana_sdk::account::Account;
+use borsh::BorshDeserialize;
 use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::thread;
 
 /// Load pools from multiple protocols using async getProgramAccounts JSON RPC calls. Fetches and
 /// processes pool accounts for the specified protocols in parallel using multiple threads. 
-/// Processed pools are inserted into the central context for later access.
+/// Processed pools are inserted into the central context for later access. The configs of loaded
+/// Meteora DBC pools are fetched first with `CentralContext::load_dbc_pool_configs`. Pumpfun
+/// bonding curves don't store their token address so they are loaded with
+/// `load_pf_bonding_curves` instead.
+///
+/// AMMv4, CPMM, Pumpswap and Meteora AMM pool accounts don't hold their reserves, those pools are
+/// loaded unhydrated (see `PoolTrait::is_hydrated`). With `hydrate` set, the vaults, Meteora vaults,
+/// LP mints and LP vaults of every loaded pool are batch fetched with
+/// `CentralContext::refresh_pools_async` once the pools are inserted.
+///
+/// Protocols whose getProgramAccounts request fails and accounts that can't be decoded are skipped
+/// and reported, the other pools are still loaded.
 pub async fn load_pools(
   protocols_to_load: &[(Pubkey, usize)],
   central_context: Arc<CentralContext>,
   threads: usize,
+  hydrate: bool,
 ) {
   println!(
     "load_pools: Loading pools for the following protocols: {:?}",
     protocols_to_load
   );
+  let program_configs = protocols_to_load
+    .iter()
+    .map(|(protocol, size)| {
+      let config = RpcProgramAccountsConfig {
+        filters: Some(Vec::from([RpcFilterType::DataSize(*size as u64)])),
+        account_config: RpcAccountInfoConfig {
+          encoding: Some(UiAccountEncoding::Base64),
+          data_slice: None,
+          commitment: None,
+          min_context_slot: None,
+        },
+        with_context: None,
+        sort_results: None,
+      };
+      (*protocol, config)
+    })
+    .collect();
+  load_program_pools(program_configs, central_context, threads, hydrate).await;
+}
+
+/// Same as `load_pools` but each protocol is described by a `PoolLoadSpec`, so only the pools
+/// matching its filters (e.g. quote mint or config) are downloaded. Specs for pool types that
+/// can't be loaded, or with filters on fields their accounts don't have, are skipped and reported.
+/// Specs with a data slice are skipped and reported too, sliced accounts can't be built into
+/// pools, fetch them with `get_pool_accounts` instead.
+pub async fn load_pools_with_specs(
+  specs: &[PoolLoadSpec],
+  central_context: Arc<CentralContext>,
+  threads: usize,
+  hydrate: bool,
+) {
+  println!(
+    "load_pools_with_specs: Loading pools for the following specs: {:?}",
+    specs
+  );
+  let program_configs = specs
+    .iter()
+    .filter_map(|spec| {
+      if spec.data_slice.is_some() {
+        println!(
+          "load_pools_with_specs: Skipping spec with a data slice {:?}, use get_pool_accounts",
+          spec
+        );
+        return None;
+      }
+      let program_config = spec.program().zip(spec.rpc_config());
+      if program_config.is_none() {
+        println!("load_pools_with_specs: Skipping unsupported spec {:?}", spec);
+      }
+      program_config
+    })
+    .collect();
+  load_program_pools(program_configs, central_context, threads, hydrate).await;
+}
+
+/// Fetch the accounts of every program with its getProgramAccounts config, then process them into
+/// pools on `threads` threads and insert them into the central context, hydrating them if `hydrate`
+/// is set
+async fn load_program_pools(
+  program_configs: Vec<(Pubkey, RpcProgramAccountsConfig)>,
+  central_context: Arc<CentralContext>,
+  threads: usize,
+  hydrate: bool,
+) {
   // Use async await to fetch all dex pools using get program accounts and wait for results
   let mut futures = Vec::new();
-  for (protocol, size) in protocols_to_load {
-    let config = RpcProgramAccountsConfig {
-      filters: Some(Vec::from([RpcFilterType::DataSize(*size as u64)])),
-      account_config: RpcAccountInfoConfig {
-        encoding: Some(UiAccountEncoding::Base64),
-        data_slice: None,
-        commitment: None,
-        min_context_slot: None,
-      },
-      with_context: None,
-      sort_results: None,
-    };
+  for (program, config) in &program_configs {
     futures.push(
       central_context
         .json_rpc_client_async
-        .get_program_accounts_with_config(protocol, config),
+        .get_program_ui_accounts_with_config(program, config.clone()),
     );
   }
   let results = join_all(futures).await;
@@ -46,9 +116,39 @@ pub async fn load_pools(
 
   // Pack results into big raw data array to be parsed
   let mut accounts_raw_data: Vec<(Pubkey, Account)> = Vec::new();
-  for result in results {
-    let accounts = result.unwrap();
-    accounts_raw_data.extend(accounts);
+  for ((program, _), result) in program_configs.iter().zip(results) {
+    let accounts = match result {
+      Ok(accounts) => accounts,
+      Err(error) => {
+        println!(
+          "load_pools: getProgramAccounts failed for program {}, skipping it: {}",
+          program, error
+        );
+        continue;
+      }
+    };
+    accounts_raw_data.extend(
+      accounts
+        .into_iter()
+        .filter_map(|(pubkey, account)| account.decode::<Account>().map(|account| (pubkey, account))),
+    );
+  }
+
+  // DBC pools can only be built from their config, fetch the ones not cached yet
+  let dbc_config_addresses: HashSet<Pubkey> = accounts_raw_data
+    .iter()
+    .filter(|(_, account)| {
+      account.owner == METEORA_CONSTANTS.dbc_program
+        && account.data.len() == POOLS_ACCOUNT_SIZES.meteora_dbc
+    })
+    .filter_map(|(_, account)| DbcVirtualPool::try_from_slice(&account.data).ok())
+    .map(|dbc_virtual_pool| dbc_virtual_pool.config)
+    .collect();
+  if !dbc_config_addresses.is_empty() {
+    let dbc_config_addresses: Vec<Pubkey> = dbc_config_addresses.into_iter().collect();
+    central_context
+      .load_dbc_pool_configs(&dbc_config_addresses)
+      .await;
   }
 
   // Compute base chunk size for each thread to go through
@@ -84,10 +184,21 @@ pub async fn load_pools(
   Load processed pools into the markets cache on a single thread, write locks would make it single
   thread anyways
   */
+  let mut pool_addresses = Vec::new();
   for handle in handles {
     let results = handle.join().unwrap();
     for result in results {
+      let result_unlocked = result.read().unwrap();
+      if hydrate && !result_unlocked.is_hydrated() {
+        pool_addresses.push(*result_unlocked.pool_address());
+      }
+      drop(result_unlocked);
       central_context.insert_pool(result);
     }
   }
+
+  if hydrate {
+    println!("load_pools: Hydrating {} pools", pool_addresses.len());
+    central_context.refresh_pools_async(&pool_addresses).await;
+  }
 }
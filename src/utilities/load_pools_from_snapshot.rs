@@ -0,0 +1,47 @@
+use crate::CentralContext;
+use crate::types::error::Error;
+use crate::utilities::load_pools::load_pools;
+use solana_sdk::pubkey::Pubkey;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Boot the central context from a snapshot written by `CentralContext::save_snapshot`, falling
+/// back to a full load when the snapshot is missing, corrupt or from another snapshot version.
+///
+/// After a restore, pools are caught up to the current chain state with
+/// `CentralContext::refresh_pools_async`, which only fetches their dependent accounts. The full
+/// load runs `load_cpmm_pool_configs` and then `load_pools` with hydration, like a cold start. The
+/// context is consumed because restoring and loading CPMM configs need exclusive access, the
+/// loaded context is returned shared. Returns an error if the full load can't fetch the CPMM
+/// configs.
+pub async fn load_pools_from_snapshot(
+  snapshot_path: &Path,
+  mut central_context: CentralContext,
+  protocols_to_load: &[(Pubkey, usize)],
+  threads: usize,
+) -> Result<Arc<CentralContext>, Error> {
+  match central_context.restore_snapshot(snapshot_path) {
+    Ok(slot) => {
+      println!(
+        "load_pools_from_snapshot: Restored snapshot from slot {}, catching up",
+        slot
+      );
+      let central_context = Arc::new(central_context);
+      let pool_addresses: Vec<Pubkey> =
+        central_context.pools_map.read().unwrap().keys().copied().collect();
+      central_context.refresh_pools_async(&pool_addresses).await;
+      Ok(central_context)
+    }
+    Err(error) => {
+      println!(
+        "load_pools_from_snapshot: Can't restore snapshot {}, doing a full load: {}",
+        snapshot_path.display(),
+        error
+      );
+      central_context.load_cpmm_pool_configs()?;
+      let central_context = Arc::new(central_context);
+      load_pools(protocols_to_load, central_context.clone(), threads, true).await;
+      Ok(central_context)
+    }
+  }
+}
@@ -4,7 +4,19 @@
 //! - Transaction parsing and account extraction
 //! - Pool loading and processing
 //! - Token legitimacy validation
+//! - Wallet link extraction from transfers
+//! - Raw SPL token account and mint decoding
 
+pub mod extract_links;
+pub mod get_mint_supply;
+pub mod get_pool_accounts;
+pub mod get_token_account_amount;
+pub mod get_token_account_owners;
+pub mod get_tx_account_keys;
+pub mod get_tx_instructions;
+pub mod load_graduation_successors;
+pub mod load_pf_bonding_curves;
 pub mod load_pools;
+pub mod load_pools_from_snapshot;
 pub mod process_get_program_accounts_pool;
 pub mod is_legit_token;
@@ -1,11 +1,16 @@
 use crate::CentralContext;
 use crate::constants::{METEORA_CONSTANTS, POOLS_ACCOUNT_SIZES, PUMP_CONSTANTS, RAYDIUM_CONSTANTS};
+use crate::protocol_idls::meteora::DbcVirtualPool;
+use crate::types::error::Error;
 use crate::types::meteora_amm_pool::MeteoraAmmPool;
 use crate::types::meteora_dammv2_pool::MeteoraDammV2Pool;
+use crate::types::meteora_dbc::MeteoraDbc;
 use crate::types::pool::PoolTrait;
 use crate::types::pumpswap_pool::PumpswapPool;
 use crate::types::raydium_ammv4_pool::RaydiumAmmV4Pool;
 use crate::types::raydium_cpmm_pool::RaydiumCpmmPool;
+use crate::types::raydium_launchpad::RaydiumLaunchpad;
+use borsh::BorshDeserialize;
 use solana_sdk::account::Account;
 use solana_sdk::pubkey::Pubkey;
 use std::sync::{Arc, RwLock};
@@ -13,7 +18,10 @@ use std::sync::{Arc, RwLock};
 /// Process raw account data from getProgramAccounts into pool instances. Identifies pools by their
 /// program owner and account size, then parses them into the appropriate pool type (Meteora,
 /// Raydium, Pumpswap, etc.). Designed to be called from multiple threads with different slice
-/// ranges.
+/// ranges. Raydium launchpads and Meteora DBC pools are also added to their caches in the central
+/// context. DBC pools whose config isn't in `dbc_pool_configs` are skipped, see
+/// `CentralContext::load_dbc_pool_configs`. Accounts that can't be decoded are skipped and
+/// reported instead of aborting the thread.
 pub fn process_get_program_accounts_pool(
   raw_accounts: Arc<Vec<(Pubkey, Account)>>,
   central_context: Arc<CentralContext>,
@@ -23,41 +31,89 @@ pub fn process_get_program_accounts_pool(
   let raw_accounts = &raw_accounts.as_ref()[start..end];
   let mut results: Vec<Arc<RwLock<dyn PoolTrait>>> = Vec::new();
   for (pubkey, account) in raw_accounts {
-    if account.owner == PUMP_CONSTANTS.pump_swap_program
-      && account.data.len() == POOLS_ACCOUNT_SIZES.pump_swap
-    {
-      results.push(Arc::from(RwLock::from(PumpswapPool::from_account_info(
-        pubkey.clone(),
-        &account.data,
-      ))));
-    } else if account.owner == METEORA_CONSTANTS.amm_program
-      && account.data.len() == POOLS_ACCOUNT_SIZES.meteora_amm
-    {
-      results.push(Arc::from(RwLock::from(MeteoraAmmPool::from_account_info(
-        pubkey.clone(),
-        &account.data,
-        central_context.clone(),
-      ))));
-    } else if account.owner == METEORA_CONSTANTS.dammv2_program
-      && account.data.len() == POOLS_ACCOUNT_SIZES.meteora_dammv2
-    {
-      results.push(Arc::from(RwLock::from(
-        MeteoraDammV2Pool::from_account_info(pubkey.clone(), &account.data),
-      )));
-    } else if account.owner == RAYDIUM_CONSTANTS.amm_program
-      && account.data.len() == POOLS_ACCOUNT_SIZES.raydium_ammv4
-    {
-      results.push(Arc::from(RwLock::from(
-        RaydiumAmmV4Pool::from_account_info(pubkey.clone(), &account.data),
-      )));
-    } else if account.owner == RAYDIUM_CONSTANTS.cpmm_program
-      && account.data.len() == POOLS_ACCOUNT_SIZES.raydium_cpmm
-    {
-      results.push(Arc::from(RwLock::from(RaydiumCpmmPool::from_account_info(
-        pubkey.clone(),
-        &account.data,
-      ))));
+    match process_pool_account(pubkey, account, &central_context) {
+      Ok(Some(pool)) => results.push(pool),
+      Ok(None) => {}
+      Err(error) => println!(
+        "process_get_program_accounts_pool: Skipping account {}: {}",
+        pubkey, error
+      ),
     }
   }
   results
 }
+
+/// Build the pool of one account, `None` if the account isn't a pool account that can be loaded
+pub(crate) fn process_pool_account(
+  pubkey: &Pubkey,
+  account: &Account,
+  central_context: &Arc<CentralContext>,
+) -> Result<Option<Arc<RwLock<dyn PoolTrait>>>, Error> {
+  let pool: Arc<RwLock<dyn PoolTrait>> = if account.owner == PUMP_CONSTANTS.pump_swap_program
+    && account.data.len() == POOLS_ACCOUNT_SIZES.pump_swap
+  {
+    Arc::new(RwLock::new(PumpswapPool::try_from_account_info(*pubkey, &account.data)?))
+  } else if account.owner == METEORA_CONSTANTS.amm_program
+    && account.data.len() == POOLS_ACCOUNT_SIZES.meteora_amm
+  {
+    Arc::new(RwLock::new(MeteoraAmmPool::try_from_account_info(
+      *pubkey,
+      &account.data,
+      central_context.clone(),
+    )?))
+  } else if account.owner == METEORA_CONSTANTS.dammv2_program
+    && account.data.len() == POOLS_ACCOUNT_SIZES.meteora_dammv2
+  {
+    Arc::new(RwLock::new(MeteoraDammV2Pool::try_from_account_info(*pubkey, &account.data)?))
+  } else if account.owner == RAYDIUM_CONSTANTS.amm_program
+    && account.data.len() == POOLS_ACCOUNT_SIZES.raydium_ammv4
+  {
+    Arc::new(RwLock::new(RaydiumAmmV4Pool::try_from_account_info(*pubkey, &account.data)?))
+  } else if account.owner == RAYDIUM_CONSTANTS.cpmm_program
+    && account.data.len() == POOLS_ACCOUNT_SIZES.raydium_cpmm
+  {
+    Arc::new(RwLock::new(RaydiumCpmmPool::try_from_account_info(*pubkey, &account.data)?))
+  } else if account.owner == RAYDIUM_CONSTANTS.launchpad_program
+    && account.data.len() == POOLS_ACCOUNT_SIZES.raydium_launchpad
+  {
+    let launchpad = Arc::new(RwLock::new(RaydiumLaunchpad::try_from_account_info(
+      *pubkey,
+      &account.data,
+    )?));
+    central_context
+      .raydium_launchpads
+      .lock()
+      .unwrap()
+      .insert(*pubkey, launchpad.clone());
+    launchpad
+  } else if account.owner == METEORA_CONSTANTS.dbc_program
+    && account.data.len() == POOLS_ACCOUNT_SIZES.meteora_dbc
+  {
+    let dbc_virtual_pool = DbcVirtualPool::try_from_slice(&account.data)
+      .map_err(|error| Error::decode(*pubkey, error))?;
+    let Some(dbc_pool_config) = central_context
+      .dbc_pool_configs
+      .read()
+      .unwrap()
+      .get(&dbc_virtual_pool.config)
+      .cloned()
+    else {
+      return Ok(None);
+    };
+    let dbc = Arc::new(RwLock::new(MeteoraDbc::try_from_account_info(
+      *pubkey,
+      &account.data,
+      &dbc_pool_config,
+      dbc_virtual_pool.config,
+    )?));
+    central_context
+      .meteora_dbcs
+      .lock()
+      .unwrap()
+      .insert(*pubkey, dbc.clone());
+    dbc
+  } else {
+    return Ok(None);
+  };
+  Ok(Some(pool))
+}
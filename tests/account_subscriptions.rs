@@ -0,0 +1,262 @@
+mod common;
+
+use common::{account, fixture_context, mint_data, pumpswap_pool, token_account_data};
+use futures::{SinkExt, StreamExt};
+use serde_json::{Value, json};
+use solana_account_decoder::{UiAccountEncoding, encode_ui_account};
+use solana_central::constants::SOLANA_PROGRAMS;
+use solana_central::{CentralContext, PoolTrait};
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+enum Command {
+  Notify(Pubkey, u64, Account),
+  Close,
+}
+
+/// What the mock pubsub server saw, shared with the test
+#[derive(Default)]
+struct ServerState {
+  connections: usize,
+  /// Live subscription ids of the current connection
+  subscriptions: HashMap<u64, Pubkey>,
+  unsubscribed: Vec<Pubkey>,
+  commands: Option<mpsc::UnboundedSender<Command>>,
+}
+
+/// A websocket server answering `accountSubscribe` and `accountUnsubscribe` like an RPC node,
+/// or failing every subscription
+struct MockPubsub {
+  url: String,
+  state: Arc<Mutex<ServerState>>,
+}
+
+impl MockPubsub {
+  async fn start(fail_subscriptions: bool) -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let state = Arc::new(Mutex::new(ServerState::default()));
+    let server_state = state.clone();
+    tokio::spawn(async move {
+      let mut next_subscription_id = 1;
+      loop {
+        let (stream, _) = listener.accept().await.unwrap();
+        let Ok(mut ws) = tokio_tungstenite::accept_async(stream).await else {
+          continue;
+        };
+        let (commands, mut command_receiver) = mpsc::unbounded_channel();
+        {
+          let mut state = server_state.lock().unwrap();
+          state.connections += 1;
+          state.subscriptions.clear();
+          state.commands = Some(commands);
+        }
+        loop {
+          tokio::select! {
+            message = ws.next() => {
+              let Some(Ok(Message::Text(text))) = message else {
+                break;
+              };
+              let request: Value = serde_json::from_str(&text).unwrap();
+              let response = match request["method"].as_str().unwrap() {
+                "accountSubscribe" if fail_subscriptions => {
+                  json!({"code": -32602, "message": "Invalid params"})
+                }
+                "accountSubscribe" => {
+                  let address = request["params"][0].as_str().unwrap().parse().unwrap();
+                  server_state
+                    .lock()
+                    .unwrap()
+                    .subscriptions
+                    .insert(next_subscription_id, address);
+                  next_subscription_id += 1;
+                  json!(next_subscription_id - 1)
+                }
+                "accountUnsubscribe" => {
+                  let mut state = server_state.lock().unwrap();
+                  let id = request["params"][0].as_u64().unwrap();
+                  if let Some(address) = state.subscriptions.remove(&id) {
+                    state.unsubscribed.push(address);
+                  }
+                  json!(true)
+                }
+                method => panic!("unexpected method {}", method),
+              };
+              let key = if fail_subscriptions { "error" } else { "result" };
+              let response = json!({"jsonrpc": "2.0", key: response, "id": request["id"]});
+              ws.send(Message::text(response.to_string())).await.unwrap();
+            }
+            Some(command) = command_receiver.recv() => {
+              let Command::Notify(address, slot, account) = command else {
+                let _ = ws.close(None).await;
+                break;
+              };
+              let subscription = server_state
+                .lock()
+                .unwrap()
+                .subscriptions
+                .iter()
+                .find(|(_, subscribed)| **subscribed == address)
+                .map(|(id, _)| *id)
+                .unwrap();
+              let value = encode_ui_account(&address, &account, UiAccountEncoding::Base64, None, None);
+              let notification = json!({
+                "jsonrpc": "2.0",
+                "method": "accountNotification",
+                "params": {
+                  "result": {"context": {"slot": slot}, "value": value},
+                  "subscription": subscription,
+                },
+              });
+              ws.send(Message::text(notification.to_string())).await.unwrap();
+            }
+          }
+        }
+      }
+    });
+    Self { url, state }
+  }
+
+  fn send(&self, command: Command) {
+    let state = self.state.lock().unwrap();
+    state.commands.as_ref().unwrap().send(command).unwrap();
+  }
+
+  fn is_subscribed(&self, address: &Pubkey) -> bool {
+    let state = self.state.lock().unwrap();
+    state
+      .subscriptions
+      .values()
+      .any(|subscribed| subscribed == address)
+  }
+}
+
+/// Poll `condition` until it holds, failing after 5s
+async fn wait_until(mut condition: impl FnMut() -> bool) {
+  let start = Instant::now();
+  while !condition() {
+    assert!(start.elapsed() < Duration::from_secs(5), "timed out");
+    tokio::time::sleep(Duration::from_millis(10)).await;
+  }
+}
+
+/// Run the subscriptions of `central_context` against `server` in the background
+fn spawn_subscriptions(central_context: &Arc<CentralContext>, server: &MockPubsub) {
+  let central_context = central_context.clone();
+  let url = server.url.clone();
+  tokio::spawn(async move {
+    central_context
+      .run_account_subscriptions(&url, POLL_INTERVAL)
+      .await;
+  });
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn notifications_update_pools_across_reconnects() {
+  let (_, central_context) = fixture_context();
+  let pool = pumpswap_pool(1_000, 2_000, 1);
+  let (pool_address, token_a, token_a_vault) = (
+    *pool.pool_address(),
+    *pool.token_a_address(),
+    *pool.token_a_vault_address(),
+  );
+  let pool = Arc::new(RwLock::new(pool));
+  central_context.insert_pool(pool.clone());
+  let server = MockPubsub::start(false).await;
+  spawn_subscriptions(&central_context, &server);
+  let vault = |amount| {
+    account(
+      SOLANA_PROGRAMS.token_program,
+      token_account_data(&token_a, &pool_address, amount),
+    )
+  };
+
+  wait_until(|| server.is_subscribed(&token_a_vault)).await;
+  server.send(Command::Notify(token_a_vault, 10, vault(4_000)));
+  wait_until(|| pool.read().unwrap().token_a_amount_units() == 4_000).await;
+  assert_eq!(pool.read().unwrap().last_updated_slot(), 10);
+
+  // Everything is subscribed again on a new connection
+  server.send(Command::Close);
+  wait_until(|| {
+    server.state.lock().unwrap().connections == 2 && server.is_subscribed(&token_a_vault)
+  })
+  .await;
+  server.send(Command::Notify(token_a_vault, 11, vault(5_000)));
+  wait_until(|| pool.read().unwrap().token_a_amount_units() == 5_000).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn removed_pool_accounts_are_unsubscribed() {
+  let (_, central_context) = fixture_context();
+  let pool = pumpswap_pool(1_000, 2_000, 1);
+  let (pool_address, token_a_vault, token_b_vault) = (
+    *pool.pool_address(),
+    *pool.token_a_vault_address(),
+    *pool.token_b_vault_address(),
+  );
+  central_context.insert_pool(Arc::new(RwLock::new(pool)));
+  let server = MockPubsub::start(false).await;
+  spawn_subscriptions(&central_context, &server);
+
+  wait_until(|| server.is_subscribed(&token_b_vault)).await;
+  central_context.remove_pool(&pool_address).unwrap();
+  wait_until(|| server.state.lock().unwrap().subscriptions.is_empty()).await;
+  let state = server.state.lock().unwrap();
+  for account in [pool_address, token_a_vault, token_b_vault] {
+    assert!(state.unsubscribed.contains(&account));
+  }
+  // Unsubscribing doesn't drop the connection
+  assert_eq!(state.connections, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failed_subscriptions_back_off_before_reconnecting() {
+  let (_, central_context) = fixture_context();
+  central_context.insert_pool(Arc::new(RwLock::new(pumpswap_pool(1_000, 2_000, 1))));
+  let server = MockPubsub::start(true).await;
+  spawn_subscriptions(&central_context, &server);
+
+  // Connects at 0s, then after 0.5s and 1s of backoff
+  tokio::time::sleep(Duration::from_millis(1_200)).await;
+  let connections = server.state.lock().unwrap().connections;
+  assert!(
+    (2..=3).contains(&connections),
+    "{} connections",
+    connections
+  );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn tracked_mints_are_kept_current() {
+  let (backend, central_context) = fixture_context();
+  let mint = Pubkey::new_unique();
+  backend.set_slot(100);
+  backend.set_account(
+    mint,
+    account(SOLANA_PROGRAMS.token_program, mint_data(6, 1_000, None)),
+  );
+  central_context.load_token_metadata(&[mint]).await;
+  let server = MockPubsub::start(false).await;
+  spawn_subscriptions(&central_context, &server);
+
+  wait_until(|| server.is_subscribed(&mint)).await;
+  server.send(Command::Notify(
+    mint,
+    101,
+    account(SOLANA_PROGRAMS.token_program, mint_data(6, 2_000, None)),
+  ));
+  wait_until(|| central_context.token_registry.read().unwrap()[&mint].supply == 2_000).await;
+  assert_eq!(
+    central_context.token_registry.read().unwrap()[&mint].last_updated_slot,
+    101
+  );
+}
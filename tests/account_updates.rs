@@ -0,0 +1,253 @@
+mod common;
+
+use common::{
+  fixture_context, meteora_amm_pool, meteora_vault, mint_data, pumpswap_pool, token_account_data,
+};
+use solana_central::constants::SOLANA_PROGRAMS;
+use solana_central::{BackpressurePolicy, MarketSubscription, PoolTrait};
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+
+#[test]
+fn vault_balance_update_reprices_pool() {
+  let (_, central_context) = fixture_context();
+  let updates = central_context.subscribe_market_updates(
+    MarketSubscription::All,
+    8,
+    BackpressurePolicy::DropNewest,
+  );
+  let pool = pumpswap_pool(1_000, 2_000, 1);
+  let (pool_address, token_a, token_a_vault) = (
+    *pool.pool_address(),
+    *pool.token_a_address(),
+    *pool.token_a_vault_address(),
+  );
+  central_context.insert_pool(Arc::new(RwLock::new(pool)));
+
+  let updated = central_context.apply_account_update(
+    &token_a_vault,
+    &SOLANA_PROGRAMS.token_program,
+    &token_account_data(&token_a, &pool_address, 4_000),
+    10,
+    None,
+  );
+  assert_eq!(updated.len(), 1);
+  let pool = updated[0].read().unwrap();
+  assert_eq!(pool.token_a_amount_units(), 4_000);
+  assert_eq!(pool.last_updated_slot(), 10);
+  let update = updates.try_recv().unwrap();
+  assert_eq!(update.market_address, pool_address);
+  assert_eq!(update.price_a_b, 2_000_000_000);
+
+  // Accounts no pool depends on are ignored
+  assert!(
+    central_context
+      .apply_account_update(
+        &token_a,
+        &SOLANA_PROGRAMS.token_program,
+        &mint_data(6, 1, None),
+        11,
+        None,
+      )
+      .is_empty()
+  );
+}
+
+#[test]
+fn reserve_change_without_price_change_is_published() {
+  let (_, central_context) = fixture_context();
+  let updates = central_context.subscribe_market_updates(
+    MarketSubscription::All,
+    8,
+    BackpressurePolicy::DropNewest,
+  );
+  let pool = pumpswap_pool(3_000_000_000_000_000_000, 7_000_000_000_000_000_000, 1);
+  let (pool_address, token_a, token_a_vault) = (
+    *pool.pool_address(),
+    *pool.token_a_address(),
+    *pool.token_a_vault_address(),
+  );
+  central_context.insert_pool(Arc::new(RwLock::new(pool)));
+
+  // Too small a change to move either price
+  central_context.apply_account_update(
+    &token_a_vault,
+    &SOLANA_PROGRAMS.token_program,
+    &token_account_data(&token_a, &pool_address, 3_000_000_000_000_001_000),
+    10,
+    None,
+  );
+  let update = updates.try_recv().unwrap();
+  assert_eq!(update.price_a_b, 428_571_428);
+  assert_eq!(update.price_b_a, 2_333_333_333);
+  assert_eq!(update.token_a_amount, 3_000_000_000_000_001_000);
+  assert_eq!(update.token_b_amount, 7_000_000_000_000_000_000);
+
+  // An update that changes nothing isn't published
+  central_context.apply_account_update(
+    &token_a_vault,
+    &SOLANA_PROGRAMS.token_program,
+    &token_account_data(&token_a, &pool_address, 3_000_000_000_000_001_000),
+    11,
+    None,
+  );
+  assert!(updates.try_recv().is_err());
+}
+
+#[test]
+fn shared_vault_update_applies_to_every_dependent_pool() {
+  let (_, central_context) = fixture_context();
+  let updates = central_context.subscribe_market_updates(
+    MarketSubscription::All,
+    8,
+    BackpressurePolicy::DropNewest,
+  );
+  let shared_vault = meteora_vault(1_000, 1_000);
+  let pool_1 = meteora_amm_pool(&shared_vault, &meteora_vault(1_000, 1_000), 100, 100);
+  let pool_2 = meteora_amm_pool(&shared_vault, &meteora_vault(1_000, 1_000), 500, 100);
+  let (pool_1_address, pool_2_address) = (pool_1.info.pool_address, pool_2.info.pool_address);
+  central_context.insert_pool(Arc::new(RwLock::new(pool_1)));
+  let pool_2 = Arc::new(RwLock::new(pool_2));
+  central_context.insert_pool(pool_2.clone());
+  let lp_mint = shared_vault.read().unwrap().lp_token_address;
+
+  // Halving the LP supply doubles both pools' share of the shared vault
+  let updated = central_context.apply_account_update(
+    &lp_mint,
+    &SOLANA_PROGRAMS.token_program,
+    &mint_data(9, 500, None),
+    20,
+    None,
+  );
+  let updated_addresses: HashSet<_> = updated
+    .iter()
+    .map(|pool| *pool.read().unwrap().pool_address())
+    .collect();
+  assert_eq!(
+    updated_addresses,
+    HashSet::from([pool_1_address, pool_2_address])
+  );
+  assert_eq!(pool_2.read().unwrap().token_a_amount_units(), 1_000);
+  let published: HashSet<_> = updates
+    .try_iter()
+    .map(|update| update.market_address)
+    .collect();
+  assert_eq!(published, HashSet::from([pool_1_address, pool_2_address]));
+
+  // The shared account keeps routing to the remaining pool
+  central_context.remove_pool(&pool_1_address).unwrap();
+  assert!(Arc::ptr_eq(
+    &central_context.pools_map.read().unwrap()[&lp_mint],
+    &(pool_2.clone() as Arc<RwLock<dyn PoolTrait>>)
+  ));
+  let updated = central_context.apply_account_update(
+    &lp_mint,
+    &SOLANA_PROGRAMS.token_program,
+    &mint_data(9, 1_000, None),
+    21,
+    None,
+  );
+  assert_eq!(updated.len(), 1);
+  assert_eq!(pool_2.read().unwrap().token_a_amount_units(), 500);
+
+  central_context.remove_pool(&pool_2_address).unwrap();
+  assert!(
+    !central_context
+      .pools_map
+      .read()
+      .unwrap()
+      .contains_key(&lp_mint)
+  );
+  assert!(
+    !central_context
+      .account_dependents
+      .read()
+      .unwrap()
+      .contains_key(&lp_mint)
+  );
+}
+
+#[test]
+fn stale_updates_are_rejected() {
+  let (_, central_context) = fixture_context();
+  let pool = pumpswap_pool(1_000, 2_000, 1);
+  let (pool_address, token_a, token_a_vault) = (
+    *pool.pool_address(),
+    *pool.token_a_address(),
+    *pool.token_a_vault_address(),
+  );
+  let pool = Arc::new(RwLock::new(pool));
+  central_context.insert_pool(pool.clone());
+  let apply = |amount: u64, slot: u64, write_version: Option<u64>| {
+    !central_context
+      .apply_account_update(
+        &token_a_vault,
+        &SOLANA_PROGRAMS.token_program,
+        &token_account_data(&token_a, &pool_address, amount),
+        slot,
+        write_version,
+      )
+      .is_empty()
+  };
+
+  assert!(apply(5_000, 10, Some(3)));
+  // Older slot, or the same slot and an older or equal write version
+  assert!(!apply(6_000, 9, Some(9)));
+  assert!(!apply(6_000, 10, Some(2)));
+  assert!(!apply(6_000, 10, Some(3)));
+  assert_eq!(pool.read().unwrap().token_a_amount_units(), 5_000);
+  assert!(apply(7_000, 10, Some(4)));
+  // Without write versions only the slot orders updates
+  assert!(apply(8_000, 10, None));
+  assert!(!apply(9_000, 9, None));
+  assert!(apply(9_000, 11, None));
+  assert_eq!(pool.read().unwrap().token_a_amount_units(), 9_000);
+  assert_eq!(
+    central_context.account_update_versions.read().unwrap()[&token_a_vault],
+    (11, 0)
+  );
+}
+
+#[test]
+fn concurrent_updates_keep_the_newest() {
+  let (_, central_context) = fixture_context();
+  let pool = pumpswap_pool(1_000, 2_000, 1);
+  let (pool_address, token_a, token_a_vault) = (
+    *pool.pool_address(),
+    *pool.token_a_address(),
+    *pool.token_a_vault_address(),
+  );
+  let pool = Arc::new(RwLock::new(pool));
+  central_context.insert_pool(pool.clone());
+
+  // Deliver write versions 1..=400 from 8 threads in interleaved orders
+  let threads: Vec<_> = (0..8u64)
+    .map(|thread| {
+      let central_context = central_context.clone();
+      std::thread::spawn(move || {
+        for step in 0..50u64 {
+          let write_version = if thread % 2 == 0 {
+            step * 8 + thread + 1
+          } else {
+            (49 - step) * 8 + thread + 1
+          };
+          central_context.apply_account_update(
+            &token_a_vault,
+            &SOLANA_PROGRAMS.token_program,
+            &token_account_data(&token_a, &pool_address, write_version),
+            100,
+            Some(write_version),
+          );
+        }
+      })
+    })
+    .collect();
+  for thread in threads {
+    thread.join().unwrap();
+  }
+  assert_eq!(pool.read().unwrap().token_a_amount_units(), 400);
+  assert_eq!(
+    central_context.account_update_versions.read().unwrap()[&token_a_vault],
+    (100, 400)
+  );
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use common::{account, fixture_context, pumpswap_pool, token_account_data};
+use solana_central::constants::{PUMP_CONSTANTS, SOLANA_PROGRAMS, TOKENS};
+use solana_central::{FieldMismatch, FixtureRpcBackend, PoolTrait, PumpswapPool};
+use solana_sdk::pubkey::Pubkey;
+use std::sync::{Arc, RwLock};
+
+/// Store the pool account and the vault token accounts of a Pumpswap pool
+fn set_pool_accounts(backend: &FixtureRpcBackend, pool: &PumpswapPool) {
+  backend.set_account(
+    *pool.pool_address(),
+    account(PUMP_CONSTANTS.pump_swap_program, vec![0; 8]),
+  );
+  for (vault, mint, amount) in [
+    (
+      *pool.token_a_vault_address(),
+      *pool.token_a_address(),
+      pool.token_a_vault_amount,
+    ),
+    (
+      *pool.token_b_vault_address(),
+      *pool.token_b_address(),
+      pool.token_b_vault_amount,
+    ),
+  ] {
+    backend.set_account(
+      vault,
+      account(
+        SOLANA_PROGRAMS.token_program,
+        token_account_data(&mint, pool.pool_address(), amount),
+      ),
+    );
+  }
+}
+
+/// Canonical Pumpswap pool, its fees depend on its market cap
+fn canonical_pool(base_amount: u64, quote_amount: u64) -> PumpswapPool {
+  let mut pool = pumpswap_pool(base_amount, quote_amount, 0);
+  pool.info.token_b_address = TOKENS.wsol;
+  pool.pool_creator = PumpswapPool::pump_pool_authority_pda(pool.token_a_address()).unwrap();
+  pool
+}
+
+#[tokio::test]
+async fn pools_matching_chain_are_consistent() {
+  let (backend, central_context) = fixture_context();
+  let pool = canonical_pool(1_000_000, 100_000_000);
+  set_pool_accounts(&backend, &pool);
+  let pool_address = *pool.pool_address();
+  central_context.insert_pool(Arc::new(RwLock::new(pool)));
+
+  let audits = central_context
+    .audit_pools(&[pool_address, Pubkey::new_unique()])
+    .await;
+  // Unknown pools aren't audited
+  assert_eq!(audits.len(), 1);
+  assert_eq!(audits[0].pool_address, pool_address);
+  assert!(audits[0].is_consistent(), "{:?}", audits[0]);
+}
+
+#[tokio::test]
+async fn drifted_vaults_and_fees_are_reported() {
+  let (backend, central_context) = fixture_context();
+  let pool = canonical_pool(1_000_000, 100_000_000);
+  set_pool_accounts(&backend, &pool);
+  let pool_address = *pool.pool_address();
+  let (quote_mint, quote_vault) = (*pool.token_b_address(), *pool.token_b_vault_address());
+  let pool = Arc::new(RwLock::new(pool));
+  central_context.insert_pool(pool.clone());
+  let total_swap_fee_lp = pool.read().unwrap().total_swap_fee_lp(&central_context);
+
+  // A missed update moved the market cap to the next fee tier on chain
+  backend.set_account(
+    quote_vault,
+    account(
+      SOLANA_PROGRAMS.token_program,
+      token_account_data(&quote_mint, &pool_address, 1_000_000_000),
+    ),
+  );
+  let audits = central_context.audit_pools(&[pool_address]).await;
+  assert_eq!(audits.len(), 1);
+  let audit = &audits[0];
+  assert!(audit.missing_accounts.is_empty());
+  let mismatch = |field: &str| {
+    audit
+      .mismatches
+      .iter()
+      .find(|mismatch| mismatch.field == field)
+      .cloned()
+  };
+  assert_eq!(
+    mismatch("token_b_vault_amount"),
+    Some(FieldMismatch {
+      field: "token_b_vault_amount",
+      in_memory: 100_000_000,
+      on_chain: 1_000_000_000,
+    })
+  );
+  assert!(mismatch("price_a_over_b_lp").is_some());
+  assert_eq!(total_swap_fee_lp, 12_500_000);
+  assert_eq!(
+    mismatch("total_swap_fee_lp"),
+    Some(FieldMismatch {
+      field: "total_swap_fee_lp",
+      in_memory: 12_500_000,
+      on_chain: 12_000_000,
+    })
+  );
+  // Pumpswap charges its fee in the quote token in both directions
+  for field in ["a_to_b_fee_b_lp", "b_to_a_fee_b_lp"] {
+    let mismatch = mismatch(field).unwrap();
+    assert_eq!(
+      (mismatch.in_memory, mismatch.on_chain),
+      (12_500_000, 12_000_000)
+    );
+  }
+  assert!(mismatch("a_to_b_fee_a_lp").is_none());
+  // The audit never touches the context
+  assert_eq!(pool.read().unwrap().token_b_vault_amount, 100_000_000);
+
+  // A vault closed on chain is reported missing
+  backend.remove_account(&quote_vault);
+  let audits = central_context.audit_pools(&[pool_address]).await;
+  assert_eq!(audits[0].missing_accounts, vec![quote_vault]);
+  assert!(!audits[0].is_consistent());
+}
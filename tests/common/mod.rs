@@ -0,0 +1,301 @@
+#![allow(dead_code)]
+
+use solana_central::constants::{POOLS_ACCOUNT_SIZES, PUMP_CONSTANTS};
+use solana_central::protocol_idls::meteora::DbcVirtualPool;
+use solana_central::protocol_idls::pumpswap::PumpAmmPoolAccount;
+use solana_central::protocol_idls::raydium::LaunchpadPoolIdl;
+use solana_central::{
+  CentralContext, CentralContextBuilder, FixtureRpcBackend, Instruction, MeteoraAmmPool,
+  MeteoraVault, PfBondingCurve, Pool, Pools, PumpswapPool, RaydiumCpmmPool,
+};
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::{Arc, RwLock};
+
+/// Context answering RPC requests from an in-memory fixture backend
+pub fn fixture_context() -> (Arc<FixtureRpcBackend>, Arc<CentralContext>) {
+  let backend = Arc::new(FixtureRpcBackend::new());
+  let central_context = CentralContextBuilder::new()
+    .rpc_backend(backend.clone())
+    .build()
+    .unwrap();
+  (backend, Arc::new(central_context))
+}
+
+/// Instruction invoking the last of `tx_account_keys` with every other key as its accounts, in
+/// order
+pub fn instruction<'a>(
+  tx_account_keys: &'a [Pubkey],
+  accounts: &'a [u8],
+  data: &'a [u8],
+) -> Instruction<'a> {
+  Instruction::new(
+    tx_account_keys,
+    accounts,
+    data,
+    (tx_account_keys.len() - 1) as u8,
+    1,
+  )
+}
+
+/// `count` unique keys followed by `program_id`
+pub fn keys_with_program(count: usize, program_id: Pubkey) -> (Vec<Pubkey>, Vec<u8>) {
+  let mut keys: Vec<Pubkey> = (0..count).map(|_| Pubkey::new_unique()).collect();
+  keys.push(program_id);
+  (keys, (0..count as u8).collect())
+}
+
+/// Account owned by `owner` holding `data`
+pub fn account(owner: Pubkey, data: Vec<u8>) -> Account {
+  Account {
+    lamports: 1_000_000,
+    data,
+    owner,
+    executable: false,
+    rent_epoch: 0,
+  }
+}
+
+/// Raw SPL token account data with the given mint, owner and amount
+pub fn token_account_data(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Vec<u8> {
+  let mut data = vec![0u8; 165];
+  data[0..32].copy_from_slice(mint.as_ref());
+  data[32..64].copy_from_slice(owner.as_ref());
+  data[64..72].copy_from_slice(&amount.to_le_bytes());
+  // Initialized
+  data[108] = 1;
+  data
+}
+
+/// Raw SPL token mint data
+pub fn mint_data(decimals: u8, supply: u64, mint_authority: Option<Pubkey>) -> Vec<u8> {
+  let mut data = vec![0u8; 82];
+  if let Some(mint_authority) = mint_authority {
+    data[0] = 1;
+    data[4..36].copy_from_slice(mint_authority.as_ref());
+  }
+  data[36..44].copy_from_slice(&supply.to_le_bytes());
+  data[44] = decimals;
+  data[45] = 1;
+  data
+}
+
+/// Meteora vault holding `total_amount` tokens against `lp_supply` vault LP tokens
+pub fn meteora_vault(total_amount: u64, lp_supply: u64) -> Arc<RwLock<MeteoraVault>> {
+  Arc::new(RwLock::new(MeteoraVault {
+    vault: Pubkey::new_unique(),
+    vault_token_account: Pubkey::new_unique(),
+    token_address: Pubkey::new_unique(),
+    lp_token_address: Pubkey::new_unique(),
+    last_updated_locked_profit: 0,
+    last_report: 0,
+    locked_profit_degradation: 0,
+    total_amount,
+    lp_supply,
+  }))
+}
+
+/// Small (1232 bytes) Meteora vault account data holding `total_amount` tokens with `lp_mint`
+pub fn meteora_vault_data(total_amount: u64, lp_mint: &Pubkey) -> Vec<u8> {
+  let mut data = vec![0u8; 1232];
+  data[11..19].copy_from_slice(&total_amount.to_le_bytes());
+  data[115..147].copy_from_slice(lp_mint.as_ref());
+  data
+}
+
+/// Hydrated Meteora AMM pool owning `token_a_lp_amount` and `token_b_lp_amount` of the vaults' LP
+pub fn meteora_amm_pool(
+  token_a_vault: &Arc<RwLock<MeteoraVault>>,
+  token_b_vault: &Arc<RwLock<MeteoraVault>>,
+  token_a_lp_amount: u128,
+  token_b_lp_amount: u128,
+) -> MeteoraAmmPool {
+  MeteoraAmmPool {
+    info: Pool {
+      pool_address: Pubkey::new_unique(),
+      token_a_address: token_a_vault.read().unwrap().token_address,
+      token_b_address: token_b_vault.read().unwrap().token_address,
+      token_a_vault_address: token_a_vault.read().unwrap().vault,
+      token_b_vault_address: token_b_vault.read().unwrap().vault,
+      pool_type: Pools::MeteoraAmm,
+      last_updated_slot: 0,
+      hydrated: true,
+    },
+    enabled: true,
+    token_a_vault: token_a_vault.clone(),
+    token_b_vault: token_b_vault.clone(),
+    token_a_fee_address: Pubkey::new_unique(),
+    token_b_fee_address: Pubkey::new_unique(),
+    token_a_lp_vault_address: Pubkey::new_unique(),
+    token_b_lp_vault_address: Pubkey::new_unique(),
+    token_a_lp_amount,
+    token_b_lp_amount,
+    trade_fee_numerator: 25,
+    trade_fee_denominator: 10_000,
+    protocol_trade_fee_numerator: 0,
+    protocol_trade_fee_denominator: 10_000,
+  }
+}
+
+/// Pumpswap create_pool instruction data
+pub fn pumpswap_create_pool_data(
+  base_amount_in: u64,
+  quote_amount_in: u64,
+  coin_creator: &Pubkey,
+) -> Vec<u8> {
+  let mut data = PUMP_CONSTANTS
+    .create_pool_instruction_discriminator
+    .to_vec();
+  data.extend(0u16.to_le_bytes());
+  data.extend(base_amount_in.to_le_bytes());
+  data.extend(quote_amount_in.to_le_bytes());
+  data.extend(coin_creator.to_bytes());
+  data
+}
+
+/// Hydrated Pumpswap pool with unique addresses holding the given reserves, created at `slot`
+pub fn pumpswap_pool(base_amount: u64, quote_amount: u64, slot: u64) -> PumpswapPool {
+  let (keys, accounts) = keys_with_program(11, PUMP_CONSTANTS.pump_swap_program);
+  let data = pumpswap_create_pool_data(base_amount, quote_amount, &Pubkey::new_unique());
+  PumpswapPool::from_create_pool_instruction(&instruction(&keys, &accounts, &data), slot).unwrap()
+}
+
+/// Raw Pumpswap pool account data trading `base_mint` against `quote_mint` out of the given vaults,
+/// with a unique LP mint
+pub fn pumpswap_pool_data(
+  base_mint: &Pubkey,
+  quote_mint: &Pubkey,
+  base_vault: &Pubkey,
+  quote_vault: &Pubkey,
+) -> Vec<u8> {
+  let mut data = vec![0u8; POOLS_ACCOUNT_SIZES.pump_swap];
+  data[..8].copy_from_slice(&PumpAmmPoolAccount::DISCRIMINATOR);
+  let base_mint_offset = PumpAmmPoolAccount::BASE_MINT_OFFSET;
+  data[base_mint_offset..base_mint_offset + 32].copy_from_slice(base_mint.as_ref());
+  let quote_mint_offset = PumpAmmPoolAccount::QUOTE_MINT_OFFSET;
+  // Quote mint, LP mint, base vault and quote vault
+  let lp_mint = Pubkey::new_unique();
+  for (offset, address) in [quote_mint, &lp_mint, base_vault, quote_vault]
+    .into_iter()
+    .enumerate()
+  {
+    let offset = quote_mint_offset + offset * 32;
+    data[offset..offset + 32].copy_from_slice(address.as_ref());
+  }
+  data
+}
+
+/// Bonding curve with unique addresses that hasn't completed yet
+pub fn pf_bonding_curve() -> PfBondingCurve {
+  PfBondingCurve {
+    virtual_sol_reserves: 30_000_000_000,
+    virtual_token_reserves: 1_073_000_000_000_000,
+    complete: false,
+    token_address: Pubkey::new_unique(),
+    bonding_curve_address: Pubkey::new_unique(),
+    bonding_curve_associated_token_account_address: Pubkey::new_unique(),
+    creator_vault_address: Pubkey::new_unique(),
+    last_updated_slot: 0,
+  }
+}
+
+/// Raw bonding curve account data with the given virtual reserves and completion flag
+pub fn pf_bonding_curve_data(
+  virtual_token_reserves: u64,
+  virtual_sol_reserves: u64,
+  complete: bool,
+) -> Vec<u8> {
+  let mut data = vec![0u8; 150];
+  data[8..16].copy_from_slice(&virtual_token_reserves.to_le_bytes());
+  data[16..24].copy_from_slice(&virtual_sol_reserves.to_le_bytes());
+  data[48] = complete as u8;
+  data
+}
+
+/// Raydium CPMM pool with unique addresses that opens at `open_time`
+pub fn raydium_cpmm_pool(open_time: u64) -> RaydiumCpmmPool {
+  RaydiumCpmmPool {
+    info: Pool {
+      pool_address: Pubkey::new_unique(),
+      token_a_address: Pubkey::new_unique(),
+      token_b_address: Pubkey::new_unique(),
+      token_a_vault_address: Pubkey::new_unique(),
+      token_b_vault_address: Pubkey::new_unique(),
+      pool_type: Pools::RaydiumCpmm,
+      last_updated_slot: 0,
+      hydrated: true,
+    },
+    pool_config_account: Pubkey::new_unique(),
+    fee_fraction_lp: 2_500_000,
+    observation_state_account: Pubkey::new_unique(),
+    token_a_vault_amount: 1_000,
+    token_b_vault_amount: 2_000,
+    protocol_fees_token_a: 0,
+    protocol_fees_token_b: 0,
+    fund_fees_token_a: 0,
+    fund_fees_token_b: 0,
+    creator_fees_token_a: 0,
+    creator_fees_token_b: 0,
+    open_time,
+  }
+}
+
+/// Raw Raydium launchpad pool account data with the given mints, vaults, reserves and status
+pub fn launchpad_pool_data(
+  base_mint: &Pubkey,
+  quote_mint: &Pubkey,
+  virtual_reserves: (u64, u64),
+  real_reserves: (u64, u64),
+  status: u8,
+) -> Vec<u8> {
+  let mut data = vec![0u8; POOLS_ACCOUNT_SIZES.raydium_launchpad];
+  data[..8].copy_from_slice(&LaunchpadPoolIdl::DISCRIMINATOR);
+  data[17] = status;
+  // Migrate to CPMM
+  data[20] = 1;
+  data[37..45].copy_from_slice(&virtual_reserves.0.to_le_bytes());
+  data[45..53].copy_from_slice(&virtual_reserves.1.to_le_bytes());
+  data[53..61].copy_from_slice(&real_reserves.0.to_le_bytes());
+  data[61..69].copy_from_slice(&real_reserves.1.to_le_bytes());
+  let base_mint_offset = LaunchpadPoolIdl::BASE_MINT_OFFSET;
+  data[base_mint_offset..base_mint_offset + 32].copy_from_slice(base_mint.as_ref());
+  let quote_mint_offset = LaunchpadPoolIdl::QUOTE_MINT_OFFSET;
+  data[quote_mint_offset..quote_mint_offset + 32].copy_from_slice(quote_mint.as_ref());
+  // Base vault, quote vault and creator
+  for offset in [32, 64, 96] {
+    let offset = quote_mint_offset + offset;
+    data[offset..offset + 32].copy_from_slice(Pubkey::new_unique().as_ref());
+  }
+  data
+}
+
+/// Raw Meteora DBC virtual pool account data using `config` with the given base mint and reserves
+pub fn dbc_virtual_pool_data(
+  config: &Pubkey,
+  base_mint: &Pubkey,
+  base_reserve: u64,
+  quote_reserve: u64,
+) -> Vec<u8> {
+  let mut data = vec![0u8; POOLS_ACCOUNT_SIZES.meteora_dbc];
+  data[..8].copy_from_slice(&DbcVirtualPool::DISCRIMINATOR);
+  let config_offset = DbcVirtualPool::CONFIG_OFFSET;
+  data[config_offset..config_offset + 32].copy_from_slice(config.as_ref());
+  let base_mint_offset = DbcVirtualPool::BASE_MINT_OFFSET;
+  data[base_mint_offset..base_mint_offset + 32].copy_from_slice(base_mint.as_ref());
+  // Base and quote vaults
+  for offset in [32, 64] {
+    let offset = base_mint_offset + offset;
+    data[offset..offset + 32].copy_from_slice(Pubkey::new_unique().as_ref());
+  }
+  data[232..240].copy_from_slice(&base_reserve.to_le_bytes());
+  data[240..248].copy_from_slice(&quote_reserve.to_le_bytes());
+  data
+}
+
+/// Raw (1048 bytes) Meteora DBC pool config account data with `quote_mint` and every other field
+/// zeroed
+pub fn dbc_pool_config_data(quote_mint: &Pubkey) -> Vec<u8> {
+  let mut data = vec![0u8; 1048];
+  data[8..40].copy_from_slice(quote_mint.as_ref());
+  data
+}
@@ -0,0 +1,73 @@
+use solana_central::constants::PUMP_CONSTANTS;
+use solana_central::{DecodedTransaction, PoolTrait, PumpswapPool};
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta;
+use solana_transaction_status_client_types::option_serializer::OptionSerializer;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+fn load_transaction(name: &str) -> EncodedConfirmedTransactionWithStatusMeta {
+  let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+    .join("tests/fixtures/transactions")
+    .join(name);
+  serde_json::from_slice(&fs::read(path).unwrap()).unwrap()
+}
+
+fn pubkey(address: &str) -> Pubkey {
+  Pubkey::from_str(address).unwrap()
+}
+
+#[test]
+fn decode_saved_v0_transaction() {
+  let encoded = load_transaction("pumpswap_create_pool_v0.json");
+  let decoded = DecodedTransaction::from_encoded_confirmed_transaction(&encoded).unwrap();
+  let creator = pubkey("DgX9xEoN7RZGWevFVCy13JuzKsnmAx9B3VLfvoJxwqKn");
+  let pool_address = pubkey("3gLESRnfLgzAqu6PwGhBwsiBsnQ7BAtyWHhZ5zNcDPMF");
+  let pool_base_vault = pubkey("7ABTZ2e6WivscQhRnzKcxeCGCGxHaydHNwzofaEhpcJV");
+  let base_mint = pubkey("7Wq5JxR153ykwQ2FxYGu9eKt1NNSKuh3vkqbBYwWi5uf");
+  let wsol = pubkey("So11111111111111111111111111111111111111112");
+
+  assert_eq!(decoded.slot, 350_000_000);
+  assert_eq!(decoded.block_time, Some(1_760_000_000));
+  assert!(decoded.err.is_none());
+  assert_eq!(decoded.log_messages.len(), 7);
+  // 11 static keys, then the 2 writable and the 1 readonly loaded address
+  assert_eq!(decoded.tx_account_keys.len(), 14);
+  assert_eq!(decoded.tx_account_keys[11], pool_base_vault);
+  assert_eq!(decoded.tx_account_keys[13], wsol);
+  assert!(decoded.message().address_table_lookups().is_some());
+
+  let instructions = decoded.instructions();
+  let stack_heights: Vec<_> = instructions
+    .iter()
+    .map(|instruction| instruction.stack_height)
+    .collect();
+  assert_eq!(stack_heights, vec![1, 1, 2]);
+  assert_eq!(
+    instructions[1].program_id(),
+    Some(&PUMP_CONSTANTS.pump_swap_program)
+  );
+  // The inner token transfer moves the base tokens into the pool vault loaded from the table
+  assert_eq!(instructions[2].account(1), Some(&pool_base_vault));
+
+  let pool = PumpswapPool::from_create_pool_instruction(&instructions[1], decoded.slot).unwrap();
+  assert_eq!(*pool.pool_address(), pool_address);
+  assert_eq!(*pool.token_a_address(), base_mint);
+  assert_eq!(*pool.token_b_address(), wsol);
+  assert_eq!(*pool.token_b_vault_address(), decoded.tx_account_keys[12]);
+  assert_eq!(pool.token_a_amount_units(), 200_000_000_000_000);
+  assert_eq!(pool.token_b_amount_units(), 80_000_000_000);
+
+  let owners = decoded.token_account_owners();
+  assert_eq!(owners[&decoded.tx_account_keys[3]], creator);
+  assert_eq!(owners[&pool_base_vault], pool_address);
+}
+
+#[test]
+fn transactions_without_loaded_addresses_are_rejected() {
+  let mut encoded = load_transaction("pumpswap_create_pool_v0.json");
+  // A v0 transaction using lookup tables can't be resolved without them
+  encoded.transaction.meta.as_mut().unwrap().loaded_addresses = OptionSerializer::None;
+  assert!(DecodedTransaction::from_encoded_confirmed_transaction(&encoded).is_none());
+}
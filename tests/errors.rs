@@ -0,0 +1,180 @@
+mod common;
+
+use common::{account, fixture_context, pumpswap_pool, pumpswap_pool_data};
+use solana_central::constants::{METEORA_CONSTANTS, POOLS_ACCOUNT_SIZES, PUMP_CONSTANTS, TOKENS};
+use solana_central::{
+  Error, MeteoraDammV2Pool, PfBondingCurve, PoolTrait, PumpswapPool, RaydiumAmmV4Pool,
+  RaydiumCpmmPool, RaydiumLaunchpad, derive_bonding_curve, load_pools,
+};
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_sdk::pubkey::Pubkey;
+use std::error::Error as _;
+
+/// Offsets of the mints, `collect_fee_mode` and `version` in a DAMMv2 pool account
+const DAMMV2_TOKEN_A_MINT_OFFSET: usize = 168;
+const DAMMV2_COLLECT_FEE_MODE_OFFSET: usize = 484;
+const DAMMV2_VERSION_OFFSET: usize = 486;
+
+/// DAMMv2 pool account data of two new mints, with `collect_fee_mode` and `version` set
+fn dammv2_pool_data(collect_fee_mode: u8, version: u8) -> Vec<u8> {
+  let mut data = vec![0; POOLS_ACCOUNT_SIZES.meteora_dammv2];
+  for offset in [DAMMV2_TOKEN_A_MINT_OFFSET, DAMMV2_TOKEN_A_MINT_OFFSET + 32] {
+    data[offset..offset + 32].copy_from_slice(Pubkey::new_unique().as_ref());
+  }
+  data[DAMMV2_COLLECT_FEE_MODE_OFFSET] = collect_fee_mode;
+  data[DAMMV2_VERSION_OFFSET] = version;
+  data
+}
+
+/// Error of a decoding that must fail, pool types don't all implement `Debug`
+fn expect_err<T>(result: Result<T, Error>) -> Error {
+  result.err().expect("decoding should fail")
+}
+
+#[test]
+fn short_accounts_fail_to_decode_with_their_address() {
+  let pubkey = Pubkey::new_unique();
+  let data = [0u8; 16];
+  let errors = [
+    expect_err(PumpswapPool::try_from_account_info(pubkey, &data)),
+    expect_err(RaydiumAmmV4Pool::try_from_account_info(pubkey, &data)),
+    expect_err(RaydiumCpmmPool::try_from_account_info(pubkey, &data)),
+    expect_err(RaydiumLaunchpad::try_from_account_info(pubkey, &data)),
+    expect_err(MeteoraDammV2Pool::try_from_account_info(pubkey, &data)),
+  ];
+  for error in errors {
+    assert!(
+      matches!(&error, Error::Decode { account, .. } if *account == pubkey),
+      "{:?}",
+      error
+    );
+    assert!(
+      error
+        .to_string()
+        .starts_with(&format!("Failed to decode account {}: ", pubkey))
+    );
+    assert!(error.source().is_none());
+  }
+  assert_eq!(
+    PumpswapPool::try_from_account_info(pubkey, &data)
+      .unwrap_err()
+      .to_string(),
+    format!(
+      "Failed to decode account {}: expected 300 bytes, got 16",
+      pubkey
+    )
+  );
+
+  // Bonding curves report the curve derived from the token, not the token
+  let token_address = Pubkey::new_unique();
+  let error = PfBondingCurve::try_from_account_info(token_address, &data).unwrap_err();
+  assert!(matches!(
+    error,
+    Error::Decode { account, .. } if account == derive_bonding_curve(&token_address)
+  ));
+}
+
+#[test]
+fn unsupported_dammv2_layouts_are_rejected() {
+  let pubkey = Pubkey::new_unique();
+  assert!(MeteoraDammV2Pool::try_from_account_info(pubkey, &dammv2_pool_data(1, 1)).is_ok());
+
+  let error =
+    MeteoraDammV2Pool::try_from_account_info(pubkey, &dammv2_pool_data(0, 2)).unwrap_err();
+  assert!(matches!(&error, Error::UnsupportedLayout { account, .. } if *account == pubkey));
+  assert_eq!(
+    error.to_string(),
+    format!(
+      "Unsupported layout for account {}: unknown DAMMv2 pool version 2",
+      pubkey
+    )
+  );
+
+  let error =
+    MeteoraDammV2Pool::try_from_account_info(pubkey, &dammv2_pool_data(2, 0)).unwrap_err();
+  assert_eq!(
+    error.to_string(),
+    format!(
+      "Unsupported layout for account {}: unknown DAMMv2 collect fee mode 2",
+      pubkey
+    )
+  );
+}
+
+#[test]
+fn undefined_prices_are_arithmetic_errors() {
+  let pool = pumpswap_pool(1_000, 0, 0);
+  let error = pool.try_price_a_over_b_lp().unwrap_err();
+  assert!(matches!(
+    error,
+    Error::Arithmetic {
+      operation: "price_a_over_b_lp"
+    }
+  ));
+  assert_eq!(
+    error.to_string(),
+    "Arithmetic overflow or division by zero in price_a_over_b_lp"
+  );
+  // The infallible variant reports the undefined price as 0
+  assert_eq!(pool.price_a_over_b_lp(), 0);
+  assert!(pool.try_price_b_over_a_lp().is_ok());
+}
+
+#[test]
+fn client_errors_convert_into_rpc_errors() {
+  let error: Error = ClientError::from(ClientErrorKind::Custom("node down".to_string())).into();
+  assert!(matches!(error, Error::Rpc(_)));
+  assert_eq!(error.to_string(), "RPC error: Custom: node down");
+  assert_eq!(error.source().unwrap().to_string(), "Custom: node down");
+}
+
+// The load fetches the CPMM configs with the blocking client
+#[tokio::test(flavor = "multi_thread")]
+async fn loaders_skip_malformed_accounts() {
+  let (backend, central_context) = fixture_context();
+  let supported = Pubkey::new_unique();
+  let unsupported = Pubkey::new_unique();
+  backend.set_account(
+    supported,
+    account(METEORA_CONSTANTS.dammv2_program, dammv2_pool_data(0, 1)),
+  );
+  backend.set_account(
+    unsupported,
+    account(METEORA_CONSTANTS.dammv2_program, dammv2_pool_data(0, 7)),
+  );
+  let pumpswap_address = Pubkey::new_unique();
+  backend.set_account(
+    pumpswap_address,
+    account(
+      PUMP_CONSTANTS.pump_swap_program,
+      pumpswap_pool_data(
+        &Pubkey::new_unique(),
+        &TOKENS.wsol,
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+      ),
+    ),
+  );
+
+  load_pools(
+    &[
+      (
+        METEORA_CONSTANTS.dammv2_program,
+        POOLS_ACCOUNT_SIZES.meteora_dammv2,
+      ),
+      (
+        PUMP_CONSTANTS.pump_swap_program,
+        POOLS_ACCOUNT_SIZES.pump_swap,
+      ),
+    ],
+    central_context.clone(),
+    2,
+    false,
+  )
+  .await;
+
+  let pools_map = central_context.pools_map.read().unwrap();
+  assert!(pools_map.contains_key(&supported));
+  assert!(pools_map.contains_key(&pumpswap_address));
+  assert!(!pools_map.contains_key(&unsupported));
+}
@@ -0,0 +1,207 @@
+use serde_json::{Value, json};
+use solana_central::{CentralContextBuilder, FailoverRpcSender, RetryPolicy};
+use solana_client::client_error::ClientErrorKind;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::RpcClientConfig;
+use solana_client::rpc_request::RpcError;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// HTTP JSON RPC endpoint answering every request with the same status and JSON RPC payload
+struct Endpoint {
+  url: String,
+  requests: Arc<AtomicUsize>,
+}
+
+impl Endpoint {
+  /// Serve `payload` (a `result` or `error` member) with HTTP `status` on a local port
+  async fn spawn(status: u16, payload: Value) -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(AtomicUsize::new(0));
+    let request_count = requests.clone();
+    tokio::spawn(async move {
+      loop {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let request = read_request(&mut stream).await;
+        request_count.fetch_add(1, Ordering::SeqCst);
+        let mut body = payload.clone();
+        body["jsonrpc"] = json!("2.0");
+        body["id"] = request["id"].clone();
+        let body = body.to_string();
+        let response = format!(
+          "HTTP/1.1 {} Test\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+          status,
+          body.len(),
+          body
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+        let _ = stream.shutdown().await;
+      }
+    });
+    Self { url, requests }
+  }
+
+  /// Healthy endpoint answering every request with `result`
+  async fn healthy(result: Value) -> Self {
+    Self::spawn(200, json!({ "result": result })).await
+  }
+
+  /// Endpoint answering every request with a JSON RPC error `code`
+  async fn rpc_error(code: i64) -> Self {
+    Self::spawn(
+      200,
+      json!({ "error": { "code": code, "message": "test error" } }),
+    )
+    .await
+  }
+
+  fn requests(&self) -> usize {
+    self.requests.load(Ordering::SeqCst)
+  }
+}
+
+/// Read one HTTP request and parse its JSON body
+async fn read_request(stream: &mut TcpStream) -> Value {
+  let mut data = Vec::new();
+  let mut buffer = [0u8; 4096];
+  loop {
+    let read = stream.read(&mut buffer).await.unwrap();
+    assert!(read > 0, "connection closed mid request");
+    data.extend_from_slice(&buffer[..read]);
+    let Some(headers_end) = data.windows(4).position(|window| window == b"\r\n\r\n") else {
+      continue;
+    };
+    let headers = String::from_utf8_lossy(&data[..headers_end]).to_lowercase();
+    let content_length: usize = headers
+      .lines()
+      .find_map(|line| line.strip_prefix("content-length:"))
+      .map(|length| length.trim().parse().unwrap())
+      .unwrap_or(0);
+    let body_start = headers_end + 4;
+    if data.len() >= body_start + content_length {
+      return serde_json::from_slice(&data[body_start..body_start + content_length]).unwrap();
+    }
+  }
+}
+
+fn retry_policy(max_retries: u32) -> RetryPolicy {
+  RetryPolicy {
+    max_retries,
+    initial_backoff: Duration::from_millis(20),
+    max_backoff: Duration::from_millis(30),
+  }
+}
+
+fn client(endpoints: &[&Endpoint], retry_policy: RetryPolicy) -> RpcClient {
+  let urls: Vec<String> = endpoints
+    .iter()
+    .map(|endpoint| endpoint.url.clone())
+    .collect();
+  RpcClient::new_sender(
+    FailoverRpcSender::new(&urls, Duration::from_secs(5), retry_policy),
+    RpcClientConfig::default(),
+  )
+}
+
+#[tokio::test]
+async fn failing_primary_fails_over_and_fallback_stays_active() {
+  let primary = Endpoint::spawn(503, json!({ "result": 1 })).await;
+  let fallback = Endpoint::healthy(json!(42)).await;
+  let client = client(&[&primary, &fallback], retry_policy(0));
+
+  assert_eq!(client.get_slot().await.unwrap(), 42);
+  assert_eq!((primary.requests(), fallback.requests()), (1, 1));
+  assert_eq!(client.url(), fallback.url);
+
+  // The endpoint that last answered is tried first
+  assert_eq!(client.get_slot().await.unwrap(), 42);
+  assert_eq!((primary.requests(), fallback.requests()), (1, 2));
+}
+
+#[tokio::test]
+async fn unhealthy_and_lagging_nodes_fail_over() {
+  // Node unhealthy, minimum context slot not reached
+  for code in [-32005, -32016] {
+    let primary = Endpoint::rpc_error(code).await;
+    let fallback = Endpoint::healthy(json!(42)).await;
+    let client = client(&[&primary, &fallback], retry_policy(0));
+    assert_eq!(client.get_slot().await.unwrap(), 42);
+    assert_eq!((primary.requests(), fallback.requests()), (1, 1));
+  }
+}
+
+#[tokio::test]
+async fn request_errors_are_returned_without_failing_over() {
+  // Invalid params, every endpoint would answer the same
+  let primary = Endpoint::rpc_error(-32602).await;
+  let fallback = Endpoint::healthy(json!(42)).await;
+  let client = client(&[&primary, &fallback], retry_policy(2));
+
+  let error = client.get_slot().await.unwrap_err();
+  assert!(matches!(
+    error.kind(),
+    ClientErrorKind::RpcError(RpcError::RpcResponseError { code: -32602, .. })
+  ));
+  assert_eq!((primary.requests(), fallback.requests()), (1, 0));
+}
+
+#[tokio::test]
+async fn failed_passes_are_retried_with_backoff() {
+  let primary = Endpoint::spawn(503, json!({ "result": 1 })).await;
+  let fallback = Endpoint::rpc_error(-32005).await;
+  let client = client(&[&primary, &fallback], retry_policy(2));
+
+  let start = Instant::now();
+  let error = client.get_slot().await.unwrap_err();
+  // 20ms then 30ms (capped) between the 3 passes
+  assert!(start.elapsed() >= Duration::from_millis(50));
+  // The last endpoint's error is returned
+  assert!(matches!(
+    error.kind(),
+    ClientErrorKind::RpcError(RpcError::RpcResponseError { code: -32005, .. })
+  ));
+  assert_eq!((primary.requests(), fallback.requests()), (3, 3));
+}
+
+#[tokio::test]
+async fn unreachable_endpoints_fail_over() {
+  // Nothing listens on the port of a dropped listener
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let unreachable_url = format!("http://{}", listener.local_addr().unwrap());
+  drop(listener);
+  let fallback = Endpoint::healthy(json!(42)).await;
+
+  let central_context = CentralContextBuilder::new()
+    .endpoint(unreachable_url)
+    .endpoint(fallback.url.clone())
+    .timeout(Duration::from_secs(5))
+    .retry_policy(retry_policy(0))
+    .build()
+    .unwrap();
+  assert_eq!(
+    central_context
+      .json_rpc_client_async
+      .get_slot()
+      .await
+      .unwrap(),
+    42
+  );
+  assert_eq!(fallback.requests(), 1);
+}
+
+#[test]
+fn building_without_endpoint_or_backend_fails() {
+  assert!(CentralContextBuilder::new().build().is_err());
+  assert_eq!(
+    RetryPolicy::default(),
+    RetryPolicy {
+      max_retries: 2,
+      initial_backoff: Duration::from_millis(500),
+      max_backoff: Duration::from_secs(10),
+    }
+  );
+}
@@ -0,0 +1,220 @@
+mod common;
+
+use common::{
+  account, fixture_context, meteora_amm_pool, meteora_vault, meteora_vault_data, mint_data,
+  pumpswap_pool, token_account_data,
+};
+use solana_central::constants::{METEORA_CONSTANTS, PUMP_CONSTANTS, SOLANA_PROGRAMS};
+use solana_central::{
+  BackpressurePolicy, Error, FixtureRpcBackend, MarketSubscription, PoolTrait, PumpswapPool,
+};
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::{Arc, RwLock};
+
+/// Store the pool account and vault balances of `pool` in `backend`
+fn set_pumpswap_accounts(
+  backend: &FixtureRpcBackend,
+  pool: &PumpswapPool,
+  base_amount: u64,
+  quote_amount: u64,
+) {
+  let pool_address = *pool.pool_address();
+  for (vault, mint, amount) in [
+    (
+      *pool.token_a_vault_address(),
+      *pool.token_a_address(),
+      base_amount,
+    ),
+    (
+      *pool.token_b_vault_address(),
+      *pool.token_b_address(),
+      quote_amount,
+    ),
+  ] {
+    backend.set_account(
+      vault,
+      account(
+        SOLANA_PROGRAMS.token_program,
+        token_account_data(&mint, &pool_address, amount),
+      ),
+    );
+  }
+  backend.set_account(
+    pool_address,
+    account(PUMP_CONSTANTS.pump_swap_program, vec![0; 8]),
+  );
+}
+
+#[tokio::test]
+async fn async_fetch_rejects_state_older_than_the_pool() {
+  let (backend, central_context) = fixture_context();
+  let pool = pumpswap_pool(1_000, 2_000, 1);
+  let (pool_address, token_a, token_a_vault) = (
+    *pool.pool_address(),
+    *pool.token_a_address(),
+    *pool.token_a_vault_address(),
+  );
+  set_pumpswap_accounts(&backend, &pool, 4_000, 3_000);
+  let pool = Arc::new(RwLock::new(pool));
+  central_context.insert_pool(pool.clone());
+  central_context.apply_account_update(
+    &token_a_vault,
+    &SOLANA_PROGRAMS.token_program,
+    &token_account_data(&token_a, &pool_address, 9_000),
+    50,
+    None,
+  );
+
+  backend.set_slot(40);
+  central_context
+    .fetch_market_state_from_rpc_async(&pool_address)
+    .await
+    .unwrap();
+  let pool = pool.read().unwrap();
+  assert_eq!(pool.token_a_amount_units(), 9_000);
+  assert_eq!(pool.token_b_amount_units(), 3_000);
+  assert_eq!(pool.last_updated_slot(), 50);
+}
+
+#[tokio::test]
+async fn async_fetch_hydrates_meteora_pool_with_its_actual_lp_mint() {
+  let (backend, central_context) = fixture_context();
+  let updates = central_context.subscribe_market_updates(
+    MarketSubscription::All,
+    8,
+    BackpressurePolicy::DropNewest,
+  );
+  let (token_a_vault, token_b_vault) = (meteora_vault(0, 0), meteora_vault(0, 0));
+  let mut pool = meteora_amm_pool(&token_a_vault, &token_b_vault, 0, 0);
+  pool.info.hydrated = false;
+  let pool_address = pool.info.pool_address;
+  // The first vault's LP mint differs from the derived one, only known from the vault account
+  let actual_lp_mint = Pubkey::new_unique();
+  for (vault, total_amount, lp_mint, lp_supply) in [
+    (&token_a_vault, 1_000, actual_lp_mint, 500),
+    (
+      &token_b_vault,
+      2_000,
+      token_b_vault.read().unwrap().lp_token_address,
+      1_000,
+    ),
+  ] {
+    let vault = vault.read().unwrap();
+    backend.set_account(
+      vault.vault,
+      account(
+        METEORA_CONSTANTS.vault_program,
+        meteora_vault_data(total_amount, &lp_mint),
+      ),
+    );
+    backend.set_account(
+      lp_mint,
+      account(SOLANA_PROGRAMS.token_program, mint_data(9, lp_supply, None)),
+    );
+  }
+  for (lp_vault, lp_mint, amount) in [
+    (pool.token_a_lp_vault_address, actual_lp_mint, 100),
+    (
+      pool.token_b_lp_vault_address,
+      token_b_vault.read().unwrap().lp_token_address,
+      500,
+    ),
+  ] {
+    backend.set_account(
+      lp_vault,
+      account(
+        SOLANA_PROGRAMS.token_program,
+        token_account_data(&lp_mint, &pool_address, amount),
+      ),
+    );
+  }
+  backend.set_account(
+    pool_address,
+    account(METEORA_CONSTANTS.amm_program, vec![0; 8]),
+  );
+  let pool = Arc::new(RwLock::new(pool));
+  central_context.insert_pool(pool.clone());
+
+  central_context
+    .fetch_market_state_from_rpc_async(&pool_address)
+    .await
+    .unwrap();
+  assert_eq!(
+    token_a_vault.read().unwrap().lp_token_address,
+    actual_lp_mint
+  );
+  assert_eq!(token_a_vault.read().unwrap().lp_supply, 500);
+  let pool = pool.read().unwrap();
+  assert!(pool.is_hydrated());
+  assert_eq!(pool.token_a_amount_units(), 200);
+  assert_eq!(pool.token_b_amount_units(), 1_000);
+  // Later LP mint updates reach the pool
+  assert!(
+    central_context
+      .account_dependents
+      .read()
+      .unwrap()
+      .contains_key(&actual_lp_mint)
+  );
+  let update = updates.try_iter().last().unwrap();
+  assert_eq!(update.market_address, pool_address);
+  assert_eq!(update.price_a_b, 200_000_000);
+}
+
+#[tokio::test]
+async fn async_fetch_reports_missing_accounts_and_unknown_pools() {
+  let (backend, central_context) = fixture_context();
+  let updates = central_context.subscribe_market_updates(
+    MarketSubscription::All,
+    8,
+    BackpressurePolicy::DropNewest,
+  );
+  let mut pool = pumpswap_pool(0, 0, 0);
+  pool.info.hydrated = false;
+  let (pool_address, token_b_vault) = (*pool.pool_address(), *pool.token_b_vault_address());
+  set_pumpswap_accounts(&backend, &pool, 1_000, 2_000);
+  backend.remove_account(&token_b_vault);
+  let pool = Arc::new(RwLock::new(pool));
+  central_context.insert_pool(pool.clone());
+
+  let error = central_context
+    .fetch_market_state_from_rpc_async(&pool_address)
+    .await
+    .unwrap_err();
+  assert!(matches!(error, Error::Rpc(_)));
+  assert!(error.to_string().contains(&token_b_vault.to_string()));
+  assert!(!pool.read().unwrap().is_hydrated());
+  assert!(updates.try_recv().is_err());
+
+  let unknown_pool = Pubkey::new_unique();
+  assert!(matches!(
+    central_context
+      .fetch_market_state_from_rpc_async(&unknown_pool)
+      .await,
+    Err(Error::InvalidPool { pool, .. }) if pool == unknown_pool
+  ));
+}
+
+#[tokio::test]
+async fn async_slot_fetch_does_not_move_the_slot_back() {
+  let (backend, central_context) = fixture_context();
+  let blockhash = Hash::new_unique();
+  backend.set_slot(50);
+  backend.set_latest_blockhash(blockhash);
+  central_context.apply_slot_update(60);
+
+  central_context
+    .fetch_current_slot_blockhash_async()
+    .await
+    .unwrap();
+  assert_eq!(*central_context.current_slot.read().unwrap(), 60);
+  assert_eq!(*central_context.latest_blockhash.read().unwrap(), blockhash);
+
+  backend.set_slot(70);
+  central_context
+    .fetch_current_slot_blockhash_async()
+    .await
+    .unwrap();
+  assert_eq!(*central_context.current_slot.read().unwrap(), 70);
+}
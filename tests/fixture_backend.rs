@@ -0,0 +1,89 @@
+use solana_central::constants::{POOLS_ACCOUNT_SIZES, PUMP_CONSTANTS, TOKENS};
+use solana_central::{
+  CentralContextBuilder, FixtureRpcBackend, RpcBackend, is_legit_token, load_pools,
+};
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Accounts of `tests/fixtures`, see its README
+const POOL_ADDRESS: Pubkey = pubkey!("5EtnVqKsGT9q2nXK4ArmPnQfqbEKozjRmMQvD5yDjfp6");
+const BASE_MINT: Pubkey = pubkey!("EzmzoVnQXr23QvCyDgMUU11Ypm7DJ6y2SjXvqoSKEagC");
+const UNKNOWN_MINT: Pubkey = pubkey!("5EzRkUrYoPoBeYVHCNAydKUtxxGNR2JcjPxdzMZkP7er");
+
+fn fixture_backend() -> FixtureRpcBackend {
+  FixtureRpcBackend::from_directory(&Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures"))
+    .unwrap()
+}
+
+// The blocking RPC client needs the multi threaded runtime
+#[tokio::test(flavor = "multi_thread")]
+async fn pools_load_refresh_and_validate_offline() {
+  let backend = Arc::new(fixture_backend());
+  backend.set_slot(350_000_000);
+  let central_context = Arc::new(
+    CentralContextBuilder::new()
+      .rpc_backend(backend.clone())
+      .build()
+      .unwrap(),
+  );
+
+  load_pools(
+    &[(
+      PUMP_CONSTANTS.pump_swap_program,
+      POOLS_ACCOUNT_SIZES.pump_swap,
+    )],
+    central_context.clone(),
+    1,
+    false,
+  )
+  .await;
+  let pool = central_context.pools_map.read().unwrap()[&POOL_ADDRESS].clone();
+  {
+    let pool = pool.read().unwrap();
+    assert_eq!(*pool.token_a_address(), BASE_MINT);
+    assert_eq!(*pool.token_b_address(), TOKENS.wsol);
+    assert!(!pool.is_hydrated());
+  }
+
+  central_context.refresh_pools(&[POOL_ADDRESS]);
+  {
+    let pool = pool.read().unwrap();
+    assert!(pool.is_hydrated());
+    assert_eq!(pool.last_updated_slot(), 350_000_000);
+    assert_eq!(pool.token_a_amount_units(), 206_900_000_000_000);
+    assert_eq!(pool.token_b_amount_units(), 84_000_000_000);
+    assert_eq!(pool.price_a_over_b_lp(), 2_463_095_238_095);
+  }
+
+  // Pumpfun metadata is legit, other update authorities and missing metadata aren't
+  assert!(is_legit_token(&BASE_MINT, &central_context));
+  assert!(!is_legit_token(&UNKNOWN_MINT, &central_context));
+  assert!(!is_legit_token(&TOKENS.wsol, &central_context));
+}
+
+#[test]
+fn fixture_directory_skips_other_files_and_subdirectories() {
+  let directory = std::env::temp_dir().join(format!("solana_central_{}", Pubkey::new_unique()));
+  fs::create_dir_all(directory.join("nested.json")).unwrap();
+  fs::write(directory.join("notes.txt"), "not an account").unwrap();
+  let fixture_path =
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/pumpswap_pool.json");
+  fs::copy(fixture_path, directory.join("pool.json")).unwrap();
+  let backend = FixtureRpcBackend::from_directory(&directory);
+
+  fs::write(directory.join("invalid.json"), "{}").unwrap();
+  let invalid_backend = FixtureRpcBackend::from_directory(&directory);
+  fs::remove_dir_all(&directory).unwrap();
+
+  let pool = backend
+    .unwrap()
+    .get_account(&POOL_ADDRESS)
+    .unwrap()
+    .unwrap();
+  assert_eq!(pool.owner, PUMP_CONSTANTS.pump_swap_program);
+  assert_eq!(pool.data.len(), POOLS_ACCOUNT_SIZES.pump_swap);
+  assert!(invalid_backend.is_err());
+}
@@ -0,0 +1,443 @@
+#![cfg(feature = "geyser")]
+
+mod common;
+
+use common::{account, fixture_context, mint_data, pumpswap_pool, token_account_data};
+use futures::StreamExt;
+use solana_central::constants::{PUMP_CONSTANTS, SOLANA_PROGRAMS, SUPPORTED_PROGRAMS};
+use solana_central::{CentralContext, DecodedTransaction, PoolTrait};
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::TransactionError;
+use std::collections::HashSet;
+use std::sync::mpsc::sync_channel;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use yellowstone_grpc_proto::prelude::geyser_server::{Geyser, GeyserServer};
+use yellowstone_grpc_proto::prelude::subscribe_update::UpdateOneof;
+use yellowstone_grpc_proto::prelude::*;
+use yellowstone_grpc_proto::tonic::codegen::BoxStream;
+use yellowstone_grpc_proto::tonic::codegen::tokio_stream::wrappers::ReceiverStream;
+use yellowstone_grpc_proto::tonic::transport::Server;
+use yellowstone_grpc_proto::tonic::transport::server::TcpIncoming;
+use yellowstone_grpc_proto::tonic::{self, Request, Response, Status, Streaming};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// What the fake geyser server saw, shared with the test
+#[derive(Default)]
+struct FakeGeyserState {
+  subscriptions: usize,
+  /// Filter requests of every stream, pings excluded
+  requests: Vec<SubscribeRequest>,
+  updates: Option<mpsc::Sender<Result<SubscribeUpdate, Status>>>,
+}
+
+/// A Yellowstone server streaming the updates pushed by the test
+#[derive(Clone, Default)]
+struct FakeGeyser(Arc<Mutex<FakeGeyserState>>);
+
+impl FakeGeyser {
+  async fn start() -> (Self, String) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    let fake_geyser = Self::default();
+    let service = GeyserServer::new(fake_geyser.clone());
+    tokio::spawn(async move {
+      Server::builder()
+        .add_service(service)
+        .serve_with_incoming(TcpIncoming::from(listener))
+        .await
+        .unwrap();
+    });
+    (fake_geyser, endpoint)
+  }
+
+  async fn send(&self, update: UpdateOneof) {
+    let updates = self.0.lock().unwrap().updates.clone().unwrap();
+    let update = SubscribeUpdate {
+      update_oneof: Some(update),
+      ..Default::default()
+    };
+    updates.send(Ok(update)).await.unwrap();
+  }
+
+  /// End the current stream
+  fn close(&self) {
+    self.0.lock().unwrap().updates = None;
+  }
+
+  fn last_request(&self) -> Option<SubscribeRequest> {
+    self.0.lock().unwrap().requests.last().cloned()
+  }
+}
+
+#[tonic::async_trait]
+impl Geyser for FakeGeyser {
+  type SubscribeStream = ReceiverStream<Result<SubscribeUpdate, Status>>;
+  type SubscribeDeshredStream = BoxStream<SubscribeUpdateDeshred>;
+  type SubscribeGossipStream = BoxStream<SubscribeUpdateGossip>;
+
+  async fn subscribe(
+    &self,
+    request: Request<Streaming<SubscribeRequest>>,
+  ) -> Result<Response<Self::SubscribeStream>, Status> {
+    let (sender, receiver) = mpsc::channel(16);
+    {
+      let mut state = self.0.lock().unwrap();
+      state.subscriptions += 1;
+      state.updates = Some(sender);
+    }
+    let state = self.0.clone();
+    let mut requests = request.into_inner();
+    tokio::spawn(async move {
+      while let Some(Ok(request)) = requests.next().await {
+        if request.ping.is_none() {
+          state.lock().unwrap().requests.push(request);
+        }
+      }
+    });
+    Ok(Response::new(ReceiverStream::new(receiver)))
+  }
+
+  async fn subscribe_deshred(
+    &self,
+    _: Request<Streaming<SubscribeDeshredRequest>>,
+  ) -> Result<Response<Self::SubscribeDeshredStream>, Status> {
+    Err(Status::unimplemented("subscribe_deshred"))
+  }
+
+  async fn subscribe_gossip(
+    &self,
+    _: Request<SubscribeGossipRequest>,
+  ) -> Result<Response<Self::SubscribeGossipStream>, Status> {
+    Err(Status::unimplemented("subscribe_gossip"))
+  }
+
+  async fn subscribe_replay_info(
+    &self,
+    _: Request<SubscribeReplayInfoRequest>,
+  ) -> Result<Response<SubscribeReplayInfoResponse>, Status> {
+    Err(Status::unimplemented("subscribe_replay_info"))
+  }
+
+  async fn ping(&self, _: Request<PingRequest>) -> Result<Response<PongResponse>, Status> {
+    Err(Status::unimplemented("ping"))
+  }
+
+  async fn get_latest_blockhash(
+    &self,
+    _: Request<GetLatestBlockhashRequest>,
+  ) -> Result<Response<GetLatestBlockhashResponse>, Status> {
+    Err(Status::unimplemented("get_latest_blockhash"))
+  }
+
+  async fn get_block_height(
+    &self,
+    _: Request<GetBlockHeightRequest>,
+  ) -> Result<Response<GetBlockHeightResponse>, Status> {
+    Err(Status::unimplemented("get_block_height"))
+  }
+
+  async fn get_slot(
+    &self,
+    _: Request<GetSlotRequest>,
+  ) -> Result<Response<GetSlotResponse>, Status> {
+    Err(Status::unimplemented("get_slot"))
+  }
+
+  async fn is_blockhash_valid(
+    &self,
+    _: Request<IsBlockhashValidRequest>,
+  ) -> Result<Response<IsBlockhashValidResponse>, Status> {
+    Err(Status::unimplemented("is_blockhash_valid"))
+  }
+
+  async fn get_version(
+    &self,
+    _: Request<GetVersionRequest>,
+  ) -> Result<Response<GetVersionResponse>, Status> {
+    Err(Status::unimplemented("get_version"))
+  }
+}
+
+/// Poll `condition` until it holds, failing after 5s
+async fn wait_until(mut condition: impl FnMut() -> bool) {
+  let start = Instant::now();
+  while !condition() {
+    assert!(start.elapsed() < Duration::from_secs(5), "timed out");
+    tokio::time::sleep(Duration::from_millis(10)).await;
+  }
+}
+
+fn subscribed_accounts(request: &SubscribeRequest) -> HashSet<Pubkey> {
+  request.accounts["dependent_accounts"]
+    .account
+    .iter()
+    .map(|account| account.parse().unwrap())
+    .collect()
+}
+
+/// A legacy transaction of `signer` calling the pumpswap program with one inner token transfer,
+/// failed with `err` if set
+fn transaction_info(
+  signer: &Pubkey,
+  token_account: &Pubkey,
+  err: Option<TransactionError>,
+) -> SubscribeUpdateTransactionInfo {
+  let account_keys = [
+    *signer,
+    *token_account,
+    PUMP_CONSTANTS.pump_swap_program,
+    SOLANA_PROGRAMS.token_program,
+  ];
+  let signature = Signature::from([7; 64]);
+  SubscribeUpdateTransactionInfo {
+    signature: signature.as_ref().to_vec(),
+    transaction: Some(Transaction {
+      signatures: vec![signature.as_ref().to_vec()],
+      message: Some(Message {
+        header: Some(MessageHeader {
+          num_required_signatures: 1,
+          num_readonly_signed_accounts: 0,
+          num_readonly_unsigned_accounts: 2,
+        }),
+        account_keys: account_keys
+          .iter()
+          .map(|key| key.to_bytes().to_vec())
+          .collect(),
+        recent_blockhash: vec![0; 32],
+        instructions: vec![CompiledInstruction {
+          program_id_index: 2,
+          accounts: vec![0, 1],
+          data: vec![1, 2, 3],
+        }],
+        ..Default::default()
+      }),
+    }),
+    meta: Some(TransactionStatusMeta {
+      err: err.map(|err| yellowstone_grpc_proto::prelude::TransactionError {
+        err: bincode::serialize(&err).unwrap(),
+      }),
+      inner_instructions: vec![InnerInstructions {
+        index: 0,
+        instructions: vec![InnerInstruction {
+          program_id_index: 3,
+          accounts: vec![1],
+          data: vec![3],
+          stack_height: Some(2),
+        }],
+      }],
+      post_token_balances: vec![TokenBalance {
+        account_index: 1,
+        mint: Pubkey::new_unique().to_string(),
+        ui_token_amount: Some(UiTokenAmount {
+          ui_amount: 1.0,
+          decimals: 6,
+          amount: "1000000".to_string(),
+          ui_amount_string: "1".to_string(),
+        }),
+        owner: signer.to_string(),
+        program_id: SOLANA_PROGRAMS.token_program.to_string(),
+      }],
+      log_messages: vec!["Program log: swap".to_string()],
+      ..Default::default()
+    }),
+    ..Default::default()
+  }
+}
+
+fn spawn_geyser_subscription(
+  central_context: &Arc<CentralContext>,
+  endpoint: &str,
+  transactions: Option<std::sync::mpsc::SyncSender<DecodedTransaction>>,
+) {
+  let central_context = central_context.clone();
+  let endpoint = endpoint.to_string();
+  tokio::spawn(async move {
+    central_context
+      .run_geyser_subscription(&endpoint, None, transactions, POLL_INTERVAL)
+      .await;
+  });
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn geyser_updates_reach_pools_slots_and_transactions() {
+  let (_, central_context) = fixture_context();
+  let pool = pumpswap_pool(1_000, 2_000, 1);
+  let (pool_address, token_a, token_a_vault) = (
+    *pool.pool_address(),
+    *pool.token_a_address(),
+    *pool.token_a_vault_address(),
+  );
+  let pool = Arc::new(RwLock::new(pool));
+  central_context.insert_pool(pool.clone());
+  let (fake_geyser, endpoint) = FakeGeyser::start().await;
+  let (sender, transactions) = sync_channel(8);
+  spawn_geyser_subscription(&central_context, &endpoint, Some(sender));
+
+  wait_until(|| fake_geyser.last_request().is_some()).await;
+  let request = fake_geyser.last_request().unwrap();
+  let supported_programs: Vec<String> = SUPPORTED_PROGRAMS
+    .iter()
+    .map(|program| program.to_string())
+    .collect();
+  assert_eq!(
+    request.accounts["supported_programs"].owner,
+    supported_programs
+  );
+  assert!(subscribed_accounts(&request).contains(&token_a_vault));
+  assert_eq!(
+    request.transactions["supported_programs"].account_include,
+    supported_programs
+  );
+
+  fake_geyser
+    .send(UpdateOneof::Account(SubscribeUpdateAccount {
+      account: Some(SubscribeUpdateAccountInfo {
+        pubkey: token_a_vault.to_bytes().to_vec(),
+        owner: SOLANA_PROGRAMS.token_program.to_bytes().to_vec(),
+        data: token_account_data(&token_a, &pool_address, 4_000),
+        write_version: 5,
+        ..Default::default()
+      }),
+      slot: 10,
+      ..Default::default()
+    }))
+    .await;
+  wait_until(|| pool.read().unwrap().token_a_amount_units() == 4_000).await;
+  assert_eq!(
+    central_context.account_update_versions.read().unwrap()[&token_a_vault],
+    (10, 5)
+  );
+
+  fake_geyser
+    .send(UpdateOneof::Slot(SubscribeUpdateSlot {
+      slot: 12,
+      ..Default::default()
+    }))
+    .await;
+  wait_until(|| *central_context.current_slot.read().unwrap() == 12).await;
+
+  let (signer, token_account) = (Pubkey::new_unique(), Pubkey::new_unique());
+  fake_geyser
+    .send(UpdateOneof::Transaction(SubscribeUpdateTransaction {
+      transaction: Some(transaction_info(&signer, &token_account, None)),
+      slot: 13,
+      ..Default::default()
+    }))
+    .await;
+  let transaction =
+    tokio::task::spawn_blocking(move || transactions.recv_timeout(Duration::from_secs(5)).unwrap())
+      .await
+      .unwrap();
+  assert_eq!(transaction.slot, 13);
+  assert_eq!(transaction.signature(), Some(&Signature::from([7; 64])));
+  let instructions: Vec<_> = transaction
+    .instructions()
+    .iter()
+    .map(|instruction| (instruction.program_id().copied(), instruction.stack_height))
+    .collect();
+  assert_eq!(
+    instructions,
+    vec![
+      (Some(PUMP_CONSTANTS.pump_swap_program), 1),
+      (Some(SOLANA_PROGRAMS.token_program), 2),
+    ]
+  );
+  assert_eq!(transaction.token_account_owners()[&token_account], signer);
+  assert_eq!(transaction.log_messages, vec!["Program log: swap"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn geyser_subscription_follows_new_pools_and_reconnects() {
+  let (_, central_context) = fixture_context();
+  let (fake_geyser, endpoint) = FakeGeyser::start().await;
+  spawn_geyser_subscription(&central_context, &endpoint, None);
+  wait_until(|| fake_geyser.last_request().is_some()).await;
+  assert!(fake_geyser.last_request().unwrap().transactions.is_empty());
+
+  let pool = pumpswap_pool(1_000, 2_000, 1);
+  let token_b_vault = *pool.token_b_vault_address();
+  central_context.insert_pool(Arc::new(RwLock::new(pool)));
+  wait_until(|| {
+    fake_geyser
+      .last_request()
+      .is_some_and(|request| subscribed_accounts(&request).contains(&token_b_vault))
+  })
+  .await;
+  assert_eq!(fake_geyser.0.lock().unwrap().subscriptions, 1);
+
+  // Subscribes again with every account once the stream ends
+  fake_geyser.close();
+  wait_until(|| fake_geyser.0.lock().unwrap().subscriptions == 2).await;
+  wait_until(|| {
+    fake_geyser
+      .last_request()
+      .is_some_and(|request| subscribed_accounts(&request).contains(&token_b_vault))
+  })
+  .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn geyser_subscription_keeps_tracked_mints_current() {
+  let (backend, central_context) = fixture_context();
+  let (fake_geyser, endpoint) = FakeGeyser::start().await;
+  spawn_geyser_subscription(&central_context, &endpoint, None);
+  wait_until(|| fake_geyser.last_request().is_some()).await;
+
+  // Mints tracked after the stream started are added to it
+  let mint = Pubkey::new_unique();
+  backend.set_slot(100);
+  backend.set_account(
+    mint,
+    account(SOLANA_PROGRAMS.token_program, mint_data(6, 1_000, None)),
+  );
+  central_context.load_token_metadata(&[mint]).await;
+  wait_until(|| {
+    fake_geyser
+      .last_request()
+      .is_some_and(|request| subscribed_accounts(&request).contains(&mint))
+  })
+  .await;
+
+  fake_geyser
+    .send(UpdateOneof::Account(SubscribeUpdateAccount {
+      account: Some(SubscribeUpdateAccountInfo {
+        pubkey: mint.to_bytes().to_vec(),
+        owner: SOLANA_PROGRAMS.token_program.to_bytes().to_vec(),
+        data: mint_data(6, 2_000, None),
+        write_version: 3,
+        ..Default::default()
+      }),
+      slot: 101,
+      ..Default::default()
+    }))
+    .await;
+  wait_until(|| central_context.token_registry.read().unwrap()[&mint].supply == 2_000).await;
+  assert_eq!(
+    central_context.token_registry.read().unwrap()[&mint].last_updated_slot,
+    101
+  );
+}
+
+#[test]
+fn failed_geyser_transaction_keeps_its_error() {
+  let (signer, token_account) = (Pubkey::new_unique(), Pubkey::new_unique());
+  let err = TransactionError::InstructionError(0, InstructionError::Custom(6001));
+  let transaction = DecodedTransaction::from_geyser_transaction(
+    5,
+    &transaction_info(&signer, &token_account, Some(err.clone())),
+  )
+  .unwrap();
+  assert_eq!(transaction.err, Some(err.into()));
+
+  // Malformed account keys
+  let mut info = transaction_info(&signer, &token_account, None);
+  let message = info.transaction.as_mut().unwrap().message.as_mut().unwrap();
+  message.account_keys[0].truncate(31);
+  assert!(DecodedTransaction::from_geyser_transaction(5, &info).is_none());
+}
@@ -0,0 +1,316 @@
+mod common;
+
+use common::{
+  account, dbc_pool_config_data, dbc_virtual_pool_data, fixture_context, instruction,
+  keys_with_program, pf_bonding_curve, pf_bonding_curve_data, pumpswap_pool, raydium_cpmm_pool,
+};
+use solana_central::constants::{
+  METEORA_CONSTANTS, POOLS_ACCOUNT_SIZES, PUMP_CONSTANTS, RAYDIUM_CONSTANTS, TOKENS,
+};
+use solana_central::protocol_idls::meteora::MeteoraDammv2PoolIdl;
+use solana_central::{
+  Pool, Pools, PumpswapPool, RaydiumLaunchpad, load_graduation_successors, load_pools,
+};
+use solana_sdk::pubkey::Pubkey;
+use std::sync::{Arc, RwLock};
+
+/// Completed Raydium launchpad with unique addresses migrating to a CPMM pool
+fn completed_launchpad() -> RaydiumLaunchpad {
+  RaydiumLaunchpad {
+    info: Pool {
+      pool_address: Pubkey::new_unique(),
+      token_a_address: Pubkey::new_unique(),
+      token_b_address: TOKENS.wsol,
+      token_a_vault_address: Pubkey::new_unique(),
+      token_b_vault_address: Pubkey::new_unique(),
+      pool_type: Pools::RaydiumLaunchpad,
+      last_updated_slot: 0,
+      hydrated: true,
+    },
+    platform_config: Pubkey::new_unique(),
+    platform_vault: Pubkey::new_unique(),
+    creator_vault: Pubkey::new_unique(),
+    virtual_token_a_reserve: 1_073_000_000_000_000,
+    virtual_token_b_reserve: 30_000_000_000,
+    real_token_a_reserve: 793_100_000_000_000,
+    real_token_b_reserve: 85_000_000_000,
+    status: 1,
+    migrate_type: 1,
+  }
+}
+
+/// Raydium launchpad `migrate_to_cpswap` instruction keys migrating `curve` to `cpmm_pool`
+fn migrate_to_cpswap_keys(curve: &Pubkey, cpmm_pool: &Pubkey) -> (Vec<Pubkey>, Vec<u8>) {
+  let (mut keys, accounts) = keys_with_program(20, RAYDIUM_CONSTANTS.launchpad_program);
+  keys[5] = *cpmm_pool;
+  keys[17] = *curve;
+  (keys, accounts)
+}
+
+#[test]
+fn pumpfun_curve_links_the_derived_canonical_pool_only() {
+  let (_, central_context) = fixture_context();
+  let graduations = central_context.subscribe_graduations(8);
+  let curve = pf_bonding_curve();
+  let (curve_address, token) = (curve.bonding_curve_address, curve.token_address);
+  central_context.insert_pool(Arc::new(RwLock::new(curve)));
+  central_context.apply_account_update(
+    &curve_address,
+    &PUMP_CONSTANTS.bonding_curve_program,
+    &pf_bonding_curve_data(1_000, 2_000, true),
+    5,
+    None,
+  );
+  let canonical_pool_address = PumpswapPool::derive_canonical_pool(&token).unwrap();
+  let graduation = graduations.try_recv().unwrap();
+  assert_eq!(graduation.pool_address, curve_address);
+  assert_eq!(graduation.successor_pool_type, Pools::PumpswapAmm);
+  assert_eq!(
+    graduation.successor_pool_address,
+    Some(canonical_pool_address)
+  );
+  assert!(!graduation.successor_inserted);
+
+  // Anyone can create another pool of the same pair, it isn't the successor
+  let mut other_pool = pumpswap_pool(1_000, 2_000, 6);
+  other_pool.info.token_a_address = token;
+  other_pool.info.token_b_address = TOKENS.wsol;
+  central_context.insert_pool(Arc::new(RwLock::new(other_pool)));
+  assert!(graduations.try_recv().is_err());
+  assert!(!central_context.graduations.read().unwrap()[&curve_address].successor_inserted);
+
+  let mut canonical_pool = pumpswap_pool(1_000, 2_000, 6);
+  canonical_pool.info.pool_address = canonical_pool_address;
+  canonical_pool.info.token_a_address = token;
+  canonical_pool.info.token_b_address = TOKENS.wsol;
+  central_context.insert_pool(Arc::new(RwLock::new(canonical_pool)));
+  let graduation = graduations.try_recv().unwrap();
+  assert_eq!(
+    graduation.successor_pool_address,
+    Some(canonical_pool_address)
+  );
+  assert!(graduation.successor_inserted);
+  assert_eq!(
+    central_context.graduations.read().unwrap()[&curve_address],
+    graduation
+  );
+}
+
+#[test]
+fn canonical_pool_is_derived_from_the_pool_authority() {
+  let token = Pubkey::new_unique();
+  let pump_pool_authority = PumpswapPool::pump_pool_authority_pda(&token).unwrap();
+  let (expected, _) = Pubkey::find_program_address(
+    &[
+      b"pool",
+      &0u16.to_le_bytes(),
+      pump_pool_authority.as_ref(),
+      token.as_ref(),
+      TOKENS.wsol.as_ref(),
+    ],
+    &PUMP_CONSTANTS.pump_swap_program,
+  );
+  assert_eq!(
+    PumpswapPool::derive_canonical_pool(&token).unwrap(),
+    expected
+  );
+}
+
+#[test]
+fn launchpad_successor_is_linked_from_its_migration_instruction() {
+  let (_, central_context) = fixture_context();
+  let graduations = central_context.subscribe_graduations(8);
+  let curve = completed_launchpad();
+  let (curve_address, token) = (curve.info.pool_address, curve.info.token_a_address);
+  central_context.insert_pool(Arc::new(RwLock::new(curve)));
+  let graduation = graduations.try_recv().unwrap();
+  assert_eq!(graduation.successor_pool_type, Pools::RaydiumCpmm);
+  assert_eq!(graduation.successor_pool_address, None);
+
+  // A CPMM pool of the same pair isn't linked without the migration instruction
+  let mut cpmm_pool = raydium_cpmm_pool(0);
+  cpmm_pool.info.token_a_address = token;
+  cpmm_pool.info.token_b_address = TOKENS.wsol;
+  let cpmm_pool_address = cpmm_pool.info.pool_address;
+  central_context.insert_pool(Arc::new(RwLock::new(cpmm_pool)));
+  assert!(graduations.try_recv().is_err());
+
+  let (keys, accounts) = migrate_to_cpswap_keys(&curve_address, &cpmm_pool_address);
+  let data = RAYDIUM_CONSTANTS.launchpad_migrate_to_cpswap_discriminator;
+  let graduation = central_context
+    .process_migration_instruction(&instruction(&keys, &accounts, &data), 7)
+    .unwrap();
+  assert_eq!(graduation.successor_pool_address, Some(cpmm_pool_address));
+  assert!(graduation.successor_inserted);
+  assert_eq!(graduations.try_recv().unwrap(), graduation);
+
+  // Seen again, e.g. from another stream
+  assert!(
+    central_context
+      .process_migration_instruction(&instruction(&keys, &accounts, &data), 7)
+      .is_none()
+  );
+  assert!(graduations.try_recv().is_err());
+}
+
+#[test]
+fn migration_instruction_links_a_successor_inserted_later() {
+  let (_, central_context) = fixture_context();
+  let graduations = central_context.subscribe_graduations(8);
+  let mut curve = completed_launchpad();
+  // Still trading locally, the migration instruction alone records the graduation
+  curve.status = 0;
+  let (curve_address, token) = (curve.info.pool_address, curve.info.token_a_address);
+  central_context.insert_pool(Arc::new(RwLock::new(curve)));
+  assert!(graduations.try_recv().is_err());
+
+  let cpmm_pool_address = Pubkey::new_unique();
+  let (keys, accounts) = migrate_to_cpswap_keys(&curve_address, &cpmm_pool_address);
+  let data = RAYDIUM_CONSTANTS.launchpad_migrate_to_cpswap_discriminator;
+  let graduation = central_context
+    .process_migration_instruction(&instruction(&keys, &accounts, &data), 7)
+    .unwrap();
+  assert_eq!(graduation.pool_address, curve_address);
+  assert_eq!(graduation.token_a_address, token);
+  assert_eq!(graduation.successor_pool_address, Some(cpmm_pool_address));
+  assert!(!graduation.successor_inserted);
+  assert_eq!(graduation.slot, 7);
+  assert_eq!(graduations.try_recv().unwrap(), graduation);
+
+  let mut cpmm_pool = raydium_cpmm_pool(0);
+  cpmm_pool.info.pool_address = cpmm_pool_address;
+  cpmm_pool.info.token_a_address = token;
+  cpmm_pool.info.token_b_address = TOKENS.wsol;
+  central_context.insert_pool(Arc::new(RwLock::new(cpmm_pool)));
+  let graduation = graduations.try_recv().unwrap();
+  assert!(graduation.successor_inserted);
+}
+
+#[test]
+fn dbc_migration_instructions_are_decoded() {
+  let (_, central_context) = fixture_context();
+  // Decoding doesn't depend on the curve type, any known curve will do
+  let curve = completed_launchpad();
+  let curve_address = curve.info.pool_address;
+  central_context.insert_pool(Arc::new(RwLock::new(curve)));
+
+  for (discriminator, successor_pool_type) in [
+    (
+      METEORA_CONSTANTS.dbc_migrate_damm_discriminator,
+      Pools::MeteoraAmm,
+    ),
+    (
+      METEORA_CONSTANTS.dbc_migrate_dammv2_discriminator,
+      Pools::MeteoraDammV2,
+    ),
+  ] {
+    let (mut keys, accounts) = keys_with_program(10, METEORA_CONSTANTS.dbc_program);
+    keys[0] = curve_address;
+    let graduation = central_context
+      .process_migration_instruction(&instruction(&keys, &accounts, &discriminator), 7)
+      .unwrap();
+    assert_eq!(graduation.successor_pool_type, successor_pool_type);
+    assert_eq!(graduation.successor_pool_address, Some(keys[4]));
+  }
+}
+
+#[test]
+fn other_instructions_and_unknown_curves_are_ignored() {
+  let (_, central_context) = fixture_context();
+  // A swap on the launchpad
+  let (keys, accounts) = migrate_to_cpswap_keys(&Pubkey::new_unique(), &Pubkey::new_unique());
+  let data = RAYDIUM_CONSTANTS.launchpad_swap_discriminators[0];
+  assert!(
+    central_context
+      .process_migration_instruction(&instruction(&keys, &accounts, &data), 7)
+      .is_none()
+  );
+  // A migration of a curve the context doesn't know
+  let data = RAYDIUM_CONSTANTS.launchpad_migrate_to_cpswap_discriminator;
+  assert!(
+    central_context
+      .process_migration_instruction(&instruction(&keys, &accounts, &data), 7)
+      .is_none()
+  );
+  // Too few accounts
+  let (keys, accounts) = keys_with_program(3, METEORA_CONSTANTS.dbc_program);
+  let data = METEORA_CONSTANTS.dbc_migrate_dammv2_discriminator;
+  assert!(
+    central_context
+      .process_migration_instruction(&instruction(&keys, &accounts, &data), 7)
+      .is_none()
+  );
+  assert!(central_context.graduations.read().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn dbc_successor_is_fetched_and_inserted_after_its_migration() {
+  let (backend, central_context) = fixture_context();
+  let (config_address, curve_address, base_mint) = (
+    Pubkey::new_unique(),
+    Pubkey::new_unique(),
+    Pubkey::new_unique(),
+  );
+  backend.set_account(
+    config_address,
+    account(
+      METEORA_CONSTANTS.dbc_program,
+      dbc_pool_config_data(&TOKENS.wsol),
+    ),
+  );
+  let mut curve_data = dbc_virtual_pool_data(&config_address, &base_mint, 0, 85_000_000_000);
+  // is_migrated
+  curve_data[305] = 1;
+  backend.set_account(
+    curve_address,
+    account(METEORA_CONSTANTS.dbc_program, curve_data),
+  );
+  load_pools(
+    &[(
+      METEORA_CONSTANTS.dbc_program,
+      POOLS_ACCOUNT_SIZES.meteora_dbc,
+    )],
+    central_context.clone(),
+    1,
+    false,
+  )
+  .await;
+  let graduations = central_context.subscribe_graduations(8);
+
+  let (mut keys, accounts) = keys_with_program(10, METEORA_CONSTANTS.dbc_program);
+  keys[0] = curve_address;
+  let successor_address = keys[4];
+  let data = METEORA_CONSTANTS.dbc_migrate_dammv2_discriminator;
+  let graduation = central_context
+    .process_migration_instruction(&instruction(&keys, &accounts, &data), 7)
+    .unwrap();
+  assert_eq!(graduation.successor_pool_address, Some(successor_address));
+  assert!(!graduation.successor_inserted);
+  assert!(!graduations.try_recv().unwrap().successor_inserted);
+
+  // The migration isn't confirmed yet, the successor doesn't exist
+  load_graduation_successors(central_context.clone()).await;
+  assert!(!central_context.graduations.read().unwrap()[&curve_address].successor_inserted);
+
+  let mut successor_data = vec![0u8; POOLS_ACCOUNT_SIZES.meteora_dammv2];
+  let token_a_mint_offset = MeteoraDammv2PoolIdl::TOKEN_A_MINT_OFFSET;
+  for (position, address) in [base_mint, TOKENS.wsol].iter().enumerate() {
+    let offset = token_a_mint_offset + position * 32;
+    successor_data[offset..offset + 32].copy_from_slice(address.as_ref());
+  }
+  backend.set_account(
+    successor_address,
+    account(METEORA_CONSTANTS.dammv2_program, successor_data),
+  );
+  load_graduation_successors(central_context.clone()).await;
+
+  let successor = central_context.pools_map.read().unwrap()[&successor_address].clone();
+  {
+    let successor = successor.read().unwrap();
+    assert_eq!(*successor.pool_type(), Pools::MeteoraDammV2);
+    assert_eq!(*successor.token_a_address(), base_mint);
+  }
+  let graduation = central_context.graduations.read().unwrap()[&curve_address].clone();
+  assert!(graduation.successor_inserted);
+  assert!(graduations.try_recv().unwrap().successor_inserted);
+}
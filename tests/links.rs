@@ -0,0 +1,175 @@
+mod common;
+
+use common::instruction;
+use solana_central::constants::SOLANA_PROGRAMS;
+use solana_central::{Link, extract_links, get_token_account_owners};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use solana_transaction_status_client_types::UiTransactionTokenBalance;
+use std::collections::HashMap;
+
+fn wallet() -> Pubkey {
+  Keypair::new().pubkey()
+}
+
+fn token_balance(account_index: u8, owner: Option<&Pubkey>) -> UiTransactionTokenBalance {
+  let mut balance = serde_json::json!({
+    "accountIndex": account_index,
+    "mint": Pubkey::new_unique().to_string(),
+    "uiTokenAmount": {
+      "uiAmount": 1.0,
+      "decimals": 6,
+      "amount": "1000000",
+      "uiAmountString": "1",
+    },
+  });
+  if let Some(owner) = owner {
+    balance["owner"] = owner.to_string().into();
+  }
+  serde_json::from_value(balance).unwrap()
+}
+
+fn linked_wallets(link: &Link) -> (Pubkey, Pubkey) {
+  (
+    Pubkey::new_from_array(link.link[0..32].try_into().unwrap()),
+    Pubkey::new_from_array(link.link[32..64].try_into().unwrap()),
+  )
+}
+
+fn sorted(a: Pubkey, b: Pubkey) -> (Pubkey, Pubkey) {
+  if a < b { (a, b) } else { (b, a) }
+}
+
+fn token_data(tag: &[u8], amount: u64) -> Vec<u8> {
+  let mut data = tag.to_vec();
+  data.extend(amount.to_le_bytes());
+  data.push(6);
+  data
+}
+
+#[test]
+fn token_account_owners_from_balances() {
+  let keys: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+  let (closed_owner, created_owner) = (wallet(), wallet());
+  let owners = get_token_account_owners(
+    &keys,
+    &[
+      token_balance(1, Some(&closed_owner)),
+      token_balance(2, None),
+    ],
+    &[
+      token_balance(3, Some(&created_owner)),
+      // Out of range
+      token_balance(9, Some(&created_owner)),
+    ],
+  );
+  assert_eq!(
+    owners,
+    HashMap::from([(keys[1], closed_owner), (keys[3], created_owner)])
+  );
+}
+
+#[test]
+fn links_from_system_and_token_transfers() {
+  let (alice, bob, carol, dave) = (wallet(), wallet(), wallet(), wallet());
+  let (alice_token, bob_token, carol_token, dave_token, mint) = (
+    Pubkey::new_unique(),
+    Pubkey::new_unique(),
+    Pubkey::new_unique(),
+    Pubkey::new_unique(),
+    Pubkey::new_unique(),
+  );
+  let keys = [
+    alice,
+    bob,
+    alice_token,
+    bob_token,
+    carol_token,
+    dave_token,
+    mint,
+    SOLANA_PROGRAMS.system_program,
+    SOLANA_PROGRAMS.token_program,
+    SOLANA_PROGRAMS.token_2022_program,
+  ];
+  let owners = HashMap::from([
+    (alice_token, alice),
+    (bob_token, bob),
+    (carol_token, carol),
+    (dave_token, dave),
+  ]);
+
+  let mut system_transfer = 2u32.to_le_bytes().to_vec();
+  system_transfer.extend(1_000u64.to_le_bytes());
+  let mut zero_system_transfer = 2u32.to_le_bytes().to_vec();
+  zero_system_transfer.extend(0u64.to_le_bytes());
+  let transfer = token_data(&[3], 5);
+  let transfer_checked = token_data(&[12], 5);
+  let mut transfer_checked_with_fee = token_data(&[26, 1], 5);
+  transfer_checked_with_fee.extend(1u64.to_le_bytes());
+
+  let with_program = |program_index: u8, accounts: &[u8]| {
+    let mut accounts = accounts.to_vec();
+    accounts.insert(0, program_index);
+    accounts
+  };
+  // The program is the first entry of each accounts list, see `program_id_index` below
+  let system_accounts = with_program(7, &[0, 1]);
+  let transfer_accounts = with_program(8, &[2, 3, 0]);
+  let checked_accounts = with_program(8, &[3, 6, 4, 1]);
+  let with_fee_accounts = with_program(9, &[4, 6, 5, 4]);
+  let with_fee_on_token_accounts = with_program(8, &[4, 6, 5, 4]);
+  let instructions: Vec<_> = [
+    (&system_accounts, &system_transfer),
+    (&system_accounts, &zero_system_transfer),
+    (&transfer_accounts, &transfer),
+    (&checked_accounts, &transfer_checked),
+    (&with_fee_accounts, &transfer_checked_with_fee),
+    // Transfer fee extension instructions only exist on Token-2022
+    (&with_fee_on_token_accounts, &transfer_checked_with_fee),
+  ]
+  .into_iter()
+  .map(|(accounts, data)| {
+    let mut instruction = instruction(&keys, &accounts[1..], data);
+    instruction.program_id_index = accounts[0];
+    instruction
+  })
+  .collect();
+
+  let links = extract_links(&instructions, &owners, 1_700_000_000, 10, 2);
+  let found: Vec<_> = links
+    .iter()
+    .map(|link| (link.atomic_instruction_index, linked_wallets(link)))
+    .collect();
+  assert_eq!(
+    found,
+    vec![
+      (0, sorted(alice, bob)),
+      (2, sorted(alice, bob)),
+      (3, sorted(bob, carol)),
+      (4, sorted(carol, dave)),
+    ]
+  );
+  assert!(links.iter().all(|link| link.slot == 10 && link.index == 2));
+}
+
+#[test]
+fn token_transfer_to_unknown_account_has_no_link() {
+  let alice = wallet();
+  let (alice_token, unknown_token) = (Pubkey::new_unique(), Pubkey::new_unique());
+  let keys = [
+    alice_token,
+    unknown_token,
+    alice,
+    SOLANA_PROGRAMS.token_program,
+  ];
+  let data = token_data(&[3], 5);
+  let links = extract_links(
+    &[instruction(&keys, &[0, 1, 2], &data)],
+    &HashMap::new(),
+    0,
+    0,
+    0,
+  );
+  assert!(links.is_empty());
+}
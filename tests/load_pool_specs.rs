@@ -0,0 +1,300 @@
+mod common;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use common::{account, pumpswap_pool_data};
+use solana_central::constants::{POOLS_ACCOUNT_SIZES, PUMP_CONSTANTS, TOKENS};
+use solana_central::protocol_idls::meteora::{
+  DbcVirtualPool, MeteoraAmmPoolIdl, MeteoraDammv2PoolIdl,
+};
+use solana_central::protocol_idls::pumpswap::PumpAmmPoolAccount;
+use solana_central::protocol_idls::raydium::{AmmV4PoolInfoIdl, CpmmPoolInfoIdl, LaunchpadPoolIdl};
+use solana_central::{
+  CentralContext, CentralContextBuilder, FixtureRpcBackend, PoolAccountField, PoolLoadSpec, Pools,
+  RpcBackend, get_pool_accounts, load_pools_with_specs,
+};
+use solana_client::client_error::Result;
+use solana_client::rpc_config::RpcProgramAccountsConfig;
+use solana_sdk::account::Account;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use std::slice;
+use std::sync::{Arc, Mutex};
+
+/// Fixture backend recording the program of every getProgramAccounts request
+#[derive(Default)]
+struct ProgramAccountsLog {
+  fixtures: FixtureRpcBackend,
+  programs: Mutex<Vec<Pubkey>>,
+}
+
+impl RpcBackend for ProgramAccountsLog {
+  fn get_slot(&self) -> Result<u64> {
+    self.fixtures.get_slot()
+  }
+
+  fn get_latest_blockhash(&self) -> Result<(Hash, u64)> {
+    self.fixtures.get_latest_blockhash()
+  }
+
+  fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>> {
+    self.fixtures.get_account(pubkey)
+  }
+
+  fn get_program_accounts_with_config(
+    &self,
+    program_id: &Pubkey,
+    config: &RpcProgramAccountsConfig,
+  ) -> Result<Vec<(Pubkey, Account)>> {
+    self.programs.lock().unwrap().push(*program_id);
+    self
+      .fixtures
+      .get_program_accounts_with_config(program_id, config)
+  }
+}
+
+fn logged_context() -> (Arc<ProgramAccountsLog>, Arc<CentralContext>) {
+  let backend = Arc::new(ProgramAccountsLog::default());
+  let central_context = CentralContextBuilder::new()
+    .rpc_backend(backend.clone())
+    .build()
+    .unwrap();
+  (backend, Arc::new(central_context))
+}
+
+/// Store a Pumpswap pool account trading `base_mint` against `quote_mint`, returning its address
+fn set_pumpswap_pool(
+  backend: &ProgramAccountsLog,
+  base_mint: &Pubkey,
+  quote_mint: &Pubkey,
+) -> Pubkey {
+  let data = pumpswap_pool_data(
+    base_mint,
+    quote_mint,
+    &Pubkey::new_unique(),
+    &Pubkey::new_unique(),
+  );
+  let pool_address = Pubkey::new_unique();
+  backend.fixtures.set_account(
+    pool_address,
+    account(PUMP_CONSTANTS.pump_swap_program, data),
+  );
+  pool_address
+}
+
+fn wsol_quoted_spec() -> PoolLoadSpec {
+  PoolLoadSpec {
+    filters: vec![(PoolAccountField::TokenBMint, TOKENS.wsol)],
+    ..PoolLoadSpec::new(Pools::PumpswapAmm)
+  }
+}
+
+#[tokio::test]
+async fn only_pools_matching_the_spec_filters_are_loaded() {
+  let (backend, central_context) = logged_context();
+  let wsol_pool_address = set_pumpswap_pool(&backend, &Pubkey::new_unique(), &TOKENS.wsol);
+  let usdc_pool_address = set_pumpswap_pool(&backend, &Pubkey::new_unique(), &TOKENS.usdc);
+
+  load_pools_with_specs(
+    &[
+      wsol_quoted_spec(),
+      // No loader for this pool type
+      PoolLoadSpec::new(Pools::OrcaWhirlpool),
+      // DBC pools don't store their quote mint
+      PoolLoadSpec {
+        filters: vec![(PoolAccountField::TokenBMint, TOKENS.wsol)],
+        ..PoolLoadSpec::new(Pools::MeteoraDbc)
+      },
+    ],
+    central_context.clone(),
+    1,
+    false,
+  )
+  .await;
+
+  let pools_map = central_context.pools_map.read().unwrap();
+  assert!(pools_map.contains_key(&wsol_pool_address));
+  assert!(!pools_map.contains_key(&usdc_pool_address));
+  // The unsupported specs never reach the RPC
+  assert_eq!(
+    *backend.programs.lock().unwrap(),
+    vec![PUMP_CONSTANTS.pump_swap_program]
+  );
+}
+
+#[tokio::test]
+async fn sliced_specs_are_skipped_by_the_loader() {
+  let (backend, central_context) = logged_context();
+  let pool_address = set_pumpswap_pool(&backend, &Pubkey::new_unique(), &TOKENS.wsol);
+  let sliced_spec = PoolLoadSpec {
+    data_slice: PoolAccountField::TokenBMint.data_slice(Pools::PumpswapAmm),
+    ..wsol_quoted_spec()
+  };
+
+  load_pools_with_specs(
+    slice::from_ref(&sliced_spec),
+    central_context.clone(),
+    1,
+    false,
+  )
+  .await;
+  assert!(central_context.pools_map.read().unwrap().is_empty());
+  assert!(backend.programs.lock().unwrap().is_empty());
+
+  // Sliced accounts are fetched with get_pool_accounts instead
+  let accounts = get_pool_accounts(&sliced_spec, &central_context)
+    .await
+    .unwrap();
+  assert_eq!(accounts.len(), 1);
+  assert_eq!(accounts[0].0, pool_address);
+  assert_eq!(accounts[0].1.data, TOKENS.wsol.to_bytes());
+}
+
+#[tokio::test]
+async fn unsupported_specs_are_rejected_by_get_pool_accounts() {
+  let (backend, central_context) = logged_context();
+  assert!(
+    get_pool_accounts(&PoolLoadSpec::new(Pools::OrcaWhirlpool), &central_context)
+      .await
+      .is_err()
+  );
+  assert!(backend.programs.lock().unwrap().is_empty());
+}
+
+/// Decode an IDL struct from zeroed account data of `size` bytes, let `set` write a field and
+/// serialize it back
+fn serialize_with<T: BorshDeserialize + BorshSerialize>(
+  size: usize,
+  set: impl FnOnce(&mut T),
+) -> Vec<u8> {
+  let mut layout = T::deserialize(&mut vec![0u8; size].as_slice()).unwrap();
+  set(&mut layout);
+  borsh::to_vec(&layout).unwrap()
+}
+
+/// Serialize the IDL struct of `pool_type` holding `sentinel` in `field`, `None` if the struct
+/// doesn't have the field
+fn serialize_with_field(
+  pool_type: Pools,
+  field: PoolAccountField,
+  sentinel: Pubkey,
+) -> Option<Vec<u8>> {
+  let sizes = POOLS_ACCOUNT_SIZES;
+  let data = match (pool_type, field) {
+    (Pools::RaydiumAmmV4, PoolAccountField::TokenAMint) => {
+      serialize_with(sizes.raydium_ammv4, |pool: &mut AmmV4PoolInfoIdl| {
+        pool.base_mint = sentinel
+      })
+    }
+    (Pools::RaydiumAmmV4, PoolAccountField::TokenBMint) => {
+      serialize_with(sizes.raydium_ammv4, |pool: &mut AmmV4PoolInfoIdl| {
+        pool.quote_mint = sentinel
+      })
+    }
+    (Pools::RaydiumCpmm, PoolAccountField::TokenAMint) => {
+      serialize_with(sizes.raydium_cpmm, |pool: &mut CpmmPoolInfoIdl| {
+        pool.token_0_mint = sentinel
+      })
+    }
+    (Pools::RaydiumCpmm, PoolAccountField::TokenBMint) => {
+      serialize_with(sizes.raydium_cpmm, |pool: &mut CpmmPoolInfoIdl| {
+        pool.token_1_mint = sentinel
+      })
+    }
+    (Pools::RaydiumCpmm, PoolAccountField::Config) => {
+      serialize_with(sizes.raydium_cpmm, |pool: &mut CpmmPoolInfoIdl| {
+        pool.amm_config = sentinel
+      })
+    }
+    (Pools::RaydiumLaunchpad, PoolAccountField::TokenAMint) => {
+      serialize_with(sizes.raydium_launchpad, |pool: &mut LaunchpadPoolIdl| {
+        pool.base_mint = sentinel
+      })
+    }
+    (Pools::RaydiumLaunchpad, PoolAccountField::TokenBMint) => {
+      serialize_with(sizes.raydium_launchpad, |pool: &mut LaunchpadPoolIdl| {
+        pool.quote_mint = sentinel
+      })
+    }
+    (Pools::RaydiumLaunchpad, PoolAccountField::Config) => {
+      serialize_with(sizes.raydium_launchpad, |pool: &mut LaunchpadPoolIdl| {
+        pool.platform_config = sentinel
+      })
+    }
+    (Pools::PumpswapAmm, PoolAccountField::TokenAMint) => {
+      serialize_with(sizes.pump_swap, |pool: &mut PumpAmmPoolAccount| {
+        pool.base_mint = sentinel
+      })
+    }
+    (Pools::PumpswapAmm, PoolAccountField::TokenBMint) => {
+      serialize_with(sizes.pump_swap, |pool: &mut PumpAmmPoolAccount| {
+        pool.quote_mint = sentinel
+      })
+    }
+    (Pools::MeteoraAmm, PoolAccountField::TokenAMint) => {
+      serialize_with(sizes.meteora_amm, |pool: &mut MeteoraAmmPoolIdl| {
+        pool.token_a_mint = sentinel
+      })
+    }
+    (Pools::MeteoraAmm, PoolAccountField::TokenBMint) => {
+      serialize_with(sizes.meteora_amm, |pool: &mut MeteoraAmmPoolIdl| {
+        pool.token_b_mint = sentinel
+      })
+    }
+    (Pools::MeteoraDammV2, PoolAccountField::TokenAMint) => {
+      serialize_with(sizes.meteora_dammv2, |pool: &mut MeteoraDammv2PoolIdl| {
+        pool.token_a_mint = sentinel
+      })
+    }
+    (Pools::MeteoraDammV2, PoolAccountField::TokenBMint) => {
+      serialize_with(sizes.meteora_dammv2, |pool: &mut MeteoraDammv2PoolIdl| {
+        pool.token_b_mint = sentinel
+      })
+    }
+    (Pools::MeteoraDbc, PoolAccountField::TokenAMint) => {
+      serialize_with(sizes.meteora_dbc, |pool: &mut DbcVirtualPool| {
+        pool.base_mint = sentinel
+      })
+    }
+    (Pools::MeteoraDbc, PoolAccountField::Config) => {
+      serialize_with(sizes.meteora_dbc, |pool: &mut DbcVirtualPool| {
+        pool.config = sentinel
+      })
+    }
+    _ => return None,
+  };
+  Some(data)
+}
+
+#[test]
+fn field_offsets_match_the_idl_layouts() {
+  let sentinel = Pubkey::new_unique();
+  let mut checked = 0;
+  for discriminant in 0..=13u8 {
+    let pool_type = Pools::try_from_slice(&[discriminant]).unwrap();
+    for field in [
+      PoolAccountField::TokenAMint,
+      PoolAccountField::TokenBMint,
+      PoolAccountField::Config,
+    ] {
+      let data = serialize_with_field(pool_type, field, sentinel);
+      match (field.offset(pool_type), data) {
+        (Some(offset), Some(data)) => {
+          assert_eq!(
+            &data[offset..offset + 32],
+            sentinel.as_ref(),
+            "{:?} {:?}",
+            pool_type,
+            field
+          );
+          checked += 1;
+        }
+        (None, None) => {}
+        (offset, _) => panic!(
+          "{:?} {:?}: offset {:?} isn't covered",
+          pool_type, field, offset
+        ),
+      }
+    }
+  }
+  assert_eq!(checked, 16);
+}
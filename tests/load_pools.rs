@@ -0,0 +1,354 @@
+mod common;
+
+use common::{
+  account, dbc_pool_config_data, dbc_virtual_pool_data, fixture_context, launchpad_pool_data,
+  pf_bonding_curve_data, pumpswap_pool_data, token_account_data,
+};
+use solana_central::constants::{
+  METEORA_CONSTANTS, POOLS_ACCOUNT_SIZES, PUMP_CONSTANTS, RAYDIUM_CONSTANTS, SOLANA_PROGRAMS,
+  TOKENS,
+};
+use solana_central::{
+  BackpressurePolicy, CentralContextBuilder, Error, FixtureRpcBackend, MarketSubscription,
+  MarketUpdate, PoolTrait, RetryPolicy, derive_bonding_curve, load_pf_bonding_curves, load_pools,
+};
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn launchpad_and_dbc_pools_are_bulk_loaded_into_their_caches() {
+  let (backend, central_context) = fixture_context();
+  let launchpad_address = Pubkey::new_unique();
+  let launchpad_base_mint = Pubkey::new_unique();
+  backend.set_account(
+    launchpad_address,
+    account(
+      RAYDIUM_CONSTANTS.launchpad_program,
+      launchpad_pool_data(
+        &launchpad_base_mint,
+        &TOKENS.wsol,
+        (1_073_000_000_000_000, 30_000_000_000),
+        (100_000_000_000_000, 5_000_000_000),
+        0,
+      ),
+    ),
+  );
+  let config_address = Pubkey::new_unique();
+  backend.set_account(
+    config_address,
+    account(
+      METEORA_CONSTANTS.dbc_program,
+      dbc_pool_config_data(&TOKENS.wsol),
+    ),
+  );
+  let dbc_addresses = [Pubkey::new_unique(), Pubkey::new_unique()];
+  for dbc_address in dbc_addresses {
+    backend.set_account(
+      dbc_address,
+      account(
+        METEORA_CONSTANTS.dbc_program,
+        dbc_virtual_pool_data(&config_address, &Pubkey::new_unique(), 1_000, 2_000),
+      ),
+    );
+  }
+  // A virtual pool whose config doesn't exist is skipped
+  let orphan_dbc_address = Pubkey::new_unique();
+  backend.set_account(
+    orphan_dbc_address,
+    account(
+      METEORA_CONSTANTS.dbc_program,
+      dbc_virtual_pool_data(&Pubkey::new_unique(), &Pubkey::new_unique(), 1_000, 2_000),
+    ),
+  );
+
+  load_pools(
+    &[
+      (
+        RAYDIUM_CONSTANTS.launchpad_program,
+        POOLS_ACCOUNT_SIZES.raydium_launchpad,
+      ),
+      (
+        METEORA_CONSTANTS.dbc_program,
+        POOLS_ACCOUNT_SIZES.meteora_dbc,
+      ),
+    ],
+    central_context.clone(),
+    2,
+    false,
+  )
+  .await;
+
+  let launchpad = central_context.raydium_launchpads.lock().unwrap()[&launchpad_address].clone();
+  {
+    let launchpad = launchpad.read().unwrap();
+    assert_eq!(*launchpad.token_a_address(), launchpad_base_mint);
+    assert_eq!(*launchpad.token_b_address(), TOKENS.wsol);
+    assert_eq!(launchpad.virtual_token_b_reserve, 30_000_000_000);
+    assert_eq!(launchpad.real_token_b_reserve, 5_000_000_000);
+    assert_eq!(launchpad.migrate_type, 1);
+  }
+  assert!(
+    central_context
+      .pools_map
+      .read()
+      .unwrap()
+      .contains_key(&launchpad_address)
+  );
+
+  assert!(
+    central_context
+      .dbc_pool_configs
+      .read()
+      .unwrap()
+      .contains_key(&config_address)
+  );
+  let meteora_dbcs = central_context.meteora_dbcs.lock().unwrap();
+  assert_eq!(meteora_dbcs.len(), 2);
+  for dbc_address in dbc_addresses {
+    let dbc = meteora_dbcs[&dbc_address].read().unwrap();
+    assert_eq!(dbc.config, config_address);
+    assert_eq!(*dbc.token_b_address(), TOKENS.wsol);
+    assert_eq!((dbc.base_reserve, dbc.quote_reserve), (1_000, 2_000));
+    assert!(
+      central_context
+        .pools_map
+        .read()
+        .unwrap()
+        .contains_key(&dbc_address)
+    );
+  }
+  assert!(!meteora_dbcs.contains_key(&orphan_dbc_address));
+}
+
+#[tokio::test]
+async fn cached_dbc_configs_are_not_fetched_again() {
+  let (backend, central_context) = fixture_context();
+  let config_address = Pubkey::new_unique();
+  backend.set_account(
+    config_address,
+    account(
+      METEORA_CONSTANTS.dbc_program,
+      dbc_pool_config_data(&TOKENS.wsol),
+    ),
+  );
+  central_context
+    .load_dbc_pool_configs(&[config_address])
+    .await;
+  let config = central_context.dbc_pool_configs.read().unwrap()[&config_address].clone();
+
+  // Gone on chain, the cached config is still used
+  backend.remove_account(&config_address);
+  let dbc_address = Pubkey::new_unique();
+  backend.set_account(
+    dbc_address,
+    account(
+      METEORA_CONSTANTS.dbc_program,
+      dbc_virtual_pool_data(&config_address, &Pubkey::new_unique(), 1_000, 2_000),
+    ),
+  );
+  load_pools(
+    &[(
+      METEORA_CONSTANTS.dbc_program,
+      POOLS_ACCOUNT_SIZES.meteora_dbc,
+    )],
+    central_context.clone(),
+    1,
+    false,
+  )
+  .await;
+  assert!(
+    central_context
+      .meteora_dbcs
+      .lock()
+      .unwrap()
+      .contains_key(&dbc_address)
+  );
+  assert!(Arc::ptr_eq(
+    &central_context.dbc_pool_configs.read().unwrap()[&config_address],
+    &config
+  ));
+}
+
+#[tokio::test]
+async fn pf_bonding_curves_are_loaded_from_token_addresses() {
+  let (backend, central_context) = fixture_context();
+  let tokens: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+  for (token, complete) in tokens.iter().zip([false, true]) {
+    backend.set_account(
+      derive_bonding_curve(token),
+      account(
+        PUMP_CONSTANTS.bonding_curve_program,
+        pf_bonding_curve_data(1_000_000_000_000_000, 30_000_000_000, complete),
+      ),
+    );
+  }
+  // The third token has no bonding curve
+
+  load_pf_bonding_curves(&tokens, central_context.clone()).await;
+
+  let pf_bonding_curves = central_context.pf_bonding_curves.lock().unwrap();
+  assert_eq!(pf_bonding_curves.len(), 2);
+  for (token, complete) in tokens.iter().zip([false, true]) {
+    let bonding_curve_address = derive_bonding_curve(token);
+    let bonding_curve = pf_bonding_curves[&bonding_curve_address].read().unwrap();
+    assert_eq!(bonding_curve.token_address, *token);
+    assert_eq!(bonding_curve.virtual_sol_reserves, 30_000_000_000);
+    assert_eq!(bonding_curve.complete, complete);
+    assert!(
+      central_context
+        .pools_map
+        .read()
+        .unwrap()
+        .contains_key(&bonding_curve_address)
+    );
+  }
+  assert!(!pf_bonding_curves.contains_key(&derive_bonding_curve(&tokens[2])));
+}
+
+/// Store a Pumpswap pool account and its vault token accounts holding `base_amount` and
+/// `quote_amount`, returning the pool address
+fn set_pumpswap_pool(backend: &FixtureRpcBackend, base_amount: u64, quote_amount: u64) -> Pubkey {
+  let pool_address = Pubkey::new_unique();
+  let base_mint = Pubkey::new_unique();
+  let (base_vault, quote_vault) = (Pubkey::new_unique(), Pubkey::new_unique());
+  backend.set_account(
+    pool_address,
+    account(
+      PUMP_CONSTANTS.pump_swap_program,
+      pumpswap_pool_data(&base_mint, &TOKENS.wsol, &base_vault, &quote_vault),
+    ),
+  );
+  for (vault, mint, amount) in [
+    (base_vault, base_mint, base_amount),
+    (quote_vault, TOKENS.wsol, quote_amount),
+  ] {
+    backend.set_account(
+      vault,
+      account(
+        SOLANA_PROGRAMS.token_program,
+        token_account_data(&mint, &pool_address, amount),
+      ),
+    );
+  }
+  pool_address
+}
+
+#[tokio::test]
+async fn bulk_loaded_pools_are_hydrated_on_request() {
+  let (backend, central_context) = fixture_context();
+  let pool_address = set_pumpswap_pool(&backend, 5_000, 1_000);
+  let updates = central_context.subscribe_market_updates(
+    MarketSubscription::All,
+    8,
+    BackpressurePolicy::DropNewest,
+  );
+
+  load_pools(
+    &[(
+      PUMP_CONSTANTS.pump_swap_program,
+      POOLS_ACCOUNT_SIZES.pump_swap,
+    )],
+    central_context.clone(),
+    1,
+    true,
+  )
+  .await;
+
+  let pool = central_context.pools_map.read().unwrap()[&pool_address].clone();
+  let pool = pool.read().unwrap();
+  assert!(pool.is_hydrated());
+  assert_eq!(pool.token_a_amount_units(), 5_000);
+  assert_eq!(pool.token_b_amount_units(), 1_000);
+  assert_eq!(pool.price_a_over_b_lp(), 5_000_000_000);
+  let update = MarketUpdate::from_pool(&*pool).unwrap();
+  assert_eq!(update.price_a_b, 5_000_000_000);
+  let published: Vec<Pubkey> = updates
+    .try_iter()
+    .map(|update| update.market_address)
+    .collect();
+  assert_eq!(published, vec![pool_address]);
+}
+
+#[tokio::test]
+async fn bulk_loaded_pools_have_no_price_until_hydrated() {
+  let (backend, central_context) = fixture_context();
+  let pool_address = set_pumpswap_pool(&backend, 5_000, 1_000);
+  let updates = central_context.subscribe_market_updates(
+    MarketSubscription::All,
+    8,
+    BackpressurePolicy::DropNewest,
+  );
+
+  load_pools(
+    &[(
+      PUMP_CONSTANTS.pump_swap_program,
+      POOLS_ACCOUNT_SIZES.pump_swap,
+    )],
+    central_context.clone(),
+    1,
+    false,
+  )
+  .await;
+
+  let pool = central_context.pools_map.read().unwrap()[&pool_address].clone();
+  {
+    let pool = pool.read().unwrap();
+    assert!(!pool.is_hydrated());
+    assert_eq!(pool.token_a_amount_units(), 0);
+    assert!(MarketUpdate::from_pool(&*pool).is_none());
+  }
+  assert!(updates.try_recv().is_err());
+
+  // Hydrated later, e.g. once the pool is actually needed
+  central_context.refresh_pools_async(&[pool_address]).await;
+  assert!(pool.read().unwrap().is_hydrated());
+  assert_eq!(updates.try_recv().unwrap().market_address, pool_address);
+}
+
+// The CPMM configs are fetched with the blocking client
+#[tokio::test(flavor = "multi_thread")]
+async fn undecodable_cpmm_configs_are_skipped() {
+  let backend = Arc::new(FixtureRpcBackend::new());
+  let (config, invalid_config) = (Pubkey::new_unique(), Pubkey::new_unique());
+  let mut config_data = vec![0u8; 236];
+  config_data[12..20].copy_from_slice(&2_500u64.to_le_bytes());
+  backend.set_account(
+    config,
+    account(RAYDIUM_CONSTANTS.cpmm_program, config_data.clone()),
+  );
+  // `disable_create_pool` isn't a valid bool
+  config_data[9] = 2;
+  backend.set_account(
+    invalid_config,
+    account(RAYDIUM_CONSTANTS.cpmm_program, config_data),
+  );
+  let mut central_context = CentralContextBuilder::new()
+    .rpc_backend(backend)
+    .build()
+    .unwrap();
+
+  central_context.load_cpmm_pool_configs().unwrap();
+  assert_eq!(central_context.raydium_cpmm_fee_rates_lp.len(), 1);
+  assert_eq!(
+    central_context.raydium_cpmm_fee_rates_lp[&config],
+    2_500_000
+  );
+}
+
+#[test]
+fn cpmm_config_rpc_failure_is_returned() {
+  let mut central_context = CentralContextBuilder::new()
+    .endpoint("http://127.0.0.1:1")
+    .retry_policy(RetryPolicy {
+      max_retries: 0,
+      ..RetryPolicy::default()
+    })
+    .build()
+    .unwrap();
+
+  assert!(matches!(
+    central_context.load_cpmm_pool_configs(),
+    Err(Error::Rpc(_))
+  ));
+  assert!(central_context.raydium_cpmm_fee_rates_lp.is_empty());
+}
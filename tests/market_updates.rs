@@ -0,0 +1,200 @@
+mod common;
+
+use common::{
+  account, fixture_context, instruction, keys_with_program, mint_data, pumpswap_pool,
+  token_account_data,
+};
+use solana_central::constants::{PUMP_CONSTANTS, SOLANA_PROGRAMS};
+use solana_central::{BackpressurePolicy, MarketSubscription, MarketUpdate, PoolTrait};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::sync::mpsc;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+fn market_update(market_address: Pubkey, token_a_address: Pubkey) -> MarketUpdate {
+  MarketUpdate {
+    market_address,
+    token_a_address,
+    token_b_address: Pubkey::new_unique(),
+    price_a_b: 1,
+    price_b_a: 1,
+    token_a_amount: 1,
+    token_b_amount: 1,
+  }
+}
+
+#[test]
+fn new_bonding_curve_publishes_its_virtual_price() {
+  let (_, central_context) = fixture_context();
+  let updates = central_context.subscribe_market_updates(
+    MarketSubscription::All,
+    8,
+    BackpressurePolicy::DropNewest,
+  );
+  let (keys, accounts) = keys_with_program(5, PUMP_CONSTANTS.bonding_curve_program);
+  let mut data = PUMP_CONSTANTS
+    .bonding_curve_create_instruction_discriminator
+    .to_vec();
+  for value in ["Token", "TKN", "https://example.com/token.json"] {
+    data.extend((value.len() as u32).to_le_bytes());
+    data.extend(value.as_bytes());
+  }
+  data.extend(Pubkey::new_unique().to_bytes());
+
+  central_context
+    .process_token_creation_instruction(
+      &instruction(&keys, &accounts, &data),
+      1_700_000_000,
+      5,
+      0,
+      0,
+      Signature::default(),
+    )
+    .unwrap();
+  // No real SOL reserves yet, priced from the initial virtual reserves
+  let update = updates.try_recv().unwrap();
+  assert_eq!(update.market_address, keys[2]);
+  assert_eq!(
+    update.price_b_a,
+    PUMP_CONSTANTS.bc_init_virtual_sol_reserves as u128 * 1_000_000_000
+      / PUMP_CONSTANTS.bc_init_virtual_token_reserves as u128
+  );
+}
+
+#[test]
+fn subscriptions_filter_updates() {
+  let (_, central_context) = fixture_context();
+  let (market, token) = (Pubkey::new_unique(), Pubkey::new_unique());
+  let subscribe = |subscription| {
+    central_context.subscribe_market_updates(subscription, 8, BackpressurePolicy::DropNewest)
+  };
+  let market_updates = subscribe(MarketSubscription::Market(market));
+  let token_updates = subscribe(MarketSubscription::Token(token));
+
+  central_context.publish_market_update(&market_update(market, Pubkey::new_unique()));
+  central_context.publish_market_update(&market_update(Pubkey::new_unique(), token));
+  central_context.publish_market_update(&market_update(Pubkey::new_unique(), Pubkey::new_unique()));
+  assert_eq!(market_updates.try_iter().count(), 1);
+  assert_eq!(token_updates.try_iter().count(), 1);
+}
+
+#[test]
+fn full_drop_newest_channel_drops_updates() {
+  let (_, central_context) = fixture_context();
+  let updates = central_context.subscribe_market_updates(
+    MarketSubscription::All,
+    1,
+    BackpressurePolicy::DropNewest,
+  );
+  let market = Pubkey::new_unique();
+  central_context.publish_market_update(&market_update(market, Pubkey::new_unique()));
+  central_context.publish_market_update(&market_update(Pubkey::new_unique(), Pubkey::new_unique()));
+  let delivered: Vec<_> = updates
+    .try_iter()
+    .map(|update| update.market_address)
+    .collect();
+  assert_eq!(delivered, vec![market]);
+}
+
+#[test]
+fn blocked_publisher_does_not_hold_the_subscriber_list() {
+  let (_, central_context) = fixture_context();
+  let blocking_updates =
+    central_context.subscribe_market_updates(MarketSubscription::All, 1, BackpressurePolicy::Block);
+  central_context.publish_market_update(&market_update(Pubkey::new_unique(), Pubkey::new_unique()));
+  // Blocks until the full channel is drained
+  let publisher = {
+    let central_context = central_context.clone();
+    std::thread::spawn(move || {
+      central_context
+        .publish_market_update(&market_update(Pubkey::new_unique(), Pubkey::new_unique()));
+    })
+  };
+  std::thread::sleep(Duration::from_millis(50));
+  assert!(!publisher.is_finished());
+
+  let (subscribed_sender, subscribed) = mpsc::channel();
+  {
+    let central_context = central_context.clone();
+    std::thread::spawn(move || {
+      let receiver = central_context.subscribe_market_updates(
+        MarketSubscription::All,
+        1,
+        BackpressurePolicy::DropNewest,
+      );
+      subscribed_sender.send(receiver).unwrap();
+    });
+  }
+  assert!(subscribed.recv_timeout(Duration::from_secs(5)).is_ok());
+
+  assert!(blocking_updates.recv().is_ok());
+  assert!(blocking_updates.recv().is_ok());
+  publisher.join().unwrap();
+}
+
+#[test]
+fn fetch_market_state_from_rpc_publishes() {
+  let (backend, central_context) = fixture_context();
+  let pool = pumpswap_pool(1_000, 2_000, 1);
+  let (pool_address, token_a_vault, token_b_vault) = (
+    *pool.pool_address(),
+    *pool.token_a_vault_address(),
+    *pool.token_b_vault_address(),
+  );
+  let (token_a, token_b) = (*pool.token_a_address(), *pool.token_b_address());
+  for (vault, mint, amount) in [
+    (token_a_vault, token_a, 4_000),
+    (token_b_vault, token_b, 2_000),
+  ] {
+    backend.set_account(
+      vault,
+      account(
+        SOLANA_PROGRAMS.token_program,
+        token_account_data(&mint, &pool_address, amount),
+      ),
+    );
+  }
+  for mint in [token_a, token_b] {
+    backend.set_account(
+      mint,
+      account(SOLANA_PROGRAMS.token_program, mint_data(6, 1_000_000, None)),
+    );
+  }
+  backend.set_account(
+    pool_address,
+    account(PUMP_CONSTANTS.pump_swap_program, vec![0; 8]),
+  );
+  let pool = Arc::new(RwLock::new(pool));
+  central_context.insert_pool(pool.clone());
+  let updates = central_context.subscribe_market_updates(
+    MarketSubscription::Market(pool_address),
+    8,
+    BackpressurePolicy::DropNewest,
+  );
+
+  pool
+    .write()
+    .unwrap()
+    .fetch_market_state_from_rpc(&central_context);
+  assert_eq!(updates.try_recv().unwrap().price_a_b, 2_000_000_000);
+  // Unchanged prices aren't published again
+  pool
+    .write()
+    .unwrap()
+    .fetch_market_state_from_rpc(&central_context);
+  assert!(updates.try_recv().is_err());
+
+  backend.set_account(
+    token_b_vault,
+    account(
+      SOLANA_PROGRAMS.token_program,
+      token_account_data(&token_b, &pool_address, 8_000),
+    ),
+  );
+  tokio::runtime::Runtime::new()
+    .unwrap()
+    .block_on(central_context.fetch_market_state_from_rpc_async(&pool_address))
+    .unwrap();
+  assert_eq!(updates.try_recv().unwrap().price_a_b, 500_000_000);
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use common::{account, fixture_context, meteora_vault, meteora_vault_data, mint_data};
+use solana_central::MeteoraVault;
+use solana_central::constants::{METEORA_CONSTANTS, SOLANA_PROGRAMS};
+use solana_sdk::pubkey::Pubkey;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Offset of the locked profit tracker in a small (1232 bytes) vault account
+const LOCKED_PROFIT_TRACKER_OFFSET: usize = 1203;
+
+/// Degradation unlocking the whole profit 6 hours after a report, the vault program's default
+const SIX_HOURS_DEGRADATION: u64 = 46_296_296;
+
+/// Small vault account data with its locked profit tracker set, `last_report` in unix seconds like
+/// the vault program's clock
+fn vault_data_with_locked_profit(
+  total_amount: u64,
+  lp_mint: &Pubkey,
+  locked_profit: u64,
+  last_report: u64,
+) -> Vec<u8> {
+  let mut data = meteora_vault_data(total_amount, lp_mint);
+  for (index, value) in [locked_profit, last_report, SIX_HOURS_DEGRADATION]
+    .into_iter()
+    .enumerate()
+  {
+    let offset = LOCKED_PROFIT_TRACKER_OFFSET + index * 8;
+    data[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+  }
+  data
+}
+
+fn now_secs() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap()
+    .as_secs()
+}
+
+#[tokio::test]
+async fn locked_profit_unlocks_over_seconds_since_the_report() {
+  let (backend, central_context) = fixture_context();
+  let vault = meteora_vault(0, 0);
+  let (vault_address, lp_mint) = {
+    let vault = vault.read().unwrap();
+    (vault.vault, vault.lp_token_address)
+  };
+  backend.set_account(
+    lp_mint,
+    account(SOLANA_PROGRAMS.token_program, mint_data(9, 1_000, None)),
+  );
+
+  // (seconds since the report, expected withdrawable amount range)
+  for (elapsed, withdrawable) in [
+    // Half way through the unlock, about half of the 600M locked profit is still locked
+    (3 * 60 * 60, 699_000_000..=701_000_000),
+    // Past the unlock period the whole amount is withdrawable
+    (7 * 60 * 60, 1_000_000_000..=1_000_000_000),
+  ] {
+    backend.set_account(
+      vault_address,
+      account(
+        METEORA_CONSTANTS.vault_program,
+        vault_data_with_locked_profit(1_000_000_000, &lp_mint, 600_000_000, now_secs() - elapsed),
+      ),
+    );
+    MeteoraVault::update_vault_info_async(&vault, &central_context)
+      .await
+      .unwrap();
+    let vault = vault.read().unwrap();
+    assert_eq!(vault.locked_profit_degradation, SIX_HOURS_DEGRADATION);
+    assert!(
+      withdrawable.contains(&vault.calculate_withdrawable_amount()),
+      "{} seconds after the report: {}",
+      elapsed,
+      vault.calculate_withdrawable_amount()
+    );
+  }
+
+  // A report ahead of the local clock keeps the whole profit locked
+  let mut vault = vault.write().unwrap();
+  vault.last_report = now_secs() + 60;
+  assert_eq!(vault.calculate_withdrawable_amount(), 400_000_000);
+}
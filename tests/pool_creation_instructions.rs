@@ -0,0 +1,146 @@
+mod common;
+
+use common::{fixture_context, instruction, keys_with_program, pumpswap_create_pool_data};
+use solana_central::constants::{PUMP_CONSTANTS, RAYDIUM_CONSTANTS};
+use solana_central::{
+  BackpressurePolicy, MarketSubscription, PoolTrait, Pools, PumpswapPool, RaydiumAmmV4Pool,
+  RaydiumCpmmPool,
+};
+use solana_sdk::pubkey::Pubkey;
+
+#[test]
+fn pumpswap_pool_from_create_pool_instruction() {
+  let (keys, accounts) = keys_with_program(11, PUMP_CONSTANTS.pump_swap_program);
+  let coin_creator = Pubkey::new_unique();
+  let data = pumpswap_create_pool_data(1_000_000, 5_000_000_000, &coin_creator);
+
+  let pool =
+    PumpswapPool::from_create_pool_instruction(&instruction(&keys, &accounts, &data), 77).unwrap();
+  assert_eq!(*pool.pool_address(), keys[0]);
+  assert_eq!(*pool.token_a_address(), keys[3]);
+  assert_eq!(*pool.token_b_address(), keys[4]);
+  assert_eq!(*pool.token_a_vault_address(), keys[9]);
+  assert_eq!(*pool.token_b_vault_address(), keys[10]);
+  assert_eq!(pool.token_a_amount_units(), 1_000_000);
+  assert_eq!(pool.token_b_amount_units(), 5_000_000_000);
+  assert_eq!(pool.last_updated_slot(), 77);
+  assert!(pool.is_hydrated());
+}
+
+#[test]
+fn cpmm_pool_from_initialize_instruction() {
+  let (keys, accounts) = keys_with_program(14, RAYDIUM_CONSTANTS.cpmm_program);
+  let mut data = RAYDIUM_CONSTANTS
+    .cpmm_create_pool_instruction_discriminator
+    .to_vec();
+  data.extend(2_000u64.to_le_bytes());
+  data.extend(3_000u64.to_le_bytes());
+  data.extend(1_700_000_000u64.to_le_bytes());
+
+  let pool =
+    RaydiumCpmmPool::from_initialize_instruction(&instruction(&keys, &accounts, &data), 12)
+      .unwrap();
+  assert_eq!(*pool.pool_address(), keys[3]);
+  assert_eq!(*pool.token_a_address(), keys[4]);
+  assert_eq!(*pool.token_b_address(), keys[5]);
+  assert_eq!(*pool.token_a_vault_address(), keys[10]);
+  assert_eq!(*pool.token_b_vault_address(), keys[11]);
+  assert_eq!(pool.pool_config_account, keys[1]);
+  assert_eq!(pool.observation_state_account, keys[13]);
+  assert_eq!(pool.token_a_amount_units(), 2_000);
+  assert_eq!(pool.token_b_amount_units(), 3_000);
+  assert_eq!(pool.open_time, 1_700_000_000);
+  assert_eq!(pool.last_updated_slot(), 12);
+}
+
+#[test]
+fn ammv4_pool_from_initialize2_instruction() {
+  let (keys, accounts) = keys_with_program(12, RAYDIUM_CONSTANTS.amm_program);
+  let mut data = vec![
+    RAYDIUM_CONSTANTS.ammv4_create_pool_instruction_discriminator,
+    254,
+  ];
+  data.extend(1_700_000_000u64.to_le_bytes());
+  // pc (quote) then coin (base)
+  data.extend(9_000u64.to_le_bytes());
+  data.extend(4_000u64.to_le_bytes());
+
+  let pool =
+    RaydiumAmmV4Pool::from_initialize2_instruction(&instruction(&keys, &accounts, &data), 5)
+      .unwrap();
+  assert_eq!(*pool.pool_address(), keys[4]);
+  assert_eq!(*pool.token_a_address(), keys[8]);
+  assert_eq!(*pool.token_b_address(), keys[9]);
+  assert_eq!(pool.token_a_amount_units(), 4_000);
+  assert_eq!(pool.token_b_amount_units(), 9_000);
+  assert_eq!(pool.open_time, 1_700_000_000);
+  assert_eq!(pool.last_updated_slot(), 5);
+  assert_eq!(*pool.pool_type(), Pools::RaydiumAmmV4);
+}
+
+#[test]
+fn instructions_of_other_programs_or_truncated_data_are_ignored() {
+  let (keys, accounts) = keys_with_program(11, Pubkey::new_unique());
+  let data = pumpswap_create_pool_data(1, 1, &Pubkey::new_unique());
+  assert!(
+    PumpswapPool::from_create_pool_instruction(&instruction(&keys, &accounts, &data), 0).is_none()
+  );
+
+  let (keys, accounts) = keys_with_program(11, PUMP_CONSTANTS.pump_swap_program);
+  assert!(
+    PumpswapPool::from_create_pool_instruction(&instruction(&keys, &accounts, &data[..20]), 0)
+      .is_none()
+  );
+  // Missing accounts
+  assert!(
+    PumpswapPool::from_create_pool_instruction(&instruction(&keys, &accounts[..5], &data), 0)
+      .is_none()
+  );
+}
+
+#[test]
+fn process_pool_creation_instruction_inserts_and_publishes_once() {
+  let (_, central_context) = fixture_context();
+  let updates = central_context.subscribe_market_updates(
+    MarketSubscription::All,
+    8,
+    BackpressurePolicy::DropNewest,
+  );
+  let (keys, accounts) = keys_with_program(11, PUMP_CONSTANTS.pump_swap_program);
+  let data = pumpswap_create_pool_data(1_000, 2_000, &Pubkey::new_unique());
+  let creation = instruction(&keys, &accounts, &data);
+
+  let pool = central_context
+    .process_pool_creation_instruction(&creation, 100)
+    .unwrap();
+  assert_eq!(pool.read().unwrap().last_updated_slot(), 100);
+  assert!(
+    central_context
+      .pools_map
+      .read()
+      .unwrap()
+      .contains_key(&keys[0])
+  );
+  assert!(
+    central_context
+      .pools_map
+      .read()
+      .unwrap()
+      .contains_key(&keys[9])
+  );
+  let pair_pools = central_context.markets.read().unwrap()[&keys[3]][&keys[4]].clone();
+  assert_eq!(pair_pools.read().unwrap().len(), 1);
+
+  let update = updates.try_recv().unwrap();
+  assert_eq!(update.market_address, keys[0]);
+  assert_eq!(update.price_a_b, 500_000_000);
+  assert_eq!(update.price_b_a, 2_000_000_000);
+
+  // Seen again, e.g. from a second stream
+  assert!(
+    central_context
+      .process_pool_creation_instruction(&creation, 100)
+      .is_none()
+  );
+  assert!(updates.try_recv().is_err());
+}
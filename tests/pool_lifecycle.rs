@@ -0,0 +1,407 @@
+mod common;
+
+use common::{
+  fixture_context, pf_bonding_curve, pf_bonding_curve_data, pumpswap_pool, pumpswap_pool_data,
+  raydium_cpmm_pool, token_account_data,
+};
+use solana_central::constants::{PUMP_CONSTANTS, SOLANA_PROGRAMS};
+use solana_central::{CentralContext, PfBondingCurve, PoolLifecycle, PoolTrait, SweepPolicy};
+use solana_sdk::pubkey::Pubkey;
+use std::sync::mpsc;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn lifecycle(central_context: &CentralContext, pool: &Arc<RwLock<dyn PoolTrait>>) -> PoolLifecycle {
+  central_context.get_pool_lifecycle(&*pool.read().unwrap())
+}
+
+/// Whether any key of `pools_map`, `account_dependents` or `markets` still refers to `pool`
+fn is_referenced(central_context: &CentralContext, pool: &Arc<RwLock<dyn PoolTrait>>) -> bool {
+  central_context
+    .pools_map
+    .read()
+    .unwrap()
+    .values()
+    .any(|mapped_pool| Arc::ptr_eq(mapped_pool, pool))
+    || central_context
+      .account_dependents
+      .read()
+      .unwrap()
+      .values()
+      .flatten()
+      .any(|dependent| Arc::ptr_eq(dependent, pool))
+    || central_context
+      .markets
+      .read()
+      .unwrap()
+      .values()
+      .flat_map(|token_markets| token_markets.values())
+      .any(|pair_markets| {
+        pair_markets
+          .read()
+          .unwrap()
+          .iter()
+          .any(|market| Arc::ptr_eq(market, pool))
+      })
+}
+
+#[test]
+fn lifecycle_states_follow_pool_state() {
+  let (_, central_context) = fixture_context();
+  let now = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap()
+    .as_secs();
+
+  let pending: Arc<RwLock<dyn PoolTrait>> = Arc::new(RwLock::new(raydium_cpmm_pool(now + 3_600)));
+  let opened: Arc<RwLock<dyn PoolTrait>> = Arc::new(RwLock::new(raydium_cpmm_pool(now - 1)));
+  assert_eq!(
+    lifecycle(&central_context, &pending),
+    PoolLifecycle::PendingActivation
+  );
+  assert_eq!(lifecycle(&central_context, &opened), PoolLifecycle::Active);
+
+  let curve = pf_bonding_curve();
+  let curve_address = curve.bonding_curve_address;
+  let curve: Arc<RwLock<dyn PoolTrait>> = Arc::new(RwLock::new(curve));
+  central_context.insert_pool(curve.clone());
+  assert_eq!(lifecycle(&central_context, &curve), PoolLifecycle::Active);
+
+  // The default sweep policy keeps completed and closed pools
+  let data = pf_bonding_curve_data(1_000, 2_000, true);
+  let updated = central_context.apply_account_update(
+    &curve_address,
+    &PUMP_CONSTANTS.bonding_curve_program,
+    &data,
+    5,
+    None,
+  );
+  assert_eq!(updated.len(), 1);
+  assert_eq!(
+    lifecycle(&central_context, &curve),
+    PoolLifecycle::Completed
+  );
+  assert!(
+    central_context
+      .graduations
+      .read()
+      .unwrap()
+      .contains_key(&curve_address)
+  );
+
+  central_context.apply_account_update(
+    &curve_address,
+    &SOLANA_PROGRAMS.system_program,
+    &[],
+    6,
+    None,
+  );
+  assert_eq!(lifecycle(&central_context, &curve), PoolLifecycle::Closed);
+  assert!(is_referenced(&central_context, &curve));
+}
+
+#[test]
+fn sweep_policy_removes_pools_as_updates_arrive() {
+  let (_, central_context) = fixture_context();
+  *central_context.sweep_policy.write().unwrap() = SweepPolicy {
+    remove_completed: true,
+    ..SweepPolicy::default()
+  };
+  let curve = pf_bonding_curve();
+  let curve_address = curve.bonding_curve_address;
+  let curve: Arc<RwLock<dyn PoolTrait>> = Arc::new(RwLock::new(curve));
+  central_context.insert_pool(curve.clone());
+
+  // Still trading, kept
+  let data = pf_bonding_curve_data(1_000, 2_000, false);
+  central_context.apply_account_update(
+    &curve_address,
+    &PUMP_CONSTANTS.bonding_curve_program,
+    &data,
+    5,
+    None,
+  );
+  assert!(is_referenced(&central_context, &curve));
+
+  let data = pf_bonding_curve_data(1_000, 2_000, true);
+  let updated = central_context.apply_account_update(
+    &curve_address,
+    &PUMP_CONSTANTS.bonding_curve_program,
+    &data,
+    6,
+    None,
+  );
+  assert_eq!(updated.len(), 1);
+  assert!(!is_referenced(&central_context, &curve));
+  assert!(
+    !central_context
+      .account_update_versions
+      .read()
+      .unwrap()
+      .contains_key(&curve_address)
+  );
+  // The graduation is tracked before the curve is removed and outlives it
+  assert!(
+    central_context
+      .graduations
+      .read()
+      .unwrap()
+      .contains_key(&curve_address)
+  );
+}
+
+#[test]
+fn sweep_policy_removes_closed_pools() {
+  let (_, central_context) = fixture_context();
+  *central_context.sweep_policy.write().unwrap() = SweepPolicy {
+    remove_closed: true,
+    ..SweepPolicy::default()
+  };
+  let pool = pumpswap_pool(1_000, 2_000, 1);
+  let (pool_address, token_a_vault) = (*pool.pool_address(), *pool.token_a_vault_address());
+  let pool: Arc<RwLock<dyn PoolTrait>> = Arc::new(RwLock::new(pool));
+  central_context.insert_pool(pool.clone());
+
+  // A closed vault doesn't close the pool
+  central_context.apply_account_update(
+    &token_a_vault,
+    &SOLANA_PROGRAMS.system_program,
+    &[],
+    5,
+    None,
+  );
+  assert!(is_referenced(&central_context, &pool));
+
+  central_context.apply_account_update(
+    &pool_address,
+    &SOLANA_PROGRAMS.system_program,
+    &[],
+    6,
+    None,
+  );
+  assert!(!is_referenced(&central_context, &pool));
+  assert!(
+    !central_context
+      .closed_pools
+      .read()
+      .unwrap()
+      .contains(&pool_address)
+  );
+}
+
+#[test]
+fn stale_close_does_not_close_a_newer_pool() {
+  let (_, central_context) = fixture_context();
+  *central_context.sweep_policy.write().unwrap() = SweepPolicy {
+    remove_closed: true,
+    ..SweepPolicy::default()
+  };
+  let pool = pumpswap_pool(1_000, 2_000, 1);
+  let pool_address = *pool.pool_address();
+  let pool_data = pumpswap_pool_data(
+    pool.token_a_address(),
+    pool.token_b_address(),
+    pool.token_a_vault_address(),
+    pool.token_b_vault_address(),
+  );
+  let pool: Arc<RwLock<dyn PoolTrait>> = Arc::new(RwLock::new(pool));
+  central_context.insert_pool(pool.clone());
+
+  central_context.apply_account_update(
+    &pool_address,
+    &PUMP_CONSTANTS.pump_swap_program,
+    &pool_data,
+    10,
+    None,
+  );
+  // A close from before the last update arrives late
+  central_context.apply_account_update(
+    &pool_address,
+    &SOLANA_PROGRAMS.system_program,
+    &[],
+    9,
+    None,
+  );
+  assert!(is_referenced(&central_context, &pool));
+  assert_eq!(lifecycle(&central_context, &pool), PoolLifecycle::Active);
+
+  central_context.apply_account_update(
+    &pool_address,
+    &SOLANA_PROGRAMS.system_program,
+    &[],
+    11,
+    None,
+  );
+  assert!(!is_referenced(&central_context, &pool));
+}
+
+#[test]
+fn sweep_pools_removes_pools_loaded_before_the_policy() {
+  let (_, central_context) = fixture_context();
+  let completed_curve = pf_bonding_curve();
+  let completed_curve_address = completed_curve.bonding_curve_address;
+  let completed_curve: Arc<RwLock<dyn PoolTrait>> = Arc::new(RwLock::new(PfBondingCurve {
+    complete: true,
+    ..completed_curve
+  }));
+  let active_curve: Arc<RwLock<dyn PoolTrait>> = Arc::new(RwLock::new(pf_bonding_curve()));
+  central_context.insert_pool(completed_curve.clone());
+  central_context.insert_pool(active_curve.clone());
+  assert!(central_context.sweep_pools().is_empty());
+
+  *central_context.sweep_policy.write().unwrap() = SweepPolicy {
+    remove_completed: true,
+    ..SweepPolicy::default()
+  };
+  let removed = central_context.sweep_pools();
+  assert_eq!(removed.len(), 1);
+  assert!(Arc::ptr_eq(&removed[0], &completed_curve));
+  assert!(!is_referenced(&central_context, &completed_curve));
+  assert!(is_referenced(&central_context, &active_curve));
+  assert!(
+    central_context
+      .graduations
+      .read()
+      .unwrap()
+      .contains_key(&completed_curve_address)
+  );
+}
+
+#[test]
+fn remove_pool_cleans_up_markets_and_every_key() {
+  let (_, central_context) = fixture_context();
+  let pool_1 = pumpswap_pool(1_000, 2_000, 1);
+  let (token_a, token_b) = (*pool_1.token_a_address(), *pool_1.token_b_address());
+  let mut pool_2 = pumpswap_pool(3_000, 4_000, 1);
+  // Same pair as the first pool
+  pool_2.info.token_a_address = token_a;
+  pool_2.info.token_b_address = token_b;
+  let (pool_1_address, pool_1_vault) = (*pool_1.pool_address(), *pool_1.token_a_vault_address());
+  let pool_2_address = *pool_2.pool_address();
+  let pool_1: Arc<RwLock<dyn PoolTrait>> = Arc::new(RwLock::new(pool_1));
+  let pool_2: Arc<RwLock<dyn PoolTrait>> = Arc::new(RwLock::new(pool_2));
+  central_context.insert_pool(pool_1.clone());
+  central_context.insert_pool(pool_2.clone());
+  central_context.apply_account_update(
+    &pool_1_vault,
+    &SOLANA_PROGRAMS.token_program,
+    &token_account_data(&token_a, &pool_1_address, 5_000),
+    5,
+    None,
+  );
+
+  // Only the pool address removes a pool, not one of its dependent accounts
+  assert!(central_context.remove_pool(&pool_1_vault).is_none());
+  assert!(central_context.remove_pool(&Pubkey::new_unique()).is_none());
+
+  let removed = central_context.remove_pool(&pool_1_address).unwrap();
+  assert!(Arc::ptr_eq(&removed, &pool_1));
+  assert!(!is_referenced(&central_context, &pool_1));
+  assert!(
+    !central_context
+      .account_update_versions
+      .read()
+      .unwrap()
+      .contains_key(&pool_1_vault)
+  );
+  // The pair is kept for the remaining pool, from both sides
+  for (token, other_token) in [(token_a, token_b), (token_b, token_a)] {
+    let markets = central_context.markets.read().unwrap();
+    let pair_markets = markets[&token][&other_token].read().unwrap();
+    assert_eq!(pair_markets.len(), 1);
+    assert!(Arc::ptr_eq(&pair_markets[0], &pool_2));
+  }
+  assert!(central_context.remove_pool(&pool_1_address).is_none());
+
+  central_context.remove_pool(&pool_2_address).unwrap();
+  let markets = central_context.markets.read().unwrap();
+  assert!(!markets.contains_key(&token_a));
+  assert!(!markets.contains_key(&token_b));
+  assert!(central_context.pools_map.read().unwrap().is_empty());
+  assert!(
+    central_context
+      .account_dependents
+      .read()
+      .unwrap()
+      .is_empty()
+  );
+}
+
+#[test]
+fn concurrent_removal_and_updates_do_not_deadlock_or_leak() {
+  let (_, central_context) = fixture_context();
+  let pools: Vec<_> = (0..50)
+    .map(|_| {
+      let pool = pumpswap_pool(1_000, 2_000, 1);
+      let accounts = (
+        *pool.pool_address(),
+        *pool.token_a_address(),
+        *pool.token_a_vault_address(),
+      );
+      let pool: Arc<RwLock<dyn PoolTrait>> = Arc::new(RwLock::new(pool));
+      central_context.insert_pool(pool.clone());
+      (pool, accounts)
+    })
+    .collect();
+  let accounts: Vec<_> = pools.iter().map(|(_, accounts)| *accounts).collect();
+
+  let (done_sender, done_receiver) = mpsc::channel();
+  let mut threads = Vec::new();
+  // Updates to every pool's vault while the pools are removed and new ones inserted
+  for thread in 0..4u64 {
+    let central_context = central_context.clone();
+    let accounts = accounts.clone();
+    let done_sender = done_sender.clone();
+    threads.push(std::thread::spawn(move || {
+      for step in 0..20u64 {
+        for (pool_address, token_a, token_a_vault) in &accounts {
+          central_context.apply_account_update(
+            token_a_vault,
+            &SOLANA_PROGRAMS.token_program,
+            &token_account_data(token_a, pool_address, step * 4 + thread),
+            10 + step,
+            Some(thread),
+          );
+        }
+      }
+      done_sender.send(()).unwrap();
+    }));
+  }
+  {
+    let central_context = central_context.clone();
+    let accounts = accounts.clone();
+    let done_sender = done_sender.clone();
+    threads.push(std::thread::spawn(move || {
+      for (pool_address, _, _) in &accounts {
+        central_context.remove_pool(pool_address).unwrap();
+      }
+      done_sender.send(()).unwrap();
+    }));
+  }
+  {
+    let central_context = central_context.clone();
+    threads.push(std::thread::spawn(move || {
+      for _ in 0..50 {
+        central_context.insert_pool(Arc::new(RwLock::new(pumpswap_pool(1_000, 2_000, 1))));
+      }
+      done_sender.send(()).unwrap();
+    }));
+  }
+
+  // A lock order inversion hangs the threads, fail instead of hanging the test run
+  for _ in 0..threads.len() {
+    done_receiver
+      .recv_timeout(Duration::from_secs(30))
+      .expect("threads deadlocked");
+  }
+  for thread in threads {
+    thread.join().unwrap();
+  }
+
+  let account_update_versions = central_context.account_update_versions.read().unwrap();
+  for ((pool, _), (pool_address, _, token_a_vault)) in pools.iter().zip(&accounts) {
+    assert!(!is_referenced(&central_context, pool));
+    assert!(!account_update_versions.contains_key(pool_address));
+    assert!(!account_update_versions.contains_key(token_a_vault));
+  }
+}
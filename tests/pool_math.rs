@@ -0,0 +1,202 @@
+mod common;
+
+use borsh::BorshDeserialize;
+use common::{
+  dbc_pool_config_data, dbc_virtual_pool_data, fixture_context, meteora_amm_pool, meteora_vault,
+  pf_bonding_curve, pumpswap_pool, raydium_cpmm_pool,
+};
+use proptest::prelude::*;
+use solana_central::constants::{LAMPORTS_PER_SOL, POOLS_ACCOUNT_SIZES, TOKENS};
+use solana_central::protocol_idls::meteora::DbcPoolConfig;
+use solana_central::{Error, MeteoraDammV2Pool, MeteoraDbc, PoolTrait, SwapDirection};
+use solana_sdk::pubkey::Pubkey;
+
+/// Reserves biased towards the edges: empty, tiny and close to the type's maximum
+fn extreme_u64() -> impl Strategy<Value = u64> {
+  prop_oneof![
+    Just(0),
+    Just(1),
+    Just(u64::MAX),
+    0..1_000u64,
+    u64::MAX - 1_000..=u64::MAX,
+    any::<u64>(),
+  ]
+}
+
+fn extreme_u128() -> impl Strategy<Value = u128> {
+  prop_oneof![
+    Just(0),
+    Just(1),
+    Just(u128::MAX),
+    0..1_000u128,
+    u128::MAX - 1_000..=u128::MAX,
+    any::<u128>(),
+  ]
+}
+
+/// Reserve ratio in lamports if it fits in a u128, the price every reserve based pool reports
+fn reserve_ratio(numerator: u64, denominator: u64) -> Option<u128> {
+  (numerator as u128)
+    .checked_mul(LAMPORTS_PER_SOL)?
+    .checked_div(denominator as u128)
+}
+
+/// The infallible prices of `pool` are its fallible prices with undefined prices reported as 0,
+/// and the fallible ones only ever fail with `Error::Arithmetic`
+fn check_price_consistency(pool: &dyn PoolTrait) -> Result<(), TestCaseError> {
+  for (price, try_price) in [
+    (pool.price_a_over_b_lp(), pool.try_price_a_over_b_lp()),
+    (pool.price_b_over_a_lp(), pool.try_price_b_over_a_lp()),
+  ] {
+    match try_price {
+      Ok(try_price) => prop_assert_eq!(price, try_price),
+      Err(Error::Arithmetic { .. }) => prop_assert_eq!(price, 0),
+      Err(error) => prop_assert!(false, "unexpected error {}", error),
+    }
+  }
+  Ok(())
+}
+
+proptest! {
+  #[test]
+  fn pumpswap_prices_are_the_reserve_ratio(base in extreme_u64(), quote in extreme_u64()) {
+    let pool = pumpswap_pool(base, quote, 0);
+    check_price_consistency(&pool)?;
+    prop_assert_eq!(pool.try_price_a_over_b_lp().ok(), reserve_ratio(base, quote));
+    prop_assert_eq!(pool.try_price_b_over_a_lp().ok(), reserve_ratio(quote, base));
+  }
+
+  #[test]
+  fn quotes_never_drain_the_output_reserve(
+    base in extreme_u64(),
+    quote in extreme_u64(),
+    amount_in in extreme_u64(),
+  ) {
+    let (_, central_context) = fixture_context();
+    let pool = pumpswap_pool(base, quote, 0);
+    prop_assert!(pool.quote(amount_in, SwapDirection::AToB, &central_context) < quote.max(1));
+    prop_assert!(pool.quote(amount_in, SwapDirection::BToA, &central_context) < base.max(1));
+  }
+
+  #[test]
+  fn raydium_cpmm_prices_never_panic(
+    token_a in extreme_u64(),
+    token_b in extreme_u64(),
+    fees in prop::array::uniform6(extreme_u64()),
+  ) {
+    let mut pool = raydium_cpmm_pool(0);
+    pool.token_a_vault_amount = token_a;
+    pool.token_b_vault_amount = token_b;
+    [
+      pool.protocol_fees_token_a,
+      pool.protocol_fees_token_b,
+      pool.fund_fees_token_a,
+      pool.fund_fees_token_b,
+      pool.creator_fees_token_a,
+      pool.creator_fees_token_b,
+    ] = fees;
+    // Accrued fees bigger than the vault leave empty reserves
+    prop_assert!(pool.token_a_amount_units() <= token_a);
+    prop_assert!(pool.token_b_amount_units() <= token_b);
+    check_price_consistency(&pool)?;
+  }
+
+  #[test]
+  fn pf_bonding_curve_prices_use_virtual_reserves(
+    virtual_token_reserves in extreme_u64(),
+    virtual_sol_reserves in extreme_u64(),
+  ) {
+    let mut curve = pf_bonding_curve();
+    curve.virtual_token_reserves = virtual_token_reserves;
+    curve.virtual_sol_reserves = virtual_sol_reserves;
+    // Real reserves saturate below the initial virtual reserves
+    prop_assert!(curve.token_a_amount_units() <= virtual_token_reserves);
+    prop_assert!(curve.token_b_amount_units() <= virtual_sol_reserves);
+    check_price_consistency(&curve)?;
+    prop_assert_eq!(
+      curve.try_price_a_over_b_lp().ok(),
+      reserve_ratio(virtual_token_reserves, virtual_sol_reserves)
+    );
+  }
+
+  #[test]
+  fn meteora_amm_reserves_are_capped_at_the_vault(
+    total_amount in extreme_u64(),
+    lp_supply in extreme_u64(),
+    lp_amount in extreme_u128(),
+    locked_profit in extreme_u64(),
+  ) {
+    let token_a_vault = meteora_vault(total_amount, lp_supply);
+    token_a_vault.write().unwrap().last_updated_locked_profit = locked_profit;
+    let token_b_vault = meteora_vault(1_000, 1_000);
+    let pool = meteora_amm_pool(&token_a_vault, &token_b_vault, lp_amount, 1_000);
+    let vault = token_a_vault.read().unwrap();
+    let withdrawable = vault.calculate_withdrawable_amount();
+    prop_assert!(withdrawable <= total_amount);
+    prop_assert!(vault.get_amount_by_share(lp_amount) <= withdrawable);
+    if lp_supply == 0 {
+      prop_assert_eq!(vault.get_amount_by_share(lp_amount), 0);
+    }
+    drop(vault);
+    prop_assert!(pool.token_a_amount_units() <= total_amount);
+    check_price_consistency(&pool)?;
+  }
+
+  #[test]
+  fn meteora_dammv2_reserves_never_panic(
+    liquidity in extreme_u128(),
+    sqrt_min_price in extreme_u128(),
+    sqrt_price in extreme_u128(),
+    sqrt_max_price in extreme_u128(),
+    amount_in in extreme_u64(),
+  ) {
+    let mut pool = MeteoraDammV2Pool::try_from_account_info(
+      Pubkey::new_unique(),
+      &vec![0; POOLS_ACCOUNT_SIZES.meteora_dammv2],
+    )
+    .unwrap();
+    pool.liquidity = liquidity;
+    pool.sqrt_min_price = sqrt_min_price;
+    pool.sqrt_price = sqrt_price;
+    pool.sqrt_max_price = sqrt_max_price;
+    // Prices outside of the pool's range leave it empty
+    if sqrt_price < sqrt_min_price || sqrt_price > sqrt_max_price || liquidity == 0 {
+      prop_assert_eq!(pool.token_a_amount_units(), 0);
+      prop_assert_eq!(pool.token_b_amount_units(), 0);
+    }
+    check_price_consistency(&pool)?;
+    // Swaps stop at the bounds of the range, never paying out more than it holds
+    let (_, central_context) = fixture_context();
+    for (direction, reserve_out) in [
+      (SwapDirection::AToB, pool.token_b_amount_units()),
+      (SwapDirection::BToA, pool.token_a_amount_units()),
+    ] {
+      prop_assert!(pool.quote(amount_in, direction, &central_context) <= reserve_out);
+    }
+  }
+
+  #[test]
+  fn meteora_dbc_prices_never_panic(
+    sqrt_price in prop_oneof![Just(1u128 << 64), extreme_u128()],
+  ) {
+    let config = DbcPoolConfig::deserialize(&mut &dbc_pool_config_data(&TOKENS.wsol)[..]).unwrap();
+    let mut dbc = MeteoraDbc::try_from_account_info(
+      Pubkey::new_unique(),
+      &dbc_virtual_pool_data(&Pubkey::new_unique(), &Pubkey::new_unique(), 1_000, 2_000),
+      &config,
+      Pubkey::new_unique(),
+    )
+    .unwrap();
+    dbc.sqrt_price = sqrt_price;
+    check_price_consistency(&dbc)?;
+    if sqrt_price == 0 {
+      prop_assert!(dbc.try_price_a_over_b_lp().is_err());
+      prop_assert_eq!(dbc.try_price_b_over_a_lp().ok(), Some(0));
+    }
+    // A Q64.64 sqrt price of 1 is a price of 1
+    if sqrt_price == 1 << 64 {
+      prop_assert_eq!(dbc.price_a_over_b_lp(), LAMPORTS_PER_SOL);
+      prop_assert_eq!(dbc.price_b_over_a_lp(), LAMPORTS_PER_SOL);
+    }
+  }
+}
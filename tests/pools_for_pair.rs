@@ -0,0 +1,133 @@
+mod common;
+
+use common::{fixture_context, launchpad_pool_data, pumpswap_pool};
+use solana_central::{
+  Error, PairFilter, PairRanking, PoolTrait, PumpswapPool, RaydiumLaunchpad, SwapDirection,
+};
+use solana_sdk::pubkey::Pubkey;
+use std::sync::{Arc, RwLock};
+
+#[test]
+fn snapshots_of_pools_without_a_price_have_no_price() {
+  let (_, central_context) = fixture_context();
+  let pool = pumpswap_pool(1_000, 0, 0);
+  let (token_a, token_b) = (*pool.token_a_address(), *pool.token_b_address());
+  central_context.insert_pool(Arc::new(RwLock::new(pool)));
+
+  let snapshots = central_context.pools_for_pair(&token_a, &token_b, &PairFilter::default());
+  assert_eq!(snapshots.len(), 1);
+  assert_eq!(snapshots[0].price_a_over_b_lp, None);
+  assert_eq!(snapshots[0].price_b_over_a_lp, Some(0));
+
+  // Queried the other way around, the prices are swapped
+  let snapshots = central_context.pools_for_pair(&token_b, &token_a, &PairFilter::default());
+  assert_eq!(snapshots[0].price_a_over_b_lp, Some(0));
+  assert_eq!(snapshots[0].price_b_over_a_lp, None);
+}
+
+/// Pumpswap pool trading `token_a` against `token_b` with the given reserves
+fn pumpswap_pool_for(token_a: &Pubkey, token_b: &Pubkey, base: u64, quote: u64) -> PumpswapPool {
+  let mut pool = pumpswap_pool(base, quote, 0);
+  pool.info.token_a_address = *token_a;
+  pool.info.token_b_address = *token_b;
+  pool
+}
+
+#[test]
+fn best_price_ranks_pools_by_their_quote() {
+  let (_, central_context) = fixture_context();
+  let (token_a, token_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+  // Same price, the deeper pool moves less for the same size
+  let shallow = pumpswap_pool_for(&token_a, &token_b, 10_000_000, 20_000_000);
+  let deep = pumpswap_pool_for(&token_a, &token_b, 10_000_000_000, 20_000_000_000);
+  let quotes = [
+    shallow.quote(1_000_000, SwapDirection::AToB, &central_context),
+    deep.quote(1_000_000, SwapDirection::AToB, &central_context),
+    deep.quote(1_000_000, SwapDirection::BToA, &central_context),
+  ];
+  let (shallow_address, deep_address) = (shallow.info.pool_address, deep.info.pool_address);
+  central_context.insert_pool(Arc::new(RwLock::new(shallow)));
+  central_context.insert_pool(Arc::new(RwLock::new(deep)));
+
+  let filter = PairFilter {
+    ranking: PairRanking::BestPrice {
+      amount_in: 1_000_000,
+    },
+    ..PairFilter::default()
+  };
+  let snapshots = central_context.pools_for_pair(&token_a, &token_b, &filter);
+  assert_eq!(
+    snapshots
+      .iter()
+      .map(|snapshot| (snapshot.pool_address, snapshot.amount_out))
+      .collect::<Vec<_>>(),
+    [
+      (deep_address, Some(quotes[1])),
+      (shallow_address, Some(quotes[0]))
+    ]
+  );
+  assert!(quotes[0] < quotes[1]);
+
+  // Queried the other way around, the pools are quoted selling token B
+  let snapshots = central_context.pools_for_pair(&token_b, &token_a, &filter);
+  assert_eq!(snapshots[0].pool_address, deep_address);
+  assert_eq!(snapshots[0].amount_out, Some(quotes[2]));
+}
+
+#[test]
+fn pools_with_an_unknown_fee_are_ranked_last_or_excluded() {
+  let (_, central_context) = fixture_context();
+  let pumpswap = pumpswap_pool(1_000_000_000, 1_000_000_000, 0);
+  let (token_a, token_b) = (*pumpswap.token_a_address(), *pumpswap.token_b_address());
+  let pumpswap_address = *pumpswap.pool_address();
+  let launchpad_address = Pubkey::new_unique();
+  let launchpad = RaydiumLaunchpad::try_from_account_info(
+    launchpad_address,
+    &launchpad_pool_data(
+      &token_a,
+      &token_b,
+      (2_000_000_000, 1_000_000_000),
+      (1_000_000_000, 0),
+      0,
+    ),
+  )
+  .unwrap();
+  assert!(matches!(
+    launchpad.try_total_swap_fee_lp(&central_context),
+    Err(Error::InvalidPool { .. })
+  ));
+  central_context.insert_pool(Arc::new(RwLock::new(pumpswap)));
+  central_context.insert_pool(Arc::new(RwLock::new(launchpad)));
+
+  for ranking in [
+    PairRanking::LowestFee,
+    PairRanking::BestPrice { amount_in: 1_000 },
+  ] {
+    let filter = PairFilter {
+      ranking,
+      ..PairFilter::default()
+    };
+    let snapshots = central_context.pools_for_pair(&token_a, &token_b, &filter);
+    assert_eq!(
+      snapshots
+        .iter()
+        .map(|snapshot| snapshot.pool_address)
+        .collect::<Vec<_>>(),
+      [pumpswap_address, launchpad_address],
+      "{:?}",
+      ranking
+    );
+    assert!(snapshots[0].total_swap_fee_lp.is_some());
+    assert_eq!(snapshots[1].total_swap_fee_lp, None);
+    assert_eq!(snapshots[1].amount_out, None);
+  }
+
+  // Any maximum fee excludes the launchpad, whose fee can't be checked against it
+  let filter = PairFilter {
+    max_fee_lp: Some(u64::MAX),
+    ..PairFilter::default()
+  };
+  let snapshots = central_context.pools_for_pair(&token_a, &token_b, &filter);
+  assert_eq!(snapshots.len(), 1);
+  assert_eq!(snapshots[0].pool_address, pumpswap_address);
+}
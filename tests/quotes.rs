@@ -0,0 +1,156 @@
+mod common;
+
+use borsh::BorshDeserialize;
+use common::{dbc_pool_config_data, dbc_virtual_pool_data, fixture_context, pf_bonding_curve};
+use solana_central::constants::{POOLS_ACCOUNT_SIZES, TOKENS};
+use solana_central::protocol_idls::meteora::{DbcPoolConfig, LiquidityDistributionConfig};
+use solana_central::{Error, MeteoraDammV2Pool, MeteoraDbc, PoolTrait, SwapDirection};
+use solana_sdk::pubkey::Pubkey;
+
+/// Q64.64 sqrt price of 1
+const SQRT_PRICE_ONE: u128 = 1 << 64;
+
+/// Liquidity of 1B units of each token around a price of 1
+const LIQUIDITY: u128 = 1_000_000_000 << 64;
+
+/// Fee free DAMMv2 pool priced at 1 over the given sqrt price range
+fn dammv2_pool(sqrt_min_price: u128, sqrt_max_price: u128) -> MeteoraDammV2Pool {
+  let mut pool = MeteoraDammV2Pool::try_from_account_info(
+    Pubkey::new_unique(),
+    &vec![0; POOLS_ACCOUNT_SIZES.meteora_dammv2],
+  )
+  .unwrap();
+  pool.liquidity = LIQUIDITY;
+  pool.sqrt_price = SQRT_PRICE_ONE;
+  pool.sqrt_min_price = sqrt_min_price;
+  pool.sqrt_max_price = sqrt_max_price;
+  pool
+}
+
+/// Fee free DBC pool at `sqrt_price` on a curve starting at a price of 1/4
+fn dbc_pool(sqrt_price: u128, curve: Vec<LiquidityDistributionConfig>) -> MeteoraDbc {
+  let config = DbcPoolConfig::deserialize(&mut &dbc_pool_config_data(&TOKENS.wsol)[..]).unwrap();
+  let mut dbc = MeteoraDbc::try_from_account_info(
+    Pubkey::new_unique(),
+    &dbc_virtual_pool_data(&Pubkey::new_unique(), &Pubkey::new_unique(), 0, 0),
+    &config,
+    Pubkey::new_unique(),
+  )
+  .unwrap();
+  dbc.sqrt_price = sqrt_price;
+  dbc.sqrt_start_price = SQRT_PRICE_ONE / 2;
+  dbc.curve = curve;
+  dbc
+}
+
+#[test]
+fn dammv2_quotes_follow_the_liquidity_range() {
+  let (_, central_context) = fixture_context();
+  // Over the widest range the pool trades like a constant product on 1B units of each token
+  let pool = dammv2_pool(1, u128::MAX);
+  let amount_out = pool.quote(1_000_000, SwapDirection::AToB, &central_context);
+  let constant_product = 1_000_000_000u128 * 1_000_000 / (1_000_000_000 + 1_000_000);
+  assert!(
+    constant_product.abs_diff(amount_out as u128) <= 1,
+    "{}",
+    amount_out
+  );
+
+  // A narrow range below the price holds about 1M of token B, small swaps still trade at 1
+  let pool = dammv2_pool(SQRT_PRICE_ONE - SQRT_PRICE_ONE / 1_000, u128::MAX);
+  let token_b_amount = pool.token_b_amount_units();
+  assert!(
+    (999_000..=1_001_000).contains(&token_b_amount),
+    "{}",
+    token_b_amount
+  );
+  let amount_out = pool.quote(1_000, SwapDirection::AToB, &central_context);
+  assert!((998..=1_000).contains(&amount_out), "{}", amount_out);
+  // Large swaps are filled down to the bottom of the range
+  let amount_out = pool.quote(u64::MAX, SwapDirection::AToB, &central_context);
+  assert!(amount_out <= token_b_amount && token_b_amount - amount_out <= 1);
+
+  // A pool without liquidity can't quote
+  let mut pool = dammv2_pool(1, u128::MAX);
+  pool.liquidity = 0;
+  assert!(matches!(
+    pool.try_quote(1_000, SwapDirection::AToB, &central_context),
+    Err(Error::Arithmetic { .. })
+  ));
+}
+
+#[test]
+fn dbc_quotes_walk_the_price_curve() {
+  let (_, central_context) = fixture_context();
+  // A single segment from a price of 1/4 to 4
+  let curve = vec![LiquidityDistributionConfig {
+    sqrt_price: 2 * SQRT_PRICE_ONE,
+    liquidity: LIQUIDITY,
+  }];
+  let dbc = dbc_pool(SQRT_PRICE_ONE, curve);
+  // Small swaps trade at the current price of 1
+  for direction in [SwapDirection::AToB, SwapDirection::BToA] {
+    let amount_out = dbc.quote(1_000, direction, &central_context);
+    assert!(
+      (998..=1_000).contains(&amount_out),
+      "{:?}: {}",
+      direction,
+      amount_out
+    );
+  }
+  // Buys are filled up to the end of the curve and sells down to its start, 500M either way
+  for direction in [SwapDirection::AToB, SwapDirection::BToA] {
+    let amount_out = dbc.quote(u64::MAX, direction, &central_context);
+    assert!(
+      500_000_000u64.abs_diff(amount_out) <= 1,
+      "{:?}: {}",
+      direction,
+      amount_out
+    );
+  }
+
+  // Buying through two segments from the start of the curve adds up both segments' base amounts:
+  // 1B between 1/4 and 1 and 1B between 1 and 4 with twice the liquidity
+  let curve = vec![
+    LiquidityDistributionConfig {
+      sqrt_price: SQRT_PRICE_ONE,
+      liquidity: LIQUIDITY,
+    },
+    LiquidityDistributionConfig {
+      sqrt_price: 2 * SQRT_PRICE_ONE,
+      liquidity: 2 * LIQUIDITY,
+    },
+  ];
+  let dbc = dbc_pool(SQRT_PRICE_ONE / 2, curve);
+  let amount_out = dbc.quote(u64::MAX, SwapDirection::BToA, &central_context);
+  assert!(2_000_000_000u64.abs_diff(amount_out) <= 2, "{}", amount_out);
+}
+
+#[test]
+fn completed_curves_have_no_quote() {
+  let (_, central_context) = fixture_context();
+  let mut curve = pf_bonding_curve();
+  assert!(curve.quote(1_000_000, SwapDirection::BToA, &central_context) > 0);
+  curve.complete = true;
+  assert!(matches!(
+    curve.try_quote(1_000_000, SwapDirection::BToA, &central_context),
+    Err(Error::InvalidPool { .. })
+  ));
+  assert_eq!(
+    curve.quote(1_000_000, SwapDirection::BToA, &central_context),
+    0
+  );
+
+  let mut dbc = dbc_pool(
+    SQRT_PRICE_ONE,
+    vec![LiquidityDistributionConfig {
+      sqrt_price: 2 * SQRT_PRICE_ONE,
+      liquidity: LIQUIDITY,
+    }],
+  );
+  dbc.is_migrated = true;
+  assert!(matches!(
+    dbc.try_quote(1_000, SwapDirection::BToA, &central_context),
+    Err(Error::InvalidPool { .. })
+  ));
+}
@@ -0,0 +1,198 @@
+mod common;
+
+use common::{account, pumpswap_pool, token_account_data};
+use solana_central::constants::{PUMP_CONSTANTS, SOLANA_PROGRAMS};
+use solana_central::{
+  BackpressurePolicy, CentralContext, CentralContextBuilder, FixtureRpcBackend, MarketSubscription,
+  PoolTrait, PumpswapPool, RpcBackend,
+};
+use solana_client::client_error::Result;
+use solana_client::rpc_config::RpcProgramAccountsConfig;
+use solana_sdk::account::Account;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Fixture backend recording the size of every getMultipleAccounts request, moving to the next
+/// slot before answering each one
+#[derive(Default)]
+struct CountingBackend {
+  fixtures: FixtureRpcBackend,
+  slot: AtomicU64,
+  requests: Mutex<Vec<usize>>,
+}
+
+impl RpcBackend for CountingBackend {
+  fn get_slot(&self) -> Result<u64> {
+    Ok(self.slot.load(Ordering::SeqCst))
+  }
+
+  fn get_latest_blockhash(&self) -> Result<(Hash, u64)> {
+    self.fixtures.get_latest_blockhash()
+  }
+
+  fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>> {
+    self.fixtures.get_account(pubkey)
+  }
+
+  fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+    self.requests.lock().unwrap().push(pubkeys.len());
+    self.slot.fetch_add(1, Ordering::SeqCst);
+    pubkeys
+      .iter()
+      .map(|pubkey| self.fixtures.get_account(pubkey))
+      .collect()
+  }
+
+  fn get_program_accounts_with_config(
+    &self,
+    program_id: &Pubkey,
+    config: &RpcProgramAccountsConfig,
+  ) -> Result<Vec<(Pubkey, Account)>> {
+    self
+      .fixtures
+      .get_program_accounts_with_config(program_id, config)
+  }
+}
+
+fn counting_context() -> (Arc<CountingBackend>, CentralContext) {
+  let backend = Arc::new(CountingBackend::default());
+  backend.slot.store(100, Ordering::SeqCst);
+  let central_context = CentralContextBuilder::new()
+    .rpc_backend(backend.clone())
+    .build()
+    .unwrap();
+  (backend, central_context)
+}
+
+/// Unhydrated Pumpswap pool whose vaults hold `base_amount` and `quote_amount` in `backend`
+fn bulk_loaded_pool(
+  backend: &CountingBackend,
+  base_amount: u64,
+  quote_amount: u64,
+) -> Arc<RwLock<PumpswapPool>> {
+  let mut pool = pumpswap_pool(0, 0, 0);
+  pool.info.hydrated = false;
+  let pool_address = *pool.pool_address();
+  for (vault, mint, amount) in [
+    (
+      *pool.token_a_vault_address(),
+      *pool.token_a_address(),
+      base_amount,
+    ),
+    (
+      *pool.token_b_vault_address(),
+      *pool.token_b_address(),
+      quote_amount,
+    ),
+  ] {
+    backend.fixtures.set_account(
+      vault,
+      account(
+        SOLANA_PROGRAMS.token_program,
+        token_account_data(&mint, &pool_address, amount),
+      ),
+    );
+  }
+  backend.fixtures.set_account(
+    pool_address,
+    account(PUMP_CONSTANTS.pump_swap_program, vec![0; 8]),
+  );
+  Arc::new(RwLock::new(pool))
+}
+
+#[test]
+fn refresh_pools_fetches_in_chunks_at_each_chunk_slot() {
+  let (backend, central_context) = counting_context();
+  let updates = central_context.subscribe_market_updates(
+    MarketSubscription::All,
+    64,
+    BackpressurePolicy::DropNewest,
+  );
+  // 3 dependent accounts each, 120 accounts in total
+  let pools: Vec<_> = (0..40u64)
+    .map(|index| bulk_loaded_pool(&backend, 1_000 + index, 2_000))
+    .collect();
+  for pool in &pools {
+    central_context.insert_pool(pool.clone());
+  }
+  let pool_addresses: Vec<Pubkey> = pools
+    .iter()
+    .map(|pool| *pool.read().unwrap().pool_address())
+    .collect();
+
+  central_context.refresh_pools(&pool_addresses);
+  assert_eq!(*backend.requests.lock().unwrap(), vec![100, 20]);
+  for (index, pool) in pools.iter().enumerate() {
+    let pool = pool.read().unwrap();
+    assert!(pool.is_hydrated());
+    assert_eq!(pool.token_a_amount_units(), 1_000 + index as u64);
+    assert_eq!(pool.token_b_amount_units(), 2_000);
+  }
+  // Pools of the first chunk are at its slot, the pool split across chunks at the newest one
+  let slots: Vec<u64> = pools
+    .iter()
+    .map(|pool| pool.read().unwrap().last_updated_slot())
+    .collect();
+  assert!(slots[..33].iter().all(|slot| *slot == 101));
+  assert!(slots[33..].iter().all(|slot| *slot == 102));
+  let published: HashSet<Pubkey> = updates
+    .try_iter()
+    .map(|update| update.market_address)
+    .collect();
+  assert_eq!(published, pool_addresses.iter().copied().collect());
+}
+
+#[test]
+fn refresh_pools_skips_unknown_pools_and_missing_accounts() {
+  let (backend, central_context) = counting_context();
+  let pool = bulk_loaded_pool(&backend, 1_000, 2_000);
+  let incomplete_pool = bulk_loaded_pool(&backend, 3_000, 4_000);
+  let missing_vault = *incomplete_pool.read().unwrap().token_b_vault_address();
+  backend.fixtures.remove_account(&missing_vault);
+  central_context.insert_pool(pool.clone());
+  central_context.insert_pool(incomplete_pool.clone());
+  let updates = central_context.subscribe_market_updates(
+    MarketSubscription::All,
+    8,
+    BackpressurePolicy::DropNewest,
+  );
+
+  let pool_address = *pool.read().unwrap().pool_address();
+  let incomplete_pool_address = *incomplete_pool.read().unwrap().pool_address();
+  central_context.refresh_pools(&[
+    pool_address,
+    Pubkey::new_unique(),
+    incomplete_pool_address,
+    pool_address,
+  ]);
+  // Duplicates and unknown pools aren't fetched
+  assert_eq!(*backend.requests.lock().unwrap(), vec![6]);
+  assert!(pool.read().unwrap().is_hydrated());
+  // The fetched vault is applied, but the pool stays unhydrated and unpublished
+  let incomplete_pool = incomplete_pool.read().unwrap();
+  assert!(!incomplete_pool.is_hydrated());
+  assert_eq!(incomplete_pool.token_a_amount_units(), 3_000);
+  let published: Vec<Pubkey> = updates
+    .try_iter()
+    .map(|update| update.market_address)
+    .collect();
+  assert_eq!(published, vec![pool_address]);
+}
+
+#[tokio::test]
+async fn refresh_pools_async_matches_refresh_pools() {
+  let (backend, central_context) = counting_context();
+  let pool = bulk_loaded_pool(&backend, 5_000, 1_000);
+  central_context.insert_pool(pool.clone());
+
+  let pool_address = *pool.read().unwrap().pool_address();
+  central_context.refresh_pools_async(&[pool_address]).await;
+  let pool = pool.read().unwrap();
+  assert!(pool.is_hydrated());
+  assert_eq!(pool.token_a_amount_units(), 5_000);
+  assert_eq!(pool.last_updated_slot(), 101);
+  assert_eq!(pool.price_a_over_b_lp(), 5_000_000_000);
+}
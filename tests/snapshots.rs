@@ -0,0 +1,267 @@
+mod common;
+
+use common::{
+  account, meteora_amm_pool, meteora_vault, pf_bonding_curve, pumpswap_pool, pumpswap_pool_data,
+  token_account_data,
+};
+use solana_central::constants::{
+  POOLS_ACCOUNT_SIZES, PUMP_CONSTANTS, SNAPSHOT_MAGIC, SOLANA_PROGRAMS, TOKENS,
+};
+use solana_central::{
+  CentralContext, CentralContextBuilder, FixtureRpcBackend, MeteoraAmmPool, PoolTrait,
+  PumpswapPool, load_pools_from_snapshot,
+};
+use solana_sdk::pubkey::Pubkey;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+fn context(backend: &Arc<FixtureRpcBackend>) -> CentralContext {
+  CentralContextBuilder::new()
+    .rpc_backend(backend.clone())
+    .build()
+    .unwrap()
+}
+
+/// Unique snapshot path in the temporary directory
+fn snapshot_path() -> PathBuf {
+  std::env::temp_dir().join(format!("solana_central_{}.snapshot", Pubkey::new_unique()))
+}
+
+/// Context holding two Meteora AMM pools sharing a vault, a Pumpswap pool and a bonding curve
+fn populated_context(backend: &Arc<FixtureRpcBackend>) -> CentralContext {
+  let central_context = context(backend);
+  let shared_vault = meteora_vault(5_000, 5_000);
+  for vault in [meteora_vault(1_000, 1_000), meteora_vault(2_000, 1_000)] {
+    let pool = meteora_amm_pool(&vault, &shared_vault, 500, 1_000);
+    let mut vault_cache = central_context.meteora_vault_cache.lock().unwrap();
+    vault_cache.insert(vault.read().unwrap().token_address, vault.clone());
+    vault_cache.insert(
+      shared_vault.read().unwrap().token_address,
+      shared_vault.clone(),
+    );
+    drop(vault_cache);
+    central_context.insert_pool(Arc::new(RwLock::new(pool)));
+  }
+  central_context.insert_pool(Arc::new(RwLock::new(pumpswap_pool(3_000, 4_000, 7))));
+  let curve = Arc::new(RwLock::new(pf_bonding_curve()));
+  let curve_address = curve.read().unwrap().bonding_curve_address;
+  central_context
+    .pf_bonding_curves
+    .lock()
+    .unwrap()
+    .insert(curve_address, curve.clone());
+  central_context.insert_pool(curve);
+  central_context.apply_slot_update(42);
+  central_context
+    .legit_tokens
+    .lock()
+    .unwrap()
+    .insert(Pubkey::new_unique(), true);
+  central_context
+    .account_update_versions
+    .write()
+    .unwrap()
+    .insert(curve_address, (40, 3));
+  central_context
+}
+
+/// The distinct pools of a context by address, with their reserves and update slot
+fn pool_states(central_context: &CentralContext) -> Vec<(Pubkey, u64, u64, u64)> {
+  let mut states: Vec<_> = central_context
+    .pools_map
+    .read()
+    .unwrap()
+    .iter()
+    .filter_map(|(address, pool)| {
+      let pool = pool.read().unwrap();
+      (pool.pool_address() == address).then(|| {
+        (
+          *address,
+          pool.token_a_amount_units(),
+          pool.token_b_amount_units(),
+          pool.last_updated_slot(),
+        )
+      })
+    })
+    .collect();
+  states.sort();
+  states
+}
+
+#[test]
+fn snapshot_round_trip_restores_the_context() {
+  let backend = Arc::new(FixtureRpcBackend::new());
+  let central_context = populated_context(&backend);
+  let path = snapshot_path();
+  assert_eq!(central_context.save_snapshot(&path).unwrap(), 42);
+
+  let mut restored = context(&backend);
+  assert_eq!(restored.restore_snapshot(&path).unwrap(), 42);
+  fs::remove_file(&path).unwrap();
+
+  assert_eq!(pool_states(&restored), pool_states(&central_context));
+  assert_eq!(pool_states(&restored).len(), 4);
+  assert_eq!(*restored.current_slot.read().unwrap(), 42);
+  assert_eq!(
+    *restored.legit_tokens.lock().unwrap(),
+    *central_context.legit_tokens.lock().unwrap()
+  );
+  assert_eq!(
+    *restored.account_update_versions.read().unwrap(),
+    *central_context.account_update_versions.read().unwrap()
+  );
+  // Bonding curves are back in their cache
+  let pf_bonding_curves = restored.pf_bonding_curves.lock().unwrap();
+  assert_eq!(pf_bonding_curves.len(), 1);
+  let curve_address = pf_bonding_curves.keys().next().unwrap();
+  assert!(
+    restored
+      .pools_map
+      .read()
+      .unwrap()
+      .contains_key(curve_address)
+  );
+
+  // Meteora pools share the restored cached vaults again
+  let vault_cache = restored.meteora_vault_cache.lock().unwrap();
+  assert_eq!(vault_cache.len(), 3);
+  let pools_map = restored.pools_map.read().unwrap();
+  let meteora_pools: Vec<_> = pools_map
+    .values()
+    .filter(|pool| pool.read().unwrap().as_any().is::<MeteoraAmmPool>())
+    .collect();
+  assert!(!meteora_pools.is_empty());
+  for pool in meteora_pools {
+    let pool = pool.read().unwrap();
+    let pool = pool.as_any().downcast_ref::<MeteoraAmmPool>().unwrap();
+    for vault in [&pool.token_a_vault, &pool.token_b_vault] {
+      let token_address = vault.read().unwrap().token_address;
+      assert!(Arc::ptr_eq(vault, &vault_cache[&token_address]));
+    }
+  }
+}
+
+#[test]
+fn unusable_snapshots_leave_the_context_untouched() {
+  let backend = Arc::new(FixtureRpcBackend::new());
+  let path = snapshot_path();
+  populated_context(&backend).save_snapshot(&path).unwrap();
+  let snapshot = fs::read(&path).unwrap();
+
+  let mut other_version = snapshot.clone();
+  other_version[SNAPSHOT_MAGIC.len()..SNAPSHOT_MAGIC.len() + 4]
+    .copy_from_slice(&u32::MAX.to_le_bytes());
+  let mut trailing_data = snapshot.clone();
+  trailing_data.push(0);
+  for data in [
+    other_version,
+    snapshot[..snapshot.len() - 1].to_vec(),
+    trailing_data,
+    b"not a snapshot".to_vec(),
+  ] {
+    fs::write(&path, data).unwrap();
+    let mut central_context = context(&backend);
+    assert!(central_context.restore_snapshot(&path).is_err());
+    assert!(central_context.pools_map.read().unwrap().is_empty());
+    assert!(
+      central_context
+        .meteora_vault_cache
+        .lock()
+        .unwrap()
+        .is_empty()
+    );
+    assert_eq!(*central_context.current_slot.read().unwrap(), 0);
+  }
+  fs::remove_file(&path).unwrap();
+
+  let mut central_context = context(&backend);
+  assert!(central_context.restore_snapshot(&path).is_err());
+}
+
+#[tokio::test]
+async fn restored_pools_are_caught_up_from_chain() {
+  let backend = Arc::new(FixtureRpcBackend::new());
+  let central_context = context(&backend);
+  let pool = pumpswap_pool(3_000, 4_000, 7);
+  let pool_address = *pool.pool_address();
+  let (base_mint, base_vault) = (*pool.token_a_address(), *pool.token_a_vault_address());
+  central_context.insert_pool(Arc::new(RwLock::new(pool)));
+  let path = snapshot_path();
+  central_context.save_snapshot(&path).unwrap();
+
+  // The base vault changed while the snapshot was on disk
+  backend.set_account(
+    base_vault,
+    account(
+      SOLANA_PROGRAMS.token_program,
+      token_account_data(&base_mint, &pool_address, 3_500),
+    ),
+  );
+  let central_context = load_pools_from_snapshot(
+    &path,
+    context(&backend),
+    &[(
+      PUMP_CONSTANTS.pump_swap_program,
+      POOLS_ACCOUNT_SIZES.pump_swap,
+    )],
+    1,
+  )
+  .await
+  .unwrap();
+  fs::remove_file(&path).unwrap();
+
+  let pool = central_context.pools_map.read().unwrap()[&pool_address].clone();
+  let pool = pool.read().unwrap();
+  assert_eq!(pool.token_a_amount_units(), 3_500);
+  // The quote vault isn't on chain, it keeps its snapshot state
+  assert_eq!(pool.token_b_amount_units(), 4_000);
+}
+
+// The full load fetches the CPMM configs with the blocking client
+#[tokio::test(flavor = "multi_thread")]
+async fn missing_snapshot_falls_back_to_a_full_load() {
+  let backend = Arc::new(FixtureRpcBackend::new());
+  let pool_address = Pubkey::new_unique();
+  let base_mint = Pubkey::new_unique();
+  let (base_vault, quote_vault) = (Pubkey::new_unique(), Pubkey::new_unique());
+  backend.set_account(
+    pool_address,
+    account(
+      PUMP_CONSTANTS.pump_swap_program,
+      pumpswap_pool_data(&base_mint, &TOKENS.wsol, &base_vault, &quote_vault),
+    ),
+  );
+  for (vault, mint, amount) in [
+    (base_vault, base_mint, 5_000),
+    (quote_vault, TOKENS.wsol, 1_000),
+  ] {
+    backend.set_account(
+      vault,
+      account(
+        SOLANA_PROGRAMS.token_program,
+        token_account_data(&mint, &pool_address, amount),
+      ),
+    );
+  }
+
+  let central_context = load_pools_from_snapshot(
+    &snapshot_path(),
+    context(&backend),
+    &[(
+      PUMP_CONSTANTS.pump_swap_program,
+      POOLS_ACCOUNT_SIZES.pump_swap,
+    )],
+    1,
+  )
+  .await
+  .unwrap();
+
+  // Loaded with hydration, like a cold start
+  let pool = central_context.pools_map.read().unwrap()[&pool_address].clone();
+  let pool = pool.read().unwrap();
+  assert!(pool.as_any().is::<PumpswapPool>());
+  assert!(pool.is_hydrated());
+  assert_eq!(pool.token_a_amount_units(), 5_000);
+  assert_eq!(pool.token_b_amount_units(), 1_000);
+}
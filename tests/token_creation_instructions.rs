@@ -0,0 +1,284 @@
+mod common;
+
+use borsh::BorshDeserialize;
+use common::{account, fixture_context, instruction, keys_with_program};
+use solana_central::constants::{METEORA_CONSTANTS, PUMP_CONSTANTS, RAYDIUM_CONSTANTS};
+use solana_central::protocol_idls::meteora::DbcPoolConfig;
+use solana_central::{MeteoraDbc, PfBondingCurve, PoolTrait, RaydiumLaunchpad};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+fn borsh_string(data: &mut Vec<u8>, value: &str) {
+  data.extend((value.len() as u32).to_le_bytes());
+  data.extend(value.as_bytes());
+}
+
+fn metadata_data(discriminator: &[u8]) -> Vec<u8> {
+  let mut data = discriminator.to_vec();
+  borsh_string(&mut data, "Token");
+  borsh_string(&mut data, "TKN");
+  borsh_string(&mut data, "https://example.com/token.json");
+  data
+}
+
+fn dbc_initialize_data() -> Vec<u8> {
+  metadata_data(&METEORA_CONSTANTS.dbc_initialize_virtual_pool_discriminators[0])
+}
+
+fn dbc_pool_config(quote_mint: Pubkey) -> DbcPoolConfig {
+  let mut config = DbcPoolConfig::deserialize(&mut &[0u8; 2048][..]).unwrap();
+  config.quote_mint = quote_mint;
+  config.swap_base_amount = 800_000_000;
+  config.migration_base_threshold = 200_000_000;
+  config.sqrt_start_price = 1 << 64;
+  config
+}
+
+#[test]
+fn pf_bonding_curve_from_create_instruction() {
+  let (keys, accounts) = keys_with_program(5, PUMP_CONSTANTS.bonding_curve_program);
+  let creator = Pubkey::new_unique();
+  let mut data = metadata_data(&PUMP_CONSTANTS.bonding_curve_create_v2_instruction_discriminator);
+  data.extend(creator.to_bytes());
+
+  let (bonding_curve, token_creation) = PfBondingCurve::from_create_instruction(
+    &instruction(&keys, &accounts, &data),
+    1_700_000_000,
+    42,
+    3,
+    1,
+    Signature::default(),
+  )
+  .unwrap();
+  assert_eq!(bonding_curve.token_address, keys[0]);
+  assert_eq!(bonding_curve.bonding_curve_address, keys[2]);
+  assert_eq!(
+    bonding_curve.bonding_curve_associated_token_account_address,
+    keys[3]
+  );
+  assert_eq!(
+    bonding_curve.virtual_sol_reserves,
+    PUMP_CONSTANTS.bc_init_virtual_sol_reserves
+  );
+  assert_eq!(bonding_curve.last_updated_slot(), 42);
+  assert_eq!(token_creation.address, keys[0]);
+  assert_eq!(token_creation.creator, creator);
+  assert_eq!(token_creation.market_address, keys[2]);
+  assert_eq!(token_creation.name, "Token");
+  assert_eq!(token_creation.symbol, "TKN");
+  assert_eq!(token_creation.uri, "https://example.com/token.json");
+  assert_eq!(token_creation.slot, 42);
+  assert_eq!(token_creation.index, 3);
+}
+
+/// Launchpad initialize_v2 args: 6 decimals metadata, a constant curve selling 793.1M of 1B tokens
+/// for 85 SOL, then a vesting param locking `total_locked_amount` tokens
+fn launchpad_initialize_data(total_locked_amount: u64) -> Vec<u8> {
+  let mut data = RAYDIUM_CONSTANTS.launchpad_initialize_discriminators[1].to_vec();
+  data.push(6);
+  borsh_string(&mut data, "Token");
+  borsh_string(&mut data, "TKN");
+  borsh_string(&mut data, "https://example.com/token.json");
+  // Constant curve: supply, total_base_sell, total_quote_fund_raising, migrate_type
+  data.push(0);
+  data.extend(1_000_000_000_000_000u64.to_le_bytes());
+  data.extend(793_100_000_000_000u64.to_le_bytes());
+  data.extend(85_000_000_000u64.to_le_bytes());
+  data.push(1);
+  // Vesting: total_locked_amount, cliff_period, unlock_period
+  data.extend(total_locked_amount.to_le_bytes());
+  data.extend(0u64.to_le_bytes());
+  data.extend(0u64.to_le_bytes());
+  data
+}
+
+fn launchpad_from_data(keys: &[Pubkey], accounts: &[u8], data: &[u8]) -> Option<RaydiumLaunchpad> {
+  RaydiumLaunchpad::from_initialize_instruction(
+    &instruction(keys, accounts, data),
+    1_700_000_000,
+    9,
+    0,
+    0,
+    Signature::default(),
+  )
+  .map(|(launchpad, _)| launchpad)
+}
+
+#[test]
+fn launchpad_from_initialize_instruction() {
+  let (keys, accounts) = keys_with_program(10, RAYDIUM_CONSTANTS.launchpad_program);
+  let data = launchpad_initialize_data(0);
+
+  let (launchpad, token_creation) = RaydiumLaunchpad::from_initialize_instruction(
+    &instruction(&keys, &accounts, &data),
+    1_700_000_000,
+    9,
+    0,
+    0,
+    Signature::default(),
+  )
+  .unwrap();
+  assert_eq!(*launchpad.pool_address(), keys[5]);
+  assert_eq!(*launchpad.token_a_address(), keys[6]);
+  assert_eq!(*launchpad.token_b_address(), keys[7]);
+  assert_eq!(*launchpad.token_a_vault_address(), keys[8]);
+  assert_eq!(*launchpad.token_b_vault_address(), keys[9]);
+  assert_eq!(launchpad.migrate_type, 1);
+  // sell^2 / (sell - remaining) and raised * (virtual_base - supply) / remaining
+  assert_eq!(launchpad.virtual_token_a_reserve, 1_073_025_605_595_359);
+  assert_eq!(launchpad.virtual_token_b_reserve, 30_000_852_951);
+  assert_eq!(token_creation.creator, keys[1]);
+  assert_eq!(token_creation.market_address, keys[5]);
+  assert_eq!(token_creation.symbol, "TKN");
+
+  // Non constant curves aren't supported
+  let mut fixed = data.clone();
+  let curve_offset = data.len() - 24 - 26;
+  fixed[curve_offset] = 1;
+  fixed.truncate(curve_offset + 1 + 17);
+  fixed.extend([0; 24]);
+  assert!(launchpad_from_data(&keys, &accounts, &fixed).is_none());
+  // Neither are instructions without their vesting param
+  assert!(launchpad_from_data(&keys, &accounts, &data[..data.len() - 24]).is_none());
+}
+
+#[test]
+fn launchpad_vesting_is_left_out_of_the_migration_pool() {
+  let (keys, accounts) = keys_with_program(10, RAYDIUM_CONSTANTS.launchpad_program);
+  let (supply, total_base_sell, raised, locked) = (
+    1_000_000_000_000_000u128,
+    793_100_000_000_000u128,
+    85_000_000_000u128,
+    50_000_000_000_000u64,
+  );
+  let launchpad =
+    launchpad_from_data(&keys, &accounts, &launchpad_initialize_data(locked)).unwrap();
+  // remaining = supply - sell - locked = 156.9M tokens migrate with the raised quote
+  assert_eq!(launchpad.virtual_token_a_reserve, 988_694_765_796_919);
+  assert_eq!(launchpad.virtual_token_b_reserve, 20_962_747_563);
+
+  // Selling the whole sale along the curve ends at the migration pool's price
+  let remaining = supply - total_base_sell - locked as u128;
+  let (virtual_base, virtual_quote) = (
+    launchpad.virtual_token_a_reserve as u128,
+    launchpad.virtual_token_b_reserve as u128,
+  );
+  let final_price = (virtual_quote + raised) as f64 / (virtual_base - total_base_sell) as f64;
+  let migration_price = raised as f64 / remaining as f64;
+  assert!((final_price / migration_price - 1.0).abs() < 1e-9);
+
+  // Locking more than the unsold supply is rejected
+  let too_much = (supply - total_base_sell) as u64 + 1;
+  assert!(launchpad_from_data(&keys, &accounts, &launchpad_initialize_data(too_much)).is_none());
+}
+
+#[test]
+fn dbc_from_initialize_instruction() {
+  let (keys, accounts) = keys_with_program(8, METEORA_CONSTANTS.dbc_program);
+  let data = dbc_initialize_data();
+  let quote_mint = Pubkey::new_unique();
+
+  let (dbc, token_creation) = MeteoraDbc::from_initialize_instruction(
+    &instruction(&keys, &accounts, &data),
+    &dbc_pool_config(quote_mint),
+    1_700_000_000,
+    55,
+    0,
+    0,
+    Signature::default(),
+  )
+  .unwrap();
+  assert_eq!(*dbc.pool_address(), keys[5]);
+  assert_eq!(dbc.config, keys[0]);
+  assert_eq!(*dbc.token_a_address(), keys[3]);
+  assert_eq!(*dbc.token_b_address(), quote_mint);
+  assert_eq!(dbc.base_reserve, 1_000_000_000);
+  assert_eq!(dbc.quote_reserve, 0);
+  assert_eq!(dbc.activation_point, 55);
+  assert_eq!(token_creation.creator, keys[2]);
+  assert_eq!(token_creation.name, "Token");
+}
+
+#[test]
+fn dbc_pool_waits_for_its_config_without_rpc() {
+  let (backend, central_context) = fixture_context();
+  let (keys, accounts) = keys_with_program(8, METEORA_CONSTANTS.dbc_program);
+  let data = dbc_initialize_data();
+  let config_address = keys[0];
+  let quote_mint = Pubkey::new_unique();
+  let mut config_data = Vec::new();
+  borsh::BorshSerialize::serialize(&dbc_pool_config(quote_mint), &mut config_data).unwrap();
+  // Only reachable through load_dbc_pool_configs
+  backend.set_account(
+    config_address,
+    account(METEORA_CONSTANTS.dbc_program, config_data),
+  );
+
+  let token_creation = central_context
+    .process_token_creation_instruction(
+      &instruction(&keys, &accounts, &data),
+      1_700_000_000,
+      70,
+      0,
+      0,
+      Signature::default(),
+    )
+    .unwrap();
+  assert_eq!(token_creation.market_address, keys[5]);
+  assert!(central_context.meteora_dbcs.lock().unwrap().is_empty());
+  assert!(dbc_pool_config_is_uncached(
+    &central_context,
+    &config_address
+  ));
+  assert_eq!(
+    central_context.pending_dbc_pool_configs(),
+    vec![config_address]
+  );
+
+  tokio::runtime::Runtime::new()
+    .unwrap()
+    .block_on(central_context.load_dbc_pool_configs(&central_context.pending_dbc_pool_configs()));
+  assert!(central_context.pending_dbc_pool_configs().is_empty());
+  let dbc = central_context.meteora_dbcs.lock().unwrap()[&keys[5]].clone();
+  assert_eq!(*dbc.read().unwrap().token_b_address(), quote_mint);
+  assert_eq!(dbc.read().unwrap().last_updated_slot(), 70);
+  assert!(
+    central_context
+      .pools_map
+      .read()
+      .unwrap()
+      .contains_key(&keys[5])
+  );
+
+  // Later pools of the same config are built straight away
+  let (mut other_keys, other_accounts) = keys_with_program(8, METEORA_CONSTANTS.dbc_program);
+  other_keys[0] = config_address;
+  central_context
+    .process_token_creation_instruction(
+      &instruction(&other_keys, &other_accounts, &data),
+      1_700_000_001,
+      71,
+      0,
+      0,
+      Signature::default(),
+    )
+    .unwrap();
+  assert!(
+    central_context
+      .meteora_dbcs
+      .lock()
+      .unwrap()
+      .contains_key(&other_keys[5])
+  );
+}
+
+fn dbc_pool_config_is_uncached(
+  central_context: &solana_central::CentralContext,
+  config_address: &Pubkey,
+) -> bool {
+  !central_context
+    .dbc_pool_configs
+    .read()
+    .unwrap()
+    .contains_key(config_address)
+}
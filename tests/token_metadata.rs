@@ -0,0 +1,256 @@
+mod common;
+
+use common::{account, fixture_context, mint_data, pumpswap_pool};
+use solana_central::constants::SOLANA_PROGRAMS;
+use solana_central::{Error, PoolTrait, TokenMetadata};
+use solana_sdk::pubkey::Pubkey;
+
+/// Raw mint data with a freeze authority set
+fn mint_data_with_freeze_authority(
+  decimals: u8,
+  supply: u64,
+  freeze_authority: &Pubkey,
+) -> Vec<u8> {
+  let mut data = mint_data(decimals, supply, None);
+  data[46] = 1;
+  data[50..82].copy_from_slice(freeze_authority.as_ref());
+  data
+}
+
+#[test]
+fn mint_accounts_decode_into_metadata() {
+  let (mint, mint_authority, freeze_authority) = (
+    Pubkey::new_unique(),
+    Pubkey::new_unique(),
+    Pubkey::new_unique(),
+  );
+  let mut data = mint_data_with_freeze_authority(6, 1_500_000, &freeze_authority);
+  data[..36].copy_from_slice(&mint_data(6, 1_500_000, Some(mint_authority))[..36]);
+  // Token-2022 mints share the base layout, extensions after it are ignored
+  data.extend([0; 100]);
+  let token_metadata =
+    TokenMetadata::try_from_mint_account(mint, &SOLANA_PROGRAMS.token_2022_program, &data, 42)
+      .unwrap();
+  assert_eq!(
+    token_metadata,
+    TokenMetadata {
+      mint,
+      token_program: SOLANA_PROGRAMS.token_2022_program,
+      decimals: 6,
+      supply: 1_500_000,
+      mint_authority: Some(mint_authority),
+      freeze_authority: Some(freeze_authority),
+      last_updated_slot: 42,
+    }
+  );
+  assert_eq!(token_metadata.ui_amount(1_500_000), 1.5);
+
+  let token_program = SOLANA_PROGRAMS.token_program;
+  let mut uninitialized = mint_data(6, 0, None);
+  uninitialized[45] = 0;
+  let mut invalid_option = mint_data(6, 0, None);
+  invalid_option[0] = 2;
+  for (owner, data, message) in [
+    (
+      &SOLANA_PROGRAMS.system_program,
+      mint_data(6, 0, None),
+      "isn't a token program",
+    ),
+    (
+      &token_program,
+      mint_data(6, 0, None)[..81].to_vec(),
+      "too short for a mint",
+    ),
+    (&token_program, uninitialized, "mint isn't initialized"),
+    (&token_program, invalid_option, "invalid option tag 2"),
+  ] {
+    match TokenMetadata::try_from_mint_account(mint, owner, &data, 0) {
+      Err(error @ Error::Decode { .. }) => {
+        assert!(error.to_string().contains(message), "{}", error)
+      }
+      result => panic!(
+        "expected a decode error with {:?}, got {:?}",
+        message, result
+      ),
+    }
+  }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn metadata_is_fetched_once_and_cached() {
+  let (backend, central_context) = fixture_context();
+  let mint = Pubkey::new_unique();
+  backend.set_account(
+    mint,
+    account(SOLANA_PROGRAMS.token_program, mint_data(9, 1_000, None)),
+  );
+  *central_context.current_slot.write().unwrap() = 7;
+
+  let token_metadata = central_context.get_token_metadata(&mint).unwrap();
+  assert_eq!(
+    (token_metadata.decimals, token_metadata.last_updated_slot),
+    (9, 7)
+  );
+
+  // Later lookups are served from the registry without the RPC
+  backend.remove_account(&mint);
+  assert_eq!(
+    central_context.get_token_metadata(&mint).unwrap(),
+    token_metadata
+  );
+  assert_eq!(
+    central_context
+      .get_token_metadata_async(&mint)
+      .await
+      .unwrap(),
+    token_metadata
+  );
+
+  // Unknown mints fail and aren't cached
+  let missing_mint = Pubkey::new_unique();
+  assert!(central_context.get_token_metadata(&missing_mint).is_err());
+  assert!(
+    central_context
+      .get_token_metadata_async(&missing_mint)
+      .await
+      .is_err()
+  );
+  assert!(
+    !central_context
+      .token_registry
+      .read()
+      .unwrap()
+      .contains_key(&missing_mint)
+  );
+}
+
+#[tokio::test]
+async fn load_token_metadata_fetches_missing_mints_in_bulk() {
+  let (backend, central_context) = fixture_context();
+  backend.set_slot(350);
+  // More mints than fit in one request
+  let mints: Vec<Pubkey> = (0..150).map(|_| Pubkey::new_unique()).collect();
+  for (index, mint) in mints.iter().enumerate() {
+    backend.set_account(
+      *mint,
+      account(
+        SOLANA_PROGRAMS.token_program,
+        mint_data(index as u8 % 10, index as u64, None),
+      ),
+    );
+  }
+  // A cached mint isn't fetched again, a missing one and a non mint account are skipped
+  let cached_mint = mints[0];
+  central_context
+    .get_token_metadata_async(&cached_mint)
+    .await
+    .unwrap();
+  backend.set_account(
+    cached_mint,
+    account(SOLANA_PROGRAMS.token_program, mint_data(0, 999, None)),
+  );
+  let (missing_mint, not_a_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+  backend.set_account(
+    not_a_mint,
+    account(SOLANA_PROGRAMS.system_program, vec![0; 82]),
+  );
+
+  let mut requested_mints = mints.clone();
+  requested_mints.extend([missing_mint, not_a_mint]);
+  central_context.load_token_metadata(&requested_mints).await;
+
+  let token_registry = central_context.token_registry.read().unwrap();
+  assert_eq!(token_registry.len(), 150);
+  assert_eq!(token_registry[&cached_mint].supply, 0);
+  for (index, mint) in mints.iter().enumerate().skip(1) {
+    let token_metadata = &token_registry[mint];
+    assert_eq!(token_metadata.decimals, index as u8 % 10);
+    assert_eq!(token_metadata.supply, index as u64);
+    assert_eq!(token_metadata.last_updated_slot, 350);
+  }
+}
+
+#[tokio::test]
+async fn mint_account_updates_refresh_tracked_mints() {
+  let (backend, central_context) = fixture_context();
+  let (mint, untracked_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+  backend.set_slot(100);
+  backend.set_account(
+    mint,
+    account(SOLANA_PROGRAMS.token_program, mint_data(6, 1_000, None)),
+  );
+  central_context.load_token_metadata(&[mint]).await;
+
+  let supply = |mint| {
+    central_context
+      .token_registry
+      .read()
+      .unwrap()
+      .get(mint)
+      .map(|token_metadata: &TokenMetadata| token_metadata.supply)
+  };
+  central_context.apply_account_update(
+    &mint,
+    &SOLANA_PROGRAMS.token_program,
+    &mint_data(6, 2_000, None),
+    101,
+    None,
+  );
+  assert_eq!(supply(&mint), Some(2_000));
+  // Updates older than the metadata are ignored
+  central_context.apply_account_update(
+    &mint,
+    &SOLANA_PROGRAMS.token_program,
+    &mint_data(6, 500, None),
+    99,
+    None,
+  );
+  assert_eq!(supply(&mint), Some(2_000));
+  // Mints that aren't tracked aren't added
+  central_context.apply_account_update(
+    &untracked_mint,
+    &SOLANA_PROGRAMS.token_program,
+    &mint_data(6, 500, None),
+    102,
+    None,
+  );
+  assert_eq!(supply(&untracked_mint), None);
+  // Closes older than the metadata are ignored
+  central_context.apply_account_update(&mint, &SOLANA_PROGRAMS.system_program, &[], 100, None);
+  assert_eq!(supply(&mint), Some(2_000));
+  // Closed mints are dropped
+  central_context.apply_account_update(&mint, &SOLANA_PROGRAMS.system_program, &[], 103, None);
+  assert_eq!(supply(&mint), None);
+}
+
+#[tokio::test]
+async fn ui_prices_scale_by_the_token_decimals() {
+  let (backend, central_context) = fixture_context();
+  // 1 UI unit of a 9 decimals token against 150 UI units of a 6 decimals token
+  let mut pool = pumpswap_pool(1_000_000_000, 150_000_000, 0);
+  assert_eq!(central_context.get_ui_prices(&pool), None);
+
+  for (mint, decimals) in [(*pool.token_a_address(), 9), (*pool.token_b_address(), 6)] {
+    backend.set_account(
+      mint,
+      account(SOLANA_PROGRAMS.token_program, mint_data(decimals, 0, None)),
+    );
+  }
+  central_context
+    .load_token_metadata(&[*pool.token_a_address(), *pool.token_b_address()])
+    .await;
+  let (price_a_over_b, price_b_over_a) = central_context.get_ui_prices(&pool).unwrap();
+  assert!(
+    (price_a_over_b - 1.0 / 150.0).abs() < 1e-9,
+    "{}",
+    price_a_over_b
+  );
+  assert!((price_b_over_a - 150.0).abs() < 1e-9, "{}", price_b_over_a);
+
+  // Undefined prices and unhydrated pools have no UI prices
+  pool.token_b_vault_amount = 0;
+  assert_eq!(central_context.get_ui_prices(&pool), None);
+  pool.token_b_vault_amount = 150_000_000;
+  pool.info.hydrated = false;
+  assert_eq!(central_context.get_ui_prices(&pool), None);
+}
@@ -0,0 +1,218 @@
+mod common;
+
+use common::{account, fixture_context};
+use solana_central::{get_tx_account_keys, get_tx_instructions};
+use solana_sdk::message::compiled_instruction::CompiledInstruction;
+use solana_sdk::message::v0::{LoadedAddresses, MessageAddressTableLookup};
+use solana_sdk::message::{MessageHeader, VersionedMessage, legacy, v0};
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status_client_types::{InnerInstruction, InnerInstructions};
+
+/// Address lookup table account data holding `addresses`
+fn lookup_table_data(addresses: &[Pubkey]) -> Vec<u8> {
+  let mut data = vec![0u8; 56];
+  for address in addresses {
+    data.extend(address.to_bytes());
+  }
+  data
+}
+
+fn unique_keys(count: usize) -> Vec<Pubkey> {
+  (0..count).map(|_| Pubkey::new_unique()).collect()
+}
+
+fn v0_message(
+  account_keys: Vec<Pubkey>,
+  address_table_lookups: Vec<MessageAddressTableLookup>,
+  instructions: Vec<CompiledInstruction>,
+) -> VersionedMessage {
+  VersionedMessage::V0(v0::Message {
+    header: MessageHeader::default(),
+    account_keys,
+    recent_blockhash: Default::default(),
+    instructions,
+    address_table_lookups,
+  })
+}
+
+#[test]
+fn v0_keys_put_writable_before_readonly_across_tables() {
+  let (backend, central_context) = fixture_context();
+  let static_keys = unique_keys(2);
+  let (table_1, table_2) = (Pubkey::new_unique(), Pubkey::new_unique());
+  let table_1_addresses = unique_keys(4);
+  let table_2_addresses = unique_keys(3);
+  backend.set_account(
+    table_1,
+    account(Pubkey::new_unique(), lookup_table_data(&table_1_addresses)),
+  );
+  backend.set_account(
+    table_2,
+    account(Pubkey::new_unique(), lookup_table_data(&table_2_addresses)),
+  );
+  let message = v0_message(
+    static_keys.clone(),
+    vec![
+      MessageAddressTableLookup {
+        account_key: table_1,
+        writable_indexes: vec![3],
+        readonly_indexes: vec![0],
+      },
+      MessageAddressTableLookup {
+        account_key: table_2,
+        writable_indexes: vec![1],
+        readonly_indexes: vec![2],
+      },
+    ],
+    Vec::new(),
+  );
+
+  let loaded_addresses = central_context.resolve_loaded_addresses(&message).unwrap();
+  assert_eq!(
+    loaded_addresses.writable,
+    vec![table_1_addresses[3], table_2_addresses[1]]
+  );
+  assert_eq!(
+    loaded_addresses.readonly,
+    vec![table_1_addresses[0], table_2_addresses[2]]
+  );
+  assert_eq!(
+    get_tx_account_keys(&message, &loaded_addresses),
+    vec![
+      static_keys[0],
+      static_keys[1],
+      table_1_addresses[3],
+      table_2_addresses[1],
+      table_1_addresses[0],
+      table_2_addresses[2],
+    ]
+  );
+}
+
+#[test]
+fn extended_lookup_table_is_fetched_again() {
+  let (backend, central_context) = fixture_context();
+  let table = Pubkey::new_unique();
+  let addresses = unique_keys(4);
+  backend.set_account(
+    table,
+    account(Pubkey::new_unique(), lookup_table_data(&addresses[..2])),
+  );
+  let lookup = |index| {
+    v0_message(
+      Vec::new(),
+      vec![MessageAddressTableLookup {
+        account_key: table,
+        writable_indexes: Vec::new(),
+        readonly_indexes: vec![index],
+      }],
+      Vec::new(),
+    )
+  };
+  // Caches the 2 address table
+  assert!(
+    central_context
+      .resolve_loaded_addresses(&lookup(1))
+      .is_some()
+  );
+
+  backend.set_account(
+    table,
+    account(Pubkey::new_unique(), lookup_table_data(&addresses)),
+  );
+  let loaded_addresses = central_context
+    .resolve_loaded_addresses(&lookup(3))
+    .unwrap();
+  assert_eq!(loaded_addresses.readonly, vec![addresses[3]]);
+  // Out of range even after refreshing, or a missing table
+  assert!(
+    central_context
+      .resolve_loaded_addresses(&lookup(4))
+      .is_none()
+  );
+  backend.remove_account(&table);
+  central_context
+    .address_lookup_tables
+    .write()
+    .unwrap()
+    .clear();
+  assert!(
+    central_context
+      .resolve_loaded_addresses(&lookup(0))
+      .is_none()
+  );
+}
+
+#[test]
+fn legacy_messages_only_use_static_keys() {
+  let (_, central_context) = fixture_context();
+  let static_keys = unique_keys(3);
+  let message = VersionedMessage::Legacy(legacy::Message {
+    account_keys: static_keys.clone(),
+    ..Default::default()
+  });
+  let loaded_addresses = central_context.resolve_loaded_addresses(&message).unwrap();
+  assert_eq!(loaded_addresses, LoadedAddresses::default());
+  let ignored = LoadedAddresses {
+    writable: unique_keys(1),
+    readonly: Vec::new(),
+  };
+  assert_eq!(get_tx_account_keys(&message, &ignored), static_keys);
+}
+
+#[test]
+fn instructions_in_execution_order_resolve_loaded_accounts() {
+  let static_keys = unique_keys(3);
+  let loaded_addresses = LoadedAddresses {
+    writable: unique_keys(1),
+    readonly: unique_keys(1),
+  };
+  let compiled = |program_id_index: u8, accounts: Vec<u8>, data: Vec<u8>| CompiledInstruction {
+    program_id_index,
+    accounts,
+    data,
+  };
+  let message = v0_message(
+    static_keys.clone(),
+    Vec::new(),
+    vec![
+      compiled(2, vec![0, 3], vec![0]),
+      compiled(4, vec![1], vec![1]),
+    ],
+  );
+  let inner = |instruction, stack_height| InnerInstruction {
+    instruction,
+    stack_height,
+  };
+  // Listed out of order, the second one recorded before stack heights existed
+  let inner_instructions = vec![
+    InnerInstructions {
+      index: 1,
+      instructions: vec![inner(compiled(2, vec![3], vec![3]), None)],
+    },
+    InnerInstructions {
+      index: 0,
+      instructions: vec![
+        inner(compiled(4, vec![0], vec![2]), Some(2)),
+        inner(compiled(2, vec![], vec![4]), Some(3)),
+      ],
+    },
+  ];
+
+  let tx_account_keys = get_tx_account_keys(&message, &loaded_addresses);
+  let instructions = get_tx_instructions(&tx_account_keys, &message, &inner_instructions);
+  let order: Vec<_> = instructions
+    .iter()
+    .map(|instruction| (instruction.data[0], instruction.stack_height))
+    .collect();
+  assert_eq!(order, vec![(0, 1), (2, 2), (4, 3), (1, 1), (3, 2)]);
+  assert_eq!(
+    instructions[0].account(1),
+    Some(&loaded_addresses.writable[0])
+  );
+  assert_eq!(
+    instructions[1].program_id(),
+    Some(&loaded_addresses.readonly[0])
+  );
+  assert_eq!(instructions[3].account(1), None);
+}